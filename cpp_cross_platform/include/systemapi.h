@@ -14,6 +14,11 @@
     #define SYSTEMAPI_API __attribute__((visibility("default")))
 #endif
 
+// ABI version of this header. A new major version changes or removes existing exports; a new
+// minor version only adds exports. Callers should refuse a library with a different major.
+#define SYSTEMAPI_API_VERSION_MAJOR 1
+#define SYSTEMAPI_API_VERSION_MINOR 4
+
 // C linkage for FFI compatibility
 #ifdef __cplusplus
 extern "C" {
@@ -31,6 +36,79 @@ SYSTEMAPI_API uint32_t GetCurrentProcessID();
 // Calculate factorial (template-based in implementation)
 SYSTEMAPI_API uint64_t CalculateFactorial(int n);
 
+// The exports below were added in 1.1; callers should treat them as optional
+
+// Get CPU model name, e.g. "Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz"
+SYSTEMAPI_API bool GetCpuModelString(char* buffer, int bufferSize);
+
+// Get CPU vendor, e.g. "GenuineIntel"
+SYSTEMAPI_API bool GetCpuVendorString(char* buffer, int bufferSize);
+
+// Get physical core count (0 if unknown)
+SYSTEMAPI_API uint32_t GetPhysicalCoreCount();
+
+// Get logical processor count (0 if unknown)
+SYSTEMAPI_API uint32_t GetLogicalCoreCount();
+
+// Get base (nominal) CPU frequency in MHz (0 if unknown)
+SYSTEMAPI_API uint64_t GetCpuBaseFrequencyMHz();
+
+// Get per-core utilization in percent since the previous call (the first call samples briefly).
+// Writes up to `count` values and returns the number of cores, or -1 on failure.
+SYSTEMAPI_API int GetCpuCoreUsage(double* usage, int count);
+
+// The exports below were added in 1.2; callers should treat them as optional
+
+// Charge states reported in SystemPowerStatus::chargeState
+#define SYSTEMAPI_CHARGE_UNKNOWN 0
+#define SYSTEMAPI_CHARGE_CHARGING 1
+#define SYSTEMAPI_CHARGE_DISCHARGING 2
+#define SYSTEMAPI_CHARGE_FULL 3
+#define SYSTEMAPI_CHARGE_NOT_CHARGING 4
+
+// Battery and AC adapter state; batteries are combined when there are several
+typedef struct {
+    int32_t hasBattery;       // 1 if the machine has a battery, else 0
+    int32_t batteryPercent;   // 0-100, or -1 if unknown or there is no battery
+    int32_t chargeState;      // One of SYSTEMAPI_CHARGE_*
+    int32_t acOnline;         // 1 on AC power, 0 on battery, -1 if unknown
+    int64_t secondsRemaining; // Until empty when discharging, until full when charging; -1 if unknown
+} SystemPowerStatus;
+
+// Get the current power status; returns false if it couldn't be read
+SYSTEMAPI_API bool GetPowerStatus(SystemPowerStatus* status);
+
+// The exports below were added in 1.3; callers should treat them as optional
+
+// Memory ownership: an export that returns a pointer allocates it inside the library, and the
+// caller owns it from then on. Release it exactly once with FreeBuffer, never with the caller's
+// own free() or delete, since the library may use a different heap (e.g. another C runtime on
+// Windows). Any future allocating export must follow this rule and document it.
+
+// Release memory returned by an allocating export; NULL is ignored
+SYSTEMAPI_API void FreeBuffer(void* buffer);
+
+// Get the library version, e.g. "1.3.0"; the caller releases it with FreeBuffer.
+// Returns NULL on failure.
+SYSTEMAPI_API char* GetLibraryVersionString();
+
+// Time since boot and, where the OS keeps them, run queue load averages
+typedef struct {
+    uint64_t uptimeSeconds;
+    int64_t bootTime;       // Unix timestamp in seconds
+    double loadAverage1;    // Over 1, 5 and 15 minutes; -1 where there are none (Windows)
+    double loadAverage5;
+    double loadAverage15;
+} SystemUptimeInfo;
+
+// Get uptime, boot time and load averages; returns false if they couldn't be read
+SYSTEMAPI_API bool GetUptimeInfo(SystemUptimeInfo* info);
+
+// The exports below were added in 1.4; callers should treat them as optional
+
+// Get the ABI version the library was built with, packed as (major << 16) | minor
+SYSTEMAPI_API uint32_t GetApiVersion();
+
 #ifdef __cplusplus
 }
 #endif
@@ -1,18 +1,35 @@
 #include "systemapi.h"
+#include <chrono>
+#include <cstdlib>
 #include <cstring>
+#include <ctime>
+#include <mutex>
+#include <string>
+#include <thread>
+#include <vector>
 
 // Platform-specific includes
 #ifdef _WIN32
     #include <windows.h>
     #include <sysinfoapi.h>
+    #include <winternl.h>
 #elif __APPLE__
     #include <sys/types.h>
     #include <sys/sysctl.h>
     #include <unistd.h>
+    #include <mach/mach.h>
+    #include <CoreFoundation/CoreFoundation.h>
+    #include <IOKit/ps/IOPowerSources.h>
+    #include <IOKit/ps/IOPSKeys.h>
 #else // Linux
     #include <sys/sysinfo.h>
     #include <unistd.h>
     #include <limits.h>
+    #include <dirent.h>
+    #include <fstream>
+    #include <set>
+    #include <sstream>
+    #include <utility>
 #endif
 
 // Template-based factorial calculator
@@ -94,3 +111,554 @@ uint64_t CalculateFactorial(int n) {
     // For demonstration, we'll use runtime calculation
     return calculateFactorialRuntime(n);
 }
+
+// Copy a string into a caller-provided buffer; fails if it doesn't fit with its terminator
+static bool copyString(const std::string& value, char* buffer, int bufferSize) {
+    if (buffer == nullptr || bufferSize <= 0 || value.size() >= static_cast<size_t>(bufferSize)) {
+        return false;
+    }
+    std::memcpy(buffer, value.c_str(), value.size() + 1);
+    return true;
+}
+
+#ifdef _WIN32
+static const char* CPU_REGISTRY_KEY = "HARDWARE\\DESCRIPTION\\System\\CentralProcessor\\0";
+
+static std::string readCpuRegistryString(const char* name) {
+    char value[256];
+    DWORD size = sizeof(value);
+    if (RegGetValueA(HKEY_LOCAL_MACHINE, CPU_REGISTRY_KEY, name, RRF_RT_REG_SZ, nullptr, value,
+                     &size) != ERROR_SUCCESS) {
+        return std::string();
+    }
+    std::string result(value);
+    // ProcessorNameString is padded with spaces on some CPUs
+    size_t start = result.find_first_not_of(' ');
+    return start == std::string::npos ? std::string() : result.substr(start);
+}
+#elif __APPLE__
+static std::string readSysctlString(const char* name) {
+    size_t length = 0;
+    if (sysctlbyname(name, nullptr, &length, nullptr, 0) != 0 || length == 0) {
+        return std::string();
+    }
+    std::vector<char> value(length);
+    if (sysctlbyname(name, value.data(), &length, nullptr, 0) != 0) {
+        return std::string();
+    }
+    return std::string(value.data());
+}
+
+static uint64_t readSysctlNumber(const char* name) {
+    uint64_t value = 0;
+    size_t length = sizeof(value);
+    if (sysctlbyname(name, &value, &length, nullptr, 0) != 0) {
+        return 0;
+    }
+    // Some values are 32-bit; the upper bytes stay zero
+    return value;
+}
+#else // Linux
+// First value of `key` in /proc/cpuinfo, e.g. "model name : Intel(R) ..."
+static std::string readCpuInfoField(const char* key) {
+    std::ifstream cpuinfo("/proc/cpuinfo");
+    std::string line;
+    while (std::getline(cpuinfo, line)) {
+        size_t colon = line.find(':');
+        if (colon == std::string::npos) {
+            continue;
+        }
+        std::string name = line.substr(0, colon);
+        name.erase(name.find_last_not_of(" \t") + 1);
+        if (name == key) {
+            size_t start = line.find_first_not_of(' ', colon + 1);
+            return start == std::string::npos ? std::string() : line.substr(start);
+        }
+    }
+    return std::string();
+}
+#endif
+
+// Get CPU model name
+bool GetCpuModelString(char* buffer, int bufferSize) {
+#ifdef _WIN32
+    std::string model = readCpuRegistryString("ProcessorNameString");
+#elif __APPLE__
+    std::string model = readSysctlString("machdep.cpu.brand_string");
+#else // Linux
+    std::string model = readCpuInfoField("model name");
+    if (model.empty()) {
+        // ARM kernels name the board or core instead
+        model = readCpuInfoField("Hardware");
+    }
+    if (model.empty()) {
+        model = readCpuInfoField("Processor");
+    }
+#endif
+    return !model.empty() && copyString(model, buffer, bufferSize);
+}
+
+// Get CPU vendor
+bool GetCpuVendorString(char* buffer, int bufferSize) {
+#ifdef _WIN32
+    std::string vendor = readCpuRegistryString("VendorIdentifier");
+#elif __APPLE__
+    std::string vendor = readSysctlString("machdep.cpu.vendor");
+    #ifdef __aarch64__
+    if (vendor.empty()) {
+        vendor = "Apple";
+    }
+    #endif
+#else // Linux
+    std::string vendor = readCpuInfoField("vendor_id");
+    if (vendor.empty()) {
+        // ARM reports an implementer code instead, e.g. 0x41 for Arm
+        vendor = readCpuInfoField("CPU implementer");
+    }
+#endif
+    return !vendor.empty() && copyString(vendor, buffer, bufferSize);
+}
+
+// Get physical core count
+uint32_t GetPhysicalCoreCount() {
+#ifdef _WIN32
+    DWORD length = 0;
+    GetLogicalProcessorInformationEx(RelationProcessorCore, nullptr, &length);
+    if (length == 0) {
+        return 0;
+    }
+    std::vector<char> buffer(length);
+    auto* info = reinterpret_cast<SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX*>(buffer.data());
+    if (!GetLogicalProcessorInformationEx(RelationProcessorCore, info, &length)) {
+        return 0;
+    }
+    // One variable-length record per physical core
+    uint32_t cores = 0;
+    for (DWORD offset = 0; offset < length;) {
+        auto* record =
+            reinterpret_cast<SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX*>(buffer.data() + offset);
+        ++cores;
+        offset += record->Size;
+    }
+    return cores;
+#elif __APPLE__
+    return static_cast<uint32_t>(readSysctlNumber("hw.physicalcpu"));
+#else // Linux
+    // Count distinct (physical id, core id) pairs
+    std::ifstream cpuinfo("/proc/cpuinfo");
+    std::set<std::pair<std::string, std::string>> cores;
+    std::string line, physicalId;
+    while (std::getline(cpuinfo, line)) {
+        size_t colon = line.find(':');
+        if (colon == std::string::npos) {
+            continue;
+        }
+        std::string value = colon + 2 <= line.size() ? line.substr(colon + 2) : std::string();
+        if (line.rfind("physical id", 0) == 0) {
+            physicalId = value;
+        } else if (line.rfind("core id", 0) == 0) {
+            cores.insert(std::make_pair(physicalId, value));
+        }
+    }
+    // Kernels that don't report topology get the logical count
+    return cores.empty() ? GetLogicalCoreCount() : static_cast<uint32_t>(cores.size());
+#endif
+}
+
+// Get logical processor count
+uint32_t GetLogicalCoreCount() {
+#ifdef _WIN32
+    return static_cast<uint32_t>(GetActiveProcessorCount(ALL_PROCESSOR_GROUPS));
+#elif __APPLE__
+    return static_cast<uint32_t>(readSysctlNumber("hw.logicalcpu"));
+#else // Linux
+    long count = sysconf(_SC_NPROCESSORS_ONLN);
+    return count > 0 ? static_cast<uint32_t>(count) : 0;
+#endif
+}
+
+// Get base CPU frequency in MHz
+uint64_t GetCpuBaseFrequencyMHz() {
+#ifdef _WIN32
+    DWORD mhz = 0;
+    DWORD size = sizeof(mhz);
+    if (RegGetValueA(HKEY_LOCAL_MACHINE, CPU_REGISTRY_KEY, "~MHz", RRF_RT_REG_DWORD, nullptr, &mhz,
+                     &size) != ERROR_SUCCESS) {
+        return 0;
+    }
+    return mhz;
+#elif __APPLE__
+    // Only Intel Macs report a nominal frequency
+    return readSysctlNumber("hw.cpufrequency") / 1000000;
+#else // Linux
+    // Written by intel_pstate and amd-pstate, in kHz
+    std::ifstream base("/sys/devices/system/cpu/cpu0/cpufreq/base_frequency");
+    uint64_t khz = 0;
+    if (base >> khz) {
+        return khz / 1000;
+    }
+    return 0;
+#endif
+}
+
+// Busy and total time of one core, in whatever units the OS counts in
+struct CoreTimes {
+    uint64_t busy;
+    uint64_t total;
+};
+
+static bool readCoreTimes(std::vector<CoreTimes>& cores) {
+    cores.clear();
+#ifdef _WIN32
+    using NtQuerySystemInformationFn = NTSTATUS(NTAPI*)(SYSTEM_INFORMATION_CLASS, PVOID, ULONG, PULONG);
+    static auto query = reinterpret_cast<NtQuerySystemInformationFn>(
+        GetProcAddress(GetModuleHandleA("ntdll.dll"), "NtQuerySystemInformation"));
+    if (query == nullptr) {
+        return false;
+    }
+    std::vector<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION> info(GetActiveProcessorCount(ALL_PROCESSOR_GROUPS));
+    ULONG length = 0;
+    if (query(SystemProcessorPerformanceInformation, info.data(),
+              static_cast<ULONG>(info.size() * sizeof(info[0])), &length) != 0) {
+        return false;
+    }
+    info.resize(length / sizeof(info[0]));
+    for (const auto& core : info) {
+        // Kernel time includes idle time
+        uint64_t total = core.KernelTime.QuadPart + core.UserTime.QuadPart;
+        cores.push_back({total - core.IdleTime.QuadPart, total});
+    }
+#elif __APPLE__
+    natural_t count = 0;
+    processor_info_array_t info = nullptr;
+    mach_msg_type_number_t infoCount = 0;
+    if (host_processor_info(mach_host_self(), PROCESSOR_CPU_LOAD_INFO, &count, &info, &infoCount) !=
+        KERN_SUCCESS) {
+        return false;
+    }
+    auto* load = reinterpret_cast<processor_cpu_load_info_t>(info);
+    for (natural_t i = 0; i < count; ++i) {
+        const auto& ticks = load[i].cpu_ticks;
+        uint64_t busy = static_cast<uint64_t>(ticks[CPU_STATE_USER]) + ticks[CPU_STATE_SYSTEM] +
+                        ticks[CPU_STATE_NICE];
+        cores.push_back({busy, busy + ticks[CPU_STATE_IDLE]});
+    }
+    vm_deallocate(mach_task_self(), reinterpret_cast<vm_address_t>(info),
+                  infoCount * sizeof(integer_t));
+#else // Linux
+    // "cpuN user nice system idle iowait irq softirq steal ..." in clock ticks
+    std::ifstream stat("/proc/stat");
+    std::string line;
+    while (std::getline(stat, line)) {
+        if (line.rfind("cpu", 0) != 0 || line.size() < 4 || line[3] < '0' || line[3] > '9') {
+            continue;
+        }
+        std::istringstream fields(line.substr(line.find(' ')));
+        uint64_t value = 0, total = 0, idle = 0;
+        for (int column = 0; column < 8 && fields >> value; ++column) {
+            total += value;
+            if (column == 3 || column == 4) {
+                idle += value;
+            }
+        }
+        cores.push_back({total - idle, total});
+    }
+#endif
+    return !cores.empty();
+}
+
+// Get per-core utilization since the previous call
+int GetCpuCoreUsage(double* usage, int count) {
+    static std::mutex lock;
+    static std::vector<CoreTimes> previous;
+    std::lock_guard<std::mutex> guard(lock);
+
+    std::vector<CoreTimes> current;
+    if (previous.empty()) {
+        if (!readCoreTimes(previous)) {
+            return -1;
+        }
+        std::this_thread::sleep_for(std::chrono::milliseconds(200));
+    }
+    if (!readCoreTimes(current)) {
+        return -1;
+    }
+
+    int cores = static_cast<int>(current.size());
+    for (int i = 0; i < cores && i < count && usage != nullptr; ++i) {
+        // Cores that came online since the last call have no baseline yet
+        double percent = 0.0;
+        if (static_cast<size_t>(i) < previous.size() && current[i].total > previous[i].total) {
+            uint64_t busy = current[i].busy - previous[i].busy;
+            uint64_t total = current[i].total - previous[i].total;
+            percent = 100.0 * static_cast<double>(busy) / static_cast<double>(total);
+        }
+        usage[i] = percent;
+    }
+    previous = current;
+    return cores;
+}
+
+#ifdef __APPLE__
+static int32_t readDictionaryNumber(CFDictionaryRef dictionary, CFStringRef key, int32_t fallback) {
+    auto number = static_cast<CFNumberRef>(CFDictionaryGetValue(dictionary, key));
+    int32_t value = fallback;
+    if (number == nullptr || !CFNumberGetValue(number, kCFNumberSInt32Type, &value)) {
+        return fallback;
+    }
+    return value;
+}
+
+static bool readDictionaryBool(CFDictionaryRef dictionary, CFStringRef key) {
+    auto value = static_cast<CFBooleanRef>(CFDictionaryGetValue(dictionary, key));
+    return value != nullptr && CFBooleanGetValue(value);
+}
+#elif !defined(_WIN32) // Linux
+// Contents of /sys/class/power_supply/<supply>/<file>, without the trailing newline
+static std::string readPowerSupplyFile(const std::string& supply, const char* file) {
+    std::ifstream input("/sys/class/power_supply/" + supply + "/" + file);
+    std::string value;
+    std::getline(input, value);
+    return value;
+}
+
+static int64_t readPowerSupplyNumber(const std::string& supply, const char* file) {
+    std::string value = readPowerSupplyFile(supply, file);
+    try {
+        return value.empty() ? -1 : std::stoll(value);
+    } catch (...) {
+        return -1;
+    }
+}
+#endif
+
+// Get battery and AC adapter state
+bool GetPowerStatus(SystemPowerStatus* status) {
+    if (status == nullptr) {
+        return false;
+    }
+    *status = {0, -1, SYSTEMAPI_CHARGE_UNKNOWN, -1, -1};
+#ifdef _WIN32
+    SYSTEM_POWER_STATUS power;
+    if (!GetSystemPowerStatus(&power)) {
+        return false;
+    }
+    // 255 and -1 mean unknown; battery flag 128 means there is no battery
+    status->acOnline = power.ACLineStatus == 255 ? -1 : power.ACLineStatus;
+    status->hasBattery = power.BatteryFlag != 255 && (power.BatteryFlag & 128) == 0;
+    if (!status->hasBattery) {
+        return true;
+    }
+    if (power.BatteryLifePercent != 255) {
+        status->batteryPercent = power.BatteryLifePercent;
+    }
+    if (power.BatteryFlag & 8) {
+        status->chargeState = SYSTEMAPI_CHARGE_CHARGING;
+    } else if (status->acOnline == 1) {
+        status->chargeState = status->batteryPercent == 100 ? SYSTEMAPI_CHARGE_FULL
+                                                             : SYSTEMAPI_CHARGE_NOT_CHARGING;
+    } else if (status->acOnline == 0) {
+        status->chargeState = SYSTEMAPI_CHARGE_DISCHARGING;
+    }
+    // Windows only estimates the time left on battery
+    if (status->chargeState == SYSTEMAPI_CHARGE_DISCHARGING && power.BatteryLifeTime != static_cast<DWORD>(-1)) {
+        status->secondsRemaining = power.BatteryLifeTime;
+    }
+    return true;
+#elif __APPLE__
+    CFTypeRef info = IOPSCopyPowerSourcesInfo();
+    if (info == nullptr) {
+        return false;
+    }
+    CFStringRef providing = IOPSGetProvidingPowerSourceType(info);
+    if (providing != nullptr) {
+        status->acOnline = CFStringCompare(providing, CFSTR(kIOPMACPowerKey), 0) == kCFCompareEqualTo;
+    }
+
+    CFArrayRef sources = IOPSCopyPowerSourcesList(info);
+    CFIndex count = sources == nullptr ? 0 : CFArrayGetCount(sources);
+    for (CFIndex i = 0; i < count; ++i) {
+        CFDictionaryRef source = IOPSGetPowerSourceDescription(info, CFArrayGetValueAtIndex(sources, i));
+        if (source == nullptr) {
+            continue;
+        }
+        auto type = static_cast<CFStringRef>(CFDictionaryGetValue(source, CFSTR(kIOPSTypeKey)));
+        if (type == nullptr || CFStringCompare(type, CFSTR(kIOPSInternalBatteryType), 0) != kCFCompareEqualTo) {
+            continue;
+        }
+        status->hasBattery = 1;
+        int32_t current = readDictionaryNumber(source, CFSTR(kIOPSCurrentCapacityKey), -1);
+        int32_t maximum = readDictionaryNumber(source, CFSTR(kIOPSMaxCapacityKey), -1);
+        if (current >= 0 && maximum > 0) {
+            status->batteryPercent = current * 100 / maximum;
+        }
+
+        bool charging = readDictionaryBool(source, CFSTR(kIOPSIsChargingKey));
+        if (charging) {
+            status->chargeState = SYSTEMAPI_CHARGE_CHARGING;
+        } else if (readDictionaryBool(source, CFSTR(kIOPSIsChargedKey))) {
+            status->chargeState = SYSTEMAPI_CHARGE_FULL;
+        } else if (status->acOnline == 1) {
+            status->chargeState = SYSTEMAPI_CHARGE_NOT_CHARGING;
+        } else {
+            status->chargeState = SYSTEMAPI_CHARGE_DISCHARGING;
+        }
+        // Minutes, or -1 while the estimate is still being calculated
+        int32_t minutes = readDictionaryNumber(
+            source, charging ? CFSTR(kIOPSTimeToFullChargeKey) : CFSTR(kIOPSTimeToEmptyKey), -1);
+        if (minutes >= 0 && status->chargeState != SYSTEMAPI_CHARGE_FULL) {
+            status->secondsRemaining = static_cast<int64_t>(minutes) * 60;
+        }
+        break;
+    }
+    if (sources != nullptr) {
+        CFRelease(sources);
+    }
+    CFRelease(info);
+    return true;
+#else // Linux
+    DIR* dir = opendir("/sys/class/power_supply");
+    if (dir == nullptr) {
+        return false;
+    }
+    // Energy (µWh) or charge (µAh) totals across batteries, with the matching rate (µW or µA)
+    int64_t now = 0, full = 0, rate = 0;
+    bool anyCharging = false, anyDischarging = false, allFull = true, anyOnline = false, anyMains = false;
+    while (dirent* entry = readdir(dir)) {
+        std::string supply = entry->d_name;
+        if (supply[0] == '.') {
+            continue;
+        }
+        std::string type = readPowerSupplyFile(supply, "type");
+        if (type == "Mains" || type == "USB") {
+            anyMains = true;
+            anyOnline = anyOnline || readPowerSupplyNumber(supply, "online") == 1;
+            continue;
+        }
+        if (type != "Battery" || readPowerSupplyNumber(supply, "present") == 0) {
+            continue;
+        }
+        status->hasBattery = 1;
+        int64_t batteryNow = readPowerSupplyNumber(supply, "energy_now");
+        int64_t batteryFull = readPowerSupplyNumber(supply, "energy_full");
+        int64_t batteryRate = readPowerSupplyNumber(supply, "power_now");
+        if (batteryNow < 0 || batteryFull <= 0) {
+            batteryNow = readPowerSupplyNumber(supply, "charge_now");
+            batteryFull = readPowerSupplyNumber(supply, "charge_full");
+            batteryRate = readPowerSupplyNumber(supply, "current_now");
+        }
+        if (batteryNow >= 0 && batteryFull > 0) {
+            now += batteryNow;
+            full += batteryFull;
+            // Some drivers report the rate as negative while discharging
+            rate += batteryRate < 0 ? -batteryRate : batteryRate;
+        }
+
+        std::string state = readPowerSupplyFile(supply, "status");
+        anyCharging = anyCharging || state == "Charging";
+        anyDischarging = anyDischarging || state == "Discharging";
+        allFull = allFull && state == "Full";
+    }
+    closedir(dir);
+
+    if (anyMains) {
+        status->acOnline = anyOnline ? 1 : 0;
+    }
+    if (!status->hasBattery) {
+        return true;
+    }
+    if (full > 0) {
+        status->batteryPercent = static_cast<int32_t>(now * 100 / full);
+        if (status->batteryPercent > 100) {
+            status->batteryPercent = 100;
+        }
+    }
+    if (anyCharging) {
+        status->chargeState = SYSTEMAPI_CHARGE_CHARGING;
+    } else if (anyDischarging) {
+        status->chargeState = SYSTEMAPI_CHARGE_DISCHARGING;
+    } else if (allFull) {
+        status->chargeState = SYSTEMAPI_CHARGE_FULL;
+    } else if (status->acOnline == 1) {
+        status->chargeState = SYSTEMAPI_CHARGE_NOT_CHARGING;
+    }
+    if (rate > 0) {
+        if (status->chargeState == SYSTEMAPI_CHARGE_DISCHARGING) {
+            status->secondsRemaining = now * 3600 / rate;
+        } else if (status->chargeState == SYSTEMAPI_CHARGE_CHARGING) {
+            status->secondsRemaining = (full - now) * 3600 / rate;
+        }
+    }
+    return true;
+#endif
+}
+
+#ifndef SYSTEMAPI_VERSION
+    #define SYSTEMAPI_VERSION "unknown"
+#endif
+
+// Every allocating export hands out memory from malloc so FreeBuffer can release it
+void FreeBuffer(void* buffer) {
+    std::free(buffer);
+}
+
+// Copy `text` into a buffer the caller releases with FreeBuffer
+static char* AllocateString(const std::string& text) {
+    char* buffer = static_cast<char*>(std::malloc(text.size() + 1));
+    if (buffer != nullptr) {
+        std::memcpy(buffer, text.c_str(), text.size() + 1);
+    }
+    return buffer;
+}
+
+// Get the library version
+char* GetLibraryVersionString() {
+    return AllocateString(SYSTEMAPI_VERSION);
+}
+
+// Get time since boot and load averages
+bool GetUptimeInfo(SystemUptimeInfo* info) {
+    if (info == nullptr) {
+        return false;
+    }
+    *info = {0, 0, -1.0, -1.0, -1.0};
+#ifdef _WIN32
+    info->uptimeSeconds = GetTickCount64() / 1000;
+    info->bootTime = static_cast<int64_t>(std::time(nullptr)) - static_cast<int64_t>(info->uptimeSeconds);
+    return true;
+#elif __APPLE__
+    int mib[2] = { CTL_KERN, KERN_BOOTTIME };
+    struct timeval boot;
+    size_t length = sizeof(boot);
+    if (sysctl(mib, 2, &boot, &length, nullptr, 0) != 0 || boot.tv_sec == 0) {
+        return false;
+    }
+    info->bootTime = boot.tv_sec;
+    info->uptimeSeconds = static_cast<uint64_t>(std::time(nullptr) - boot.tv_sec);
+    double loads[3];
+    if (getloadavg(loads, 3) == 3) {
+        info->loadAverage1 = loads[0];
+        info->loadAverage5 = loads[1];
+        info->loadAverage15 = loads[2];
+    }
+    return true;
+#else // Linux
+    struct sysinfo system;
+    if (sysinfo(&system) != 0) {
+        return false;
+    }
+    info->uptimeSeconds = static_cast<uint64_t>(system.uptime);
+    info->bootTime = static_cast<int64_t>(std::time(nullptr)) - system.uptime;
+    // Fixed point with SI_LOAD_SHIFT fractional bits
+    const double scale = static_cast<double>(1 << SI_LOAD_SHIFT);
+    info->loadAverage1 = system.loads[0] / scale;
+    info->loadAverage5 = system.loads[1] / scale;
+    info->loadAverage15 = system.loads[2] / scale;
+    return true;
+#endif
+}
+
+// Get the ABI version from systemapi.h
+uint32_t GetApiVersion() {
+    return (static_cast<uint32_t>(SYSTEMAPI_API_VERSION_MAJOR) << 16) | SYSTEMAPI_API_VERSION_MINOR;
+}
@@ -0,0 +1,67 @@
+// Compares the lane-parallel rollup kernels against the single-accumulator version.
+// Run with `cargo bench --bench stats`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use system_info_app_lib::stats;
+
+const SAMPLES: usize = 4 * 1024 * 1024;
+const ROUNDS: u32 = 20;
+
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    // Warm up caches before measuring
+    black_box(f());
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(f());
+    }
+    start.elapsed() / ROUNDS
+}
+
+fn main() {
+    // Deterministic pseudo-random samples so runs are comparable
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let values: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 10_000) as f64 / 100.0
+        })
+        .collect();
+
+    let scalar = time(|| stats::summarize_scalar(black_box(&values)));
+    let simd = time(|| stats::summarize(black_box(&values)));
+
+    let expected = stats::summarize_scalar(&values).unwrap();
+    let actual = stats::summarize(&values).unwrap();
+    assert_eq!((expected.min, expected.max), (actual.min, actual.max));
+    assert!((expected.sum - actual.sum).abs() / expected.sum < 1e-9);
+
+    let mut scratch = values.clone();
+    let percentile = time(|| {
+        scratch.copy_from_slice(&values);
+        stats::percentile(&mut scratch, 95.0)
+    });
+    let mut sorted = values.clone();
+    let sort = time(|| {
+        sorted.copy_from_slice(&values);
+        sorted.sort_unstable_by(f64::total_cmp);
+        sorted[(SAMPLES - 1) * 95 / 100]
+    });
+
+    println!("{} samples, mean of {} rounds", SAMPLES, ROUNDS);
+    println!("summarize (scalar)  {:>10.2?}", scalar);
+    println!(
+        "summarize (simd)    {:>10.2?}  {:.2}x",
+        simd,
+        scalar.as_secs_f64() / simd.as_secs_f64()
+    );
+    println!("p95 (full sort)     {:>10.2?}", sort);
+    println!(
+        "p95 (quickselect)   {:>10.2?}  {:.2}x",
+        percentile,
+        sort.as_secs_f64() / percentile.as_secs_f64()
+    );
+}
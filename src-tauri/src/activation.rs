@@ -0,0 +1,147 @@
+// OS licensing and activation status.
+// Windows: the `SoftwareLicensingProduct` entry for the installed edition (the same data
+// `slmgr /dli` shows). macOS and most Linux distributions have no activation, so they report
+// their edition only; RHEL-style systems report their subscription status when
+// `subscription-manager` is installed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivationState {
+    Licensed,
+    Unlicensed,
+    // Grace period after install or a hardware change
+    GracePeriod,
+    Notification,
+    // The OS has no activation
+    NotApplicable,
+    Unknown,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivationStatus {
+    pub state: ActivationState,
+    // Edition or product name, e.g. `Windows(R), Professional edition`
+    pub edition: Option<String>,
+    // Retail, OEM, volume (KMS/MAK) and so on
+    pub license_channel: Option<String>,
+    // Last five characters of the product key, as Windows shows them
+    pub partial_product_key: Option<String>,
+    // Minutes left in the grace period, when in one
+    pub grace_minutes_remaining: Option<u32>,
+    pub details: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LicensingProduct {
+    name: Option<String>,
+    description: Option<String>,
+    license_status: u32,
+    partial_product_key: Option<String>,
+    grace_period_remaining: Option<u32>,
+}
+
+// `LicenseStatus` values documented for SoftwareLicensingProduct
+fn windows_state(status: u32) -> ActivationState {
+    match status {
+        0 => ActivationState::Unlicensed,
+        1 => ActivationState::Licensed,
+        2..=4 | 6 => ActivationState::GracePeriod,
+        5 => ActivationState::Notification,
+        _ => ActivationState::Unknown,
+    }
+}
+
+// Descriptions look like `Windows(R) Operating System, RETAIL channel`
+fn license_channel(description: &str) -> Option<String> {
+    let (_, channel) = description.rsplit_once(", ")?;
+    Some(channel.trim_end_matches(" channel").to_string())
+}
+
+fn windows_status() -> Result<ActivationStatus, String> {
+    // The product with a key installed is the OS edition; Office and add-ons share the class
+    let script = "ConvertTo-Json -Compress -InputObject @(Get-CimInstance \
+                  -ClassName SoftwareLicensingProduct -Filter \"PartialProductKey IS NOT NULL AND \
+                  Name LIKE 'Windows%'\" | Select-Object Name, Description, LicenseStatus, \
+                  PartialProductKey, GracePeriodRemaining)";
+    let output = run_text("powershell", &["-NoProfile", "-Command", script])?;
+    let products: Vec<LicensingProduct> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+    let product = products
+        .into_iter()
+        .next()
+        .ok_or("No Windows product key is installed")?;
+
+    let state = windows_state(product.license_status);
+    Ok(ActivationStatus {
+        state,
+        edition: product.name,
+        license_channel: product.description.as_deref().and_then(license_channel),
+        partial_product_key: product.partial_product_key,
+        grace_minutes_remaining: product
+            .grace_period_remaining
+            .filter(|_| state == ActivationState::GracePeriod),
+        details: product.description,
+    })
+}
+
+fn os_edition() -> Option<String> {
+    sysinfo::System::long_os_version()
+}
+
+// `subscription-manager status` prints `Overall Status: Current` (or `Invalid`, `Disabled`...)
+fn subscription_status() -> Option<ActivationStatus> {
+    let output = run_text("subscription-manager", &["status"]).ok()?;
+    let overall = output.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Overall Status:")
+            .map(|status| status.trim().to_string())
+    })?;
+    let state = match overall.as_str() {
+        "Current" => ActivationState::Licensed,
+        // Simple Content Access: no per-system subscription to attach
+        "Disabled" => ActivationState::NotApplicable,
+        _ => ActivationState::Unlicensed,
+    };
+    Some(ActivationStatus {
+        state,
+        edition: os_edition(),
+        license_channel: Some("subscription".to_string()),
+        partial_product_key: None,
+        grace_minutes_remaining: None,
+        details: Some(format!("Overall Status: {}", overall)),
+    })
+}
+
+fn read_status() -> Result<ActivationStatus, String> {
+    if cfg!(target_os = "windows") {
+        return windows_status();
+    }
+    if cfg!(target_os = "linux") {
+        if let Some(status) = subscription_status() {
+            return Ok(status);
+        }
+    }
+    Ok(ActivationStatus {
+        state: ActivationState::NotApplicable,
+        edition: os_edition(),
+        license_channel: None,
+        partial_product_key: None,
+        grace_minutes_remaining: None,
+        details: None,
+    })
+}
+
+#[tauri::command]
+pub async fn get_activation_status() -> Result<ActivationStatus, CommandError> {
+    let status = tauri::async_runtime::spawn_blocking(read_status)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(status)
+}
@@ -0,0 +1,429 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::config::ConfigState;
+use crate::discovery::Discovery;
+use crate::error::{CommandError, ErrorCode};
+use crate::history::{Aggregation, GroupBy, History, TimeRange};
+use crate::monitor::{self, Monitoring};
+use crate::share::{ShareLinks, SHARE_ROUTE};
+use crate::{read_computer_name, read_total_memory, CppLibrary};
+
+// Default port for the remote agent endpoint (also advertised over mDNS)
+pub const AGENT_PORT: u16 = 47820;
+
+const METRICS_ROUTE: &str = "/api/metrics";
+const HISTORY_ROUTE: &str = "/api/history";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+// Header carrying `agent.token` on history requests
+const TOKEN_HEADER: &str = "X-Agent-Token";
+
+// `[agent]` settings. The agent listens on localhost only unless `address` names another
+// interface, e.g. "0.0.0.0" so other machines can compare against this one.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgentConfig {
+    pub address: String,
+    // Shared secret callers send as `X-Agent-Token` to query history; the history route is
+    // refused while it is unset
+    pub token: Option<String>,
+    // Find other instances over mDNS and announce this one while the agent runs (discovery.rs)
+    pub discovery: bool,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        AgentConfig {
+            address: "127.0.0.1".to_string(),
+            token: None,
+            discovery: false,
+        }
+    }
+}
+
+// Metrics served to remote peers, keyed by metric name
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachineMetrics {
+    pub hostname: String,
+    pub platform: String,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+// One row of the comparison table; `deltas` are relative to the first reachable machine
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachineComparison {
+    pub host: String,
+    pub hostname: Option<String>,
+    pub platform: Option<String>,
+    pub values: Vec<Option<f64>>,
+    pub deltas: Vec<Option<f64>>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonTable {
+    pub metrics: Vec<String>,
+    pub baseline_host: Option<String>,
+    pub machines: Vec<MachineComparison>,
+}
+
+// Query string accepted by the history route, e.g. `?metric=x&from=0&to=60&groupBy=minute`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryParams {
+    metric: String,
+    from: i64,
+    to: i64,
+    aggregation: Option<Aggregation>,
+    group_by: Option<GroupBy>,
+}
+
+// Running agent HTTP server, if any
+#[derive(Default)]
+pub struct AgentServer {
+    server: Mutex<Option<(u16, Arc<Server>)>>,
+}
+
+impl AgentServer {
+    // Port the agent is listening on, None while it isn't running
+    pub fn port(&self) -> Option<u16> {
+        self.server.lock().ok()?.as_ref().map(|(port, _)| *port)
+    }
+}
+
+// Hardware totals, load from the latest monitoring tick, and uptime and load averages
+fn local_metrics(app: &AppHandle) -> MachineMetrics {
+    let lib_state: &CppLibrary = &app.state::<CppLibrary>();
+    let mut metrics = BTreeMap::new();
+    let mut insert = |name: &str, value: f64| {
+        metrics.insert(name.to_string(), value);
+    };
+
+    if let Ok(total_memory) = read_total_memory(lib_state) {
+        insert("total_memory", total_memory as f64);
+    }
+    if let Ok(cpus) = std::thread::available_parallelism() {
+        insert("logical_cpus", cpus.get() as f64);
+    }
+    if let Some(tick) = app.state::<Monitoring>().latest() {
+        insert("cpu_usage", tick.cpu_usage as f64);
+        insert("used_memory", tick.used_memory as f64);
+        insert("available_memory", tick.available_memory as f64);
+        insert("process_count", tick.process_count as f64);
+    }
+    if let Ok(uptime) = lib_state.call("get_uptime_info", |provider| provider.uptime_info()) {
+        insert("uptime_seconds", uptime.uptime_seconds as f64);
+        if let Some(load) = uptime.load_average {
+            insert("load_average_1", load.one);
+            insert("load_average_5", load.five);
+            insert("load_average_15", load.fifteen);
+        }
+    }
+
+    MachineMetrics {
+        hostname: read_computer_name(lib_state)
+            .unwrap_or_else(|_| gethostname::gethostname().to_string_lossy().into_owned()),
+        platform: std::env::consts::OS.to_string(),
+        metrics,
+    }
+}
+
+fn agent_config(app: &AppHandle) -> AgentConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.agent.clone())
+        .unwrap_or_default()
+}
+
+// History can reveal what ran on the machine and when, so it needs the shared token
+fn check_token(app: &AppHandle, request: &Request) -> Result<(), (u16, String)> {
+    let Some(token) = agent_config(app).token.filter(|token| !token.is_empty()) else {
+        return Err((
+            403,
+            "History is disabled until agent.token is set".to_string(),
+        ));
+    };
+    let sent = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(TOKEN_HEADER))
+        .map(|header| header.value.as_str());
+    if sent != Some(token.as_str()) {
+        return Err((401, format!("Missing or wrong {}", TOKEN_HEADER)));
+    }
+    Ok(())
+}
+
+fn serve(app: AppHandle, server: Arc<Server>) {
+    let json_header = Header::from_bytes("Content-Type", "application/json").unwrap();
+
+    for request in server.incoming_requests() {
+        let (route, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+
+        if let Some(token) = route.strip_prefix(SHARE_ROUTE) {
+            let response = match app.state::<ShareLinks>().take(token) {
+                Some((content, content_type)) => Response::from_string(content)
+                    .with_header(Header::from_bytes("Content-Type", content_type).unwrap()),
+                // Unknown, expired and already fetched links look the same
+                None => Response::from_string("Not found").with_status_code(404),
+            };
+            if let Err(e) = request.respond(response) {
+                eprintln!("Agent failed to respond: {}", e);
+            }
+            continue;
+        }
+
+        let body = match route {
+            METRICS_ROUTE => {
+                serde_json::to_string(&local_metrics(&app)).map_err(|e| (500, e.to_string()))
+            }
+            HISTORY_ROUTE => check_token(&app, &request)
+                .and_then(|()| {
+                    serde_urlencoded::from_str::<HistoryParams>(query)
+                        .map_err(|e| (400, e.to_string()))
+                })
+                .and_then(|params| {
+                    app.state::<History>()
+                        .query(
+                            &params.metric,
+                            TimeRange {
+                                from: params.from,
+                                to: params.to,
+                            },
+                            params.aggregation.unwrap_or_default(),
+                            params.group_by.unwrap_or_default(),
+                        )
+                        .map_err(|e| (400, e))
+                })
+                .and_then(|result| {
+                    serde_json::to_string(&result).map_err(|e| (500, e.to_string()))
+                }),
+            _ => Err((404, "Not found".to_string())),
+        };
+
+        let response = match body {
+            Ok(body) => Response::from_string(body).with_header(json_header.clone()),
+            Err((status, message)) => Response::from_string(message).with_status_code(status),
+        };
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Agent failed to respond: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn start_agent(
+    port: Option<u16>,
+    app: AppHandle,
+    agent: State<AgentServer>,
+    config: State<ConfigState>,
+) -> Result<u16, CommandError> {
+    let mut running = agent.server.lock().map_err(|_| "Agent lock poisoned")?;
+    if let Some((port, _)) = running.as_ref() {
+        return Ok(*port);
+    }
+
+    let port = port.unwrap_or(AGENT_PORT);
+    let address = config.get()?.agent.address.clone();
+    let server = Arc::new(
+        Server::http((address.as_str(), port))
+            .map_err(|e| format!("Failed to listen on {}:{}: {}", address, port, e))?,
+    );
+
+    // Announcing a loopback address would only point peers at themselves
+    let local = address == "localhost"
+        || address
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback());
+    if !local {
+        app.state::<Discovery>().advertise(port);
+    }
+
+    let worker = server.clone();
+    let handle = app.clone();
+    std::thread::spawn(move || serve(handle, worker));
+
+    *running = Some((port, server));
+    drop(running);
+
+    // Load metrics come from the monitoring ticks
+    if !app.state::<Monitoring>().is_running() {
+        monitor::start_monitoring(None, app.clone(), app.state(), config)?;
+    }
+    println!("✓ Remote agent listening on {}:{}", address, port);
+    Ok(port)
+}
+
+#[tauri::command]
+pub fn stop_agent(
+    agent: State<AgentServer>,
+    discovery: State<Discovery>,
+) -> Result<(), CommandError> {
+    let mut running = agent.server.lock().map_err(|_| "Agent lock poisoned")?;
+    if let Some((_, server)) = running.take() {
+        server.unblock();
+        discovery.withdraw();
+    }
+    Ok(())
+}
+
+// `host:port` to connect to for a host given as a name or IP address, with or without a port
+// (`[v6]:port` for IPv6); the agent port unless one is given
+fn agent_address(host: &str) -> String {
+    if let Ok(address) = host.parse::<SocketAddr>() {
+        return address.to_string();
+    }
+    let bare = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return SocketAddr::new(ip, AGENT_PORT).to_string();
+    }
+    match host.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_ok() => host.to_string(),
+        _ => format!("{}:{}", host, AGENT_PORT),
+    }
+}
+
+fn fetch_metrics(host: &str) -> Result<MachineMetrics, String> {
+    let address = agent_address(host);
+
+    let client: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into();
+
+    client
+        .get(format!("http://{}{}", address, METRICS_ROUTE))
+        .call()
+        .map_err(|e| e.to_string())?
+        .body_mut()
+        .read_json::<MachineMetrics>()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn compare_machines(
+    hosts: Vec<String>,
+    metrics: Vec<String>,
+) -> Result<ComparisonTable, CommandError> {
+    if hosts.is_empty() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "agent.noHosts"));
+    }
+
+    let table = tauri::async_runtime::spawn_blocking(move || build_comparison(hosts, metrics))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(table)
+}
+
+fn build_comparison(hosts: Vec<String>, metrics: Vec<String>) -> ComparisonTable {
+    // Query every machine concurrently so one slow host doesn't serialize the rest
+    let handles: Vec<_> = hosts
+        .iter()
+        .cloned()
+        .map(|host| std::thread::spawn(move || fetch_metrics(&host)))
+        .collect();
+    let results: Vec<Result<MachineMetrics, String>> = handles
+        .into_iter()
+        .map(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err("Request thread panicked".to_string()))
+        })
+        .collect();
+
+    // Default to every metric reported by any machine
+    let metrics = if metrics.is_empty() {
+        let mut all: Vec<String> = results
+            .iter()
+            .flatten()
+            .flat_map(|m| m.metrics.keys().cloned())
+            .collect();
+        all.sort();
+        all.dedup();
+        all
+    } else {
+        metrics
+    };
+
+    let baseline = results
+        .iter()
+        .position(|r| r.is_ok())
+        .map(|i| (hosts[i].clone(), results[i].as_ref().unwrap().clone()));
+
+    let machines = hosts
+        .into_iter()
+        .zip(results)
+        .map(|(host, result)| match result {
+            Ok(machine) => {
+                let values: Vec<Option<f64>> = metrics
+                    .iter()
+                    .map(|name| machine.metrics.get(name).copied())
+                    .collect();
+                let deltas = metrics
+                    .iter()
+                    .zip(&values)
+                    .map(|(name, value)| {
+                        let base = baseline.as_ref()?.1.metrics.get(name)?;
+                        Some(value.as_ref()? - base)
+                    })
+                    .collect();
+
+                MachineComparison {
+                    host,
+                    hostname: Some(machine.hostname),
+                    platform: Some(machine.platform),
+                    values,
+                    deltas,
+                    error: None,
+                }
+            }
+            Err(e) => MachineComparison {
+                host,
+                hostname: None,
+                platform: None,
+                values: vec![None; metrics.len()],
+                deltas: vec![None; metrics.len()],
+                error: Some(e),
+            },
+        })
+        .collect();
+
+    ComparisonTable {
+        metrics,
+        baseline_host: baseline.map(|(host, _)| host),
+        machines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_address_adds_the_default_port() {
+        assert_eq!(agent_address("office-pc"), "office-pc:47820");
+        assert_eq!(agent_address("office-pc:9000"), "office-pc:9000");
+        assert_eq!(agent_address("192.168.1.20"), "192.168.1.20:47820");
+        assert_eq!(agent_address("192.168.1.20:9000"), "192.168.1.20:9000");
+    }
+
+    #[test]
+    fn agent_address_brackets_ipv6() {
+        assert_eq!(agent_address("fe80::1"), "[fe80::1]:47820");
+        assert_eq!(agent_address("::1"), "[::1]:47820");
+        assert_eq!(agent_address("[::1]"), "[::1]:47820");
+        assert_eq!(agent_address("[::1]:9000"), "[::1]:9000");
+    }
+}
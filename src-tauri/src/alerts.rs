@@ -0,0 +1,286 @@
+// User-defined alert rules such as "memory usage above 90% for 30 seconds", kept in the
+// `[alerts]` table of config.toml. The monitoring loop evaluates every rule on each tick, so
+// rules only fire while `start_monitoring` is running. A rule fires once when its condition has
+// held for `durationSeconds`, emitting `alert-triggered` and, unless `notify` is off, showing an
+// OS notification; it can fire again after the condition has cleared.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::handles;
+use crate::history::{History, TimeRange};
+use crate::monitor::MonitorTick;
+
+pub const ALERT_TRIGGERED_EVENT: &str = "alert-triggered";
+
+const MAX_DURATION_SECONDS: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertMetric {
+    // Percent across all cores
+    CpuUsage,
+    // Percent of physical memory in use
+    MemoryUsage,
+    ProcessCount,
+    // This app's own resident memory, in bytes
+    AppMemory,
+    // Open file handles system-wide (Linux only) and of this app, as last sampled by handles.rs
+    SystemHandles,
+    AppHandles,
+}
+
+impl AlertMetric {
+    fn value(self, app: &AppHandle, tick: &MonitorTick) -> Option<f64> {
+        match self {
+            AlertMetric::CpuUsage => Some(tick.cpu_usage as f64),
+            AlertMetric::MemoryUsage => (tick.total_memory > 0)
+                .then(|| tick.used_memory as f64 / tick.total_memory as f64 * 100.0),
+            AlertMetric::ProcessCount => Some(tick.process_count as f64),
+            AlertMetric::AppMemory => tick.app_memory.map(|bytes| bytes as f64),
+            AlertMetric::SystemHandles => latest_sample(app, handles::SYSTEM_METRIC, tick),
+            AlertMetric::AppHandles => latest_sample(app, handles::APP_METRIC, tick),
+        }
+    }
+
+    fn is_percent(self) -> bool {
+        matches!(self, AlertMetric::CpuUsage | AlertMetric::MemoryUsage)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            AlertMetric::CpuUsage => "CPU usage",
+            AlertMetric::MemoryUsage => "Memory usage",
+            AlertMetric::ProcessCount => "Process count",
+            AlertMetric::AppMemory => "App memory",
+            AlertMetric::SystemHandles => "Open handles",
+            AlertMetric::AppHandles => "App handles",
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            AlertMetric::CpuUsage | AlertMetric::MemoryUsage => format!("{:.1}%", value),
+            AlertMetric::ProcessCount | AlertMetric::SystemHandles | AlertMetric::AppHandles => {
+                format!("{:.0}", value)
+            }
+            AlertMetric::AppMemory => format!("{:.0} MB", value / 1e6),
+        }
+    }
+}
+
+// Latest value of a metric sampled into history on its own schedule, rather than on the tick;
+// None once the sampler has fallen more than two intervals behind (stopped or paused)
+fn latest_sample(app: &AppHandle, metric: &str, tick: &MonitorTick) -> Option<f64> {
+    let to = tick.timestamp as i64 + 1;
+    let range = TimeRange {
+        from: to - 2 * handles::SAMPLE_INTERVAL.as_secs() as i64,
+        to,
+    };
+    let points = app.state::<History>().points(metric, range).ok()?;
+    points.last().map(|point| point.value)
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+// A rule as given to `add_alert_rule`
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertCondition {
+    pub metric: AlertMetric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    // How long the condition must hold before the rule fires; 0 fires on the first tick
+    #[serde(default)]
+    pub duration_seconds: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    pub id: u32,
+    #[serde(flatten)]
+    pub condition: AlertCondition,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AlertConfig {
+    // Show an OS notification when a rule fires
+    pub notify: bool,
+    pub rules: Vec<AlertRule>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        AlertConfig {
+            notify: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertTriggered {
+    pub rule: AlertRule,
+    pub timestamp: u64,
+    pub value: f64,
+}
+
+// Per-rule progress kept by the monitoring loop
+#[derive(Default)]
+pub struct RuleStates {
+    // Rule ID -> (when its condition started holding, whether it has fired since)
+    states: BTreeMap<u32, (u64, bool)>,
+}
+
+impl AlertCondition {
+    fn holds(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+
+    fn describe(&self, value: f64) -> String {
+        let comparison = match self.comparison {
+            Comparison::Above => "above",
+            Comparison::Below => "below",
+        };
+        let mut text = format!(
+            "{} is {} {} ({})",
+            self.metric.label(),
+            comparison,
+            self.metric.format(self.threshold),
+            self.metric.format(value)
+        );
+        if self.duration_seconds > 0 {
+            text.push_str(&format!(" for {} s", self.duration_seconds));
+        }
+        text
+    }
+}
+
+pub fn check_condition(condition: &AlertCondition) -> Result<(), CommandError> {
+    let invalid = |detail: &str| {
+        CommandError::new(ErrorCode::InvalidInput, "alert.invalidRule").with("detail", detail)
+    };
+    if !condition.threshold.is_finite() {
+        return Err(invalid("the threshold must be a number"));
+    }
+    if condition.metric.is_percent() && !(0.0..=100.0).contains(&condition.threshold) {
+        return Err(invalid("percentages must be between 0 and 100"));
+    }
+    if condition.duration_seconds > MAX_DURATION_SECONDS {
+        return Err(invalid("the duration can be at most a day"));
+    }
+    Ok(())
+}
+
+fn fire(app: &AppHandle, rule: &AlertRule, value: f64, timestamp: u64, notify: bool) {
+    let description = rule.condition.describe(value);
+    println!("Alert {}: {}", rule.id, description);
+    if notify {
+        if let Err(e) = app
+            .notification()
+            .builder()
+            .title("System alert")
+            .body(&description)
+            .show()
+        {
+            eprintln!("⚠ Warning: Failed to show alert notification: {}", e);
+        }
+    }
+    let triggered = AlertTriggered {
+        rule: rule.clone(),
+        timestamp,
+        value,
+    };
+    if let Err(e) = app.emit(ALERT_TRIGGERED_EVENT, triggered) {
+        eprintln!("⚠ Warning: Failed to emit {}: {}", ALERT_TRIGGERED_EVENT, e);
+    }
+}
+
+// Evaluate the configured rules against a tick, firing those whose condition has held long
+// enough. Reads the rules on every call so changes apply to a running monitor.
+pub fn evaluate(app: &AppHandle, tick: &MonitorTick, progress: &mut RuleStates) {
+    let config = app.state::<ConfigState>();
+    let Ok(alerts) = config.get().map(|config| config.alerts.clone()) else {
+        return;
+    };
+    progress
+        .states
+        .retain(|id, _| alerts.rules.iter().any(|rule| rule.id == *id));
+
+    for rule in &alerts.rules {
+        let value = rule.condition.metric.value(app, tick);
+        let Some(value) = value.filter(|&value| rule.condition.holds(value)) else {
+            progress.states.remove(&rule.id);
+            continue;
+        };
+        let (since, fired) = progress
+            .states
+            .entry(rule.id)
+            .or_insert((tick.timestamp, false));
+        if !*fired && tick.timestamp.saturating_sub(*since) >= rule.condition.duration_seconds {
+            *fired = true;
+            fire(app, rule, value, tick.timestamp, alerts.notify);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_alert_rules(config: State<ConfigState>) -> Result<Vec<AlertRule>, CommandError> {
+    Ok(config.get()?.alerts.rules.clone())
+}
+
+// Save a new rule; returns it with its ID
+#[tauri::command]
+pub fn add_alert_rule(
+    condition: AlertCondition,
+    config: State<ConfigState>,
+) -> Result<AlertRule, CommandError> {
+    check_condition(&condition)?;
+    let mut added = None;
+    config.update(|config| {
+        let id = config
+            .alerts
+            .rules
+            .iter()
+            .map(|rule| rule.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let rule = AlertRule {
+            id,
+            condition: condition.clone(),
+        };
+        config.alerts.rules.push(rule.clone());
+        added = Some(rule);
+    })?;
+    Ok(added.ok_or("Alert rule was not saved")?)
+}
+
+#[tauri::command]
+pub fn remove_alert_rule(id: u32, config: State<ConfigState>) -> Result<(), CommandError> {
+    let mut found = false;
+    config.update(|config| {
+        let before = config.alerts.rules.len();
+        config.alerts.rules.retain(|rule| rule.id != id);
+        found = config.alerts.rules.len() != before;
+    })?;
+    if !found {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "alert.unknownRule").with("id", id));
+    }
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use rusqlite::backup::Progress;
+use rusqlite::MAIN_DB;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use tauri::{AppHandle, Manager, State};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::config::{self, AppConfig, ConfigState};
+use crate::error::CommandError;
+use crate::history::History;
+use crate::monitor::Monitoring;
+use crate::report::unix_timestamp;
+use crate::CppLibrary;
+
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "backup.json";
+const CONFIG_FILE: &str = "config.toml";
+const HISTORY_FILE: &str = "history.db";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    format_version: u32,
+    created_at: u64,
+    app_version: String,
+    includes_history: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSummary {
+    pub created_at: u64,
+    pub app_version: String,
+    pub restored_config: bool,
+    pub restored_history: bool,
+}
+
+// Scratch file for the SQLite copy, so the live database is never read mid-write
+fn scratch_database(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(format!("history-backup-{}.db", std::process::id())))
+}
+
+#[tauri::command]
+pub fn backup_app_data(
+    path: String,
+    include_history: Option<bool>,
+    app: AppHandle,
+    config: State<ConfigState>,
+    history: State<History>,
+) -> Result<(), CommandError> {
+    let include_history = include_history.unwrap_or(false);
+    let config_text = toml::to_string_pretty(&*config.get()?).map_err(|e| e.to_string())?;
+
+    let file = File::create(&path).map_err(|e| CommandError::create_failed(&path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        includes_history: include_history,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_FILE, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(&manifest).map_err(|e| e.to_string())?;
+
+    zip.start_file(CONFIG_FILE, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(config_text.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    if include_history {
+        let scratch = scratch_database(&app)?;
+        let copied = history
+            .conn()?
+            .backup(MAIN_DB, &scratch, None)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                let mut database = File::open(&scratch).map_err(|e| e.to_string())?;
+                zip.start_file(HISTORY_FILE, options)
+                    .map_err(|e| e.to_string())?;
+                std::io::copy(&mut database, &mut zip).map_err(|e| e.to_string())
+            });
+        let _ = std::fs::remove_file(&scratch);
+        copied?;
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    println!("✓ Backed up app data to {}", path);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restore_app_data(
+    path: String,
+    app: AppHandle,
+    config: State<ConfigState>,
+    history: State<History>,
+) -> Result<RestoreSummary, CommandError> {
+    let file = File::open(&path).map_err(|e| CommandError::open_failed(&path, e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))?;
+
+    let manifest: BackupManifest = {
+        let entry = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| "Not a backup archive: manifest missing".to_string())?;
+        serde_json::from_reader(entry).map_err(|e| format!("Invalid backup manifest: {}", e))?
+    };
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "Backup format {} is newer than this app supports ({})",
+            manifest.format_version, BACKUP_FORMAT_VERSION
+        )
+        .into());
+    }
+
+    // Validate the config before touching anything so a bad archive leaves the app untouched
+    let restored_config: AppConfig = {
+        let mut text = String::new();
+        archive
+            .by_name(CONFIG_FILE)
+            .map_err(|e| e.to_string())?
+            .read_to_string(&mut text)
+            .map_err(|e| e.to_string())?;
+        toml::from_str(&text).map_err(|e| format!("Invalid config in backup: {}", e))?
+    };
+    config::check_config(&restored_config)?;
+
+    let restored_history = manifest.includes_history && archive.by_name(HISTORY_FILE).is_ok();
+    if restored_history {
+        let scratch = scratch_database(&app)?;
+        let restored = archive
+            .by_name(HISTORY_FILE)
+            .map_err(|e| e.to_string())
+            .and_then(|mut entry| {
+                let mut database = File::create(&scratch).map_err(|e| e.to_string())?;
+                std::io::copy(&mut entry, &mut database).map_err(|e| e.to_string())?;
+                history
+                    .conn()?
+                    .restore(MAIN_DB, &scratch, None::<fn(Progress)>)
+                    .map_err(|e| format!("Failed to restore history: {}", e))
+            });
+        let _ = std::fs::remove_file(&scratch);
+        restored?;
+    }
+
+    // Applied like `set_config`: locale, library and monitor interval take effect right away
+    config::replace_config(
+        restored_config,
+        &config,
+        &app.state::<CppLibrary>(),
+        &app.state::<Monitoring>(),
+    )?;
+
+    println!("✓ Restored app data from {}", path);
+    Ok(RestoreSummary {
+        created_at: manifest.created_at,
+        app_version: manifest.app_version,
+        restored_config: true,
+        restored_history,
+    })
+}
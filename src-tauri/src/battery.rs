@@ -0,0 +1,269 @@
+// Battery health: design capacity against what the battery holds today, and its cycle count.
+// A reading is stored in the history database every few hours so degradation can be charted
+// over the battery's life; readings are kept regardless of the sample retention policy.
+// Linux reads `/sys/class/power_supply`, Windows the ACPI battery classes in WMI and macOS the
+// AppleSmartBattery registry entry.
+
+use rusqlite::params;
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::history::History;
+use crate::os_command::run_text;
+use crate::report::unix_timestamp;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+const RECORD_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const DEFAULT_HISTORY_DAYS: u32 = 365;
+
+#[derive(Clone, Copy, Serialize)]
+pub enum CapacityUnit {
+    #[serde(rename = "mWh")]
+    MilliwattHours,
+    // Batteries that only report charge, not energy
+    #[serde(rename = "mAh")]
+    MilliampHours,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryHealth {
+    // Identifies the battery across readings, e.g. `BAT0`
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub design_capacity: u64,
+    pub full_charge_capacity: u64,
+    pub capacity_unit: CapacityUnit,
+    // Full charge as a percentage of design capacity
+    pub health_percent: f64,
+    pub cycle_count: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryHealthPoint {
+    pub battery: String,
+    pub timestamp: i64,
+    pub full_charge_capacity: u64,
+    pub design_capacity: u64,
+    pub health_percent: f64,
+    pub cycle_count: Option<u32>,
+}
+
+fn health(
+    name: String,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    (design, full): (u64, u64),
+    capacity_unit: CapacityUnit,
+    cycle_count: Option<u32>,
+) -> Option<BatteryHealth> {
+    (design > 0).then(|| BatteryHealth {
+        name,
+        manufacturer: manufacturer.filter(|value| !value.is_empty()),
+        model: model.filter(|value| !value.is_empty()),
+        design_capacity: design,
+        full_charge_capacity: full,
+        capacity_unit,
+        health_percent: full as f64 / design as f64 * 100.0,
+        cycle_count,
+    })
+}
+
+fn linux_batteries() -> Result<Vec<BatteryHealth>, String> {
+    let entries = std::fs::read_dir(POWER_SUPPLY_DIR).map_err(|e| e.to_string())?;
+    let mut batteries = Vec::new();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |file: &str| {
+            std::fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let number = |file: &str| read(file).and_then(|value| value.parse::<u64>().ok());
+        if read("type").as_deref() != Some("Battery") {
+            continue;
+        }
+
+        // Energy is in µWh and charge in µAh; drivers provide one or the other
+        let (capacities, unit) = match (number("energy_full_design"), number("energy_full")) {
+            (Some(design), Some(full)) => ((design, full), CapacityUnit::MilliwattHours),
+            _ => match (number("charge_full_design"), number("charge_full")) {
+                (Some(design), Some(full)) => ((design, full), CapacityUnit::MilliampHours),
+                _ => continue,
+            },
+        };
+        // Drivers without a cycle counter report 0
+        let cycle_count = number("cycle_count")
+            .filter(|&cycles| cycles > 0)
+            .map(|cycles| cycles as u32);
+        batteries.extend(health(
+            entry.file_name().to_string_lossy().into_owned(),
+            read("manufacturer"),
+            read("model_name"),
+            (capacities.0 / 1000, capacities.1 / 1000),
+            unit,
+            cycle_count,
+        ));
+    }
+    Ok(batteries)
+}
+
+fn windows_batteries() -> Result<Vec<BatteryHealth>, String> {
+    // The three classes list the same batteries in the same order, in mWh
+    let script = "$design = @(Get-CimInstance -Namespace root/wmi -ClassName BatteryStaticData); \
+                  $full = @(Get-CimInstance -Namespace root/wmi -ClassName BatteryFullChargedCapacity); \
+                  $cycles = @(Get-CimInstance -Namespace root/wmi -ClassName BatteryCycleCount -ErrorAction SilentlyContinue); \
+                  ConvertTo-Json -Compress -InputObject @(for ($i = 0; $i -lt $design.Count; $i++) { \
+                  [pscustomobject]@{ Name = $design[$i].InstanceName; \
+                  Manufacturer = $design[$i].ManufactureName; Model = $design[$i].DeviceName; \
+                  Design = $design[$i].DesignedCapacity; Full = $full[$i].FullChargedCapacity; \
+                  Cycles = $cycles[$i].CycleCount } })";
+    let output = run_text("powershell", &["-NoProfile", "-Command", script])?;
+    let records: Vec<serde_json::Value> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+
+    Ok(records
+        .iter()
+        .filter_map(|record| {
+            let text = |key: &str| record[key].as_str().map(|value| value.trim().to_string());
+            health(
+                text("Name").unwrap_or_default(),
+                text("Manufacturer"),
+                text("Model"),
+                (
+                    record["Design"].as_u64().unwrap_or_default(),
+                    record["Full"].as_u64().unwrap_or_default(),
+                ),
+                CapacityUnit::MilliwattHours,
+                record["Cycles"]
+                    .as_u64()
+                    .filter(|&cycles| cycles > 0)
+                    .map(|cycles| cycles as u32),
+            )
+        })
+        .collect())
+}
+
+fn macos_batteries() -> Result<Vec<BatteryHealth>, String> {
+    // Top-level properties print as `  "CycleCount" = 123`
+    let output = run_text("ioreg", &["-r", "-c", "AppleSmartBattery", "-d", "1"])?;
+    let value = |key: &str| {
+        let prefix = format!("\"{}\" = ", key);
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(&prefix))
+            .map(|value| value.trim_matches('"').to_string())
+    };
+    let number = |key: &str| value(key).and_then(|value| value.parse::<u64>().ok());
+
+    // Apple silicon reports the real capacity as AppleRawMaxCapacity and a percentage as
+    // MaxCapacity; Intel Macs only have MaxCapacity, in mAh
+    let full = number("AppleRawMaxCapacity").or_else(|| number("MaxCapacity"));
+    let (Some(design), Some(full)) = (number("DesignCapacity"), full) else {
+        return Ok(Vec::new());
+    };
+    Ok(health(
+        value("Serial").unwrap_or_else(|| "InternalBattery".to_string()),
+        value("Manufacturer"),
+        value("DeviceName"),
+        (design, full),
+        CapacityUnit::MilliampHours,
+        number("CycleCount").map(|cycles| cycles as u32),
+    )
+    .into_iter()
+    .collect())
+}
+
+fn read_batteries() -> Result<Vec<BatteryHealth>, String> {
+    if cfg!(target_os = "linux") {
+        linux_batteries()
+    } else if cfg!(target_os = "windows") {
+        windows_batteries()
+    } else if cfg!(target_os = "macos") {
+        macos_batteries()
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+fn record(history: &History, batteries: &[BatteryHealth]) -> Result<(), String> {
+    let now = unix_timestamp() as i64;
+    let conn = history.conn()?;
+    for battery in batteries {
+        conn.execute(
+            "INSERT INTO battery_health
+                 (battery, timestamp, full_charge_capacity, design_capacity, cycle_count)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                battery.name,
+                now,
+                battery.full_charge_capacity as i64,
+                battery.design_capacity as i64,
+                battery.cycle_count
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Background job storing a health reading for each battery; machines without one skip it
+pub fn start_recording(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        match read_batteries() {
+            Ok(batteries) => {
+                if let Err(e) = record(&app.state::<History>(), &batteries) {
+                    eprintln!("Recording battery health failed: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Reading battery health failed: {}", e),
+        }
+        battery_saver::wait(&app, RECORD_INTERVAL);
+    });
+}
+
+// Every battery present; empty on machines without one
+#[tauri::command]
+pub async fn get_battery_health() -> Result<Vec<BatteryHealth>, CommandError> {
+    let batteries = tauri::async_runtime::spawn_blocking(read_batteries)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(batteries)
+}
+
+// Stored readings from the last `days` days (a year by default), oldest first
+#[tauri::command]
+pub fn get_battery_history(
+    days: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<BatteryHealthPoint>, CommandError> {
+    let since = unix_timestamp() as i64 - days.unwrap_or(DEFAULT_HISTORY_DAYS) as i64 * 86400;
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT battery, timestamp, full_charge_capacity, design_capacity, cycle_count
+             FROM battery_health WHERE timestamp >= ?1 ORDER BY timestamp, battery",
+        )
+        .map_err(|e| e.to_string())?;
+    let points = statement
+        .query_map(params![since], |row| {
+            let full_charge_capacity = row.get::<_, i64>(2)? as u64;
+            let design_capacity = row.get::<_, i64>(3)? as u64;
+            Ok(BatteryHealthPoint {
+                battery: row.get(0)?,
+                timestamp: row.get(1)?,
+                full_charge_capacity,
+                design_capacity,
+                health_percent: full_charge_capacity as f64 / design_capacity.max(1) as f64 * 100.0,
+                cycle_count: row.get(4)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(points)
+}
@@ -0,0 +1,178 @@
+// Backs off background work while the OS is in battery saver / low-power mode, so the monitor
+// isn't what drains a laptop that is trying to save power. Sampling jobs wait through
+// `wait` instead of sleeping directly: with `action = "reduce"` their interval is stretched by
+// `slowdown_factor`, with `"pause"` they stop until the saver turns off. Exporters only see what
+// the samplers publish, so they slow down with them. Benchmarks refuse to start either way.
+// `wait` also holds jobs back while a benchmark runs, whatever the saver state.
+// Set `enabled = false` under `[batterySaver]` to keep full speed regardless.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::benchmark;
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::os_command::run_text;
+
+pub const BATTERY_SAVER_CHANGED_EVENT: &str = "battery-saver-changed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Last state the watcher saw
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SaverAction {
+    // Sample less often
+    #[default]
+    Reduce,
+    // Stop sampling until the saver turns off
+    Pause,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BatterySaverConfig {
+    pub enabled: bool,
+    pub action: SaverAction,
+    // Interval multiplier for `reduce`
+    pub slowdown_factor: u32,
+}
+
+impl Default for BatterySaverConfig {
+    fn default() -> Self {
+        BatterySaverConfig {
+            enabled: true,
+            action: SaverAction::Reduce,
+            slowdown_factor: 4,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatterySaverStatus {
+    // The OS is in battery saver / low-power mode
+    pub active: bool,
+    // Background work is being held back; false while active if the config opts out
+    pub throttling: bool,
+    pub action: SaverAction,
+}
+
+fn linux_saver() -> bool {
+    // ACPI platform profile, set by power-profiles-daemon and most laptop vendor tools
+    let profile = std::fs::read_to_string("/sys/firmware/acpi/platform_profile");
+    if let Ok(profile) = profile {
+        return profile.trim() == "low-power";
+    }
+    run_text("powerprofilesctl", &["get"]).is_ok_and(|profile| profile.trim() == "power-saver")
+}
+
+fn macos_saver() -> bool {
+    // ` lowpowermode         1`, or ` powermode            1` on newer releases
+    run_text("pmset", &["-g"]).is_ok_and(|output| {
+        output.lines().any(|line| {
+            let mut fields = line.split_whitespace();
+            matches!(fields.next(), Some("lowpowermode" | "powermode"))
+                && fields.next() == Some("1")
+        })
+    })
+}
+
+fn windows_saver() -> bool {
+    let script = "[Windows.System.Power.PowerManager, Windows.System.Power, \
+                  ContentType = WindowsRuntime] | Out-Null; \
+                  [Windows.System.Power.PowerManager]::EnergySaverStatus";
+    run_text("powershell", &["-NoProfile", "-Command", script])
+        .is_ok_and(|status| status.trim() == "On")
+}
+
+fn detect() -> bool {
+    if cfg!(target_os = "windows") {
+        windows_saver()
+    } else if cfg!(target_os = "macos") {
+        macos_saver()
+    } else {
+        linux_saver()
+    }
+}
+
+fn saver_config(app: &AppHandle) -> BatterySaverConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.battery_saver.clone())
+        .unwrap_or_default()
+}
+
+fn status(app: &AppHandle) -> BatterySaverStatus {
+    let config = saver_config(app);
+    let active = ACTIVE.load(Ordering::Relaxed);
+    BatterySaverStatus {
+        active,
+        throttling: active && config.enabled,
+        action: config.action,
+    }
+}
+
+// Sleep `interval` between rounds of a background job, longer or until the saver turns off
+// while it is throttling, and until any running benchmark has finished
+pub fn wait(app: &AppHandle, interval: Duration) {
+    std::thread::sleep(interval);
+    hold_back(app, interval);
+}
+
+// What `wait` adds after the regular `interval`, for jobs that sleep through the interval
+// themselves, e.g. to notice a stop request
+pub fn hold_back(app: &AppHandle, interval: Duration) {
+    benchmark::wait_until_idle();
+    let config = saver_config(app);
+    if !config.enabled || !ACTIVE.load(Ordering::Relaxed) {
+        return;
+    }
+    match config.action {
+        SaverAction::Reduce => {
+            std::thread::sleep(interval * config.slowdown_factor.saturating_sub(1));
+        }
+        SaverAction::Pause => {
+            while ACTIVE.load(Ordering::Relaxed) && saver_config(app).enabled {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+// For heavy one-off work such as benchmarks
+pub fn check_not_throttling(app: &AppHandle) -> Result<(), CommandError> {
+    if status(app).throttling {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "benchmark.batterySaver",
+        ));
+    }
+    Ok(())
+}
+
+// Background job tracking the OS setting
+pub fn start_watching(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let active = detect();
+        if ACTIVE.swap(active, Ordering::Relaxed) != active {
+            println!("Battery saver turned {}", if active { "on" } else { "off" });
+            if let Err(e) = app.emit(BATTERY_SAVER_CHANGED_EVENT, status(&app)) {
+                eprintln!(
+                    "⚠ Warning: Failed to emit {}: {}",
+                    BATTERY_SAVER_CHANGED_EVENT, e
+                );
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_battery_saver_status(app: AppHandle) -> BatterySaverStatus {
+    status(&app)
+}
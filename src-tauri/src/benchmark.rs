@@ -0,0 +1,698 @@
+use rayon::prelude::*;
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use sysinfo::Components;
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::exporter::ExporterRegistry;
+use crate::gpu::gpu_workload;
+use crate::history::{History, HistoryPoint, TimeRange};
+use crate::monitor::Monitoring;
+use crate::policy::{Policy, SAMPLERS};
+use crate::report::unix_timestamp;
+use crate::{battery_saver, cpu, display, exporter, thermal};
+use crate::{read_total_memory, CppLibrary};
+
+// Each workload runs for roughly this long so scores are comparable between runs
+const CPU_DURATION: Duration = Duration::from_secs(2);
+const CPU_BATCH: u64 = 100_000;
+const SCALING_POINT_DURATION: Duration = Duration::from_secs(1);
+const MEMORY_BUFFER_BYTES: usize = 64 * 1024 * 1024;
+const MEMORY_PASSES: usize = 16;
+const DISK_FILE_BYTES: usize = 128 * 1024 * 1024;
+const DISK_CHUNK_BYTES: usize = 1024 * 1024;
+// Runs are shorter than the background samplers' intervals, so they record their own timeline
+const TIMELINE_INTERVAL: Duration = Duration::from_secs(1);
+// History shown on either side of a run
+const TIMELINE_PADDING_SECONDS: i64 = 30;
+// Mean core clock this far below its peak so far in the run counts as throttling
+const THROTTLE_DROP: f64 = 0.1;
+// How often paused background jobs check whether the runs have finished
+const PAUSE_POLL: Duration = Duration::from_millis(250);
+
+// Runs in progress; background samplers, exporters and monitor ticks hold off while it is not
+// zero, so the app's own monitoring doesn't compete with the workload
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchmarkKind {
+    Cpu,
+    CpuParallel,
+    Memory,
+    Disk,
+    Gpu,
+}
+
+impl BenchmarkKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            BenchmarkKind::Cpu => "cpu",
+            BenchmarkKind::CpuParallel => "cpuparallel",
+            BenchmarkKind::Memory => "memory",
+            BenchmarkKind::Disk => "disk",
+            BenchmarkKind::Gpu => "gpu",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "cpu" => Some(BenchmarkKind::Cpu),
+            "cpuparallel" => Some(BenchmarkKind::CpuParallel),
+            "memory" => Some(BenchmarkKind::Memory),
+            "disk" => Some(BenchmarkKind::Disk),
+            "gpu" => Some(BenchmarkKind::Gpu),
+            _ => None,
+        }
+    }
+}
+
+// The machine a run was measured on, so upgrades show up next to the score change
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardwareContext {
+    pub platform: String,
+    pub arch: String,
+    pub logical_cpus: usize,
+    pub total_memory: Option<u64>,
+    #[serde(default)]
+    pub gpu: Option<String>,
+    pub app_version: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkRun {
+    pub id: i64,
+    pub kind: BenchmarkKind,
+    pub started_at: i64,
+    pub duration_ms: u64,
+    pub score: f64,
+    pub unit: String,
+    pub hardware: HardwareContext,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkComparison {
+    pub previous: BenchmarkRun,
+    pub delta_percent: f64,
+    pub hardware_changed: bool,
+}
+
+// One point of the multi-core scaling curve
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScalingPoint {
+    pub threads: usize,
+    pub score: f64,
+    pub speedup: f64,
+    pub efficiency: f64,
+}
+
+// Background work held back while a run was measured
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PausedWork {
+    // Names from policy::SAMPLERS
+    pub samplers: Vec<String>,
+    pub exporters: Vec<String>,
+    // `start_monitoring` was running and skipped its ticks
+    pub monitoring: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScalingResult {
+    pub unit: String,
+    pub logical_cpus: usize,
+    pub points: Vec<ScalingPoint>,
+    pub paused: PausedWork,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResult {
+    pub run: BenchmarkRun,
+    // Score in the user's display units and precision, e.g. "812.40 Mbit/s"
+    pub display_score: String,
+    pub compared_to_previous: Option<BenchmarkComparison>,
+    pub paused: PausedWork,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineSeries {
+    pub metric: String,
+    pub unit: String,
+    pub points: Vec<HistoryPoint>,
+}
+
+// A sample during the run where the clock had dropped from its peak
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleEvent {
+    pub timestamp: i64,
+    // Mean across cores
+    pub frequency_mhz: f64,
+    pub peak_frequency_mhz: f64,
+    // Hottest sensor at the time, in the display unit
+    pub temperature: Option<f64>,
+}
+
+// A run laid over the CPU load, clocks and temperatures recorded around it
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkTimeline {
+    pub run: BenchmarkRun,
+    pub from: i64,
+    pub to: i64,
+    pub series: Vec<TimelineSeries>,
+    pub throttle_events: Vec<ThrottleEvent>,
+    // The clock dropped during the run, so the score was likely thermally limited
+    pub throttled: bool,
+}
+
+// Records CPU and temperature samples into history until dropped
+struct TimelineRecorder {
+    // Dropping it wakes the recorder and ends it
+    _stop: Sender<()>,
+}
+
+impl TimelineRecorder {
+    fn start(app: &AppHandle) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let app = app.clone();
+        std::thread::spawn(move || {
+            let mut components = Components::new_with_refreshed_list();
+            loop {
+                let mut samples = cpu::sample_now(&app).unwrap_or_default();
+                components.refresh(false);
+                samples.extend(thermal::read_temperatures(&components));
+                exporter::publish(&app, &samples);
+
+                if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(TIMELINE_INTERVAL)
+                {
+                    return;
+                }
+            }
+        });
+        TimelineRecorder { _stop: stop }
+    }
+}
+
+// Holds background work back until dropped
+struct Pause;
+
+impl Pause {
+    // Returns what it holds back, for the result
+    fn start(app: &AppHandle) -> (Self, PausedWork) {
+        RUNNING.fetch_add(1, Ordering::SeqCst);
+        let policy = app.try_state::<Policy>();
+        let paused = PausedWork {
+            samplers: SAMPLERS
+                .iter()
+                // Discovery only answers mDNS traffic and isn't held back
+                .filter(|name| **name != "discovery")
+                .filter(|name| {
+                    policy
+                        .as_ref()
+                        .is_none_or(|policy| policy.sampler_enabled(name))
+                })
+                .map(|name| name.to_string())
+                .collect(),
+            exporters: app.state::<ExporterRegistry>().names(),
+            monitoring: app.state::<Monitoring>().is_running(),
+        };
+        (Pause, paused)
+    }
+}
+
+impl Drop for Pause {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn running() -> bool {
+    RUNNING.load(Ordering::SeqCst) > 0
+}
+
+// For background jobs between rounds: block until no run is in progress
+pub fn wait_until_idle() {
+    while running() {
+        std::thread::sleep(PAUSE_POLL);
+    }
+}
+
+fn hardware_context(lib_state: &CppLibrary) -> HardwareContext {
+    HardwareContext {
+        platform: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        logical_cpus: logical_cpus(),
+        total_memory: read_total_memory(lib_state).ok(),
+        gpu: None,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+fn logical_cpus() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+// Integer-heavy loop (xorshift + modular arithmetic) run in batches until `duration` elapses;
+// returns the number of iterations completed
+fn cpu_kernel(seed: u64, duration: Duration) -> u64 {
+    let start = Instant::now();
+    let mut state = seed | 1;
+    let mut iterations = 0;
+
+    while start.elapsed() < duration {
+        for _ in 0..CPU_BATCH {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state = black_box(state.wrapping_mul(31) % 1_000_000_007) | 1;
+        }
+        iterations += CPU_BATCH;
+    }
+    iterations
+}
+
+// Run the CPU kernel on `threads` rayon workers at once; score is million iterations per second
+fn parallel_cpu_score(threads: usize, duration: Duration) -> Result<f64, String> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    let iterations: u64 = pool.install(|| {
+        (0..threads as u64)
+            .into_par_iter()
+            .map(|worker| cpu_kernel(0x9E37_79B9_7F4A_7C15 ^ worker, duration))
+            .sum()
+    });
+
+    Ok(iterations as f64 / start.elapsed().as_secs_f64() / 1e6)
+}
+
+fn cpu_workload() -> (f64, &'static str) {
+    let start = Instant::now();
+    let iterations = cpu_kernel(0x9E37_79B9_7F4A_7C15, CPU_DURATION);
+    (
+        iterations as f64 / start.elapsed().as_secs_f64() / 1e6,
+        "Mops/s",
+    )
+}
+
+// 1, 2, 4, … up to `max`, always ending at `max` itself
+fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |n| n.checked_mul(2))
+        .take_while(|&n| n < max)
+        .collect();
+    counts.push(max);
+    counts
+}
+
+// Repeated large buffer copies; score is copy bandwidth in MB/s
+fn memory_workload() -> (f64, &'static str) {
+    let source = vec![0xA5u8; MEMORY_BUFFER_BYTES];
+    let mut destination = vec![0u8; MEMORY_BUFFER_BYTES];
+
+    let start = Instant::now();
+    for _ in 0..MEMORY_PASSES {
+        destination.copy_from_slice(black_box(&source));
+        black_box(&mut destination);
+    }
+
+    let bytes = (MEMORY_BUFFER_BYTES * MEMORY_PASSES) as f64;
+    (bytes / start.elapsed().as_secs_f64() / 1e6, "MB/s")
+}
+
+// Sequential synced write followed by a read back; score is combined throughput in MB/s
+fn disk_workload(app: &AppHandle) -> Result<(f64, &'static str), String> {
+    let dir = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("disk-benchmark-{}.tmp", std::process::id()));
+
+    let run = || -> std::io::Result<Duration> {
+        let chunk = vec![0x5Au8; DISK_CHUNK_BYTES];
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+
+        let start = Instant::now();
+        for _ in 0..DISK_FILE_BYTES / DISK_CHUNK_BYTES {
+            file.write_all(&chunk)?;
+        }
+        file.sync_all()?;
+
+        file.seek(SeekFrom::Start(0))?;
+        let mut buffer = vec![0u8; DISK_CHUNK_BYTES];
+        while file.read(&mut buffer)? > 0 {
+            black_box(&buffer);
+        }
+        Ok(start.elapsed())
+    };
+
+    let elapsed = run();
+    let _ = std::fs::remove_file(&path);
+    let elapsed = elapsed.map_err(|e| format!("Disk benchmark failed: {}", e))?;
+
+    let bytes = (DISK_FILE_BYTES * 2) as f64;
+    Ok((bytes / elapsed.as_secs_f64() / 1e6, "MB/s"))
+}
+
+fn run_from_row(row: &Row) -> rusqlite::Result<BenchmarkRun> {
+    let kind: String = row.get(1)?;
+    let hardware: String = row.get(6)?;
+
+    Ok(BenchmarkRun {
+        id: row.get(0)?,
+        kind: BenchmarkKind::parse(&kind).unwrap_or(BenchmarkKind::Cpu),
+        started_at: row.get(2)?,
+        duration_ms: row.get::<_, i64>(3)? as u64,
+        score: row.get(4)?,
+        unit: row.get(5)?,
+        hardware: serde_json::from_str(&hardware).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+    })
+}
+
+const RUN_COLUMNS: &str = "id, kind, started_at, duration_ms, score, unit, hardware";
+
+fn save_run(history: &History, run: &BenchmarkRun) -> Result<i64, String> {
+    let hardware = serde_json::to_string(&run.hardware).map_err(|e| e.to_string())?;
+    let conn = history.conn()?;
+    conn.execute(
+        "INSERT INTO benchmark_runs (kind, started_at, duration_ms, score, unit, hardware)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            run.kind.as_str(),
+            run.started_at,
+            run.duration_ms as i64,
+            run.score,
+            run.unit,
+            hardware
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn previous_run(history: &History, run: &BenchmarkRun) -> Result<Option<BenchmarkRun>, String> {
+    history
+        .conn()?
+        .query_row(
+            &format!(
+                "SELECT {} FROM benchmark_runs WHERE kind = ?1 AND id < ?2 ORDER BY id DESC LIMIT 1",
+                RUN_COLUMNS
+            ),
+            params![run.kind.as_str(), run.id],
+            run_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())
+}
+
+fn run_by_id(history: &History, id: i64) -> Result<Option<BenchmarkRun>, String> {
+    history
+        .conn()?
+        .query_row(
+            &format!("SELECT {} FROM benchmark_runs WHERE id = ?1", RUN_COLUMNS),
+            params![id],
+            run_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())
+}
+
+// Samples where the mean core clock fell `THROTTLE_DROP` below its peak so far in the run.
+// Comparing with the peak so far keeps the clock ramping up at the start from counting.
+fn throttle_events(
+    frequencies: &[&TimelineSeries],
+    temperatures: &[&TimelineSeries],
+    run: (i64, i64),
+) -> Vec<ThrottleEvent> {
+    let mut means: BTreeMap<i64, (f64, usize)> = BTreeMap::new();
+    for point in frequencies.iter().flat_map(|series| &series.points) {
+        if (run.0..=run.1).contains(&point.timestamp) {
+            let (sum, count) = means.entry(point.timestamp).or_default();
+            *sum += point.value;
+            *count += 1;
+        }
+    }
+    let hottest = |timestamp: i64| {
+        temperatures
+            .iter()
+            .flat_map(|series| &series.points)
+            .filter(|point| point.timestamp == timestamp)
+            .map(|point| point.value)
+            .reduce(f64::max)
+    };
+
+    let mut peak: f64 = 0.0;
+    let mut events = Vec::new();
+    for (timestamp, (sum, count)) in means {
+        let mean = sum / count as f64;
+        peak = peak.max(mean);
+        if mean < peak * (1.0 - THROTTLE_DROP) {
+            events.push(ThrottleEvent {
+                timestamp,
+                frequency_mhz: mean,
+                peak_frequency_mhz: peak,
+                temperature: hottest(timestamp),
+            });
+        }
+    }
+    events
+}
+
+fn execute_benchmark(kind: BenchmarkKind, app: &AppHandle) -> Result<BenchmarkResult, String> {
+    let mut hardware = hardware_context(&app.state::<CppLibrary>());
+    let (pause, paused) = Pause::start(app);
+    let recorder = TimelineRecorder::start(app);
+    let started_at = unix_timestamp() as i64;
+    let start = Instant::now();
+
+    let (score, unit) = match kind {
+        BenchmarkKind::Cpu => cpu_workload(),
+        BenchmarkKind::CpuParallel => (parallel_cpu_score(logical_cpus(), CPU_DURATION)?, "Mops/s"),
+        BenchmarkKind::Memory => memory_workload(),
+        BenchmarkKind::Disk => disk_workload(app)?,
+        BenchmarkKind::Gpu => {
+            let gpu = gpu_workload()?;
+            hardware.gpu = Some(gpu.adapter);
+            (gpu.gflops, "GFLOPS")
+        }
+    };
+    drop(recorder);
+    drop(pause);
+
+    let mut run = BenchmarkRun {
+        id: 0,
+        kind,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        score,
+        unit: unit.to_string(),
+        hardware,
+    };
+
+    let history = app.state::<History>();
+    run.id = save_run(&history, &run)?;
+
+    let compared_to_previous = previous_run(&history, &run)?.map(|previous| BenchmarkComparison {
+        delta_percent: if previous.score > 0.0 {
+            (run.score - previous.score) / previous.score * 100.0
+        } else {
+            0.0
+        },
+        hardware_changed: previous.hardware != run.hardware,
+        previous,
+    });
+
+    let display_score = display::current(&app.state::<ConfigState>()).format(run.score, &run.unit);
+    println!("✓ {} benchmark: {}", kind.as_str(), display_score);
+    Ok(BenchmarkResult {
+        run,
+        display_score,
+        compared_to_previous,
+        paused,
+    })
+}
+
+#[tauri::command]
+pub async fn run_benchmark(
+    kind: BenchmarkKind,
+    app: AppHandle,
+) -> Result<BenchmarkResult, CommandError> {
+    battery_saver::check_not_throttling(&app)?;
+    let result = tauri::async_runtime::spawn_blocking(move || execute_benchmark(kind, &app))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(result?)
+}
+
+// Same as `run_benchmark` with the GPU workload; fails cleanly when no adapter is usable
+#[tauri::command]
+pub async fn run_gpu_benchmark(app: AppHandle) -> Result<BenchmarkResult, CommandError> {
+    battery_saver::check_not_throttling(&app)?;
+    let result =
+        tauri::async_runtime::spawn_blocking(move || execute_benchmark(BenchmarkKind::Gpu, &app))
+            .await
+            .map_err(|e| e.to_string())?;
+    Ok(result?)
+}
+
+#[tauri::command]
+pub async fn run_scaling_benchmark(
+    max_threads: Option<usize>,
+    app: AppHandle,
+) -> Result<ScalingResult, CommandError> {
+    battery_saver::check_not_throttling(&app)?;
+    let logical_cpus = logical_cpus();
+    let max_threads = max_threads
+        .unwrap_or(logical_cpus)
+        .clamp(1, logical_cpus * 2);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (_pause, paused) = Pause::start(&app);
+        let mut points: Vec<ScalingPoint> = Vec::new();
+
+        for threads in thread_counts(max_threads) {
+            let score = parallel_cpu_score(threads, SCALING_POINT_DURATION)?;
+            let baseline = points.first().map(|p| p.score).unwrap_or(score);
+            let speedup = if baseline > 0.0 {
+                score / baseline
+            } else {
+                0.0
+            };
+
+            points.push(ScalingPoint {
+                threads,
+                score,
+                speedup,
+                efficiency: speedup / threads as f64,
+            });
+        }
+
+        Ok(ScalingResult {
+            unit: "Mops/s".to_string(),
+            logical_cpus,
+            points,
+            paused,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_benchmark_history(
+    kind: Option<BenchmarkKind>,
+    limit: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<BenchmarkRun>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(&format!(
+            "SELECT {} FROM benchmark_runs WHERE (?1 IS NULL OR kind = ?1) ORDER BY id DESC LIMIT ?2",
+            RUN_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let runs = statement
+        .query_map(
+            params![kind.map(BenchmarkKind::as_str), limit.unwrap_or(50)],
+            run_from_row,
+        )
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(runs)
+}
+
+// The run with the CPU load, per-core clocks and temperatures recorded during it, and the
+// moments its clock was throttled. Built from raw samples, so runs older than the raw
+// retention come back without series.
+#[tauri::command]
+pub fn get_benchmark_timeline(
+    id: i64,
+    history: State<History>,
+    config: State<ConfigState>,
+) -> Result<BenchmarkTimeline, CommandError> {
+    let run = run_by_id(&history, id)?.ok_or_else(|| {
+        CommandError::new(ErrorCode::InvalidInput, "benchmark.notFound").with("id", id)
+    })?;
+    let run_end = run.started_at + run.duration_ms.div_ceil(1000) as i64;
+    let range = TimeRange {
+        from: run.started_at - TIMELINE_PADDING_SECONDS,
+        to: run_end + TIMELINE_PADDING_SECONDS,
+    };
+    let display = display::current(&config);
+
+    let mut metrics = vec![("cpu.usage".to_string(), "%")];
+    metrics.extend(
+        history
+            .metrics_with_prefix("cpu.")?
+            .into_iter()
+            .filter(|metric| metric.ends_with(".frequency"))
+            .map(|metric| (metric, "MHz")),
+    );
+    metrics.extend(
+        history
+            .metrics_with_prefix(thermal::METRIC_PREFIX)?
+            .into_iter()
+            .map(|metric| (metric, "°C")),
+    );
+
+    let mut series = Vec::new();
+    for (metric, unit) in metrics {
+        let mut points = history.points(&metric, range)?;
+        if points.is_empty() {
+            continue;
+        }
+        for point in &mut points {
+            point.value = display.convert(point.value, unit).0;
+        }
+        series.push(TimelineSeries {
+            metric,
+            unit: display.convert(0.0, unit).1.to_string(),
+            points,
+        });
+    }
+
+    let frequencies: Vec<&TimelineSeries> = series
+        .iter()
+        .filter(|series| series.metric.ends_with(".frequency"))
+        .collect();
+    let temperatures: Vec<&TimelineSeries> = series
+        .iter()
+        .filter(|series| series.metric.starts_with(thermal::METRIC_PREFIX))
+        .collect();
+    let throttle_events = throttle_events(&frequencies, &temperatures, (run.started_at, run_end));
+
+    Ok(BenchmarkTimeline {
+        run,
+        from: range.from,
+        to: range.to,
+        throttled: !throttle_events.is_empty(),
+        series,
+        throttle_events,
+    })
+}
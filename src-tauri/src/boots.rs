@@ -0,0 +1,305 @@
+// Recent boots and how each one ended, from the system logs.
+// Linux: `journalctl --list-boots`, with the end of each earlier boot's journal showing whether
+// systemd shut down cleanly. Windows: the event log's start (6005), clean stop (6006),
+// unexpected shutdown (6008) and initiated shutdown (1074) events. macOS: the
+// `Previous shutdown cause` the kernel logs at every boot.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+const DEFAULT_BOOT_LIMIT: u32 = 10;
+
+// Journal lines from the end of a boot that identify how it ended
+const REBOOT_MARKERS: [&str; 2] = [
+    "Reached target reboot.target",
+    "Reached target System Reboot",
+];
+const SHUTDOWN_MARKERS: [&str; 4] = [
+    "Reached target poweroff.target",
+    "Reached target System Power Off",
+    "Reached target halt.target",
+    "Journal stopped",
+];
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BootEventKind {
+    Boot,
+    Shutdown,
+    Reboot,
+    // The system went down without shutting down: a crash, power loss or forced power-off
+    UnexpectedShutdown,
+    PowerLoss,
+    Crash,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootEvent {
+    // None where the log only says how the previous boot ended, not when
+    pub timestamp: Option<i64>,
+    pub kind: BootEventKind,
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootHistory {
+    pub supported: bool,
+    // Newest first
+    pub events: Vec<BootEvent>,
+    // Events of any kind other than a clean shutdown, reboot or boot
+    pub unexpected: usize,
+    pub errors: Vec<String>,
+}
+
+fn event(timestamp: Option<i64>, kind: BootEventKind, detail: Option<String>) -> BootEvent {
+    BootEvent {
+        timestamp,
+        kind,
+        detail,
+    }
+}
+
+#[derive(Deserialize)]
+struct JournalBoot {
+    index: i64,
+    boot_id: String,
+    // Microseconds since the epoch
+    first_entry: i64,
+    last_entry: i64,
+}
+
+// How a finished boot ended, judging by its last journal lines
+fn journal_end(boot_id: &str) -> (BootEventKind, Option<String>) {
+    let Ok(tail) = run_text(
+        "journalctl",
+        &["-b", boot_id, "-n", "50", "-o", "cat", "--no-pager"],
+    ) else {
+        return (BootEventKind::UnexpectedShutdown, None);
+    };
+    let contains = |markers: &[&str]| markers.iter().any(|marker| tail.contains(marker));
+    if contains(&REBOOT_MARKERS) {
+        (BootEventKind::Reboot, None)
+    } else if contains(&SHUTDOWN_MARKERS) {
+        (BootEventKind::Shutdown, None)
+    } else if let Some(line) = tail.lines().find(|line| line.contains("Kernel panic")) {
+        (BootEventKind::Crash, Some(line.trim().to_string()))
+    } else {
+        (
+            BootEventKind::UnexpectedShutdown,
+            tail.lines()
+                .last()
+                .map(|line| format!("Last entry: {}", line.trim())),
+        )
+    }
+}
+
+fn linux_events(limit: u32) -> Result<Vec<BootEvent>, String> {
+    let output = run_text("journalctl", &["--list-boots", "-o", "json", "--no-pager"])?;
+    let mut boots: Vec<JournalBoot> = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+    boots.sort_by_key(|boot| std::cmp::Reverse(boot.index));
+    boots.truncate(limit as usize);
+
+    let mut events = Vec::new();
+    for boot in boots {
+        // Index 0 is the running boot, which hasn't ended
+        if boot.index != 0 {
+            let (kind, detail) = journal_end(&boot.boot_id);
+            events.push(event(Some(boot.last_entry / 1_000_000), kind, detail));
+        }
+        events.push(event(
+            Some(boot.first_entry / 1_000_000),
+            BootEventKind::Boot,
+            Some(format!("Boot {}", boot.boot_id)),
+        ));
+    }
+    Ok(events)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WindowsEvent {
+    id: u32,
+    time: i64,
+    message: Option<String>,
+}
+
+fn windows_events(limit: u32) -> Result<Vec<BootEvent>, String> {
+    // Roughly three events per boot, plus initiated shutdowns that may be repeated
+    let script = format!(
+        "ConvertTo-Json -Compress -InputObject @(Get-WinEvent -MaxEvents {} -FilterHashtable \
+         @{{LogName='System'; Id=6005,6006,6008,1074}} | Select-Object Id, \
+         @{{n='Time';e={{[DateTimeOffset]::new($_.TimeCreated).ToUnixTimeSeconds()}}}}, Message)",
+        limit * 4
+    );
+    let output = run_text("powershell", &["-NoProfile", "-Command", &script])?;
+    let mut records: Vec<WindowsEvent> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+    records.sort_by_key(|record| record.time);
+
+    // Oldest first so a 1074 can label the 6006 that follows it
+    let mut events = Vec::new();
+    let mut initiated: Option<(BootEventKind, String)> = None;
+    for record in records {
+        let first_line = record
+            .message
+            .as_deref()
+            .and_then(|message| message.lines().next())
+            .map(|line| line.trim().to_string());
+        match record.id {
+            1074 => {
+                let message = record.message.unwrap_or_default();
+                let kind = if message.to_lowercase().contains("restart") {
+                    BootEventKind::Reboot
+                } else {
+                    BootEventKind::Shutdown
+                };
+                initiated = Some((kind, first_line.unwrap_or_default()));
+            }
+            6005 => events.push(event(Some(record.time), BootEventKind::Boot, None)),
+            6006 => {
+                let (kind, detail) = initiated.take().unzip();
+                events.push(event(
+                    Some(record.time),
+                    kind.unwrap_or(BootEventKind::Shutdown),
+                    detail,
+                ));
+            }
+            6008 => events.push(event(
+                Some(record.time),
+                BootEventKind::UnexpectedShutdown,
+                first_line,
+            )),
+            _ => {}
+        }
+    }
+    events.reverse();
+    Ok(events)
+}
+
+// `Previous shutdown cause` codes: 5 normal, 3 forced with the power button, 0 power loss;
+// other values are kernel or hardware initiated
+fn macos_shutdown_kind(cause: i32) -> BootEventKind {
+    match cause {
+        5 => BootEventKind::Shutdown,
+        0 => BootEventKind::PowerLoss,
+        3 => BootEventKind::UnexpectedShutdown,
+        _ => BootEventKind::Crash,
+    }
+}
+
+fn macos_events(limit: u32) -> Result<Vec<BootEvent>, String> {
+    let output = run_text(
+        "log",
+        &[
+            "show",
+            "--style",
+            "json",
+            "--last",
+            "30d",
+            "--predicate",
+            "eventMessage CONTAINS \"Previous shutdown cause\"",
+        ],
+    )?;
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(&output).map_err(|e| e.to_string())?;
+
+    let mut events = Vec::new();
+    for entry in entries.iter().rev().take(limit as usize) {
+        let message = entry["eventMessage"].as_str().unwrap_or_default();
+        let Some(cause) = message
+            .rsplit(':')
+            .next()
+            .and_then(|cause| cause.trim().parse().ok())
+        else {
+            continue;
+        };
+        // Timestamps look like `2024-01-01 10:00:00.123456-0800`
+        let timestamp = entry["timestamp"].as_str().and_then(parse_log_timestamp);
+        events.push(event(
+            timestamp,
+            BootEventKind::Boot,
+            Some(message.trim().to_string()),
+        ));
+        events.push(event(
+            None,
+            macos_shutdown_kind(cause),
+            Some(format!("Shutdown cause {}", cause)),
+        ));
+    }
+    Ok(events)
+}
+
+// Seconds since the epoch from `YYYY-MM-DD HH:MM:SS[.ffffff]+HHMM`
+fn parse_log_timestamp(timestamp: &str) -> Option<i64> {
+    let (date, rest) = timestamp.split_once(' ')?;
+    let mut date = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let offset_at = rest.rfind(['+', '-'])?;
+    let (time, offset) = rest.split_at(offset_at);
+    let mut time = time
+        .split(':')
+        .map(|part| part.split('.').next()?.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let offset: i64 = offset[1..].parse().ok()?;
+    let offset_seconds = sign * ((offset / 100) * 3600 + (offset % 100) * 60);
+
+    // Days from the civil date (Howard Hinnant's algorithm)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(days * 86400 + hour * 3600 + minute * 60 + second - offset_seconds)
+}
+
+fn read_history(limit: u32) -> BootHistory {
+    let events = if cfg!(target_os = "linux") {
+        Some(linux_events(limit))
+    } else if cfg!(target_os = "windows") {
+        Some(windows_events(limit))
+    } else if cfg!(target_os = "macos") {
+        Some(macos_events(limit))
+    } else {
+        None
+    };
+
+    let supported = events.is_some();
+    let (events, errors) = match events {
+        Some(Ok(events)) => (events, Vec::new()),
+        Some(Err(e)) => (Vec::new(), vec![e]),
+        None => (Vec::new(), Vec::new()),
+    };
+    let unexpected = events
+        .iter()
+        .filter(|event| {
+            !matches!(
+                event.kind,
+                BootEventKind::Boot | BootEventKind::Shutdown | BootEventKind::Reboot
+            )
+        })
+        .count();
+    BootHistory {
+        supported,
+        events,
+        unexpected,
+        errors,
+    }
+}
+
+// Up to `limit` recent boots (10 by default) and how the ones before the current boot ended
+#[tauri::command]
+pub async fn get_boot_history(limit: Option<u32>) -> Result<BootHistory, CommandError> {
+    let limit = limit.unwrap_or(DEFAULT_BOOT_LIMIT).max(1);
+    let history = tauri::async_runtime::spawn_blocking(move || read_history(limit))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(history)
+}
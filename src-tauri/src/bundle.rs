@@ -0,0 +1,447 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{AesMode, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::config::ConfigState;
+use crate::display;
+use crate::error::{CommandError, ErrorCode};
+use crate::export_history::{self, ExportKind};
+use crate::history::{Aggregation, GroupBy, History, HistoryPoint, TimeRange};
+use crate::provider::{Attribution, Backend};
+use crate::report::{collect_report, unix_timestamp, Diagnostics, SystemReport};
+use crate::support_bundle::{recent_events, Events};
+use crate::CppLibrary;
+
+// Bumped whenever the layout of the archive changes
+pub const BUNDLE_FORMAT_VERSION: u32 = 2;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const REPORT_FILE: &str = "report.json";
+pub const DIAGNOSTICS_FILE: &str = "diagnostics.json";
+// Added in format 2
+pub const RECENT_HISTORY_FILE: &str = "recent-history.json";
+pub const LOGS_FILE: &str = "logs.json";
+
+// Raw samples cover the last hour, hourly averages the last day
+const RAW_HISTORY_SECONDS: i64 = 3600;
+const HOURLY_HISTORY_SECONDS: i64 = 24 * 3600;
+// Only the end of each log file is kept
+const LOG_TAIL_BYTES: u64 = 256 * 1024;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricHistory {
+    pub metric: String,
+    pub samples: Vec<HistoryPoint>,
+    pub hourly: Vec<HistoryPoint>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleLogs {
+    // The app's event logs: process, integrity and inventory changes
+    pub events: Events,
+    // File name -> end of each file in the app log directory
+    pub files: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub created_at: u64,
+    pub app_version: String,
+    pub encrypted: bool,
+    pub files: Vec<String>,
+}
+
+pub fn write_entry<T: Serialize>(
+    zip: &mut ZipWriter<File>,
+    name: &str,
+    value: &T,
+    options: FileOptions<'_, '_, ()>,
+) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| e.to_string())
+}
+
+// Raw samples of every metric over the last hour and hourly averages over the last day
+fn recent_history(history: &History) -> Result<Vec<MetricHistory>, String> {
+    let to = unix_timestamp() as i64;
+    let raw = TimeRange {
+        from: to - RAW_HISTORY_SECONDS,
+        to,
+    };
+    let hourly = TimeRange {
+        from: to - HOURLY_HISTORY_SECONDS,
+        to,
+    };
+    let mut metrics = Vec::new();
+    for metric in history.metrics_with_prefix("")? {
+        let samples = history.points(&metric, raw)?;
+        let hourly = history
+            .query(&metric, hourly, Aggregation::Avg, GroupBy::Hour)?
+            .points;
+        if !samples.is_empty() || !hourly.is_empty() {
+            metrics.push(MetricHistory {
+                metric,
+                samples,
+                hourly,
+            });
+        }
+    }
+    Ok(metrics)
+}
+
+fn log_tail(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let length = file.metadata()?.len();
+    file.seek(SeekFrom::Start(length.saturating_sub(LOG_TAIL_BYTES)))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// The event logs and whatever is in the app log directory; an unreadable file is noted in its place
+fn collect_logs(app: &AppHandle) -> BundleLogs {
+    let mut files = BTreeMap::new();
+    let entries = app
+        .path()
+        .app_log_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_dir(dir).ok());
+    for entry in entries.into_iter().flatten().flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let text = log_tail(&path).unwrap_or_else(|e| format!("Failed to read: {}", e));
+        files.insert(entry.file_name().to_string_lossy().into_owned(), text);
+    }
+    BundleLogs {
+        events: recent_events(app),
+        files,
+    }
+}
+
+#[tauri::command]
+pub fn export_bundle(
+    path: String,
+    password: Option<String>,
+    app: AppHandle,
+    lib_state: State<CppLibrary>,
+    config: State<ConfigState>,
+    history: State<History>,
+) -> Result<(), CommandError> {
+    let password = password.filter(|p| !p.is_empty());
+    let (report, diagnostics) = collect_report(&lib_state, &display::current(&config));
+    let recent = recent_history(&history)?;
+    let logs = collect_logs(&app);
+
+    let file = File::create(&path).map_err(|e| CommandError::create_failed(&path, e))?;
+    let mut zip = ZipWriter::new(file);
+
+    // The manifest stays readable so an importer can tell a password is needed
+    let plain = FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+    let content = match password.as_deref() {
+        Some(password) => plain.with_aes_encryption(AesMode::Aes256, password),
+        None => plain,
+    };
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        created_at: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        encrypted: password.is_some(),
+        files: [
+            REPORT_FILE,
+            DIAGNOSTICS_FILE,
+            RECENT_HISTORY_FILE,
+            LOGS_FILE,
+        ]
+        .map(String::from)
+        .to_vec(),
+    };
+
+    write_entry(&mut zip, MANIFEST_FILE, &manifest, plain)?;
+    write_entry(&mut zip, REPORT_FILE, &report, content)?;
+    write_entry(&mut zip, DIAGNOSTICS_FILE, &diagnostics, content)?;
+    write_entry(&mut zip, RECENT_HISTORY_FILE, &recent, content)?;
+    write_entry(&mut zip, LOGS_FILE, &logs, content)?;
+    zip.finish().map_err(|e| e.to_string())?;
+
+    // The report is kept unencrypted in the export history only when the bundle wasn't
+    // encrypted; history and logs are left out there to keep the entry small
+    let content = match password {
+        Some(_) => None,
+        None => serde_json::to_string(&ImportedReport {
+            source_path: path.clone(),
+            manifest: Some(manifest),
+            report,
+            diagnostics: Some(diagnostics),
+            recent_history: None,
+            logs: None,
+        })
+        .ok(),
+    };
+    export_history::record_or_warn(
+        &history,
+        ExportKind::Bundle,
+        Some(&path),
+        "System report bundle",
+        content.as_deref(),
+    );
+
+    println!("✓ Exported report bundle to {}", path);
+    Ok(())
+}
+
+// A report loaded from disk, browsed read-only alongside the live data
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedReport {
+    pub source_path: String,
+    pub manifest: Option<BundleManifest>,
+    pub report: SystemReport,
+    pub diagnostics: Option<Diagnostics>,
+    // Only in bundles of format 2 and later
+    pub recent_history: Option<Vec<MetricHistory>>,
+    pub logs: Option<BundleLogs>,
+}
+
+// A system snapshot as `export_report` writes it in JSON
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedSnapshot {
+    timestamp: u64,
+    // Missing in snapshots exported before it was added
+    #[serde(default)]
+    app_version: String,
+    platform: String,
+    arch: String,
+    library_loaded: bool,
+    backend: Backend,
+    computer_name: Option<String>,
+    total_memory: Option<u64>,
+    process_id: Option<u32>,
+    #[serde(default)]
+    errors: BTreeMap<String, String>,
+    #[serde(default)]
+    sources: BTreeMap<String, Attribution>,
+}
+
+impl ExportedSnapshot {
+    fn into_report(self) -> (SystemReport, Diagnostics) {
+        let report = SystemReport {
+            generated_at: self.timestamp,
+            app_version: self.app_version,
+            platform: self.platform,
+            arch: self.arch,
+            computer_name: self.computer_name,
+            total_memory: self.total_memory,
+            process_id: self.process_id,
+            formatted: BTreeMap::new(),
+            sources: self.sources,
+        };
+        let diagnostics = Diagnostics {
+            library_loaded: self.library_loaded,
+            library: None,
+            backend: Some(self.backend),
+            errors: self
+                .errors
+                .into_iter()
+                .map(|(field, error)| format!("{}: {}", field, error))
+                .collect(),
+        };
+        (report, diagnostics)
+    }
+}
+
+#[derive(Default)]
+pub struct ImportedSession {
+    report: Mutex<Option<ImportedReport>>,
+}
+
+fn read_entry<T: DeserializeOwned>(
+    archive: &mut ZipArchive<File>,
+    name: &str,
+    password: Option<&str>,
+) -> Result<T, String> {
+    let entry = match password {
+        Some(password) => archive.by_name_decrypt(name, password.as_bytes()),
+        None => archive.by_name(name),
+    };
+    let mut entry = entry.map_err(|e| match e {
+        ZipError::InvalidPassword => "Incorrect bundle password".to_string(),
+        ZipError::UnsupportedArchive(_) if password.is_none() => {
+            "This bundle is encrypted; a password is required".to_string()
+        }
+        e => format!("Failed to read {}: {}", name, e),
+    })?;
+
+    let mut json = Vec::new();
+    entry.read_to_end(&mut json).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&json).map_err(|e| format!("Invalid {}: {}", name, e))
+}
+
+fn load_bundle(path: &str, password: Option<&str>) -> Result<ImportedReport, CommandError> {
+    let file = File::open(path).map_err(|e| CommandError::open_failed(path, e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a report bundle: {}", e))?;
+
+    let manifest: BundleManifest = read_entry(&mut archive, MANIFEST_FILE, None)?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Bundle format {} is newer than this app supports ({})",
+            manifest.format_version, BUNDLE_FORMAT_VERSION
+        )
+        .into());
+    }
+    let password = if manifest.encrypted { password } else { None };
+    if manifest.encrypted && password.is_none() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "bundle.passwordRequired",
+        ));
+    }
+
+    let report = read_entry(&mut archive, REPORT_FILE, password)?;
+    let diagnostics = read_entry(&mut archive, DIAGNOSTICS_FILE, password).ok();
+    let listed = |name: &str| manifest.files.iter().any(|file| file == name);
+    let recent_history = listed(RECENT_HISTORY_FILE)
+        .then(|| read_entry(&mut archive, RECENT_HISTORY_FILE, password))
+        .transpose()?;
+    let logs = listed(LOGS_FILE)
+        .then(|| read_entry(&mut archive, LOGS_FILE, password))
+        .transpose()?;
+
+    Ok(ImportedReport {
+        source_path: path.to_string(),
+        manifest: Some(manifest),
+        report,
+        diagnostics,
+        recent_history,
+        logs,
+    })
+}
+
+// A plain JSON report: a `SystemReport`, or a snapshot saved by `export_report`
+fn load_json(path: &str) -> Result<ImportedReport, CommandError> {
+    let json = std::fs::read(path).map_err(|e| CommandError::open_failed(path, e))?;
+    let (report, diagnostics) = match serde_json::from_slice::<SystemReport>(&json) {
+        Ok(report) => (report, None),
+        Err(e) => {
+            let snapshot: ExportedSnapshot =
+                serde_json::from_slice(&json).map_err(|_| format!("Invalid report: {}", e))?;
+            let (report, diagnostics) = snapshot.into_report();
+            (report, Some(diagnostics))
+        }
+    };
+    Ok(ImportedReport {
+        source_path: path.to_string(),
+        manifest: None,
+        report,
+        diagnostics,
+        recent_history: None,
+        logs: None,
+    })
+}
+
+#[tauri::command]
+pub fn import_report(
+    path: String,
+    password: Option<String>,
+    session: State<ImportedSession>,
+) -> Result<ImportedReport, CommandError> {
+    let password = password.filter(|p| !p.is_empty());
+
+    // Plain JSON reports and exported snapshots are accepted as well as full bundles
+    let is_json = Path::new(&path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let imported = if is_json {
+        load_json(&path)?
+    } else {
+        load_bundle(&path, password.as_deref())?
+    };
+
+    *session.report.lock().map_err(|_| "Session lock poisoned")? = Some(imported.clone());
+    println!("✓ Imported report from {}", path);
+    Ok(imported)
+}
+
+#[tauri::command]
+pub fn get_imported_report(
+    session: State<ImportedSession>,
+) -> Result<Option<ImportedReport>, CommandError> {
+    Ok(session
+        .report
+        .lock()
+        .map_err(|_| "Session lock poisoned")?
+        .clone())
+}
+
+#[tauri::command]
+pub fn close_imported_report(session: State<ImportedSession>) -> Result<(), CommandError> {
+    *session.report.lock().map_err(|_| "Session lock poisoned")? = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MetricSource;
+    use crate::snapshot::SystemSnapshot;
+
+    #[test]
+    fn exported_snapshot_imports_as_report() {
+        let snapshot = SystemSnapshot {
+            timestamp: 1_700_000_000,
+            app_version: "1.2.3".to_string(),
+            platform: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            library_loaded: false,
+            backend: Backend::Rust,
+            computer_name: Some("desk".to_string()),
+            total_memory: None,
+            process_id: Some(42),
+            os_info: None,
+            cpu_info: None,
+            cpu_usage: None,
+            memory: None,
+            uptime: None,
+            disks: None,
+            errors: BTreeMap::from([("totalMemory", "access denied".to_string())]),
+            sources: BTreeMap::from([(
+                "computerName",
+                Attribution {
+                    source: MetricSource::Fallback,
+                    read_at: 1_700_000_000,
+                },
+            )]),
+        };
+        let path = std::env::temp_dir().join(format!("snapshot-{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_vec_pretty(&snapshot).unwrap()).unwrap();
+        let imported = load_json(&path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+
+        let Ok(imported) = imported else {
+            panic!("snapshot JSON should import");
+        };
+        assert_eq!(imported.report.generated_at, 1_700_000_000);
+        assert_eq!(imported.report.app_version, "1.2.3");
+        assert_eq!(imported.report.computer_name.as_deref(), Some("desk"));
+        assert_eq!(imported.report.process_id, Some(42));
+        assert!(imported.report.sources.contains_key("computerName"));
+        let diagnostics = imported.diagnostics.unwrap();
+        assert_eq!(diagnostics.errors, vec!["totalMemory: access denied"]);
+    }
+}
@@ -0,0 +1,258 @@
+// Camera and microphone privacy indicators.
+// Linux: processes holding `/dev/video*` or an ALSA capture device open, plus PulseAudio/PipeWire
+// recording streams from `pactl` (only our own processes' file descriptors are visible without
+// root). Windows: the per-app usage timestamps the privacy settings page reads from the
+// CapabilityAccessManager consent store. macOS has no public interface for this.
+// While a window is subscribed, usage is polled and `capture-device-usage-changed` is emitted
+// for every app that starts or stops using a device.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::os_command::run_text;
+use crate::report::unix_timestamp;
+use crate::subscriptions::EventSubscriptions;
+
+pub const CAPTURE_USAGE_CHANGED_EVENT: &str = "capture-device-usage-changed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+// Sound servers keep capture devices open on behalf of their clients, which `pactl` names
+const SOUND_SERVERS: [&str; 3] = ["pipewire", "pulseaudio", "wireplumber"];
+
+const CONSENT_STORE: &str =
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureDevice {
+    Camera,
+    Microphone,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureUser {
+    pub device: CaptureDevice,
+    // Executable or app name
+    pub application: String,
+    pub process_id: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureUsage {
+    // False where the OS doesn't expose capture usage
+    pub supported: bool,
+    pub camera_in_use: bool,
+    pub microphone_in_use: bool,
+    pub users: Vec<CaptureUser>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureUsageChange {
+    #[serde(flatten)]
+    pub user: CaptureUser,
+    // True when usage started, false when it stopped
+    pub in_use: bool,
+    pub timestamp: i64,
+}
+
+fn device_for_path(path: &str) -> Option<CaptureDevice> {
+    let name = path.strip_prefix("/dev/")?;
+    if name.starts_with("video") {
+        Some(CaptureDevice::Camera)
+    } else if name.starts_with("snd/pcm") && name.ends_with('c') {
+        // `pcmC0D0c` is a capture device, `pcmC0D0p` playback
+        Some(CaptureDevice::Microphone)
+    } else {
+        None
+    }
+}
+
+fn scan_proc_fds(users: &mut BTreeSet<CaptureUser>) {
+    let Ok(processes) = std::fs::read_dir("/proc") else {
+        return;
+    };
+    for process in processes.filter_map(Result::ok) {
+        let Some(pid) = process
+            .file_name()
+            .to_str()
+            .and_then(|pid| pid.parse().ok())
+        else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(process.path().join("fd")) else {
+            continue;
+        };
+        let devices: BTreeSet<CaptureDevice> = fds
+            .filter_map(Result::ok)
+            .filter_map(|fd| std::fs::read_link(fd.path()).ok())
+            .filter_map(|target| device_for_path(&target.to_string_lossy()))
+            .collect();
+        if devices.is_empty() {
+            continue;
+        }
+        let name = std::fs::read_to_string(process.path().join("comm"))
+            .map(|comm| comm.trim().to_string())
+            .unwrap_or_default();
+        if SOUND_SERVERS.contains(&name.as_str()) {
+            continue;
+        }
+        for device in devices {
+            users.insert(CaptureUser {
+                device,
+                application: name.clone(),
+                process_id: Some(pid),
+            });
+        }
+    }
+}
+
+// `pactl list source-outputs` prints one block per recording stream with properties such as
+// `application.name = "Firefox"` and `application.process.id = "1234"`
+fn scan_pactl(users: &mut BTreeSet<CaptureUser>) {
+    let Ok(output) = run_text("pactl", &["list", "source-outputs"]) else {
+        return;
+    };
+    for block in output.split("Source Output #").skip(1) {
+        let property = |name: &str| {
+            block.lines().find_map(|line| {
+                let (key, value) = line.trim().split_once(" = ")?;
+                (key == name).then(|| value.trim_matches('"').to_string())
+            })
+        };
+        users.insert(CaptureUser {
+            device: CaptureDevice::Microphone,
+            application: property("application.name")
+                .or_else(|| property("application.process.binary"))
+                .unwrap_or_default(),
+            process_id: property("application.process.id").and_then(|pid| pid.parse().ok()),
+        });
+    }
+}
+
+// An app is using the device while its `LastUsedTimeStop` is 0; `reg query /s` prints each
+// app's key followed by its `Name    REG_QWORD    0x...` values
+fn scan_consent_store(
+    device: CaptureDevice,
+    capability: &str,
+    users: &mut BTreeSet<CaptureUser>,
+) -> Result<(), String> {
+    let output = run_text(
+        "reg",
+        &["query", &format!(r"{}\{}", CONSENT_STORE, capability), "/s"],
+    )?;
+    let mut app = None;
+    let mut started = false;
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("HKEY_") {
+            // Desktop apps are stored under `NonPackaged` with `\` in their path replaced by `#`
+            app = line.rsplit('\\').next().map(|key| key.replace('#', "\\"));
+            started = false;
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(_), Some(value)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        match name {
+            "LastUsedTimeStart" => started = value != "0x0",
+            "LastUsedTimeStop" if started && value == "0x0" => {
+                if let Some(app) = app.clone().filter(|app| app != "NonPackaged") {
+                    users.insert(CaptureUser {
+                        device,
+                        application: app,
+                        process_id: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn read_usage() -> Result<CaptureUsage, String> {
+    let mut users = BTreeSet::new();
+    let supported = if cfg!(target_os = "linux") {
+        scan_proc_fds(&mut users);
+        scan_pactl(&mut users);
+        true
+    } else if cfg!(target_os = "windows") {
+        scan_consent_store(CaptureDevice::Camera, "webcam", &mut users)?;
+        scan_consent_store(CaptureDevice::Microphone, "microphone", &mut users)?;
+        true
+    } else {
+        false
+    };
+
+    let in_use = |device| users.iter().any(|user| user.device == device);
+    Ok(CaptureUsage {
+        supported,
+        camera_in_use: in_use(CaptureDevice::Camera),
+        microphone_in_use: in_use(CaptureDevice::Microphone),
+        users: users.into_iter().collect(),
+    })
+}
+
+fn emit_changes(
+    app: &AppHandle,
+    previous: &BTreeSet<CaptureUser>,
+    current: &BTreeSet<CaptureUser>,
+) {
+    let timestamp = unix_timestamp() as i64;
+    let started = current.difference(previous).map(|user| (user, true));
+    let stopped = previous.difference(current).map(|user| (user, false));
+    for (user, in_use) in started.chain(stopped) {
+        let change = CaptureUsageChange {
+            user: user.clone(),
+            in_use,
+            timestamp,
+        };
+        if let Err(e) = app.emit(CAPTURE_USAGE_CHANGED_EVENT, change) {
+            eprintln!(
+                "⚠ Warning: Failed to emit {}: {}",
+                CAPTURE_USAGE_CHANGED_EVENT, e
+            );
+        }
+    }
+}
+
+// Background job polling capture usage while a window listens for changes
+pub fn start_monitor(app: AppHandle) {
+    if !cfg!(any(target_os = "linux", target_os = "windows")) {
+        return;
+    }
+    std::thread::spawn(move || {
+        let mut previous = BTreeSet::new();
+        loop {
+            app.state::<EventSubscriptions>()
+                .wait_for_listeners(CAPTURE_USAGE_CHANGED_EVENT);
+
+            match read_usage() {
+                Ok(usage) => {
+                    let current: BTreeSet<CaptureUser> = usage.users.into_iter().collect();
+                    emit_changes(&app, &previous, &current);
+                    previous = current;
+                }
+                Err(e) => eprintln!("Capture device check failed: {}", e),
+            }
+            battery_saver::wait(&app, POLL_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_capture_device_usage() -> Result<CaptureUsage, CommandError> {
+    let usage = tauri::async_runtime::spawn_blocking(read_usage)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(usage)
+}
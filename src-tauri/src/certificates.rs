@@ -0,0 +1,258 @@
+// Certificate expiry overview.
+// Reads the machine and user certificate stores where the OS offers a command-line export
+// (trust bundle on Linux, `security` on macOS, PowerShell's Cert: drive on Windows) plus any
+// configured PEM/DER files or directories, and flags certificates close to expiring.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::os_command::run;
+use crate::report::unix_timestamp;
+
+const DAY: i64 = 86400;
+
+// System trust bundles, first one found wins
+const LINUX_BUNDLES: [&str; 3] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/cert.pem",
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CertificateConfig {
+    // Extra certificate files or directories to check
+    pub paths: Vec<PathBuf>,
+    // Certificates expiring within this many days are flagged
+    pub warn_days: u32,
+}
+
+impl Default for CertificateConfig {
+    fn default() -> Self {
+        CertificateConfig {
+            paths: Vec::new(),
+            warn_days: 30,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CertificateStatus {
+    Valid,
+    ExpiringSoon,
+    Expired,
+    NotYetValid,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSummary {
+    // Store or file the certificate came from
+    pub source: String,
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: i64,
+    pub not_after: i64,
+    pub days_remaining: i64,
+    pub status: CertificateStatus,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateOverview {
+    pub warn_days: u32,
+    pub expired: usize,
+    pub expiring_soon: usize,
+    // Sorted by expiry, soonest first
+    pub certificates: Vec<CertificateSummary>,
+    // Stores or files that couldn't be read
+    pub errors: Vec<String>,
+}
+
+// Common name if present, otherwise the full distinguished name
+fn display_name(name: &x509_parser::x509::X509Name) -> String {
+    name.iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn summarize(source: &str, der: &[u8], now: i64, warn_days: u32) -> Option<CertificateSummary> {
+    let (_, cert) = X509Certificate::from_der(der).ok()?;
+    let validity = cert.validity();
+    let not_before = validity.not_before.timestamp();
+    let not_after = validity.not_after.timestamp();
+
+    let status = if now < not_before {
+        CertificateStatus::NotYetValid
+    } else if now >= not_after {
+        CertificateStatus::Expired
+    } else if not_after - now < warn_days as i64 * DAY {
+        CertificateStatus::ExpiringSoon
+    } else {
+        CertificateStatus::Valid
+    };
+
+    Some(CertificateSummary {
+        source: source.to_string(),
+        subject: display_name(cert.subject()),
+        issuer: display_name(cert.issuer()),
+        serial: cert.raw_serial_as_string(),
+        not_before,
+        not_after,
+        days_remaining: (not_after - now).div_euclid(DAY),
+        status,
+    })
+}
+
+// DER blobs from PEM text (any number of certificates) or a single DER certificate
+fn der_certificates(data: &[u8]) -> Vec<Vec<u8>> {
+    let pems: Vec<Vec<u8>> = Pem::iter_from_buffer(data)
+        .filter_map(Result::ok)
+        .filter(|pem| pem.label == "CERTIFICATE")
+        .map(|pem| pem.contents)
+        .collect();
+    if pems.is_empty() && X509Certificate::from_der(data).is_ok() {
+        return vec![data.to_vec()];
+    }
+    pems
+}
+
+fn files_in(path: &Path) -> Vec<PathBuf> {
+    match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect(),
+        Err(_) => vec![path.to_path_buf()],
+    }
+}
+
+// A store or file and the DER certificates read from it
+type Source = (String, Result<Vec<Vec<u8>>, String>);
+
+fn system_stores() -> Vec<Source> {
+    if cfg!(target_os = "linux") {
+        LINUX_BUNDLES
+            .iter()
+            .find(|bundle| Path::new(bundle).exists())
+            .map(|bundle| {
+                let certs = std::fs::read(bundle)
+                    .map(|data| der_certificates(&data))
+                    .map_err(|e| e.to_string());
+                vec![(bundle.to_string(), certs)]
+            })
+            .unwrap_or_default()
+    } else if cfg!(target_os = "macos") {
+        [
+            (
+                "System keychain",
+                vec!["/Library/Keychains/System.keychain"],
+            ),
+            ("User keychains", vec![]),
+        ]
+        .into_iter()
+        .map(|(name, keychains)| {
+            let mut args = vec!["find-certificate", "-a", "-p"];
+            args.extend(keychains);
+            let certs = run("security", &args).map(|pem| der_certificates(&pem));
+            (name.to_string(), certs)
+        })
+        .collect()
+    } else if cfg!(target_os = "windows") {
+        ["LocalMachine", "CurrentUser"]
+            .into_iter()
+            .map(|location| {
+                let script = format!(
+                    "Get-ChildItem Cert:\\{}\\My | ForEach-Object {{ \
+                     '-----BEGIN CERTIFICATE-----'; \
+                     [Convert]::ToBase64String($_.RawData, 'InsertLineBreaks'); \
+                     '-----END CERTIFICATE-----' }}",
+                    location
+                );
+                let certs = run("powershell", &["-NoProfile", "-Command", &script])
+                    .map(|pem| der_certificates(&pem));
+                (format!("{}\\My", location), certs)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+fn collect(config: &CertificateConfig, include_system: bool) -> CertificateOverview {
+    let now = unix_timestamp() as i64;
+    let mut sources = if include_system {
+        system_stores()
+    } else {
+        Vec::new()
+    };
+    for path in &config.paths {
+        for file in files_in(path) {
+            let certs = std::fs::read(&file)
+                .map(|data| der_certificates(&data))
+                .map_err(|e| e.to_string());
+            sources.push((file.display().to_string(), certs));
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut certificates = Vec::new();
+    let mut errors = Vec::new();
+    for (source, certs) in sources {
+        match certs {
+            Ok(certs) => {
+                for der in certs {
+                    let Some(summary) = summarize(&source, &der, now, config.warn_days) else {
+                        continue;
+                    };
+                    // Stores often list the same certificate more than once
+                    if seen.insert((summary.issuer.clone(), summary.serial.clone())) {
+                        certificates.push(summary);
+                    }
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", source, e)),
+        }
+    }
+    certificates.sort_by_key(|cert| cert.not_after);
+
+    let count = |status| {
+        certificates
+            .iter()
+            .filter(|cert| cert.status == status)
+            .count()
+    };
+    CertificateOverview {
+        warn_days: config.warn_days,
+        expired: count(CertificateStatus::Expired),
+        expiring_soon: count(CertificateStatus::ExpiringSoon),
+        certificates,
+        errors,
+    }
+}
+
+// Certificates from the configured paths and, unless `include_system` is false, the OS stores
+#[tauri::command]
+pub async fn get_certificates(
+    include_system: Option<bool>,
+    config: State<'_, ConfigState>,
+) -> Result<CertificateOverview, CommandError> {
+    let config = config.get()?.certificates.clone();
+    let overview = tauri::async_runtime::spawn_blocking(move || {
+        collect(&config, include_system.unwrap_or(true))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(overview)
+}
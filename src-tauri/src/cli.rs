@@ -0,0 +1,68 @@
+// Command-line overrides read once at startup, e.g.
+// `system-info-app --lib-path /opt/systemapi/libsystemapi.so --config ./config.toml --headless`
+// Values may also be given as `--flag=value`.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::State;
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliArgs {
+    // Library file, or directory containing it, tried before the normal search
+    pub lib_path: Option<PathBuf>,
+    // Config file used instead of the one in the app config directory
+    pub config: Option<PathBuf>,
+    // Hide the window and serve metrics through the remote agent only
+    pub headless: bool,
+    // Print the available commands and exit
+    pub list: bool,
+    // Development builds: build the C++ library with CMake when none is found
+    pub build_lib: bool,
+    // Reload the C++ library whenever its file changes
+    pub watch_lib: bool,
+    // Run as the sandbox helper for this library instead of starting the app
+    pub ffi_helper: Option<PathBuf>,
+}
+
+impl CliArgs {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut parsed = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = |name: &str| {
+                let value = inline.clone().or_else(|| args.next());
+                if value.is_none() {
+                    eprintln!("⚠ Warning: {} expects a value", name);
+                }
+                value.map(PathBuf::from)
+            };
+
+            match flag.as_str() {
+                "--lib-path" => parsed.lib_path = value("--lib-path"),
+                "--config" => parsed.config = value("--config"),
+                "--headless" => parsed.headless = true,
+                "--list" => parsed.list = true,
+                "--build-lib" => parsed.build_lib = true,
+                "--watch-lib" => parsed.watch_lib = true,
+                "--ffi-helper" => parsed.ffi_helper = value("--ffi-helper"),
+                _ => eprintln!("⚠ Warning: Ignoring unknown argument {}", flag),
+            }
+        }
+        parsed
+    }
+
+    pub fn from_env() -> Self {
+        CliArgs::parse(std::env::args().skip(1))
+    }
+}
+
+#[tauri::command]
+pub fn get_startup_args(args: State<CliArgs>) -> CliArgs {
+    args.inner().clone()
+}
@@ -0,0 +1,1282 @@
+// Metadata for every registered command: its arguments, what running it involves and whether
+// this build and platform can run it. One table feeds both `list_commands` (the frontend's
+// command palette) and `--list` on the command line, so keep it in step with the
+// `generate_handler!` list in lib.rs.
+
+use serde::Serialize;
+
+use ArgType::{Array, Boolean, Integer, Number, Object};
+use Permission::{Confirmation, Elevated, Filesystem, Interactive, Network};
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgType {
+    String,
+    Integer,
+    // Fractions allowed
+    Number,
+    Boolean,
+    Object,
+    Array,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgInfo {
+    // As passed from JavaScript, i.e. camelCase
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub kind: ArgType,
+    pub required: bool,
+    // Accepted values for string enums; empty otherwise
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub values: &'static [&'static str],
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Permission {
+    // Asks the user to confirm before acting
+    Confirmation,
+    // Shows a dialog, so refused in kiosk mode
+    Interactive,
+    // Complete results need administrator or root rights
+    Elevated,
+    // Talks to other machines
+    Network,
+    // Reads or writes a file the caller names
+    Filesystem,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub description_key: &'static str,
+    // English description, for the CLI and as a fallback
+    pub description: &'static str,
+    pub args: &'static [ArgInfo],
+    pub permissions: &'static [Permission],
+    // Cargo feature the command is compiled with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature: Option<&'static str>,
+    // `std::env::consts::OS` values it works on; empty for all
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub platforms: &'static [&'static str],
+    // Only registered in debug builds
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dev_only: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandInfo {
+    #[serde(flatten)]
+    pub spec: &'static CommandSpec,
+    pub available: bool,
+}
+
+const fn arg(name: &'static str, kind: ArgType) -> ArgInfo {
+    ArgInfo {
+        name,
+        kind,
+        required: true,
+        values: &[],
+    }
+}
+
+const fn optional(name: &'static str, kind: ArgType) -> ArgInfo {
+    ArgInfo {
+        name,
+        kind,
+        required: false,
+        values: &[],
+    }
+}
+
+const fn one_of(name: &'static str, required: bool, values: &'static [&'static str]) -> ArgInfo {
+    ArgInfo {
+        name,
+        kind: ArgType::String,
+        required,
+        values,
+    }
+}
+
+const fn command(
+    name: &'static str,
+    description_key: &'static str,
+    description: &'static str,
+    args: &'static [ArgInfo],
+    permissions: &'static [Permission],
+) -> CommandSpec {
+    CommandSpec {
+        name,
+        description_key,
+        description,
+        args,
+        permissions,
+        feature: None,
+        platforms: &[],
+        dev_only: false,
+    }
+}
+
+const AGGREGATIONS: &[&str] = &["avg", "min", "max", "sum", "count", "p50", "p95", "p99"];
+const GROUP_BY: &[&str] = &["none", "minute", "hour", "day"];
+const BENCHMARK_KINDS: &[&str] = &["cpu", "cpuparallel", "memory", "disk", "gpu"];
+const LOCALES: &[&str] = &["en", "es", "de"];
+
+pub const COMMANDS: &[CommandSpec] = &[
+    command(
+        "list_commands",
+        "command.list_commands",
+        "List every command with its arguments and availability",
+        &[],
+        &[],
+    ),
+    command(
+        "get_computer_name",
+        "command.get_computer_name",
+        "Computer name",
+        &[],
+        &[],
+    ),
+    command(
+        "get_total_memory",
+        "command.get_total_memory",
+        "Total physical memory in bytes",
+        &[],
+        &[],
+    ),
+    command(
+        "get_process_id",
+        "command.get_process_id",
+        "Process ID of the app",
+        &[],
+        &[],
+    ),
+    command(
+        "calculate_factorial",
+        "command.calculate_factorial",
+        "Factorial of n, computed by the active backend",
+        &[arg("n", Integer)],
+        &[],
+    ),
+    command(
+        "get_library_load_trace",
+        "command.get_library_load_trace",
+        "Every path tried while loading the system library",
+        &[],
+        &[],
+    ),
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "get_library_load_report",
+            "command.get_library_load_report",
+            "Why the system library did or didn't load: every path tried, where it came from and the error",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "get_library_capabilities",
+            "command.get_library_capabilities",
+            "Which native functions the loaded system library exports",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "get_library_version",
+            "command.get_library_version",
+            "Version and API version of the loaded system library",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "pick_and_load_library",
+            "command.pick_and_load_library",
+            "Choose a system library file and load it",
+            &[],
+            &[Interactive, Filesystem],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "reload_library",
+            "command.reload_library",
+            "Unload the system library and search for it again",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "unload_library",
+            "command.unload_library",
+            "Unload the system library and use the Rust providers",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "get_sandboxed_commands",
+            "command.get_sandboxed_commands",
+            "Which commands call the system library from a helper process",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        ..command(
+            "set_sandboxed_commands",
+            "command.set_sandboxed_commands",
+            "Choose the commands that call the system library from a helper process",
+            &[arg("commands", Array)],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ffi"),
+        dev_only: true,
+        ..command(
+            "build_library",
+            "command.build_library",
+            "Build the C++ library with CMake and load it",
+            &[],
+            &[],
+        )
+    },
+    command(
+        "get_platform",
+        "command.get_platform",
+        "Operating system the app runs on",
+        &[],
+        &[],
+    ),
+    command(
+        "get_os_info",
+        "command.get_os_info",
+        "OS name, version, build, kernel, architecture and domain",
+        &[],
+        &[],
+    ),
+    command(
+        "get_active_backend",
+        "command.get_active_backend",
+        "Backend answering the system info commands",
+        &[],
+        &[],
+    ),
+    command(
+        "get_system_snapshot",
+        "command.get_system_snapshot",
+        "Everything the dashboard shows in one call",
+        &[],
+        &[],
+    ),
+    command(
+        "export_report",
+        "command.export_report",
+        "Save the system snapshot, or one saved with an earlier export, as JSON, CSV or HTML",
+        &[
+            one_of("format", true, &["json", "csv", "html"]),
+            optional("path", ArgType::String),
+            optional("exportId", Integer),
+        ],
+        &[Interactive, Filesystem],
+    ),
+    command(
+        "show_dashboard",
+        "command.show_dashboard",
+        "Show and focus the main window",
+        &[],
+        &[],
+    ),
+    command(
+        "get_startup_mode",
+        "command.get_startup_mode",
+        "How the app starts",
+        &[],
+        &[],
+    ),
+    command(
+        "set_startup_mode",
+        "command.set_startup_mode",
+        "Change how the app starts",
+        &[one_of(
+            "mode",
+            true,
+            &["normal", "minimized", "trayOnly", "kiosk"],
+        )],
+        &[],
+    ),
+    command(
+        "subscribe_events",
+        "command.subscribe_events",
+        "Receive the named events in this window",
+        &[arg("events", Array)],
+        &[],
+    ),
+    command(
+        "unsubscribe_events",
+        "command.unsubscribe_events",
+        "Stop receiving the named events in this window",
+        &[arg("events", Array)],
+        &[],
+    ),
+    command(
+        "get_event_subscriptions",
+        "command.get_event_subscriptions",
+        "Events this window is subscribed to",
+        &[],
+        &[],
+    ),
+    command(
+        "get_display_settings",
+        "command.get_display_settings",
+        "Units and formatting preferences",
+        &[],
+        &[],
+    ),
+    command(
+        "set_display_settings",
+        "command.set_display_settings",
+        "Change units and formatting preferences",
+        &[arg("settings", Object)],
+        &[],
+    ),
+    command(
+        "format_values",
+        "command.format_values",
+        "Format numbers, sizes and dates the way exports do",
+        &[arg("values", Array)],
+        &[],
+    ),
+    command(
+        "get_backend_status",
+        "command.get_backend_status",
+        "Result of the startup checks on the backend",
+        &[],
+        &[],
+    ),
+    command(
+        "get_startup_args",
+        "command.get_startup_args",
+        "Command-line options the app was started with",
+        &[],
+        &[],
+    ),
+    command(
+        "get_policy",
+        "command.get_policy",
+        "Commands and samplers disabled by policy, and whether an administrator set it",
+        &[],
+        &[],
+    ),
+    command(
+        "get_config",
+        "command.get_config",
+        "All persisted settings",
+        &[],
+        &[],
+    ),
+    command(
+        "set_config",
+        "command.set_config",
+        "Replace and save all settings, applying them without a restart where possible",
+        &[arg("config", Object)],
+        &[],
+    ),
+    command(
+        "get_locale",
+        "command.get_locale",
+        "Language of error messages",
+        &[],
+        &[],
+    ),
+    command(
+        "set_locale",
+        "command.set_locale",
+        "Change the language of error messages",
+        &[one_of("locale", true, LOCALES)],
+        &[],
+    ),
+    command(
+        "get_message_catalog",
+        "command.get_message_catalog",
+        "Error message translations for a language",
+        &[one_of("locale", false, LOCALES)],
+        &[],
+    ),
+    command(
+        "send_wol",
+        "command.send_wol",
+        "Send a Wake-on-LAN packet",
+        &[
+            arg("mac", ArgType::String),
+            optional("broadcast", ArgType::String),
+        ],
+        &[Network],
+    ),
+    command(
+        "get_discovered_peers",
+        "command.get_discovered_peers",
+        "Other instances found on the local network",
+        &[],
+        &[Network],
+    ),
+    command(
+        "start_agent",
+        "command.start_agent",
+        "Serve metrics to other machines",
+        &[optional("port", Integer)],
+        &[Network],
+    ),
+    command(
+        "stop_agent",
+        "command.stop_agent",
+        "Stop serving metrics",
+        &[],
+        &[],
+    ),
+    command(
+        "start_exporter",
+        "command.start_exporter",
+        "Serve /metrics in the Prometheus text format, fed by the monitoring loop",
+        &[optional("port", Integer)],
+        &[Network],
+    ),
+    command(
+        "stop_exporter",
+        "command.stop_exporter",
+        "Stop serving Prometheus metrics",
+        &[],
+        &[],
+    ),
+    command(
+        "compare_machines",
+        "command.compare_machines",
+        "Fetch metrics from other agents side by side",
+        &[arg("hosts", Array), arg("metrics", Array)],
+        &[Network],
+    ),
+    command(
+        "create_share_link",
+        "command.create_share_link",
+        "Share a saved report through a temporary link",
+        &[arg("reportId", Integer), optional("ttl", Integer)],
+        &[Network],
+    ),
+    command(
+        "export_bundle",
+        "command.export_bundle",
+        "Write a report bundle, optionally encrypted",
+        &[
+            arg("path", ArgType::String),
+            optional("password", ArgType::String),
+        ],
+        &[Filesystem],
+    ),
+    command(
+        "create_support_bundle",
+        "command.create_support_bundle",
+        "Write diagnostics, the report, recent history and events to one zip for support",
+        &[arg("path", ArgType::String)],
+        &[Filesystem],
+    ),
+    command(
+        "start_recording",
+        "command.start_recording",
+        "Record the metric event streams to a session file",
+        &[arg("path", ArgType::String)],
+        &[Filesystem],
+    ),
+    command(
+        "stop_recording",
+        "command.stop_recording",
+        "Finish the session being recorded",
+        &[],
+        &[],
+    ),
+    command(
+        "start_replay",
+        "command.start_replay",
+        "Emit the events of a recorded session again, optionally sped up",
+        &[arg("path", ArgType::String), optional("speed", Number)],
+        &[Filesystem],
+    ),
+    command(
+        "stop_replay",
+        "command.stop_replay",
+        "Stop replaying a recorded session",
+        &[],
+        &[],
+    ),
+    command(
+        "import_report",
+        "command.import_report",
+        "Open a report bundle from another machine",
+        &[
+            arg("path", ArgType::String),
+            optional("password", ArgType::String),
+        ],
+        &[Filesystem],
+    ),
+    command(
+        "get_imported_report",
+        "command.get_imported_report",
+        "Report bundle currently open",
+        &[],
+        &[],
+    ),
+    command(
+        "close_imported_report",
+        "command.close_imported_report",
+        "Close the open report bundle",
+        &[],
+        &[],
+    ),
+    command(
+        "render_report",
+        "command.render_report",
+        "Render the system report through a template from the templates folder",
+        &[
+            arg("template", ArgType::String),
+            one_of("format", true, &["html", "markdown", "text"]),
+        ],
+        &[Filesystem],
+    ),
+    command(
+        "get_export_history",
+        "command.get_export_history",
+        "Recent exports and copied snippets",
+        &[optional("limit", Integer)],
+        &[],
+    ),
+    command(
+        "record_copied_snippet",
+        "command.record_copied_snippet",
+        "Add a copied snippet to the export history",
+        &[
+            arg("summary", ArgType::String),
+            arg("content", ArgType::String),
+        ],
+        &[],
+    ),
+    command(
+        "get_retention_policy",
+        "command.get_retention_policy",
+        "How long history is kept",
+        &[],
+        &[],
+    ),
+    command(
+        "set_retention_policy",
+        "command.set_retention_policy",
+        "Change how long history is kept",
+        &[arg("policy", Object)],
+        &[],
+    ),
+    command(
+        "get_storage_usage",
+        "command.get_storage_usage",
+        "Size of the history database",
+        &[],
+        &[],
+    ),
+    command(
+        "query_history",
+        "command.query_history",
+        "Recorded values of a metric over a time range",
+        &[
+            arg("metric", ArgType::String),
+            arg("range", Object),
+            one_of("aggregation", false, AGGREGATIONS),
+            one_of("groupBy", false, GROUP_BY),
+        ],
+        &[],
+    ),
+    command(
+        "get_sparkline",
+        "command.get_sparkline",
+        "Recent values of a metric downsampled to a fixed number of points",
+        &[
+            arg("metric", ArgType::String),
+            arg("points", Integer),
+            optional("seconds", Integer),
+        ],
+        &[],
+    ),
+    command(
+        "add_annotation",
+        "command.add_annotation",
+        "Mark a point in time on the history charts",
+        &[arg("timestamp", Integer), arg("text", ArgType::String)],
+        &[],
+    ),
+    command(
+        "delete_annotation",
+        "command.delete_annotation",
+        "Remove an annotation",
+        &[arg("id", Integer)],
+        &[],
+    ),
+    command(
+        "get_thermal_history",
+        "command.get_thermal_history",
+        "Recorded temperatures over a time range",
+        &[
+            arg("range", Object),
+            one_of("aggregation", false, AGGREGATIONS),
+            one_of("groupBy", false, GROUP_BY),
+        ],
+        &[],
+    ),
+    command(
+        "get_sensors",
+        "command.get_sensors",
+        "Current temperatures and fan speeds",
+        &[],
+        &[],
+    ),
+    command(
+        "get_cpu_frequency",
+        "command.get_cpu_frequency",
+        "Current and maximum CPU clock speeds",
+        &[],
+        &[],
+    ),
+    command(
+        "get_cpu_info",
+        "command.get_cpu_info",
+        "CPU model, cores and caches",
+        &[],
+        &[],
+    ),
+    command(
+        "get_cpu_usage",
+        "command.get_cpu_usage",
+        "Load of each CPU core",
+        &[],
+        &[],
+    ),
+    command(
+        "get_disks",
+        "command.get_disks",
+        "Mounted disks and their free space",
+        &[],
+        &[],
+    ),
+    command(
+        "get_gpu_info",
+        "command.get_gpu_info",
+        "Graphics adapters, video memory and drivers",
+        &[],
+        &[],
+    ),
+    command(
+        "get_user_disk_usage",
+        "command.get_user_disk_usage",
+        "Disk space used by each user's home directory",
+        &[optional("rescan", Boolean)],
+        &[Elevated],
+    ),
+    command(
+        "get_memory_details",
+        "command.get_memory_details",
+        "Used, available and cached memory, swap and page size",
+        &[],
+        &[],
+    ),
+    command(
+        "get_memory_modules",
+        "command.get_memory_modules",
+        "Installed RAM modules and free slots",
+        &[],
+        &[Elevated],
+    ),
+    command(
+        "get_battery_health",
+        "command.get_battery_health",
+        "Battery capacity, wear and cycle count",
+        &[],
+        &[],
+    ),
+    command(
+        "get_battery_history",
+        "command.get_battery_history",
+        "Recorded battery capacity over recent days",
+        &[optional("days", Integer)],
+        &[],
+    ),
+    command(
+        "get_power_status",
+        "command.get_power_status",
+        "Battery charge, charging state and AC power",
+        &[],
+        &[],
+    ),
+    command(
+        "get_battery_saver_status",
+        "command.get_battery_saver_status",
+        "Whether battery saver is on and background sampling is held back",
+        &[],
+        &[],
+    ),
+    command(
+        "get_uptime_info",
+        "command.get_uptime_info",
+        "Time since boot, boot time and load averages",
+        &[],
+        &[],
+    ),
+    command(
+        "get_ssd_health",
+        "command.get_ssd_health",
+        "SSD wear, spare capacity and TRIM status",
+        &[],
+        &[Elevated],
+    ),
+    command(
+        "get_ssd_wear_history",
+        "command.get_ssd_wear_history",
+        "Recorded SSD wear over recent days",
+        &[optional("days", Integer)],
+        &[],
+    ),
+    command(
+        "get_network_interfaces",
+        "command.get_network_interfaces",
+        "Network interfaces, addresses and adapter details",
+        &[],
+        &[],
+    ),
+    command(
+        "get_network_throughput",
+        "command.get_network_throughput",
+        "Bytes per second on each interface since the last call",
+        &[],
+        &[],
+    ),
+    command(
+        "list_processes",
+        "command.list_processes",
+        "Running processes, sorted and filtered",
+        &[
+            one_of("sortBy", false, &["pid", "name", "cpu", "memory"]),
+            one_of("order", false, &["asc", "desc"]),
+            optional("filter", ArgType::String),
+            optional("limit", Integer),
+        ],
+        &[],
+    ),
+    command(
+        "get_process_details",
+        "command.get_process_details",
+        "Command line, environment and handles of a process",
+        &[arg("pid", Integer)],
+        &[],
+    ),
+    command(
+        "kill_process",
+        "command.kill_process",
+        "End a process after confirmation",
+        &[arg("pid", Integer), optional("force", Boolean)],
+        &[Confirmation, Interactive, Elevated],
+    ),
+    command(
+        "get_scheduler_stats",
+        "command.get_scheduler_stats",
+        "Context switches, interrupts and run queue length",
+        &[],
+        &[],
+    ),
+    command(
+        "get_handle_stats",
+        "command.get_handle_stats",
+        "Open file handles, system-wide and per process",
+        &[optional("top", Integer)],
+        &[],
+    ),
+    command(
+        "get_watchlist_status",
+        "command.get_watchlist_status",
+        "Whether the watched processes are running",
+        &[],
+        &[],
+    ),
+    command(
+        "set_watchlist",
+        "command.set_watchlist",
+        "Change the watched process names",
+        &[arg("names", Array)],
+        &[],
+    ),
+    command(
+        "get_process_events",
+        "command.get_process_events",
+        "Starts and exits of watched processes",
+        &[
+            optional("name", ArgType::String),
+            optional("limit", Integer),
+        ],
+        &[],
+    ),
+    command(
+        "get_integrity_status",
+        "command.get_integrity_status",
+        "Hashes of the watched files",
+        &[],
+        &[],
+    ),
+    command(
+        "set_integrity_paths",
+        "command.set_integrity_paths",
+        "Change the files watched for modification",
+        &[arg("paths", Array)],
+        &[Filesystem],
+    ),
+    command(
+        "get_integrity_events",
+        "command.get_integrity_events",
+        "Changes to the watched files",
+        &[optional("limit", Integer)],
+        &[],
+    ),
+    command(
+        "get_inventory",
+        "command.get_inventory",
+        "Installed disks, memory, GPUs and USB devices",
+        &[],
+        &[],
+    ),
+    command(
+        "get_inventory_changes",
+        "command.get_inventory_changes",
+        "Hardware added or removed over time",
+        &[
+            one_of("category", false, &["disk", "memory", "gpu", "usb"]),
+            optional("limit", Integer),
+        ],
+        &[],
+    ),
+    command(
+        "get_certificates",
+        "command.get_certificates",
+        "Certificates and how soon they expire",
+        &[optional("includeSystem", Boolean)],
+        &[],
+    ),
+    command(
+        "get_hosts_entries",
+        "command.get_hosts_entries",
+        "Entries in the hosts file",
+        &[],
+        &[],
+    ),
+    command(
+        "get_proxy_status",
+        "command.get_proxy_status",
+        "Proxy settings and captive portal detection",
+        &[optional("checkPortal", Boolean)],
+        &[Network],
+    ),
+    command(
+        "get_latency_status",
+        "command.get_latency_status",
+        "Latency to the configured targets",
+        &[],
+        &[Network],
+    ),
+    command(
+        "set_latency_config",
+        "command.set_latency_config",
+        "Change the latency targets",
+        &[arg("latency", Object)],
+        &[],
+    ),
+    command(
+        "start_monitoring",
+        "command.start_monitoring",
+        "Emit system metrics at an interval",
+        &[optional("intervalMs", Integer)],
+        &[],
+    ),
+    command(
+        "stop_monitoring",
+        "command.stop_monitoring",
+        "Stop emitting system metrics",
+        &[],
+        &[],
+    ),
+    command(
+        "get_alert_rules",
+        "command.get_alert_rules",
+        "Saved alert rules",
+        &[],
+        &[],
+    ),
+    command(
+        "add_alert_rule",
+        "command.add_alert_rule",
+        "Save a rule that alerts when a metric stays above or below a threshold",
+        &[arg("condition", Object)],
+        &[],
+    ),
+    command(
+        "remove_alert_rule",
+        "command.remove_alert_rule",
+        "Delete an alert rule",
+        &[arg("id", Integer)],
+        &[],
+    ),
+    command(
+        "start_kiosk",
+        "command.start_kiosk",
+        "Start rotating dashboard panels full screen",
+        &[],
+        &[],
+    ),
+    command(
+        "stop_kiosk",
+        "command.stop_kiosk",
+        "Leave kiosk mode",
+        &[],
+        &[],
+    ),
+    command(
+        "get_kiosk_status",
+        "command.get_kiosk_status",
+        "Whether kiosk mode is on and the current panel",
+        &[],
+        &[],
+    ),
+    command(
+        "set_kiosk_config",
+        "command.set_kiosk_config",
+        "Change the kiosk panels and rotation",
+        &[arg("kiosk", Object)],
+        &[],
+    ),
+    command(
+        "scan_wifi_networks",
+        "command.scan_wifi_networks",
+        "Nearby Wi-Fi networks and channel usage",
+        &[],
+        &[],
+    ),
+    CommandSpec {
+        platforms: &["linux", "windows"],
+        ..command(
+            "get_capture_device_usage",
+            "command.get_capture_device_usage",
+            "Apps using the camera or microphone",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        platforms: &["windows"],
+        ..command(
+            "get_security_products",
+            "command.get_security_products",
+            "Antivirus and firewall products registered with Windows",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        platforms: &["windows"],
+        ..command(
+            "get_perf_counters",
+            "command.get_perf_counters",
+            "Current values of the allowlisted Windows performance counters",
+            &[optional("paths", Array)],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ebpf"),
+        platforms: &["linux"],
+        ..command(
+            "get_ebpf_status",
+            "command.get_ebpf_status",
+            "Whether eBPF metrics can run here, and what is missing",
+            &[],
+            &[],
+        )
+    },
+    CommandSpec {
+        feature: Some("ebpf"),
+        platforms: &["linux"],
+        ..command(
+            "collect_ebpf_metrics",
+            "command.collect_ebpf_metrics",
+            "Trace per-process I/O latency and TCP retransmits with eBPF",
+            &[optional("durationSeconds", Integer)],
+            &[Elevated],
+        )
+    },
+    command(
+        "get_activation_status",
+        "command.get_activation_status",
+        "OS edition and activation status",
+        &[],
+        &[],
+    ),
+    command(
+        "get_pending_reboot",
+        "command.get_pending_reboot",
+        "Whether a restart is pending and why",
+        &[],
+        &[],
+    ),
+    command(
+        "get_boot_history",
+        "command.get_boot_history",
+        "Recent boots and shutdowns",
+        &[optional("limit", Integer)],
+        &[],
+    ),
+    command(
+        "get_service_status",
+        "command.get_service_status",
+        "State of a system service",
+        &[arg("name", ArgType::String)],
+        &[],
+    ),
+    command(
+        "start_service",
+        "command.start_service",
+        "Start a system service after confirmation",
+        &[arg("name", ArgType::String)],
+        &[Confirmation, Interactive, Elevated],
+    ),
+    command(
+        "stop_service",
+        "command.stop_service",
+        "Stop a system service after confirmation",
+        &[arg("name", ArgType::String)],
+        &[Confirmation, Interactive, Elevated],
+    ),
+    command(
+        "restart_service",
+        "command.restart_service",
+        "Restart a system service after confirmation",
+        &[arg("name", ArgType::String)],
+        &[Confirmation, Interactive, Elevated],
+    ),
+    command(
+        "export_history_parquet",
+        "command.export_history_parquet",
+        "Write recorded history to a Parquet file",
+        &[
+            arg("path", ArgType::String),
+            optional("metric", ArgType::String),
+            optional("range", Object),
+            one_of("source", false, &["raw", "minuteRollup", "hourRollup"]),
+        ],
+        &[Filesystem],
+    ),
+    command(
+        "backup_app_data",
+        "command.backup_app_data",
+        "Save settings and optionally history to a file",
+        &[
+            arg("path", ArgType::String),
+            optional("includeHistory", Boolean),
+        ],
+        &[Filesystem],
+    ),
+    command(
+        "restore_app_data",
+        "command.restore_app_data",
+        "Restore settings and history from a backup",
+        &[arg("path", ArgType::String)],
+        &[Filesystem],
+    ),
+    command(
+        "run_benchmark",
+        "command.run_benchmark",
+        "Run a benchmark and record the score",
+        &[one_of("kind", true, BENCHMARK_KINDS)],
+        &[],
+    ),
+    command(
+        "run_scaling_benchmark",
+        "command.run_scaling_benchmark",
+        "Measure how the CPU score scales with threads",
+        &[optional("maxThreads", Integer)],
+        &[],
+    ),
+    CommandSpec {
+        feature: Some("gpu-benchmark"),
+        ..command(
+            "run_gpu_benchmark",
+            "command.run_gpu_benchmark",
+            "Run the GPU compute benchmark",
+            &[],
+            &[],
+        )
+    },
+    command(
+        "get_benchmark_history",
+        "command.get_benchmark_history",
+        "Past benchmark results",
+        &[
+            one_of("kind", false, BENCHMARK_KINDS),
+            optional("limit", Integer),
+        ],
+        &[],
+    ),
+    command(
+        "get_benchmark_timeline",
+        "command.get_benchmark_timeline",
+        "A benchmark run overlaid with the load, clocks and temperatures recorded during it",
+        &[arg("id", Integer)],
+        &[],
+    ),
+];
+
+fn feature_enabled(feature: &str) -> bool {
+    match feature {
+        "ffi" => cfg!(feature = "ffi"),
+        "gpu-benchmark" => cfg!(feature = "gpu-benchmark"),
+        "ebpf" => cfg!(feature = "ebpf"),
+        _ => false,
+    }
+}
+
+impl CommandSpec {
+    pub fn is_available(&self) -> bool {
+        self.feature.is_none_or(feature_enabled)
+            && (!self.dev_only || cfg!(debug_assertions))
+            && (self.platforms.is_empty() || self.platforms.contains(&std::env::consts::OS))
+    }
+}
+
+// `--list`: one line per command with its arguments; optional ones end in `?`
+pub fn print_list() {
+    let width = COMMANDS
+        .iter()
+        .map(|spec| spec.name.len())
+        .max()
+        .unwrap_or(0);
+    for spec in COMMANDS {
+        let args: Vec<String> = spec
+            .args
+            .iter()
+            .map(|arg| format!("{}{}", arg.name, if arg.required { "" } else { "?" }))
+            .collect();
+        let mut line = format!(
+            "{:width$}  ({})  {}",
+            spec.name,
+            args.join(", "),
+            spec.description,
+            width = width
+        );
+        if !spec.is_available() {
+            match spec.feature.filter(|&feature| !feature_enabled(feature)) {
+                Some(feature) => line.push_str(&format!(" [needs the {} feature]", feature)),
+                None if spec.dev_only && !cfg!(debug_assertions) => {
+                    line.push_str(" [development builds only]")
+                }
+                None => line.push_str(&format!(" [{} only]", spec.platforms.join(", "))),
+            }
+        }
+        println!("{}", line);
+    }
+}
+
+#[tauri::command]
+pub fn list_commands() -> Vec<CommandInfo> {
+    COMMANDS
+        .iter()
+        .map(|spec| CommandInfo {
+            spec,
+            available: spec.is_available(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::{self, Locale};
+    use std::collections::BTreeSet;
+
+    // Command names in the `generate_handler!` list of lib.rs, without module paths or
+    // `#[cfg]` attributes
+    fn registered_handlers() -> BTreeSet<String> {
+        let lib = include_str!("lib.rs");
+        let start = lib
+            .find("generate_handler![")
+            .expect("no generate_handler! in lib.rs")
+            + "generate_handler![".len();
+        let end = start
+            + lib[start..]
+                .find("])")
+                .expect("unterminated generate_handler!");
+        lib[start..end]
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with("#["))
+            .flat_map(|line| line.split(','))
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(|path| path.rsplit("::").next().unwrap_or(path).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn every_command_is_registered() {
+        let handlers = registered_handlers();
+        let listed: BTreeSet<String> = COMMANDS.iter().map(|spec| spec.name.to_string()).collect();
+        assert_eq!(listed.len(), COMMANDS.len(), "duplicate names in COMMANDS");
+        let unregistered: Vec<_> = listed.difference(&handlers).collect();
+        let unlisted: Vec<_> = handlers.difference(&listed).collect();
+        assert!(
+            unregistered.is_empty(),
+            "in COMMANDS but not in generate_handler!: {:?}",
+            unregistered
+        );
+        assert!(
+            unlisted.is_empty(),
+            "in generate_handler! but not in COMMANDS: {:?}",
+            unlisted
+        );
+    }
+
+    #[test]
+    fn every_description_is_translated() {
+        for locale in [Locale::En, Locale::Es, Locale::De] {
+            let catalog = i18n::get_message_catalog(Some(locale));
+            let missing: Vec<_> = COMMANDS
+                .iter()
+                .map(|spec| spec.description_key)
+                .filter(|key| !catalog.contains_key(key))
+                .collect();
+            assert!(
+                missing.is_empty(),
+                "missing from the {} catalog: {:?}",
+                serde_json::to_string(&locale).unwrap(),
+                missing
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use tauri::{AppHandle, Manager, State};
+
+use crate::agent::AgentConfig;
+use crate::alerts::{self, AlertConfig};
+use crate::battery_saver::BatterySaverConfig;
+use crate::certificates::CertificateConfig;
+use crate::display::DisplaySettings;
+use crate::error::CommandError;
+use crate::exporter::ExporterConfig;
+use crate::history;
+use crate::i18n::{self, Locale};
+use crate::integrity::IntegrityConfig;
+use crate::kiosk::KioskConfig;
+use crate::latency::LatencyConfig;
+use crate::monitor::{self, MonitorConfig, Monitoring};
+use crate::perf_counters::PerfCounterConfig;
+use crate::policy::PolicyConfig;
+use crate::prometheus::PrometheusConfig;
+use crate::startup::StartupMode;
+use crate::support_bundle::PrivacyConfig;
+use crate::CppLibrary;
+
+const CONFIG_FILE: &str = "config.toml";
+
+// How long history is kept before the pruning job deletes it
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    pub raw_retention_hours: u64,
+    pub rollup_retention_days: u64,
+    pub prune_interval_minutes: u64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy {
+            raw_retention_hours: 24,
+            rollup_retention_days: 30,
+            prune_interval_minutes: 15,
+        }
+    }
+}
+
+// Where to find the native library when it isn't in the default locations, and how calls
+// into it are made
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LibraryConfig {
+    pub path: Option<PathBuf>,
+    // Files or directories searched before the built-in locations (see loader.rs)
+    pub search_paths: Vec<PathBuf>,
+    // False keeps the library loaded but answers every query from the Rust providers
+    pub native_enabled: bool,
+    // Commands whose library calls run in a helper process (see sandbox.rs)
+    pub sandboxed_commands: Vec<String>,
+    // How long a command waits for a system info call before failing with a timeout
+    pub call_timeout_seconds: u64,
+}
+
+impl Default for LibraryConfig {
+    fn default() -> Self {
+        LibraryConfig {
+            path: None,
+            search_paths: Vec::new(),
+            native_enabled: true,
+            sandboxed_commands: Vec::new(),
+            call_timeout_seconds: 10,
+        }
+    }
+}
+
+// Startup self-test of the system backend
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreflightConfig {
+    pub enabled: bool,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        PreflightConfig { enabled: true }
+    }
+}
+
+// Settings persisted to `config.toml` in the app config directory
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppConfig {
+    // Plain values must come before the tables when serialized to TOML
+    pub locale: Locale,
+    pub startup_mode: StartupMode,
+    // Process names monitored by the watchlist
+    pub watchlist: Vec<String>,
+    pub retention: RetentionPolicy,
+    pub display: DisplaySettings,
+    pub library: LibraryConfig,
+    pub monitor: MonitorConfig,
+    pub alerts: AlertConfig,
+    pub preflight: PreflightConfig,
+    pub integrity: IntegrityConfig,
+    pub certificates: CertificateConfig,
+    pub latency: LatencyConfig,
+    pub kiosk: KioskConfig,
+    pub battery_saver: BatterySaverConfig,
+    pub perf_counters: PerfCounterConfig,
+    pub privacy: PrivacyConfig,
+    pub prometheus: PrometheusConfig,
+    pub agent: AgentConfig,
+    // Overridden by a system-wide policy.toml (see policy.rs)
+    pub policy: PolicyConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exporters: Vec<ExporterConfig>,
+}
+
+pub struct ConfigState {
+    path: Option<PathBuf>,
+    config: Mutex<AppConfig>,
+}
+
+impl ConfigState {
+    // Load the config file, falling back to defaults if it is missing or unreadable
+    // `path` overrides the default location in the app config directory (--config)
+    pub fn load(app: &AppHandle, path: Option<PathBuf>) -> Self {
+        let path = path.or_else(|| {
+            app.path()
+                .app_config_dir()
+                .ok()
+                .map(|dir| dir.join(CONFIG_FILE))
+        });
+
+        let config = match path.as_ref().map(std::fs::read_to_string) {
+            Some(Ok(text)) => toml::from_str(&text).unwrap_or_else(|e| {
+                eprintln!("⚠ Warning: Ignoring invalid {}: {}", CONFIG_FILE, e);
+                AppConfig::default()
+            }),
+            _ => AppConfig::default(),
+        };
+
+        ConfigState {
+            path,
+            config: Mutex::new(config),
+        }
+    }
+
+    pub fn get(&self) -> Result<MutexGuard<'_, AppConfig>, String> {
+        self.config
+            .lock()
+            .map_err(|_| "Config lock poisoned".to_string())
+    }
+
+    // Apply a change and write the result back to disk
+    pub fn update(&self, change: impl FnOnce(&mut AppConfig)) -> Result<AppConfig, String> {
+        let mut config = self.get()?;
+        change(&mut config);
+
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            let text = toml::to_string_pretty(&*config).map_err(|e| e.to_string())?;
+            std::fs::write(path, text).map_err(|e| format!("Failed to save config: {}", e))?;
+        }
+        Ok(config.clone())
+    }
+}
+
+#[tauri::command]
+pub fn get_config(config: State<ConfigState>) -> Result<AppConfig, CommandError> {
+    Ok(config.get()?.clone())
+}
+
+// The checks a whole config has to pass before it replaces the current one
+pub fn check_config(config: &AppConfig) -> Result<(), CommandError> {
+    monitor::check_interval(config.monitor.interval_ms)?;
+    history::check_retention(&config.retention)?;
+    for rule in &config.alerts.rules {
+        alerts::check_condition(&rule.condition)?;
+    }
+    #[cfg(feature = "ffi")]
+    crate::sandbox::check_commands(&config.library.sandboxed_commands)?;
+    Ok(())
+}
+
+// Check and save `config` in place of the current one, then apply what can change while running;
+// shared by `set_config` and restoring a backup
+pub fn replace_config(
+    config: AppConfig,
+    state: &ConfigState,
+    lib_state: &CppLibrary,
+    monitoring: &Monitoring,
+) -> Result<AppConfig, CommandError> {
+    check_config(&config)?;
+    let saved = state.update(|current| *current = config)?;
+    i18n::set_current(saved.locale);
+    lib_state.configure(&saved.library);
+    monitoring.set_interval(saved.monitor.interval_ms)?;
+    Ok(saved)
+}
+
+// Replace the whole config, save it and apply what can change while running: the locale, the
+// library backend and sandboxed commands, the monitor interval and the alert rules. Other sections
+// take effect when they are next read, the policy and startup mode on the next start.
+#[tauri::command]
+pub fn set_config(
+    config: AppConfig,
+    state: State<ConfigState>,
+    lib_state: State<CppLibrary>,
+    monitoring: State<Monitoring>,
+) -> Result<AppConfig, CommandError> {
+    replace_config(config, &state, &lib_state, &monitoring)
+}
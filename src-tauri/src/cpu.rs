@@ -0,0 +1,171 @@
+// Per-core CPU utilization and clock frequency, sampled into history together so frequency
+// throttling lines up with load in charts. Metrics are `cpu.<core>.usage` (percent) and
+// `cpu.<core>.frequency` (MHz), plus `cpu.usage` across all cores.
+// `get_cpu_info` and `get_cpu_usage` go through the system info provider instead, so they come
+// from the native library when it has the CPU exports.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+use tauri::{AppHandle, Manager, State};
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::exporter;
+use crate::CppLibrary;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+// Shared with the sampler so usage is measured over the interval since its last refresh
+pub struct CpuMonitor {
+    system: Mutex<System>,
+}
+
+impl Default for CpuMonitor {
+    fn default() -> Self {
+        CpuMonitor {
+            system: Mutex::new(System::new_with_specifics(
+                RefreshKind::nothing().with_cpu(refresh_kind()),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoreFrequency {
+    pub core: usize,
+    pub frequency_mhz: u64,
+    pub usage_percent: f32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuFrequency {
+    pub cores: Vec<CoreFrequency>,
+    // cpufreq scaling governor (performance, powersave, schedutil, ...); Linux only
+    pub governor: Option<String>,
+    // Energy/performance preference hint given to the driver; Linux only
+    pub power_mode: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuInfo {
+    pub model: String,
+    pub vendor: String,
+    // None where the OS doesn't report the topology
+    pub physical_cores: Option<u32>,
+    pub logical_cores: u32,
+    // Nominal clock; None where only the current frequency is known
+    pub base_frequency_mhz: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuUsage {
+    // Percent per logical core, since the previous call
+    pub cores: Vec<f64>,
+    // Mean across cores
+    pub total: f64,
+}
+
+impl CpuUsage {
+    pub fn from_cores(cores: Vec<f64>) -> Self {
+        let total = if cores.is_empty() {
+            0.0
+        } else {
+            cores.iter().sum::<f64>() / cores.len() as f64
+        };
+        CpuUsage { cores, total }
+    }
+}
+
+fn refresh_kind() -> CpuRefreshKind {
+    CpuRefreshKind::nothing().with_cpu_usage().with_frequency()
+}
+
+pub fn read_cpufreq(file: &str) -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    std::fs::read_to_string(format!("/sys/devices/system/cpu/cpu0/cpufreq/{}", file))
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn samples(system: &System) -> Vec<(String, f64)> {
+    let mut samples = vec![("cpu.usage".to_string(), system.global_cpu_usage() as f64)];
+    for (core, cpu) in system.cpus().iter().enumerate() {
+        samples.push((format!("cpu.{}.usage", core), cpu.cpu_usage() as f64));
+        samples.push((format!("cpu.{}.frequency", core), cpu.frequency() as f64));
+    }
+    samples
+}
+
+// Refresh the shared counters and read every core; None if the monitor lock is poisoned
+pub fn sample_now(app: &AppHandle) -> Option<Vec<(String, f64)>> {
+    let monitor = app.state::<CpuMonitor>();
+    let mut system = monitor.system.lock().ok()?;
+    system.refresh_cpu_specifics(refresh_kind());
+    Some(samples(&system))
+}
+
+// Background job recording utilization and frequency for every core
+pub fn start_sampling(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        battery_saver::wait(&app, SAMPLE_INTERVAL);
+
+        let Some(samples) = sample_now(&app) else {
+            return;
+        };
+        exporter::publish(&app, &samples);
+    });
+}
+
+// Latest per-core frequencies and utilization, as of the most recent sample
+#[tauri::command]
+pub fn get_cpu_frequency(monitor: State<CpuMonitor>) -> Result<CpuFrequency, CommandError> {
+    let system = monitor
+        .system
+        .lock()
+        .map_err(|_| "CPU monitor lock poisoned")?;
+    let cores = system
+        .cpus()
+        .iter()
+        .enumerate()
+        .map(|(core, cpu)| CoreFrequency {
+            core,
+            frequency_mhz: cpu.frequency(),
+            usage_percent: cpu.cpu_usage(),
+        })
+        .collect();
+
+    Ok(CpuFrequency {
+        cores,
+        governor: read_cpufreq("scaling_governor"),
+        power_mode: read_cpufreq("energy_performance_preference"),
+    })
+}
+
+#[tauri::command]
+pub async fn get_cpu_info(app: AppHandle) -> Result<CpuInfo, CommandError> {
+    crate::call_blocking(app, "get_cpu_info", |app| {
+        app.state::<CppLibrary>()
+            .call("get_cpu_info", |provider| provider.cpu_info())
+    })
+    .await
+}
+
+// The first call after startup waits briefly so there is an interval to measure
+#[tauri::command]
+pub async fn get_cpu_usage(app: AppHandle) -> Result<CpuUsage, CommandError> {
+    let cores = crate::call_blocking(app, "get_cpu_usage", |app| {
+        app.state::<CppLibrary>()
+            .call("get_cpu_usage", |provider| provider.core_usage())
+    })
+    .await?;
+    Ok(CpuUsage::from_cores(cores))
+}
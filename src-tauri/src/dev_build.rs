@@ -0,0 +1,164 @@
+// Development builds only: build `cpp_cross_platform` with CMake and load the result, so a fresh
+// checkout runs against the native library without the manual build step. Runs from the
+// `build_library` command, or at startup with `--build-lib` when no library was found. Each line
+// CMake prints is emitted as `library-build-output`.
+
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::CommandError;
+use crate::loader;
+use crate::{CppLibrary, LibraryInfo};
+
+pub const LIBRARY_BUILD_OUTPUT_EVENT: &str = "library-build-output";
+
+// Same configuration the debug search path looks in
+const BUILD_CONFIG: &str = "Release";
+
+static BUILDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildStream {
+    Stdout,
+    Stderr,
+    // Steps starting and finishing, written by the app rather than CMake
+    Status,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildOutput {
+    pub stream: BuildStream,
+    pub line: String,
+}
+
+fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../cpp_cross_platform")
+}
+
+fn emit(app: &AppHandle, stream: BuildStream, line: String) {
+    if let Err(e) = app.emit(LIBRARY_BUILD_OUTPUT_EVENT, BuildOutput { stream, line }) {
+        eprintln!(
+            "⚠ Warning: Failed to emit {}: {}",
+            LIBRARY_BUILD_OUTPUT_EVENT, e
+        );
+    }
+}
+
+fn forward_lines(
+    app: &AppHandle,
+    stream: BuildStream,
+    output: Option<impl Read + Send + 'static>,
+) -> Option<JoinHandle<()>> {
+    let (app, output) = (app.clone(), output?);
+    Some(std::thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            emit(&app, stream, line);
+        }
+    }))
+}
+
+// Run one CMake step, streaming its output, and fail on a non-zero exit
+fn run_cmake(app: &AppHandle, step: &str, args: &[&str]) -> Result<(), String> {
+    emit(
+        app,
+        BuildStream::Status,
+        format!("cmake {}", args.join(" ")),
+    );
+    let mut child = Command::new("cmake")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run cmake: {}", e))?;
+    let readers = [
+        forward_lines(app, BuildStream::Stdout, child.stdout.take()),
+        forward_lines(app, BuildStream::Stderr, child.stderr.take()),
+    ];
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    // Let the last lines out before the step's result
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+    if !status.success() {
+        return Err(format!("CMake {} failed with {}", step, status));
+    }
+    Ok(())
+}
+
+fn build(app: &AppHandle) -> Result<LibraryInfo, String> {
+    let source = source_dir();
+    let build = source.join("build");
+    let (source_arg, build_arg) = (source.display().to_string(), build.display().to_string());
+    run_cmake(
+        app,
+        "configure",
+        &[
+            "-S",
+            &source_arg,
+            "-B",
+            &build_arg,
+            &format!("-DCMAKE_BUILD_TYPE={}", BUILD_CONFIG),
+        ],
+    )?;
+    run_cmake(
+        app,
+        "build",
+        &["--build", &build_arg, "--config", BUILD_CONFIG],
+    )?;
+
+    let output = if cfg!(target_os = "windows") {
+        build.join("bin").join(BUILD_CONFIG)
+    } else {
+        build.join("lib")
+    };
+    let loaded =
+        loader::open_validated(&output.join(loader::LIB_NAME)).map_err(|e| e.to_string())?;
+    let info = loaded.info.clone();
+    app.state::<CppLibrary>()
+        .replace(loaded)
+        .map_err(|e| e.to_string())?;
+    emit(app, BuildStream::Status, format!("Loaded {}", info.path));
+    println!("✓ Built and loaded C++ library from: {}", info.path);
+    Ok(info)
+}
+
+// One build at a time; a second request fails rather than racing the first over the build tree
+fn build_and_load(app: &AppHandle) -> Result<LibraryInfo, String> {
+    if BUILDING.swap(true, Ordering::SeqCst) {
+        return Err("A library build is already running".to_string());
+    }
+    let result = build(app);
+    BUILDING.store(false, Ordering::SeqCst);
+    if let Err(e) = &result {
+        emit(app, BuildStream::Status, e.clone());
+    }
+    result
+}
+
+// `--build-lib`: build in the background when startup found no library
+pub fn build_if_missing(app: AppHandle) {
+    if app.state::<CppLibrary>().is_loaded() {
+        return;
+    }
+    std::thread::spawn(move || {
+        if let Err(e) = build_and_load(&app) {
+            eprintln!("⚠ Warning: Building the C++ library failed: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn build_library(app: AppHandle) -> Result<LibraryInfo, CommandError> {
+    let info = tauri::async_runtime::spawn_blocking(move || build_and_load(&app))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(info)
+}
@@ -0,0 +1,182 @@
+// Finding other instances of the app on the LAN over mDNS. Off unless `discovery = true` is set
+// under `[agent]` and the policy doesn't disable the `discovery` sampler. Even then this machine
+// is only announced while the remote agent is listening on a non-local address, so it never
+// advertises a port nobody answers on.
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::error::CommandError;
+
+// mDNS service type advertised by instances whose agent is running
+const SERVICE_TYPE: &str = "_sysinfo._tcp.local.";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Peer {
+    pub instance_name: String,
+    pub hostname: String,
+    pub addresses: Vec<String>,
+    pub port: u16,
+    pub platform: Option<String>,
+    pub version: Option<String>,
+}
+
+// Discovery state: the mDNS daemon (if discovery is on and it could be started), this
+// instance's service while it is advertised, and the peers seen so far
+pub struct Discovery {
+    daemon: Option<ServiceDaemon>,
+    advertised: Mutex<Option<String>>,
+    peers: Arc<Mutex<HashMap<String, Peer>>>,
+}
+
+impl Discovery {
+    // Start browsing for other instances in the background, or do nothing when disabled
+    pub fn start(enabled: bool) -> Self {
+        let peers = Arc::new(Mutex::new(HashMap::new()));
+        let daemon = enabled
+            .then(|| browse(peers.clone()))
+            .and_then(|started| match started {
+                Ok(daemon) => {
+                    println!("✓ mDNS discovery started ({})", SERVICE_TYPE);
+                    Some(daemon)
+                }
+                Err(e) => {
+                    eprintln!("⚠ Warning: mDNS discovery unavailable: {}", e);
+                    None
+                }
+            });
+        Discovery {
+            daemon,
+            advertised: Mutex::new(None),
+            peers,
+        }
+    }
+
+    // Announce the agent listening on `port`; does nothing while discovery is off
+    pub fn advertise(&self, port: u16) {
+        let Some(daemon) = &self.daemon else {
+            return;
+        };
+        self.withdraw();
+        let hostname = gethostname::gethostname().to_string_lossy().into_owned();
+        let properties = [
+            ("platform", std::env::consts::OS),
+            ("version", env!("CARGO_PKG_VERSION")),
+        ];
+        let registered = ServiceInfo::new(
+            SERVICE_TYPE,
+            &instance_name(),
+            &format!("{}.local.", hostname),
+            "",
+            port,
+            &properties[..],
+        )
+        .map(ServiceInfo::enable_addr_auto)
+        .map_err(|e| e.to_string())
+        .and_then(|service| {
+            let fullname = service.get_fullname().to_string();
+            daemon.register(service).map_err(|e| e.to_string())?;
+            Ok(fullname)
+        });
+        match registered {
+            Ok(fullname) => {
+                if let Ok(mut advertised) = self.advertised.lock() {
+                    *advertised = Some(fullname);
+                }
+            }
+            Err(e) => eprintln!("⚠ Warning: Failed to advertise the agent: {}", e),
+        }
+    }
+
+    // Stop announcing the agent
+    pub fn withdraw(&self) {
+        let (Some(daemon), Ok(mut advertised)) = (&self.daemon, self.advertised.lock()) else {
+            return;
+        };
+        if let Some(fullname) = advertised.take() {
+            if let Err(e) = daemon.unregister(&fullname) {
+                eprintln!("⚠ Warning: Failed to withdraw the agent: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for Discovery {
+    fn drop(&mut self) {
+        if let Some(daemon) = self.daemon.take() {
+            let _ = daemon.shutdown();
+        }
+    }
+}
+
+fn instance_name() -> String {
+    format!(
+        "{}-{}",
+        gethostname::gethostname().to_string_lossy(),
+        std::process::id()
+    )
+}
+
+fn browse(peers: Arc<Mutex<HashMap<String, Peer>>>) -> Result<ServiceDaemon, String> {
+    let daemon = ServiceDaemon::new().map_err(|e| e.to_string())?;
+    let own_fullname = format!("{}.{}", instance_name(), SERVICE_TYPE);
+    let receiver = daemon.browse(SERVICE_TYPE).map_err(|e| e.to_string())?;
+
+    std::thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            match event {
+                ServiceEvent::ServiceResolved(info) if info.fullname != own_fullname => {
+                    let mut addresses: Vec<String> = info
+                        .get_addresses()
+                        .iter()
+                        .map(|ip| ip.to_ip_addr().to_string())
+                        .collect();
+                    addresses.sort();
+
+                    let instance_name = info
+                        .fullname
+                        .strip_suffix(&format!(".{}", SERVICE_TYPE))
+                        .unwrap_or(&info.fullname)
+                        .to_string();
+
+                    let peer = Peer {
+                        instance_name,
+                        hostname: info.get_hostname().trim_end_matches('.').to_string(),
+                        addresses,
+                        port: info.get_port(),
+                        platform: info.get_property_val_str("platform").map(String::from),
+                        version: info.get_property_val_str("version").map(String::from),
+                    };
+
+                    if let Ok(mut peers) = peers.lock() {
+                        peers.insert(info.fullname.clone(), peer);
+                    }
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    if let Ok(mut peers) = peers.lock() {
+                        peers.remove(&fullname);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Ok(daemon)
+}
+
+#[tauri::command]
+pub fn get_discovered_peers(discovery: State<Discovery>) -> Result<Vec<Peer>, CommandError> {
+    let peers = discovery
+        .peers
+        .lock()
+        .map_err(|_| "Peer list lock poisoned")?;
+
+    let mut list: Vec<Peer> = peers.values().cloned().collect();
+    list.sort_by(|a, b| a.instance_name.cmp(&b.instance_name));
+    Ok(list)
+}
@@ -0,0 +1,79 @@
+// Mounted disks and volumes with their capacity.
+// Windows lists one entry per drive letter (`C:\`), Unix one per mount point; sysinfo leaves
+// out pseudo filesystems such as `proc` and `sysfs`.
+
+use serde::Serialize;
+use sysinfo::{DiskKind, Disks};
+
+use crate::error::CommandError;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiskType {
+    Ssd,
+    Hdd,
+    Unknown,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskInfo {
+    // Device or volume name, e.g. `/dev/nvme0n1p2` or the volume label on Windows
+    pub name: String,
+    pub mount_point: String,
+    // Drive letter on Windows, None elsewhere
+    pub drive_letter: Option<char>,
+    pub file_system: String,
+    pub total_bytes: u64,
+    // Space available to the current user, which may be less than what is unallocated
+    pub free_bytes: u64,
+    pub removable: bool,
+    pub read_only: bool,
+    pub kind: DiskType,
+}
+
+fn drive_letter(mount_point: &str) -> Option<char> {
+    if !cfg!(target_os = "windows") {
+        return None;
+    }
+    let mut chars = mount_point.chars();
+    let letter = chars.next().filter(char::is_ascii_alphabetic)?;
+    (chars.next() == Some(':')).then(|| letter.to_ascii_uppercase())
+}
+
+pub fn list_disks() -> Vec<DiskInfo> {
+    let disks = Disks::new_with_refreshed_list();
+    let mut list: Vec<DiskInfo> = disks
+        .list()
+        .iter()
+        .map(|disk| {
+            let mount_point = disk.mount_point().display().to_string();
+            DiskInfo {
+                name: disk.name().to_string_lossy().into_owned(),
+                drive_letter: drive_letter(&mount_point),
+                mount_point,
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                total_bytes: disk.total_space(),
+                free_bytes: disk.available_space(),
+                removable: disk.is_removable(),
+                read_only: disk.is_read_only(),
+                kind: match disk.kind() {
+                    DiskKind::SSD => DiskType::Ssd,
+                    DiskKind::HDD => DiskType::Hdd,
+                    _ => DiskType::Unknown,
+                },
+            }
+        })
+        .collect();
+    list.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    list
+}
+
+// Querying network drives and sleeping disks can block, so this runs off the main thread
+#[tauri::command]
+pub async fn get_disks() -> Result<Vec<DiskInfo>, CommandError> {
+    let disks = tauri::async_runtime::spawn_blocking(list_disks)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(disks)
+}
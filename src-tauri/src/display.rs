@@ -0,0 +1,215 @@
+// User preferences for how numbers are presented: decimal places, °C or °F, and bytes or bits
+// per second. Values are always measured and stored in canonical units (°C, MB/s, bytes); these
+// settings only change the text produced for logs, command results and exports.
+// Separators and dates follow the UI language (1,234.56 in English, 1.234,56 in German) unless
+// `locale` overrides it, so an exported report reads the way the screen does. Dates are in UTC.
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::i18n::{self, Locale};
+
+// More decimals than this is noise for every metric the app reports
+pub const MAX_PRECISION: u8 = 6;
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RateUnit {
+    #[default]
+    BytesPerSecond,
+    BitsPerSecond,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DisplaySettings {
+    pub precision: u8,
+    pub temperature_unit: TemperatureUnit,
+    pub rate_unit: RateUnit,
+    // Number and date format; None follows the UI language
+    pub locale: Option<Locale>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            precision: 2,
+            temperature_unit: TemperatureUnit::default(),
+            rate_unit: RateUnit::default(),
+            locale: None,
+        }
+    }
+}
+
+impl DisplaySettings {
+    // Convert a value from its canonical unit into the preferred one.
+    // Units without a preference (Mops/s, GFLOPS, ...) pass through unchanged.
+    pub fn convert<'a>(&self, value: f64, unit: &'a str) -> (f64, &'a str) {
+        match (unit, self.temperature_unit, self.rate_unit) {
+            ("°C", TemperatureUnit::Fahrenheit, _) => (value * 9.0 / 5.0 + 32.0, "°F"),
+            ("MB/s", _, RateUnit::BitsPerSecond) => (value * 8.0, "Mbit/s"),
+            ("B/s", _, RateUnit::BitsPerSecond) => (value * 8.0, "bit/s"),
+            _ => (value, unit),
+        }
+    }
+
+    fn format_locale(&self) -> Locale {
+        self.locale.unwrap_or_else(i18n::current)
+    }
+
+    pub fn number(&self, value: f64) -> String {
+        let text = format!("{:.*}", self.precision as usize, value);
+        // (group separator, decimal separator, fewest integer digits that get grouped)
+        let (group, decimal, grouped_from) = match self.format_locale() {
+            Locale::En => (',', '.', 4),
+            // Spanish leaves four-digit numbers ungrouped
+            Locale::Es => ('.', ',', 5),
+            Locale::De => ('.', ',', 4),
+        };
+
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text.as_str()),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        let mut localized = sign.to_string();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && integer.len() >= grouped_from && (integer.len() - i) % 3 == 0 {
+                localized.push(group);
+            }
+            localized.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            localized.push(decimal);
+            localized.push_str(fraction);
+        }
+        localized
+    }
+
+    // Unix seconds as a UTC date and time, e.g. "10/15/2026, 2:03:00 PM UTC" or
+    // "15.10.2026, 14:03:00 UTC"
+    pub fn date_time(&self, timestamp: i64) -> String {
+        let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+        let seconds = timestamp.rem_euclid(86400);
+        let (hour, minute, second) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+        match self.format_locale() {
+            Locale::En => format!(
+                "{}/{}/{}, {}:{:02}:{:02} {} UTC",
+                month,
+                day,
+                year,
+                (hour + 11) % 12 + 1,
+                minute,
+                second,
+                if hour < 12 { "AM" } else { "PM" }
+            ),
+            Locale::Es => format!(
+                "{}/{}/{}, {}:{:02}:{:02} UTC",
+                day, month, year, hour, minute, second
+            ),
+            Locale::De => format!(
+                "{}.{}.{}, {:02}:{:02}:{:02} UTC",
+                day, month, year, hour, minute, second
+            ),
+        }
+    }
+
+    // e.g. `format(1250.0, "MB/s")` is "10000.00 Mbit/s" with bit rates and 2 decimals
+    pub fn format(&self, value: f64, unit: &str) -> String {
+        let (value, unit) = self.convert(value, unit);
+        format!("{} {}", self.number(value), unit)
+    }
+
+    pub fn bytes(&self, bytes: u64) -> String {
+        self.format(bytes as f64 / (1024.0 * 1024.0 * 1024.0), "GB")
+    }
+}
+
+// (year, month, day) of a day count since 1970-01-01 (Howard Hinnant's algorithm)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// One value for `format_values`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ExportValue {
+    // In its canonical unit, converted to the preferred one
+    Number { value: f64, unit: Option<String> },
+    Bytes { value: u64 },
+    // Unix seconds
+    DateTime { value: i64 },
+}
+
+// Settings from the config, or the defaults if it can't be read
+pub fn current(config: &ConfigState) -> DisplaySettings {
+    config
+        .get()
+        .map(|config| config.display.clone())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_display_settings(config: State<ConfigState>) -> DisplaySettings {
+    current(&config)
+}
+
+#[tauri::command]
+pub fn set_display_settings(
+    settings: DisplaySettings,
+    config: State<ConfigState>,
+) -> Result<DisplaySettings, CommandError> {
+    if settings.precision > MAX_PRECISION {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "display.invalidPrecision")
+                .with("max", MAX_PRECISION),
+        );
+    }
+    let config = config.update(|config| config.display = settings)?;
+    Ok(config.display)
+}
+
+// Render values the way exports do, for text the frontend builds itself such as clipboard copies
+#[tauri::command]
+pub fn format_values(values: Vec<ExportValue>, config: State<ConfigState>) -> Vec<String> {
+    let display = current(&config);
+    values
+        .into_iter()
+        .map(|value| match value {
+            ExportValue::Number { value, unit: None } => display.number(value),
+            ExportValue::Number {
+                value,
+                unit: Some(unit),
+            } => display.format(value, &unit),
+            ExportValue::Bytes { value } => display.bytes(value),
+            ExportValue::DateTime { value } => display.date_time(value),
+        })
+        .collect()
+}
@@ -0,0 +1,216 @@
+// Deeper Linux metrics than /proc offers, traced with eBPF: block I/O latency per process and
+// TCP retransmits per remote address. Opt-in twice over: compiled only with the `ebpf` feature,
+// and only run when the capability checks pass, since loading BPF programs needs root or
+// CAP_BPF with CAP_PERFMON. The programs are run through `bpftrace` rather than linking aya or
+// libbpf, so the feature adds no build dependencies; `bpftrace` must be installed.
+// I/O is charged to the process that issued the request, which for buffered writes is usually
+// a kernel flusher thread rather than the writer.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::{CommandError, ErrorCode};
+use crate::os_command::run_text;
+
+const DEFAULT_DURATION_SECONDS: u64 = 5;
+const MAX_DURATION_SECONDS: u64 = 60;
+
+// Bits in CapEff
+const CAP_SYS_ADMIN: u32 = 21;
+const CAP_PERFMON: u32 = 38;
+const CAP_BPF: u32 = 39;
+
+// Keyed by one value each, since bpftrace's JSON output joins composite keys ambiguously
+const SCRIPT: &str = r#"
+tracepoint:block:block_rq_issue {
+    @start[args->dev, args->sector] = nsecs;
+    @issuer[args->dev, args->sector] = pid;
+    @name[pid] = comm;
+}
+tracepoint:block:block_rq_complete /@start[args->dev, args->sector]/ {
+    $pid = @issuer[args->dev, args->sector];
+    $us = (nsecs - @start[args->dev, args->sector]) / 1000;
+    @io_count[$pid] = count();
+    @io_total_us[$pid] = sum($us);
+    @io_max_us[$pid] = max($us);
+    delete(@start[args->dev, args->sector]);
+    delete(@issuer[args->dev, args->sector]);
+}
+tracepoint:tcp:tcp_retransmit_skb {
+    @retransmits[ntop(args->daddr)] = count();
+}
+interval:s:DURATION { exit(); }
+END { clear(@start); clear(@issuer); }
+"#;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EbpfStatus {
+    pub available: bool,
+    // What is missing when unavailable
+    pub problems: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessIoLatency {
+    pub pid: u32,
+    pub name: Option<String>,
+    pub requests: u64,
+    pub avg_latency_us: f64,
+    pub max_latency_us: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpRetransmits {
+    pub remote_address: String,
+    pub count: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EbpfMetrics {
+    pub duration_seconds: u64,
+    // Most requests first
+    pub io_latency: Vec<ProcessIoLatency>,
+    // Most retransmits first
+    pub tcp_retransmits: Vec<TcpRetransmits>,
+}
+
+fn has_capabilities() -> bool {
+    // `CapEff:	000001ffffffffff`
+    let status = std::fs::read_to_string("/proc/self/status").unwrap_or_default();
+    let effective = status.lines().find_map(|line| {
+        let hex = line.strip_prefix("CapEff:")?.trim();
+        u64::from_str_radix(hex, 16).ok()
+    });
+    effective.is_some_and(|caps| {
+        let has = |cap: u32| caps & (1 << cap) != 0;
+        has(CAP_SYS_ADMIN) || (has(CAP_BPF) && has(CAP_PERFMON))
+    })
+}
+
+fn check() -> EbpfStatus {
+    let mut problems = Vec::new();
+    if !cfg!(target_os = "linux") {
+        problems.push("eBPF metrics are only available on Linux".to_string());
+    } else {
+        if run_text("bpftrace", &["--version"]).is_err() {
+            problems.push("bpftrace is not installed".to_string());
+        }
+        if !has_capabilities() {
+            problems.push("Needs root, or CAP_BPF and CAP_PERFMON".to_string());
+        }
+        if !Path::new("/sys/kernel/tracing/events").exists()
+            && !Path::new("/sys/kernel/debug/tracing/events").exists()
+        {
+            problems.push("Kernel tracepoints are not available".to_string());
+        }
+    }
+    EbpfStatus {
+        available: problems.is_empty(),
+        problems,
+    }
+}
+
+// Map name -> { key -> value } from bpftrace's JSON lines
+fn parse_maps(output: &str) -> BTreeMap<String, serde_json::Map<String, Value>> {
+    let mut maps = BTreeMap::new();
+    for line in output.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if record["type"] != "map" {
+            continue;
+        }
+        if let Some(data) = record["data"].as_object() {
+            for (name, entries) in data {
+                if let Some(entries) = entries.as_object() {
+                    maps.insert(name.clone(), entries.clone());
+                }
+            }
+        }
+    }
+    maps
+}
+
+fn collect(duration_seconds: u64) -> Result<EbpfMetrics, String> {
+    let script = SCRIPT.replace("DURATION", &duration_seconds.to_string());
+    let output = run_text("bpftrace", &["-f", "json", "-e", &script])?;
+    let maps = parse_maps(&output);
+    let map = |name: &str| maps.get(name).cloned().unwrap_or_default();
+    let (names, totals, maxima) = (map("@name"), map("@io_total_us"), map("@io_max_us"));
+
+    let mut io_latency: Vec<ProcessIoLatency> = map("@io_count")
+        .iter()
+        .filter_map(|(pid, count)| {
+            let requests = count.as_u64().filter(|&requests| requests > 0)?;
+            let total = totals.get(pid).and_then(Value::as_u64).unwrap_or(0);
+            Some(ProcessIoLatency {
+                pid: pid.parse().ok()?,
+                name: names.get(pid).and_then(Value::as_str).map(str::to_string),
+                requests,
+                avg_latency_us: total as f64 / requests as f64,
+                max_latency_us: maxima.get(pid).and_then(Value::as_u64).unwrap_or(0),
+            })
+        })
+        .collect();
+    io_latency.sort_by_key(|process| std::cmp::Reverse(process.requests));
+
+    let mut tcp_retransmits: Vec<TcpRetransmits> = map("@retransmits")
+        .iter()
+        .filter_map(|(address, count)| {
+            Some(TcpRetransmits {
+                remote_address: address.clone(),
+                count: count.as_u64()?,
+            })
+        })
+        .collect();
+    tcp_retransmits.sort_by_key(|remote| std::cmp::Reverse(remote.count));
+
+    Ok(EbpfMetrics {
+        duration_seconds,
+        io_latency,
+        tcp_retransmits,
+    })
+}
+
+// Whether this machine can run the eBPF metrics, and what is missing if not
+#[tauri::command]
+pub async fn get_ebpf_status() -> Result<EbpfStatus, CommandError> {
+    let status = tauri::async_runtime::spawn_blocking(check)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(status)
+}
+
+// Trace for `duration_seconds` (5 by default) and summarize
+#[tauri::command]
+pub async fn collect_ebpf_metrics(
+    duration_seconds: Option<u64>,
+) -> Result<EbpfMetrics, CommandError> {
+    let duration_seconds = duration_seconds.unwrap_or(DEFAULT_DURATION_SECONDS);
+    if !(1..=MAX_DURATION_SECONDS).contains(&duration_seconds) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "ebpf.invalidDuration")
+                .with("max", MAX_DURATION_SECONDS),
+        );
+    }
+
+    let metrics = tauri::async_runtime::spawn_blocking(move || {
+        let status = check();
+        if !status.available {
+            return Err(
+                CommandError::new(ErrorCode::PermissionDenied, "ebpf.unavailable")
+                    .with("reason", status.problems.join("; ")),
+            );
+        }
+        Ok(collect(duration_seconds)?)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    Ok(metrics)
+}
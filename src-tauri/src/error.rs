@@ -0,0 +1,94 @@
+// Error type returned by every command.
+// `code` is stable for program logic, `key` and `params` let the frontend translate the message,
+// and `message` is the same text already rendered in the active locale.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::i18n;
+
+// The pure-Rust build never fails to load a library, so some codes go unused there
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[cfg_attr(feature = "no-ffi", allow(dead_code))]
+pub enum ErrorCode {
+    LibraryNotLoaded,
+    // The file lacks exports every systemapi library has
+    SymbolMissing,
+    // A call into the native library returned an error
+    NativeCallFailed,
+    InvalidInput,
+    Io,
+    PermissionDenied,
+    Cancelled,
+    // Gave up waiting, e.g. for a hung library call or for calls still running in a library
+    // being unloaded
+    Timeout,
+    Internal,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub key: &'static str,
+    pub params: BTreeMap<&'static str, String>,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, key: &'static str) -> Self {
+        CommandError {
+            code,
+            key,
+            params: BTreeMap::new(),
+            message: i18n::render(key, &BTreeMap::new()),
+        }
+    }
+
+    // Add a `{name}` parameter to the message
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.insert(name, value.to_string());
+        self.message = i18n::render(self.key, &self.params);
+        self
+    }
+
+    #[cfg_attr(feature = "no-ffi", allow(dead_code))]
+    pub fn library_not_loaded() -> Self {
+        CommandError::new(ErrorCode::LibraryNotLoaded, "library.notLoaded")
+    }
+
+    pub fn open_failed(path: &str, detail: impl ToString) -> Self {
+        CommandError::new(ErrorCode::Io, "file.openFailed")
+            .with("path", path)
+            .with("detail", detail)
+    }
+
+    pub fn create_failed(path: &str, detail: impl ToString) -> Self {
+        CommandError::new(ErrorCode::Io, "file.createFailed")
+            .with("path", path)
+            .with("detail", detail)
+    }
+}
+
+// Errors without a catalog entry keep their English text as the `detail` parameter
+impl From<String> for CommandError {
+    fn from(detail: String) -> Self {
+        CommandError::new(ErrorCode::Internal, "error.internal").with("detail", detail)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(detail: &str) -> Self {
+        CommandError::from(detail.to_string())
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
@@ -0,0 +1,162 @@
+// The last few reports and snippets the user exported or copied, with what they contained,
+// so yesterday's report can be sent again without regenerating it. Entries live in the
+// history database and only the newest `MAX_ENTRIES` are kept.
+
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::history::History;
+use crate::report::unix_timestamp;
+
+const MAX_ENTRIES: u32 = 50;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportKind {
+    Bundle,
+    Parquet,
+    // A system snapshot from `export_report`; its content is the snapshot JSON
+    Report,
+    // Text the frontend copied to the clipboard
+    Snippet,
+}
+
+impl ExportKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExportKind::Bundle => "bundle",
+            ExportKind::Parquet => "parquet",
+            ExportKind::Report => "report",
+            ExportKind::Snippet => "snippet",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "bundle" => Some(ExportKind::Bundle),
+            "parquet" => Some(ExportKind::Parquet),
+            "report" => Some(ExportKind::Report),
+            "snippet" => Some(ExportKind::Snippet),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRecord {
+    pub id: i64,
+    pub kind: ExportKind,
+    pub created_at: i64,
+    // File written to, if any
+    pub destination: Option<String>,
+    // Short description of what was exported
+    pub summary: String,
+    // The exported data itself where it is small enough to keep (report JSON, copied text)
+    pub content: Option<String>,
+}
+
+// Remember an export, dropping the oldest entries past the limit
+pub fn record(
+    history: &History,
+    kind: ExportKind,
+    destination: Option<&str>,
+    summary: &str,
+    content: Option<&str>,
+) -> Result<(), String> {
+    let conn = history.conn()?;
+    conn.execute(
+        "INSERT INTO export_history (kind, created_at, destination, summary, content)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            kind.as_str(),
+            unix_timestamp() as i64,
+            destination,
+            summary,
+            content
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM export_history
+         WHERE id NOT IN (SELECT id FROM export_history ORDER BY id DESC LIMIT ?1)",
+        params![MAX_ENTRIES],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Exports succeed even if they can't be remembered
+pub fn record_or_warn(
+    history: &History,
+    kind: ExportKind,
+    destination: Option<&str>,
+    summary: &str,
+    content: Option<&str>,
+) {
+    if let Err(e) = record(history, kind, destination, summary, content) {
+        eprintln!("⚠ Warning: Failed to record export: {}", e);
+    }
+}
+
+pub fn find(history: &History, id: i64) -> Result<Option<ExportRecord>, String> {
+    let conn = history.conn()?;
+    conn.query_row(
+        "SELECT id, kind, created_at, destination, summary, content FROM export_history
+         WHERE id = ?1",
+        params![id],
+        read_record,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn read_record(row: &rusqlite::Row) -> rusqlite::Result<ExportRecord> {
+    let kind: String = row.get(1)?;
+    Ok(ExportRecord {
+        id: row.get(0)?,
+        kind: ExportKind::parse(&kind).unwrap_or(ExportKind::Snippet),
+        created_at: row.get(2)?,
+        destination: row.get(3)?,
+        summary: row.get(4)?,
+        content: row.get(5)?,
+    })
+}
+
+#[tauri::command]
+pub fn record_copied_snippet(
+    summary: String,
+    content: String,
+    history: State<History>,
+) -> Result<(), CommandError> {
+    Ok(record(
+        &history,
+        ExportKind::Snippet,
+        None,
+        &summary,
+        Some(&content),
+    )?)
+}
+
+// Most recent exports first
+#[tauri::command]
+pub fn get_export_history(
+    limit: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<ExportRecord>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT id, kind, created_at, destination, summary, content FROM export_history
+             ORDER BY id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let records = statement
+        .query_map(params![limit.unwrap_or(MAX_ENTRIES)], read_record)
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(records)
+}
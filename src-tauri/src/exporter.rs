@@ -0,0 +1,203 @@
+// Pluggable destinations for sampled metrics.
+// Samplers hand their readings to `publish`, which stores them in history and pushes them to
+// every exporter configured under `[[exporters]]` in config.toml. A new destination only
+// needs an `Exporter` implementation and an arm in `build`, e.g.
+//
+//     [[exporters]]
+//     kind = "jsonl"
+//     path = "/var/log/system-info/metrics.jsonl"
+
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::benchmark;
+use crate::config::ConfigState;
+use crate::history::History;
+use crate::report::unix_timestamp;
+
+pub trait Exporter: Send {
+    fn name(&self) -> &str;
+
+    // Open connections or files; an exporter that fails to start is dropped
+    fn start(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    // Deliver one batch of samples taken at `timestamp` (Unix seconds)
+    fn push(&mut self, timestamp: i64, samples: &[(String, f64)]) -> Result<(), String>;
+
+    // Flush and release resources before the app exits
+    fn shutdown(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// One `[[exporters]]` entry; options other than `kind` and `enabled` are exporter-specific
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExporterConfig {
+    pub kind: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub options: toml::Table,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl ExporterConfig {
+    fn option(&self, name: &str) -> Result<&str, String> {
+        self.options
+            .get(name)
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| format!("{} exporter needs a `{}` option", self.kind, name))
+    }
+}
+
+fn build(config: &ExporterConfig) -> Result<Box<dyn Exporter>, String> {
+    match config.kind.as_str() {
+        "jsonl" => Ok(Box::new(JsonLinesExporter::new(PathBuf::from(
+            config.option("path")?,
+        )))),
+        kind => Err(format!("Unknown exporter kind `{}`", kind)),
+    }
+}
+
+// Appends one JSON object per sample to a file
+struct JsonLinesExporter {
+    path: PathBuf,
+    file: Option<BufWriter<File>>,
+}
+
+impl JsonLinesExporter {
+    fn new(path: PathBuf) -> Self {
+        JsonLinesExporter { path, file: None }
+    }
+}
+
+impl Exporter for JsonLinesExporter {
+    fn name(&self) -> &str {
+        "jsonl"
+    }
+
+    fn start(&mut self) -> Result<(), String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        self.file = Some(BufWriter::new(file));
+        Ok(())
+    }
+
+    fn push(&mut self, timestamp: i64, samples: &[(String, f64)]) -> Result<(), String> {
+        let file = self.file.as_mut().ok_or("Exporter not started")?;
+        for (metric, value) in samples {
+            let line = serde_json::json!({
+                "metric": metric,
+                "timestamp": timestamp,
+                "value": value,
+            });
+            writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+        }
+        file.flush().map_err(|e| e.to_string())
+    }
+
+    fn shutdown(&mut self) -> Result<(), String> {
+        match self.file.take() {
+            Some(mut file) => file.flush().map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ExporterRegistry {
+    exporters: Mutex<Vec<Box<dyn Exporter>>>,
+}
+
+impl ExporterRegistry {
+    // Build and start every enabled exporter from the config, skipping any that fail
+    pub fn from_config(configs: &[ExporterConfig]) -> Self {
+        let exporters = configs
+            .iter()
+            .filter(|config| config.enabled)
+            .filter_map(|config| {
+                let started = build(config).and_then(|mut exporter| {
+                    exporter.start()?;
+                    Ok(exporter)
+                });
+                match started {
+                    Ok(exporter) => {
+                        println!("✓ Started {} exporter", exporter.name());
+                        Some(exporter)
+                    }
+                    Err(e) => {
+                        eprintln!("⚠ Warning: Skipping {} exporter: {}", config.kind, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        ExporterRegistry {
+            exporters: Mutex::new(exporters),
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.exporters
+            .lock()
+            .map(|exporters| exporters.iter().map(|e| e.name().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn push(&self, timestamp: i64, samples: &[(String, f64)]) {
+        let Ok(mut exporters) = self.exporters.lock() else {
+            return;
+        };
+        for exporter in exporters.iter_mut() {
+            if let Err(e) = exporter.push(timestamp, samples) {
+                eprintln!("{} exporter failed: {}", exporter.name(), e);
+            }
+        }
+    }
+
+    pub fn shutdown(&self) {
+        let Ok(mut exporters) = self.exporters.lock() else {
+            return;
+        };
+        for mut exporter in exporters.drain(..) {
+            if let Err(e) = exporter.shutdown() {
+                eprintln!("{} exporter failed to shut down: {}", exporter.name(), e);
+            }
+        }
+    }
+}
+
+// Store a batch of samples in history and send it to every exporter. Exporters are skipped
+// while a benchmark runs; its own timeline samples still reach history.
+pub fn publish(app: &AppHandle, samples: &[(String, f64)]) {
+    let timestamp = unix_timestamp() as i64;
+    if let Err(e) = app.state::<History>().record(timestamp, samples) {
+        eprintln!("Recording samples failed: {}", e);
+    }
+    if !benchmark::running() {
+        app.state::<ExporterRegistry>().push(timestamp, samples);
+    }
+}
+
+// Start the exporters listed in the loaded config
+pub fn start_exporters(app: &AppHandle) -> ExporterRegistry {
+    let configs = app
+        .state::<ConfigState>()
+        .get()
+        .map(|config| config.exporters.clone())
+        .unwrap_or_default();
+    ExporterRegistry::from_config(&configs)
+}
@@ -0,0 +1,818 @@
+// Safe wrappers around the systemapi C++ library.
+// Every `unsafe` block in the app lives here. `SystemApi::open` resolves every export with the
+// signature from systemapi.h once, and its methods validate inputs and own any buffer passed
+// across the boundary, so command handlers never touch raw pointers. New bindings should follow
+// the same shape: a row in `exports!`, a field resolved in `open` with `bind` and a safe method.
+// Memory an export allocates belongs to the caller and goes back through the library's
+// `FreeBuffer`; it is wrapped in a `LibraryBuffer` the moment it crosses the boundary.
+
+use libloading::{Library, Symbol};
+use std::ffi::{c_void, CStr};
+use std::fmt;
+use std::os::raw::c_char;
+use std::path::Path;
+use std::ptr::NonNull;
+
+use crate::cpu::CpuInfo;
+use crate::power::{ChargeState, PowerStatus};
+use crate::uptime::{LoadAverage, UptimeInfo};
+use crate::{MissingFeature, MAX_FACTORIAL_INPUT};
+
+// A row of the export table
+pub struct ExportSpec {
+    pub name: &'static str,
+    pub signature: &'static str,
+    pub since: (u32, u32),
+    pub feature: &'static str,
+}
+
+impl ExportSpec {
+    fn is_required(&self) -> bool {
+        self.since == FIRST_RELEASE
+    }
+
+    pub fn since_version(&self) -> String {
+        format!("{}.{}", self.since.0, self.since.1)
+    }
+}
+
+trait Export {
+    type Fn: Copy;
+    const SPEC: ExportSpec;
+}
+
+macro_rules! exports {
+    ($($name:ident as $alias:ident: $signature:ty, since ($major:literal, $minor:literal),
+        for $feature:literal;)*) => {
+        $(
+            type $alias = $signature;
+
+            enum $name {}
+
+            impl Export for $name {
+                type Fn = $alias;
+                const SPEC: ExportSpec = ExportSpec {
+                    name: stringify!($name),
+                    signature: stringify!($signature),
+                    since: ($major, $minor),
+                    feature: $feature,
+                };
+            }
+        )*
+
+        pub const EXPORTS: &[ExportSpec] = &[$(<$name as Export>::SPEC),*];
+    };
+}
+
+// Every export the app binds: its signature from systemapi.h, the library version that added
+// it and what it is for. Each row declares the signature type and a marker type carrying it, so
+// `bind::<Marker>` can only produce the pointer type the header declares, and adds a row to
+// `EXPORTS` from which an older library's gaps are reported.
+exports! {
+    GetComputerNameString as GetComputerNameStringFn:
+        unsafe extern "C" fn(*mut c_char, i32) -> bool, since (1, 0), for "computer name";
+    GetTotalPhysicalMemory as GetTotalPhysicalMemoryFn:
+        unsafe extern "C" fn() -> u64, since (1, 0), for "total memory";
+    GetCurrentProcessID as GetCurrentProcessIDFn:
+        unsafe extern "C" fn() -> u32, since (1, 0), for "process ID";
+    CalculateFactorial as CalculateFactorialFn:
+        unsafe extern "C" fn(i32) -> u64, since (1, 0), for "factorial";
+    GetCpuModelString as GetCpuModelStringFn:
+        unsafe extern "C" fn(*mut c_char, i32) -> bool, since (1, 1), for "CPU details";
+    GetCpuVendorString as GetCpuVendorStringFn:
+        unsafe extern "C" fn(*mut c_char, i32) -> bool, since (1, 1), for "CPU details";
+    GetPhysicalCoreCount as GetPhysicalCoreCountFn:
+        unsafe extern "C" fn() -> u32, since (1, 1), for "CPU details";
+    GetLogicalCoreCount as GetLogicalCoreCountFn:
+        unsafe extern "C" fn() -> u32, since (1, 1), for "CPU details";
+    GetCpuBaseFrequencyMHz as GetCpuBaseFrequencyMHzFn:
+        unsafe extern "C" fn() -> u64, since (1, 1), for "CPU details";
+    GetCpuCoreUsage as GetCpuCoreUsageFn:
+        unsafe extern "C" fn(*mut f64, i32) -> i32, since (1, 1), for "CPU details";
+    GetPowerStatus as GetPowerStatusFn:
+        unsafe extern "C" fn(*mut SystemPowerStatus) -> bool, since (1, 2), for "power status";
+    FreeBuffer as FreeBufferFn:
+        unsafe extern "C" fn(*mut c_void), since (1, 3), for "library version";
+    GetLibraryVersionString as GetLibraryVersionStringFn:
+        unsafe extern "C" fn() -> *mut c_char, since (1, 3), for "library version";
+    GetUptimeInfo as GetUptimeInfoFn:
+        unsafe extern "C" fn(*mut SystemUptimeInfo) -> bool, since (1, 3), for "uptime";
+    GetApiVersion as GetApiVersionFn:
+        unsafe extern "C" fn() -> u32, since (1, 4), for "API version";
+}
+
+// Any `char* f()` export returning memory the caller frees
+type GetAllocatedStringFn = unsafe extern "C" fn() -> *mut c_char;
+
+// Mirrors `SystemPowerStatus` in systemapi.h
+#[repr(C)]
+#[derive(Default)]
+struct SystemPowerStatus {
+    has_battery: i32,
+    battery_percent: i32,
+    charge_state: i32,
+    ac_online: i32,
+    seconds_remaining: i64,
+}
+
+// Mirrors `SystemUptimeInfo` in systemapi.h
+#[repr(C)]
+#[derive(Default)]
+struct SystemUptimeInfo {
+    uptime_seconds: u64,
+    boot_time: i64,
+    load_average_1: f64,
+    load_average_5: f64,
+    load_average_15: f64,
+}
+
+// ABI version of systemapi.h these bindings follow
+pub const API_VERSION: (u32, u32) = (1, 4);
+
+// Exports from the first release are required; later ones are optional
+const FIRST_RELEASE: (u32, u32) = (1, 0);
+
+// Most cores `GetCpuCoreUsage` is asked to report
+const MAX_CORES: usize = 1024;
+
+// String results start with this buffer and double until the text fits or the cap is hit
+const INITIAL_STRING_BUFFER: usize = 256;
+const MAX_STRING_BUFFER: usize = 64 * 1024;
+
+// Why `SystemApi::open` rejected a file
+#[derive(Debug)]
+pub enum OpenError {
+    // The OS loader couldn't load it
+    Load(String),
+    // Required exports it lacks, as `name (signature)`
+    MissingExports(Vec<String>),
+}
+
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenError::Load(message) => f.write_str(message),
+            OpenError::MissingExports(exports) => {
+                write!(f, "Not a systemapi library: missing {}", exports.join(", "))
+            }
+        }
+    }
+}
+
+impl From<OpenError> for String {
+    fn from(e: OpenError) -> Self {
+        e.to_string()
+    }
+}
+
+// A loaded library with all of its exports resolved. Nothing changes after `open`, so any
+// number of threads can call through a shared reference at once.
+pub struct SystemApi {
+    get_computer_name: GetComputerNameStringFn,
+    get_total_memory: GetTotalPhysicalMemoryFn,
+    get_process_id: GetCurrentProcessIDFn,
+    calculate_factorial: CalculateFactorialFn,
+    // None for builds older than 1.1, which don't export the CPU functions
+    cpu: Option<CpuExports>,
+    // None for builds older than 1.2
+    get_power_status: Option<GetPowerStatusFn>,
+    // None for builds older than 1.3
+    get_uptime_info: Option<GetUptimeInfoFn>,
+    // None for builds older than 1.3
+    allocating: Option<AllocatingExports>,
+    // None for builds older than 1.4
+    get_api_version: Option<GetApiVersionFn>,
+    // Optional exports the library predates, for diagnostics
+    missing_exports: Vec<&'static str>,
+    missing_features: Vec<MissingFeature>,
+    // Keeps the function pointers above valid
+    _library: Library,
+}
+
+struct CpuExports {
+    get_model: GetCpuModelStringFn,
+    get_vendor: GetCpuVendorStringFn,
+    get_physical_cores: GetPhysicalCoreCountFn,
+    get_logical_cores: GetLogicalCoreCountFn,
+    get_base_frequency: GetCpuBaseFrequencyMHzFn,
+    get_core_usage: GetCpuCoreUsageFn,
+}
+
+// Exports returning memory the caller must release. They are only bound together with
+// `FreeBuffer`, so nothing can be handed over that Rust has no way to free.
+struct AllocatingExports {
+    free_buffer: FreeBufferFn,
+    get_library_version: GetLibraryVersionStringFn,
+}
+
+// Owns memory returned by an allocating export and hands it back to `FreeBuffer` when dropped,
+// so it is released exactly once, by the heap that allocated it, on every path out. Guards never
+// outlive the `SystemApi` method that created them, which keeps the library loaded meanwhile.
+struct LibraryBuffer {
+    ptr: NonNull<c_char>,
+    free: FreeBufferFn,
+}
+
+impl LibraryBuffer {
+    // None for a NULL result, which the library uses for failure.
+    // SAFETY: `ptr` must come from an allocating export of the library `free` belongs to, and
+    // nothing else may free it
+    unsafe fn new(ptr: *mut c_char, free: FreeBufferFn) -> Option<Self> {
+        NonNull::new(ptr).map(|ptr| LibraryBuffer { ptr, free })
+    }
+
+    // The buffer as NUL-terminated text; invalid UTF-8 is replaced rather than rejected
+    fn to_string_lossy(&self) -> String {
+        // SAFETY: string exports return NUL-terminated text, alive until the guard drops
+        unsafe { CStr::from_ptr(self.ptr.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+impl Drop for LibraryBuffer {
+    fn drop(&mut self) {
+        // SAFETY: the pointer came from this library and `new`'s contract makes this the only free
+        unsafe { (self.free)(self.ptr.as_ptr() as *mut c_void) }
+    }
+}
+
+// Resolve an export with the signature its table row declares
+fn bind<E: Export>(lib: &Library) -> Result<E::Fn, String> {
+    // SAFETY: `E::Fn` is the signature systemapi.h declares for `E::SPEC.name`
+    let symbol: Symbol<E::Fn> =
+        unsafe { lib.get(E::SPEC.name.as_bytes()) }.map_err(|e| e.to_string())?;
+    Ok(*symbol)
+}
+
+// Rows of the export table the library doesn't provide
+fn missing_exports(lib: &Library) -> Vec<&'static ExportSpec> {
+    EXPORTS
+        .iter()
+        // SAFETY: the symbol is only looked up, never called through this type
+        .filter(|spec| unsafe { lib.get::<*const ()>(spec.name.as_bytes()) }.is_err())
+        .collect()
+}
+
+// Optional features a library is too old for, one entry per feature
+fn missing_features(missing: &[&ExportSpec]) -> Vec<MissingFeature> {
+    let mut features: Vec<MissingFeature> = Vec::new();
+    for spec in missing.iter().filter(|spec| !spec.is_required()) {
+        match features
+            .iter_mut()
+            .find(|feature| feature.feature == spec.feature)
+        {
+            Some(feature) => feature.exports.push(spec.name.to_string()),
+            None => features.push(MissingFeature {
+                feature: spec.feature.to_string(),
+                since: spec.since_version(),
+                exports: vec![spec.name.to_string()],
+            }),
+        }
+    }
+    features
+}
+
+// The CPU exports, if the library has all of them
+fn cpu_exports(lib: &Library) -> Option<CpuExports> {
+    Some(CpuExports {
+        get_model: bind::<GetCpuModelString>(lib).ok()?,
+        get_vendor: bind::<GetCpuVendorString>(lib).ok()?,
+        get_physical_cores: bind::<GetPhysicalCoreCount>(lib).ok()?,
+        get_logical_cores: bind::<GetLogicalCoreCount>(lib).ok()?,
+        get_base_frequency: bind::<GetCpuBaseFrequencyMHz>(lib).ok()?,
+        get_core_usage: bind::<GetCpuCoreUsage>(lib).ok()?,
+    })
+}
+
+// The allocating exports, if the library has `FreeBuffer` and all of them
+fn allocating_exports(lib: &Library) -> Option<AllocatingExports> {
+    Some(AllocatingExports {
+        free_buffer: bind::<FreeBuffer>(lib).ok()?,
+        get_library_version: bind::<GetLibraryVersionString>(lib).ok()?,
+    })
+}
+
+// Call a `char* f()` export and take ownership of the result before reading it
+fn read_allocated_string(
+    get: GetAllocatedStringFn,
+    free: FreeBufferFn,
+    what: &str,
+) -> Result<String, String> {
+    // SAFETY: takes no arguments and returns NULL or a string allocated by the same library as
+    // `free`, which nothing else frees
+    let buffer = unsafe { LibraryBuffer::new(get(), free) };
+    buffer
+        .map(|buffer| buffer.to_string_lossy())
+        .ok_or_else(|| format!("Failed to get {}", what))
+}
+
+// Call a `bool f(char* buffer, int size)` export, growing the buffer until the result fits.
+// The text ends at the first NUL; a call that fills the buffer without one was truncated and is
+// retried with more room. Invalid UTF-8 is replaced rather than rejected.
+fn read_string(get: GetComputerNameStringFn, what: &str) -> Result<String, String> {
+    let mut size = INITIAL_STRING_BUFFER;
+    loop {
+        let mut buffer = vec![0u8; size];
+        // SAFETY: the pointer and length describe `buffer`, which outlives the call
+        let ok = unsafe { get(buffer.as_mut_ptr() as *mut c_char, size as i32) };
+
+        if ok {
+            if let Some(end) = buffer.iter().position(|&byte| byte == 0) {
+                return Ok(String::from_utf8_lossy(&buffer[..end]).into_owned());
+            }
+        }
+        if size >= MAX_STRING_BUFFER {
+            return Err(format!("Failed to get {}", what));
+        }
+        size *= 2;
+    }
+}
+
+fn read_cpu_info(cpu: &CpuExports) -> Result<CpuInfo, String> {
+    // SAFETY: the count and frequency exports take no arguments and only query the OS
+    let (physical, logical, base_frequency) = unsafe {
+        (
+            (cpu.get_physical_cores)(),
+            (cpu.get_logical_cores)(),
+            (cpu.get_base_frequency)(),
+        )
+    };
+    // The library reports 0 for anything it couldn't read
+    Ok(CpuInfo {
+        model: read_string(cpu.get_model, "CPU model")?,
+        vendor: read_string(cpu.get_vendor, "CPU vendor")?,
+        physical_cores: (physical > 0).then_some(physical),
+        logical_cores: logical,
+        base_frequency_mhz: (base_frequency > 0).then_some(base_frequency),
+    })
+}
+
+// The library reports -1 for anything it couldn't read
+fn power_status(raw: &SystemPowerStatus) -> PowerStatus {
+    PowerStatus {
+        has_battery: raw.has_battery == 1,
+        battery_percent: u8::try_from(raw.battery_percent)
+            .ok()
+            .filter(|_| raw.has_battery == 1),
+        charge_state: match raw.charge_state {
+            1 => ChargeState::Charging,
+            2 => ChargeState::Discharging,
+            3 => ChargeState::Full,
+            4 => ChargeState::NotCharging,
+            _ => ChargeState::Unknown,
+        },
+        ac_online: (raw.ac_online >= 0).then_some(raw.ac_online == 1),
+        seconds_remaining: u64::try_from(raw.seconds_remaining).ok(),
+    }
+}
+
+// The library reports -1 load averages where the OS keeps none
+fn uptime_info(raw: &SystemUptimeInfo) -> UptimeInfo {
+    UptimeInfo {
+        uptime_seconds: raw.uptime_seconds,
+        boot_time: raw.boot_time,
+        load_average: (raw.load_average_1 >= 0.0).then_some(LoadAverage {
+            one: raw.load_average_1,
+            five: raw.load_average_5,
+            fifteen: raw.load_average_15,
+        }),
+    }
+}
+
+impl SystemApi {
+    // Load the shared library at `path`, running its initialisers, and resolve every export
+    pub fn open(path: &Path) -> Result<Self, OpenError> {
+        // SAFETY: only systemapi builds from the app's own search paths are loaded, and its
+        // initialisers have no preconditions
+        let library = unsafe { Library::new(path) }.map_err(|e| OpenError::Load(e.to_string()))?;
+        let missing = missing_exports(&library);
+        let required: Vec<String> = missing
+            .iter()
+            .filter(|spec| spec.is_required())
+            .map(|spec| format!("{} ({})", spec.name, spec.signature))
+            .collect();
+        if !required.is_empty() {
+            return Err(OpenError::MissingExports(required));
+        }
+
+        Ok(SystemApi {
+            get_computer_name: bind::<GetComputerNameString>(&library).map_err(OpenError::Load)?,
+            get_total_memory: bind::<GetTotalPhysicalMemory>(&library).map_err(OpenError::Load)?,
+            get_process_id: bind::<GetCurrentProcessID>(&library).map_err(OpenError::Load)?,
+            calculate_factorial: bind::<CalculateFactorial>(&library).map_err(OpenError::Load)?,
+            cpu: cpu_exports(&library),
+            get_power_status: bind::<GetPowerStatus>(&library).ok(),
+            get_uptime_info: bind::<GetUptimeInfo>(&library).ok(),
+            allocating: allocating_exports(&library),
+            get_api_version: bind::<GetApiVersion>(&library).ok(),
+            missing_exports: missing.iter().map(|spec| spec.name).collect(),
+            missing_features: missing_features(&missing),
+            _library: library,
+        })
+    }
+
+    pub fn computer_name(&self) -> Result<String, String> {
+        read_string(self.get_computer_name, "computer name")
+    }
+
+    pub fn total_physical_memory(&self) -> u64 {
+        // SAFETY: takes no arguments and only queries the OS
+        unsafe { (self.get_total_memory)() }
+    }
+
+    pub fn current_process_id(&self) -> u32 {
+        // SAFETY: takes no arguments and only queries the OS
+        unsafe { (self.get_process_id)() }
+    }
+
+    pub fn calculate_factorial(&self, n: i32) -> Result<u64, String> {
+        if !(0..=MAX_FACTORIAL_INPUT).contains(&n) {
+            return Err(format!(
+                "Factorial input must be between 0 and {}",
+                MAX_FACTORIAL_INPUT
+            ));
+        }
+        // SAFETY: plain integer arithmetic; the range check above keeps the result from overflowing
+        Ok(unsafe { (self.calculate_factorial)(n) })
+    }
+
+    // None when the library predates the CPU exports
+    pub fn cpu_info(&self) -> Option<Result<CpuInfo, String>> {
+        self.cpu.as_ref().map(read_cpu_info)
+    }
+
+    // Per-core utilization since the previous call; None when the library predates the CPU
+    // exports
+    pub fn core_usage(&self) -> Option<Result<Vec<f64>, String>> {
+        let cpu = self.cpu.as_ref()?;
+        let mut usage = vec![0.0; MAX_CORES];
+        // SAFETY: the pointer and length describe `usage`, which outlives the call
+        let cores = unsafe { (cpu.get_core_usage)(usage.as_mut_ptr(), MAX_CORES as i32) };
+        if cores < 0 {
+            return Some(Err("Failed to get CPU usage".to_string()));
+        }
+        usage.truncate((cores as usize).min(MAX_CORES));
+        Some(Ok(usage))
+    }
+
+    // None when the library predates the power export
+    pub fn power_status(&self) -> Option<Result<PowerStatus, String>> {
+        let get = self.get_power_status?;
+        let mut raw = SystemPowerStatus::default();
+        // SAFETY: the pointer is to `raw`, which has the C layout and outlives the call
+        if !unsafe { get(&mut raw) } {
+            return Some(Err("Failed to get power status".to_string()));
+        }
+        Some(Ok(power_status(&raw)))
+    }
+
+    // None when the library predates the uptime export
+    pub fn uptime_info(&self) -> Option<Result<UptimeInfo, String>> {
+        let get = self.get_uptime_info?;
+        let mut raw = SystemUptimeInfo::default();
+        // SAFETY: the pointer is to `raw`, which has the C layout and outlives the call
+        if !unsafe { get(&mut raw) } {
+            return Some(Err("Failed to get uptime".to_string()));
+        }
+        Some(Ok(uptime_info(&raw)))
+    }
+
+    // Whether the library has the CPU exports of 1.1
+    pub fn has_cpu_exports(&self) -> bool {
+        self.cpu.is_some()
+    }
+
+    // Whether the library has `GetUptimeInfo` of 1.3
+    pub fn has_uptime_info(&self) -> bool {
+        self.get_uptime_info.is_some()
+    }
+
+    // Whether the library exports the function in this table row
+    pub fn provides(&self, spec: &ExportSpec) -> bool {
+        !self.missing_exports.contains(&spec.name)
+    }
+
+    // (major, minor) ABI version the library was built with; None when it predates
+    // `GetApiVersion`
+    pub fn api_version(&self) -> Option<(u32, u32)> {
+        let get = self.get_api_version?;
+        // SAFETY: takes no arguments and returns a constant
+        let packed = unsafe { get() };
+        Some((packed >> 16, packed & 0xFFFF))
+    }
+
+    // Optional features this build is too old to provide
+    pub fn missing_features(&self) -> &[MissingFeature] {
+        &self.missing_features
+    }
+
+    // Version the library was built as; None when it predates `GetLibraryVersionString`
+    pub fn library_version(&self) -> Option<Result<String, String>> {
+        let exports = self.allocating.as_ref()?;
+        Some(read_allocated_string(
+            exports.get_library_version,
+            exports.free_buffer,
+            "library version",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::cell::RefCell;
+
+    // Mock of the systemapi string export, configured per test thread
+    #[derive(Default)]
+    struct MockLibrary {
+        output: Vec<u8>,
+        // Report failure when the text does not fit instead of silently truncating
+        strict: bool,
+        offered: Vec<i32>,
+    }
+
+    thread_local! {
+        static MOCK: RefCell<MockLibrary> = RefCell::new(MockLibrary::default());
+    }
+
+    // Behaves like gethostname: copies as much as fits and only terminates when there is room
+    unsafe extern "C" fn mock_get_string(buffer: *mut c_char, size: i32) -> bool {
+        MOCK.with(|mock| {
+            let mut mock = mock.borrow_mut();
+            mock.offered.push(size);
+            let size = size as usize;
+            if mock.strict && mock.output.len() >= size {
+                return false;
+            }
+            let buffer = std::slice::from_raw_parts_mut(buffer as *mut u8, size);
+            let copied = mock.output.len().min(size);
+            buffer[..copied].copy_from_slice(&mock.output[..copied]);
+            if copied < size {
+                buffer[copied] = 0;
+            }
+            true
+        })
+    }
+
+    unsafe extern "C" fn mock_always_fails(_: *mut c_char, size: i32) -> bool {
+        MOCK.with(|mock| mock.borrow_mut().offered.push(size));
+        false
+    }
+
+    fn run(output: &[u8], strict: bool) -> (Result<String, String>, Vec<i32>) {
+        MOCK.with(|mock| {
+            *mock.borrow_mut() = MockLibrary {
+                output: output.to_vec(),
+                strict,
+                offered: Vec::new(),
+            }
+        });
+        let result = read_string(mock_get_string, "mock string");
+        let offered = MOCK.with(|mock| std::mem::take(&mut mock.borrow_mut().offered));
+        (result, offered)
+    }
+
+    fn expected(output: &[u8]) -> String {
+        let end = output
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(output.len());
+        String::from_utf8_lossy(&output[..end]).into_owned()
+    }
+
+    proptest! {
+        #[test]
+        fn returns_text_up_to_first_nul(
+            output in prop::collection::vec(any::<u8>(), 0..4096),
+            strict in any::<bool>(),
+        ) {
+            let (result, _) = run(&output, strict);
+            prop_assert_eq!(result, Ok(expected(&output)));
+        }
+
+        #[test]
+        fn invalid_utf8_is_replaced_not_rejected(
+            // Bytes 0xf5-0xff never appear in valid UTF-8
+            output in prop::collection::vec(0xf5u8..=0xff, 1..512),
+        ) {
+            let (result, _) = run(&output, false);
+            let text = result.unwrap();
+            prop_assert!(text.contains(char::REPLACEMENT_CHARACTER));
+            prop_assert_eq!(text, String::from_utf8_lossy(&output).into_owned());
+        }
+
+        #[test]
+        fn buffer_grows_past_truncation(
+            boundary in prop::sample::select(vec![
+                INITIAL_STRING_BUFFER,
+                INITIAL_STRING_BUFFER * 2,
+                INITIAL_STRING_BUFFER * 16,
+                MAX_STRING_BUFFER,
+            ]),
+            offset in -2isize..=2,
+            strict in any::<bool>(),
+        ) {
+            let len = boundary.saturating_add_signed(offset);
+            let output = vec![b'a'; len];
+            let (result, offered) = run(&output, strict);
+
+            // Offers start at the initial size, double each retry and never exceed the cap
+            prop_assert_eq!(offered[0] as usize, INITIAL_STRING_BUFFER);
+            for pair in offered.windows(2) {
+                prop_assert_eq!(pair[1], pair[0] * 2);
+            }
+            prop_assert!(offered.iter().all(|&size| size as usize <= MAX_STRING_BUFFER));
+
+            // Text needs one extra byte for its terminator
+            if len < MAX_STRING_BUFFER {
+                prop_assert_eq!(result, Ok(expected(&output)));
+                prop_assert!(*offered.last().unwrap() as usize > len);
+            } else {
+                prop_assert_eq!(result, Err("Failed to get mock string".to_string()));
+                prop_assert_eq!(*offered.last().unwrap() as usize, MAX_STRING_BUFFER);
+            }
+        }
+    }
+
+    #[test]
+    fn persistent_failure_gives_up_at_the_cap() {
+        MOCK.with(|mock| *mock.borrow_mut() = MockLibrary::default());
+        let result = read_string(mock_always_fails, "computer name");
+        let offered = MOCK.with(|mock| std::mem::take(&mut mock.borrow_mut().offered));
+
+        assert_eq!(result, Err("Failed to get computer name".to_string()));
+        assert_eq!(offered.first(), Some(&(INITIAL_STRING_BUFFER as i32)));
+        assert_eq!(offered.last(), Some(&(MAX_STRING_BUFFER as i32)));
+    }
+
+    unsafe extern "C" fn mock_zero_count() -> u32 {
+        0
+    }
+
+    unsafe extern "C" fn mock_zero_frequency() -> u64 {
+        0
+    }
+
+    unsafe extern "C" fn mock_core_usage(_usage: *mut f64, _count: i32) -> i32 {
+        -1
+    }
+
+    #[test]
+    fn unknown_cpu_values_become_none() {
+        MOCK.with(|mock| {
+            *mock.borrow_mut() = MockLibrary {
+                output: b"Mock CPU\0".to_vec(),
+                ..MockLibrary::default()
+            }
+        });
+        let cpu = CpuExports {
+            get_model: mock_get_string,
+            get_vendor: mock_get_string,
+            get_physical_cores: mock_zero_count,
+            get_logical_cores: mock_zero_count,
+            get_base_frequency: mock_zero_frequency,
+            get_core_usage: mock_core_usage,
+        };
+        let info = read_cpu_info(&cpu).unwrap();
+
+        assert_eq!(info.model, "Mock CPU");
+        assert_eq!(info.physical_cores, None);
+        assert_eq!(info.base_frequency_mhz, None);
+    }
+
+    thread_local! {
+        // Addresses handed out by the mock allocating export, and those given back to it
+        static ALLOCATED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+        static FREED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    }
+
+    unsafe extern "C" fn mock_allocate_version() -> *mut c_char {
+        let ptr = std::ffi::CString::new(vec![b'1', b'.', 0xff])
+            .unwrap()
+            .into_raw();
+        ALLOCATED.with(|allocated| allocated.borrow_mut().push(ptr as usize));
+        ptr
+    }
+
+    unsafe extern "C" fn mock_allocate_nothing() -> *mut c_char {
+        std::ptr::null_mut()
+    }
+
+    unsafe extern "C" fn mock_free_buffer(buffer: *mut c_void) {
+        FREED.with(|freed| freed.borrow_mut().push(buffer as usize));
+        if !buffer.is_null() {
+            drop(std::ffi::CString::from_raw(buffer as *mut c_char));
+        }
+    }
+
+    #[test]
+    fn allocated_strings_are_freed_exactly_once() {
+        let first = read_allocated_string(mock_allocate_version, mock_free_buffer, "version");
+        let second = read_allocated_string(mock_allocate_version, mock_free_buffer, "version");
+
+        assert_eq!(first, Ok("1.\u{fffd}".to_string()));
+        assert_eq!(second, first);
+        let allocated = ALLOCATED.with(|allocated| allocated.borrow().clone());
+        let freed = FREED.with(|freed| freed.borrow().clone());
+        assert_eq!(allocated.len(), 2);
+        assert_eq!(freed, allocated);
+    }
+
+    #[test]
+    fn null_results_fail_without_freeing() {
+        let result = read_allocated_string(mock_allocate_nothing, mock_free_buffer, "version");
+
+        assert_eq!(result, Err("Failed to get version".to_string()));
+        assert!(FREED.with(|freed| freed.borrow().is_empty()));
+    }
+
+    #[test]
+    fn unknown_power_values_become_none() {
+        let raw = SystemPowerStatus {
+            has_battery: 1,
+            battery_percent: -1,
+            charge_state: 0,
+            ac_online: -1,
+            seconds_remaining: -1,
+        };
+        let status = power_status(&raw);
+
+        assert!(status.has_battery);
+        assert_eq!(status.battery_percent, None);
+        assert_eq!(status.charge_state, ChargeState::Unknown);
+        assert_eq!(status.ac_online, None);
+        assert_eq!(status.seconds_remaining, None);
+    }
+
+    #[test]
+    fn export_table_matches_the_header() {
+        let header = include_str!("../../cpp_cross_platform/include/systemapi.h");
+        let declared: Vec<&str> = header
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("SYSTEMAPI_API "))
+            .filter_map(|declaration| declaration.split('(').next()?.split_whitespace().last())
+            .map(|name| name.trim_start_matches('*'))
+            .collect();
+        let bound: Vec<&str> = EXPORTS.iter().map(|spec| spec.name).collect();
+
+        assert_eq!(bound, declared);
+    }
+
+    #[test]
+    fn api_version_matches_the_header() {
+        let header = include_str!("../../cpp_cross_platform/include/systemapi.h");
+        let define = |name: &str| -> Option<u32> {
+            header.lines().find_map(|line| {
+                let value = line.trim().strip_prefix("#define ")?.strip_prefix(name)?;
+                value.trim().parse().ok()
+            })
+        };
+
+        assert_eq!(
+            (
+                define("SYSTEMAPI_API_VERSION_MAJOR"),
+                define("SYSTEMAPI_API_VERSION_MINOR")
+            ),
+            (Some(API_VERSION.0), Some(API_VERSION.1))
+        );
+        // Every export must be available by the version the header declares
+        assert!(EXPORTS.iter().all(|spec| spec.since <= API_VERSION));
+    }
+
+    #[test]
+    fn missing_exports_are_grouped_by_feature() {
+        let missing: Vec<&ExportSpec> = EXPORTS
+            .iter()
+            .filter(|spec| (1, 1) <= spec.since && spec.since <= (1, 2))
+            .collect();
+        let features = missing_features(&missing);
+
+        assert_eq!(features.len(), 2);
+        assert_eq!(
+            features[0].to_string(),
+            "CPU details needs systemapi 1.1 or later (missing GetCpuModelString, \
+             GetCpuVendorString, GetPhysicalCoreCount, GetLogicalCoreCount, \
+             GetCpuBaseFrequencyMHz, GetCpuCoreUsage)"
+        );
+        assert_eq!(features[1].feature, "power status");
+        assert_eq!(features[1].since, "1.2");
+    }
+
+    #[test]
+    fn missing_load_averages_become_none() {
+        let raw = SystemUptimeInfo {
+            uptime_seconds: 3600,
+            boot_time: 1_700_000_000,
+            load_average_1: -1.0,
+            load_average_5: -1.0,
+            load_average_15: -1.0,
+        };
+        let info = uptime_info(&raw);
+
+        assert_eq!(info.uptime_seconds, 3600);
+        assert_eq!(info.boot_time, 1_700_000_000);
+        assert_eq!(info.load_average, None);
+    }
+}
@@ -0,0 +1,149 @@
+// GPU compute workload for the benchmark subsystem, built on wgpu.
+// Compiled only with the `gpu-benchmark` feature; otherwise the command reports it is unavailable.
+
+pub struct GpuScore {
+    pub adapter: String,
+    pub gflops: f64,
+}
+
+#[cfg(not(feature = "gpu-benchmark"))]
+pub fn gpu_workload() -> Result<GpuScore, String> {
+    Err(
+        "GPU benchmark support was not compiled in (build with the `gpu-benchmark` feature)"
+            .to_string(),
+    )
+}
+
+#[cfg(feature = "gpu-benchmark")]
+pub use workload::gpu_workload;
+
+#[cfg(feature = "gpu-benchmark")]
+mod workload {
+    use super::GpuScore;
+    use std::time::Instant;
+    use wgpu::util::DeviceExt;
+
+    const ELEMENTS: u32 = 1024 * 1024;
+    const WORKGROUP_SIZE: u32 = 64;
+    const DISPATCHES: u32 = 16;
+    // Must match the loop count in the shader; each iteration is one FMA (2 flops)
+    const ITERATIONS: u32 = 1024;
+
+    const SHADER: &str = "
+        @group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+        @compute @workgroup_size(64)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+            let i = id.x;
+            if (i >= arrayLength(&data)) {
+                return;
+            }
+            var x = data[i];
+            for (var n = 0u; n < 1024u; n = n + 1u) {
+                x = fma(x, 0.999999, 0.000001);
+            }
+            data[i] = x;
+        }";
+
+    fn expected(mut x: f32) -> f32 {
+        for _ in 0..ITERATIONS * DISPATCHES {
+            x = x.mul_add(0.999999, 0.000001);
+        }
+        x
+    }
+
+    pub fn gpu_workload() -> Result<GpuScore, String> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .map_err(|e| format!("No GPU adapter available: {}", e))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&Default::default()))
+            .map_err(|e| format!("Failed to open GPU device: {}", e))?;
+
+        let input: Vec<f32> = (0..ELEMENTS).map(|i| (i % 1000) as f32 / 1000.0).collect();
+        let bytes: Vec<u8> = input.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let storage = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("benchmark-data"),
+            contents: &bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("benchmark-readback"),
+            size: bytes.len() as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("benchmark-shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("benchmark-pipeline"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("benchmark-bindings"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: storage.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            for _ in 0..DISPATCHES {
+                pass.dispatch_workgroups(ELEMENTS / WORKGROUP_SIZE, 1, 1);
+            }
+        }
+        encoder.copy_buffer_to_buffer(&storage, 0, &readback, 0, bytes.len() as u64);
+
+        let start = Instant::now();
+        queue.submit([encoder.finish()]);
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|e| e.to_string())?;
+        let elapsed = start.elapsed();
+
+        readback.map_async(wgpu::MapMode::Read, .., |_| {});
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|e| e.to_string())?;
+        let output: Vec<f32> = readback
+            .slice(..)
+            .get_mapped_range()
+            .map_err(|e| e.to_string())?
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        readback.unmap();
+
+        // Spot-check results against the CPU so a broken driver can't report a fast score
+        for index in [0usize, 1, 999, ELEMENTS as usize - 1] {
+            if (output[index] - expected(input[index])).abs() > 1e-3 {
+                return Err(format!(
+                    "GPU produced an incorrect result at element {}",
+                    index
+                ));
+            }
+        }
+
+        let flops = ELEMENTS as f64 * ITERATIONS as f64 * DISPATCHES as f64 * 2.0;
+        let info = adapter.get_info();
+        Ok(GpuScore {
+            adapter: format!("{} ({:?})", info.name, info.backend),
+            gflops: flops / elapsed.as_secs_f64() / 1e9,
+        })
+    }
+}
@@ -0,0 +1,285 @@
+// Graphics adapters: name, vendor, dedicated memory and driver version for each GPU.
+// Windows reads Win32_VideoController (with the 64-bit memory size from the driver's registry
+// key, since AdapterRAM stops at 4 GiB), Linux the DRM devices in sysfs plus `lspci` for names
+// and `nvidia-smi` for NVIDIA memory, and macOS `system_profiler`. When that finds nothing and
+// the `gpu-benchmark` feature is compiled in, the adapters wgpu can see are listed instead.
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+const DRM_CLASS_DIR: &str = "/sys/class/drm";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuInfo {
+    pub name: String,
+    pub vendor: String,
+    // Dedicated video memory; None for integrated GPUs sharing system memory, or when unknown
+    pub vram_bytes: Option<u64>,
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+}
+
+fn pci_vendor(id: u32) -> Option<&'static str> {
+    Some(match id {
+        0x10DE => "NVIDIA",
+        0x1002 => "AMD",
+        0x8086 => "Intel",
+        0x106B => "Apple",
+        0x5143 => "Qualcomm",
+        0x13B5 => "ARM",
+        0x1414 => "Microsoft",
+        0x15AD => "VMware",
+        0x1AF4 => "Red Hat",
+        _ => return None,
+    })
+}
+
+fn vendor_name(id: u32) -> String {
+    pci_vendor(id)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("0x{:04x}", id))
+}
+
+fn windows_gpus() -> Result<Vec<GpuInfo>, String> {
+    let script = "$keys = @(Get-ItemProperty 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Class\\{4d36e968-e325-11ce-bfc1-08002be10318}\\0*' -ErrorAction SilentlyContinue); \
+                  ConvertTo-Json -Compress -InputObject @(Get-CimInstance Win32_VideoController | ForEach-Object { \
+                  $name = $_.Name; \
+                  [pscustomobject]@{ Name = $name; Vendor = $_.AdapterCompatibility; \
+                  AdapterRam = $_.AdapterRAM; DriverVersion = $_.DriverVersion; \
+                  Driver = $_.InstalledDisplayDrivers; \
+                  MemorySize = ($keys | Where-Object DriverDesc -eq $name | Select-Object -First 1).'HardwareInformation.qwMemorySize' } })";
+    let output = run_text("powershell", &["-NoProfile", "-Command", script])?;
+    let records: Vec<serde_json::Value> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+
+    Ok(records
+        .iter()
+        .map(|record| {
+            let text = |key: &str| {
+                record[key]
+                    .as_str()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            GpuInfo {
+                name: text("Name").unwrap_or_default(),
+                vendor: text("Vendor").unwrap_or_default(),
+                vram_bytes: record["MemorySize"]
+                    .as_u64()
+                    .or_else(|| record["AdapterRam"].as_u64())
+                    .filter(|&bytes| bytes > 0),
+                // A comma-separated list of driver files; the first is the user-mode driver
+                driver: text("Driver")
+                    .and_then(|drivers| drivers.split(',').next().map(str::to_string)),
+                driver_version: text("DriverVersion"),
+            }
+        })
+        .collect())
+}
+
+// PCI bus ID without the domain, e.g. `01:00.0`; sysfs and nvidia-smi pad the domain differently
+fn bus_id(slot: &str) -> &str {
+    slot.split_once(':').map_or(slot, |(_, rest)| rest)
+}
+
+// (bus ID, memory in bytes, driver version) for each NVIDIA GPU
+fn nvidia_smi() -> Vec<(String, u64, String)> {
+    let Ok(output) = run_text(
+        "nvidia-smi",
+        &[
+            "--query-gpu=pci.bus_id,memory.total,driver_version",
+            "--format=csv,noheader,nounits",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [bus, memory_mib, version] = fields[..] else {
+                return None;
+            };
+            Some((
+                bus_id(bus).to_lowercase(),
+                memory_mib.parse::<u64>().ok()? * 1024 * 1024,
+                version.to_string(),
+            ))
+        })
+        .collect()
+}
+
+// `lspci -mm -s <slot>` quotes each field: slot "class" "vendor" "device" ...
+fn lspci_device(slot: &str) -> Option<String> {
+    let output = run_text("lspci", &["-mm", "-s", slot]).ok()?;
+    let fields: Vec<&str> = output
+        .lines()
+        .next()?
+        .split('"')
+        .filter(|field| !field.trim().is_empty())
+        .collect();
+    fields.get(3).map(|device| device.to_string())
+}
+
+fn linux_gpus() -> Result<Vec<GpuInfo>, String> {
+    let entries = std::fs::read_dir(DRM_CLASS_DIR).map_err(|e| e.to_string())?;
+    let nvidia = nvidia_smi();
+    let mut cards: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        // `card0` is the device; `card0-HDMI-A-1` and the like are its connectors
+        .filter(|name| name.starts_with("card") && !name.contains('-'))
+        .collect();
+    cards.sort();
+
+    let mut gpus = Vec::new();
+    for card in cards {
+        let device = Path::new(DRM_CLASS_DIR).join(&card).join("device");
+        let read = |file: &str| {
+            std::fs::read_to_string(device.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let Some(vendor) =
+            read("vendor").and_then(|id| u32::from_str_radix(id.trim_start_matches("0x"), 16).ok())
+        else {
+            continue;
+        };
+        let slot = std::fs::canonicalize(&device)
+            .ok()
+            .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        let driver = std::fs::read_link(device.join("driver"))
+            .ok()
+            .and_then(|link| Some(link.file_name()?.to_string_lossy().into_owned()));
+        let smi = nvidia
+            .iter()
+            .find(|(bus, _, _)| *bus == bus_id(&slot).to_lowercase());
+
+        gpus.push(GpuInfo {
+            name: lspci_device(&slot).unwrap_or_else(|| {
+                format!(
+                    "{} {}",
+                    vendor_name(vendor),
+                    read("device").unwrap_or_default()
+                )
+            }),
+            vendor: vendor_name(vendor),
+            // amdgpu reports its VRAM; NVIDIA's driver only through nvidia-smi
+            vram_bytes: read("mem_info_vram_total")
+                .and_then(|bytes| bytes.parse().ok())
+                .or(smi.map(|&(_, bytes, _)| bytes))
+                .filter(|&bytes| bytes > 0),
+            driver_version: smi.map(|(_, _, version)| version.clone()).or_else(|| {
+                let module = driver.as_ref()?;
+                std::fs::read_to_string(format!("/sys/module/{}/version", module))
+                    .ok()
+                    .map(|version| version.trim().to_string())
+            }),
+            driver,
+        });
+    }
+    Ok(gpus)
+}
+
+// `system_profiler` sizes look like `8 GB` or `1536 MB`
+fn profiler_bytes(size: &str) -> Option<u64> {
+    let mut parts = size.split_whitespace();
+    let value: u64 = parts.next()?.parse().ok()?;
+    let unit = match parts.next()? {
+        "GB" => 1024 * 1024 * 1024,
+        "MB" => 1024 * 1024,
+        _ => return None,
+    };
+    Some(value * unit)
+}
+
+fn macos_gpus() -> Result<Vec<GpuInfo>, String> {
+    let output = run_text("system_profiler", &["-json", "SPDisplaysDataType"])?;
+    let report: serde_json::Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+    let text = |value: &serde_json::Value, key: &str| value[key].as_str().map(str::to_string);
+
+    Ok(report["SPDisplaysDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|gpu| {
+            // Vendors look like `sppci_vendor_Apple` or `NVIDIA (0x10de)`
+            let vendor = text(gpu, "spdisplays_vendor")
+                .map(|vendor| {
+                    let vendor = vendor.trim_start_matches("sppci_vendor_");
+                    vendor.split(" (").next().unwrap_or(vendor).to_string()
+                })
+                .unwrap_or_default();
+            GpuInfo {
+                name: text(gpu, "sppci_model").unwrap_or_default(),
+                vendor,
+                // Integrated GPUs report `spdisplays_vram_shared` instead
+                vram_bytes: text(gpu, "spdisplays_vram").and_then(|vram| profiler_bytes(&vram)),
+                driver: None,
+                driver_version: None,
+            }
+        })
+        .collect())
+}
+
+#[cfg(feature = "gpu-benchmark")]
+fn wgpu_gpus() -> Vec<GpuInfo> {
+    let instance = wgpu::Instance::default();
+    let mut gpus: Vec<GpuInfo> =
+        pollster::block_on(instance.enumerate_adapters(wgpu::Backends::all()))
+            .iter()
+            .map(|adapter| adapter.get_info())
+            // Software rasterizers aren't GPUs
+            .filter(|info| info.device_type != wgpu::DeviceType::Cpu)
+            .map(|info| GpuInfo {
+                name: info.name,
+                vendor: vendor_name(info.vendor),
+                vram_bytes: None,
+                driver: Some(info.driver).filter(|driver| !driver.is_empty()),
+                driver_version: Some(info.driver_info).filter(|version| !version.is_empty()),
+            })
+            .collect();
+    // Each adapter is listed once per backend that can drive it
+    gpus.sort_by(|a, b| a.name.cmp(&b.name));
+    gpus.dedup_by(|a, b| a.name == b.name);
+    gpus
+}
+
+#[cfg(not(feature = "gpu-benchmark"))]
+fn wgpu_gpus() -> Vec<GpuInfo> {
+    Vec::new()
+}
+
+fn read_gpus() -> Result<Vec<GpuInfo>, String> {
+    let gpus = if cfg!(target_os = "windows") {
+        windows_gpus()
+    } else if cfg!(target_os = "macos") {
+        macos_gpus()
+    } else {
+        linux_gpus()
+    };
+    match gpus {
+        Ok(gpus) if !gpus.is_empty() => Ok(gpus),
+        Ok(_) => Ok(wgpu_gpus()),
+        Err(e) => {
+            let fallback = wgpu_gpus();
+            if fallback.is_empty() {
+                return Err(e);
+            }
+            Ok(fallback)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_gpu_info() -> Result<Vec<GpuInfo>, CommandError> {
+    let gpus = tauri::async_runtime::spawn_blocking(read_gpus)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(gpus)
+}
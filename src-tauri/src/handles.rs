@@ -0,0 +1,140 @@
+// Open file descriptor (Unix) or handle (Windows) counts, system-wide and for the processes
+// holding the most, with their limits. The system total (Linux) and the app's own count are
+// also sampled into history as `handles.system` and `handles.app`, so a steady climb (a leak)
+// can be charted, and alerted on with the `systemHandles` and `appHandles` rule metrics.
+
+use serde::Serialize;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::AppHandle;
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::exporter;
+
+const DEFAULT_TOP_PROCESSES: usize = 10;
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+pub const SYSTEM_METRIC: &str = "handles.system";
+pub const APP_METRIC: &str = "handles.app";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessHandles {
+    pub pid: u32,
+    pub name: String,
+    pub open: u64,
+    // Per-process limit (`ulimit -n` soft limit on Unix)
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandleStats {
+    // Allocated system-wide; on platforms without a kernel counter this is the sum over
+    // the processes we can inspect
+    pub system_open: u64,
+    // Kernel-wide maximum (fs.file-max), Linux only
+    pub system_limit: Option<u64>,
+    // Default per-process limit
+    pub process_limit: Option<u64>,
+    pub app: Option<ProcessHandles>,
+    pub top_processes: Vec<ProcessHandles>,
+}
+
+// (allocated, max) from /proc/sys/fs/file-nr
+fn kernel_file_counts() -> Option<(u64, u64)> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let text = std::fs::read_to_string("/proc/sys/fs/file-nr").ok()?;
+    let mut fields = text
+        .split_whitespace()
+        .map(|field| field.parse::<u64>().ok());
+    let allocated = fields.next()??;
+    let _unused = fields.next()??;
+    let max = fields.next()??;
+    Some((allocated, max))
+}
+
+fn process_handles(system: &System) -> Vec<ProcessHandles> {
+    system
+        .processes()
+        .values()
+        .filter_map(|process| {
+            Some(ProcessHandles {
+                pid: process.pid().as_u32(),
+                name: process.name().to_string_lossy().into_owned(),
+                // None for processes we aren't allowed to inspect
+                open: process.open_files()? as u64,
+                limit: process.open_files_limit().map(|limit| limit as u64),
+            })
+        })
+        .collect()
+}
+
+fn collect(top: usize) -> HandleStats {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    let mut processes = process_handles(&system);
+
+    let own_pid = std::process::id();
+    let app = processes
+        .iter()
+        .position(|process| process.pid == own_pid)
+        .map(|index| processes.swap_remove(index));
+    let process_sum = processes.iter().map(|process| process.open).sum::<u64>()
+        + app.as_ref().map_or(0, |app| app.open);
+    let kernel = kernel_file_counts();
+
+    processes.sort_unstable_by_key(|process| std::cmp::Reverse(process.open));
+    processes.truncate(top);
+
+    HandleStats {
+        system_open: kernel.map_or(process_sum, |(allocated, _)| allocated),
+        system_limit: kernel.map(|(_, max)| max),
+        process_limit: System::open_files_limit().map(|limit| limit as u64),
+        app,
+        top_processes: processes,
+    }
+}
+
+fn app_handles() -> Option<u64> {
+    let mut system = System::new();
+    let pid = Pid::from_u32(std::process::id());
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    Some(system.process(pid)?.open_files()? as u64)
+}
+
+// Background job recording the system-wide and app handle counts
+pub fn start_sampling(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let mut samples = Vec::new();
+        // Summing every process is too expensive to repeat each minute, so the system total is
+        // only sampled where the kernel keeps a counter
+        if let Some((allocated, _)) = kernel_file_counts() {
+            samples.push((SYSTEM_METRIC.to_string(), allocated as f64));
+        }
+        if let Some(open) = app_handles() {
+            samples.push((APP_METRIC.to_string(), open as f64));
+        }
+
+        if !samples.is_empty() {
+            exporter::publish(&app, &samples);
+        }
+        battery_saver::wait(&app, SAMPLE_INTERVAL);
+    });
+}
+
+// Handle counts with the `top` processes holding the most (10 by default)
+#[tauri::command]
+pub async fn get_handle_stats(top: Option<usize>) -> Result<HandleStats, CommandError> {
+    let top = top.unwrap_or(DEFAULT_TOP_PROCESSES);
+    let stats = tauri::async_runtime::spawn_blocking(move || collect(top))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(stats)
+}
@@ -0,0 +1,1012 @@
+use rusqlite::{params, Connection, OptionalExtension, Statement};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::{ConfigState, RetentionPolicy};
+use crate::error::{CommandError, ErrorCode};
+use crate::report::unix_timestamp;
+use crate::stats;
+
+const DATABASE_FILE: &str = "history.db";
+
+// Key in `history_meta` of the raw cutoff of the latest prune: raw samples from before it may
+// have been deleted, later ones are all still there
+const RAW_PRUNED_BEFORE: &str = "rawPrunedBefore";
+
+// Rollup resolutions in seconds; hourly buckets are built from the minute buckets
+pub const MINUTE: i64 = 60;
+pub const HOUR: i64 = 3600;
+
+// How often completed buckets are folded into the rollup table
+const ROLLUP_INTERVAL: Duration = Duration::from_secs(60);
+
+// Upper bounds of the retention policy
+const MAX_RAW_RETENTION_HOURS: u64 = 365 * 24;
+const MAX_ROLLUP_RETENTION_DAYS: u64 = 10 * 365;
+const MAX_PRUNE_INTERVAL_MINUTES: u64 = 7 * 24 * 60;
+
+const SPARKLINE_MAX_POINTS: usize = 500;
+const SPARKLINE_DEFAULT_SECONDS: i64 = HOUR;
+
+// Time-series store for sampled metrics, kept in the app data directory
+pub struct History {
+    path: Option<PathBuf>,
+    conn: Mutex<Connection>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    pub database_path: Option<String>,
+    pub database_bytes: u64,
+    pub raw_samples: u64,
+    pub rollup_rows: u64,
+    pub oldest_sample: Option<i64>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct TimeRange {
+    pub from: i64,
+    pub to: i64,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Aggregation {
+    #[default]
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+    P50,
+    P95,
+    P99,
+}
+
+impl Aggregation {
+    // Percentiles can't be rebuilt from rollups, so they are computed from raw samples only
+    fn percentile(self) -> Option<f64> {
+        match self {
+            Aggregation::P50 => Some(50.0),
+            Aggregation::P95 => Some(95.0),
+            Aggregation::P99 => Some(99.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl GroupBy {
+    fn seconds(self) -> Option<i64> {
+        match self {
+            GroupBy::None => None,
+            GroupBy::Minute => Some(MINUTE),
+            GroupBy::Hour => Some(HOUR),
+            GroupBy::Day => Some(86400),
+        }
+    }
+}
+
+// Which table a query was answered from
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HistorySource {
+    Raw,
+    MinuteRollup,
+    HourRollup,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPoint {
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+// A user note pinned to a moment in history ("installed new driver here")
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    pub id: i64,
+    pub timestamp: i64,
+    pub text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQueryResult {
+    pub metric: String,
+    pub aggregation: Aggregation,
+    pub group_by: GroupBy,
+    pub source: HistorySource,
+    pub points: Vec<HistoryPoint>,
+    // Annotations inside the queried range, for event markers on the chart
+    pub annotations: Vec<Annotation>,
+}
+
+// A fixed-length series for tray and overlay charts: `values[i]` is the mean of the i-th of
+// `values.len()` equal slices of the window, None where the slice has no samples
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sparkline {
+    pub metric: String,
+    pub from: i64,
+    pub to: i64,
+    pub values: Vec<Option<f64>>,
+    // Over `values`, for scaling the chart; None without samples
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub latest: Option<f64>,
+}
+
+// Average raw points into `count` equal slices of `range`
+fn downsample(points: &[HistoryPoint], range: TimeRange, count: usize) -> Vec<Option<f64>> {
+    let width = (range.to - range.from).max(1) as i128;
+    let mut slices: Vec<Vec<f64>> = vec![Vec::new(); count];
+    for point in points {
+        let offset = (point.timestamp - range.from) as i128;
+        let slice = (offset * count as i128 / width).clamp(0, count as i128 - 1) as usize;
+        slices[slice].push(point.value);
+    }
+    slices
+        .iter()
+        .map(|values| stats::summarize(values).map(|summary| summary.avg()))
+        .collect()
+}
+
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    // auto_vacuum only takes effect on a fresh database, before any table exists
+    conn.execute_batch(
+        "PRAGMA auto_vacuum = INCREMENTAL;
+         CREATE TABLE IF NOT EXISTS samples (
+             metric TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             value REAL NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS samples_metric_time ON samples (metric, timestamp);
+         CREATE TABLE IF NOT EXISTS rollups (
+             resolution INTEGER NOT NULL,
+             metric TEXT NOT NULL,
+             bucket INTEGER NOT NULL,
+             min REAL NOT NULL,
+             max REAL NOT NULL,
+             avg REAL NOT NULL,
+             count INTEGER NOT NULL,
+             PRIMARY KEY (resolution, metric, bucket)
+         );
+         CREATE TABLE IF NOT EXISTS annotations (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             timestamp INTEGER NOT NULL,
+             text TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS annotations_time ON annotations (timestamp);
+         CREATE TABLE IF NOT EXISTS process_events (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             name TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             kind TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS file_hashes (
+             path TEXT PRIMARY KEY,
+             hash TEXT,
+             checked_at INTEGER NOT NULL,
+             changed_at INTEGER
+         );
+         CREATE TABLE IF NOT EXISTS integrity_events (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             path TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             previous_hash TEXT,
+             current_hash TEXT
+         );
+         CREATE TABLE IF NOT EXISTS export_history (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             kind TEXT NOT NULL,
+             created_at INTEGER NOT NULL,
+             destination TEXT,
+             summary TEXT NOT NULL,
+             content TEXT
+         );
+         CREATE TABLE IF NOT EXISTS benchmark_runs (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             kind TEXT NOT NULL,
+             started_at INTEGER NOT NULL,
+             duration_ms INTEGER NOT NULL,
+             score REAL NOT NULL,
+             unit TEXT NOT NULL,
+             hardware TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS inventory (
+             category TEXT NOT NULL,
+             key TEXT NOT NULL,
+             description TEXT NOT NULL,
+             first_seen INTEGER NOT NULL,
+             PRIMARY KEY (category, key)
+         );
+         CREATE TABLE IF NOT EXISTS inventory_changes (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             timestamp INTEGER NOT NULL,
+             category TEXT NOT NULL,
+             key TEXT NOT NULL,
+             kind TEXT NOT NULL,
+             previous TEXT,
+             current TEXT
+         );
+         CREATE TABLE IF NOT EXISTS battery_health (
+             battery TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             full_charge_capacity INTEGER NOT NULL,
+             design_capacity INTEGER NOT NULL,
+             cycle_count INTEGER
+         );
+         CREATE INDEX IF NOT EXISTS battery_health_time ON battery_health (timestamp);
+         CREATE TABLE IF NOT EXISTS ssd_wear (
+             drive TEXT NOT NULL,
+             timestamp INTEGER NOT NULL,
+             percentage_used INTEGER,
+             bytes_written INTEGER
+         );
+         CREATE INDEX IF NOT EXISTS ssd_wear_time ON ssd_wear (timestamp);
+         CREATE TABLE IF NOT EXISTS history_meta (
+             key TEXT PRIMARY KEY,
+             value INTEGER NOT NULL
+         );",
+    )
+}
+
+fn write_rollup(
+    insert: &mut Statement,
+    metric: &str,
+    bucket: i64,
+    values: &[f64],
+) -> rusqlite::Result<usize> {
+    let Some(summary) = stats::summarize(values) else {
+        return Ok(0);
+    };
+    insert.execute(params![
+        MINUTE,
+        metric,
+        bucket,
+        summary.min,
+        summary.max,
+        summary.avg(),
+        summary.count as i64
+    ])
+}
+
+fn annotations_in(conn: &Connection, range: TimeRange) -> rusqlite::Result<Vec<Annotation>> {
+    let mut statement = conn.prepare_cached(
+        "SELECT id, timestamp, text FROM annotations
+         WHERE timestamp >= ?1 AND timestamp < ?2
+         ORDER BY timestamp",
+    )?;
+    let annotations = statement
+        .query_map(params![range.from, range.to], |row| {
+            Ok(Annotation {
+                id: row.get(0)?,
+                timestamp: row.get(1)?,
+                text: row.get(2)?,
+            })
+        })?
+        .collect();
+    annotations
+}
+
+fn rollup_minutes(conn: &Connection, from: i64, to: i64) -> rusqlite::Result<usize> {
+    let transaction = conn.unchecked_transaction()?;
+    let mut select = transaction.prepare(
+        "SELECT metric, timestamp, value FROM samples
+         WHERE timestamp >= ?1 AND timestamp < ?2
+         ORDER BY metric, timestamp",
+    )?;
+    let mut insert = transaction.prepare(
+        "INSERT OR REPLACE INTO rollups (resolution, metric, bucket, min, max, avg, count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+
+    let mut written = 0;
+    let mut current: Option<(String, i64)> = None;
+    let mut values = Vec::new();
+    let mut rows = select.query(params![from, to])?;
+    while let Some(row) = rows.next()? {
+        let metric: String = row.get(0)?;
+        let timestamp: i64 = row.get(1)?;
+        let key = (metric, timestamp - timestamp.rem_euclid(MINUTE));
+
+        if current.as_ref() != Some(&key) {
+            if let Some((metric, bucket)) = current.replace(key) {
+                written += write_rollup(&mut insert, &metric, bucket, &values)?;
+                values.clear();
+            }
+        }
+        values.push(row.get(2)?);
+    }
+    if let Some((metric, bucket)) = current {
+        written += write_rollup(&mut insert, &metric, bucket, &values)?;
+    }
+
+    drop(rows);
+    drop(select);
+    drop(insert);
+    transaction.commit()?;
+    Ok(written)
+}
+
+// `count` periods of `seconds` before `now`; a config edited by hand can hold any value, so
+// this clamps instead of overflowing
+fn cutoff(now: i64, count: u64, seconds: u64) -> i64 {
+    now.saturating_sub(i64::try_from(count.saturating_mul(seconds)).unwrap_or(i64::MAX))
+}
+
+// Close out one group of raw values as a percentile point, leaving the buffer empty
+fn percentile_point(
+    timestamp: Option<i64>,
+    values: &mut Vec<f64>,
+    percent: f64,
+) -> Option<HistoryPoint> {
+    let value = stats::percentile(values, percent);
+    values.clear();
+    Some(HistoryPoint {
+        timestamp: timestamp?,
+        value: value?,
+    })
+}
+
+impl History {
+    // Open (or create) the history database; falls back to an in-memory store on failure
+    pub fn open(app: &AppHandle) -> Self {
+        let path = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(DATABASE_FILE));
+
+        let opened = path
+            .as_ref()
+            .ok_or("No app data directory".to_string())
+            .and_then(|path| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+                }
+                let conn = Connection::open(path).map_err(|e| e.to_string())?;
+                init_schema(&conn).map_err(|e| e.to_string())?;
+                Ok(conn)
+            });
+
+        match opened {
+            Ok(conn) => History {
+                path,
+                conn: Mutex::new(conn),
+            },
+            Err(e) => {
+                eprintln!(
+                    "⚠ Warning: History database unavailable ({}), keeping history in memory",
+                    e
+                );
+                History::in_memory()
+            }
+        }
+    }
+
+    fn in_memory() -> Self {
+        let conn = Connection::open_in_memory().expect("in-memory SQLite database");
+        init_schema(&conn).expect("history schema");
+        History {
+            path: None,
+            conn: Mutex::new(conn),
+        }
+    }
+
+    pub fn conn(&self) -> Result<MutexGuard<'_, Connection>, String> {
+        self.conn
+            .lock()
+            .map_err(|_| "History lock poisoned".to_string())
+    }
+
+    // Store one sample per metric taken at `timestamp`
+    pub fn record(&self, timestamp: i64, samples: &[(String, f64)]) -> Result<(), String> {
+        let conn = self.conn()?;
+        let transaction = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        {
+            let mut insert = transaction
+                .prepare_cached(
+                    "INSERT INTO samples (metric, timestamp, value) VALUES (?1, ?2, ?3)",
+                )
+                .map_err(|e| e.to_string())?;
+            for (metric, value) in samples {
+                insert
+                    .execute(params![metric, timestamp, value])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    // Distinct metric names starting with `prefix`, from raw samples and rollups
+    pub fn metrics_with_prefix(&self, prefix: &str) -> Result<Vec<String>, String> {
+        let conn = self.conn()?;
+        let mut statement = conn
+            .prepare(
+                "SELECT metric FROM samples WHERE substr(metric, 1, length(?1)) = ?1
+                 UNION
+                 SELECT metric FROM rollups WHERE substr(metric, 1, length(?1)) = ?1
+                 ORDER BY metric",
+            )
+            .map_err(|e| e.to_string())?;
+        statement
+            .query_map(params![prefix], |row| row.get(0))
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<String>>>())
+            .map_err(|e| e.to_string())
+    }
+
+    // Fold completed minute buckets from raw samples, then hour buckets from minute rollups.
+    // The most recent existing bucket is recomputed so late samples are picked up.
+    pub fn rollup(&self) -> Result<usize, String> {
+        let now = unix_timestamp() as i64;
+        let conn = self.conn()?;
+        let mut written = 0;
+
+        let since = |resolution: i64| -> rusqlite::Result<i64> {
+            conn.query_row(
+                "SELECT COALESCE(MAX(bucket), 0) FROM rollups WHERE resolution = ?1",
+                params![resolution],
+                |row| row.get(0),
+            )
+        };
+
+        // Minute buckets are aggregated in Rust with the SIMD kernels rather than in SQLite
+        written += rollup_minutes(
+            &conn,
+            since(MINUTE).map_err(|e| e.to_string())?,
+            now - now % MINUTE,
+        )
+        .map_err(|e| e.to_string())?;
+
+        written += conn
+            .execute(
+                "INSERT OR REPLACE INTO rollups (resolution, metric, bucket, min, max, avg, count)
+                 SELECT ?1, metric, (bucket / ?1) * ?1 AS hour,
+                        MIN(min), MAX(max), SUM(avg * count) / SUM(count), SUM(count)
+                 FROM rollups
+                 WHERE resolution = ?2 AND bucket >= ?3 AND bucket < ?4
+                 GROUP BY metric, hour",
+                params![
+                    HOUR,
+                    MINUTE,
+                    since(HOUR).map_err(|e| e.to_string())?,
+                    now - now % HOUR
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+
+        Ok(written)
+    }
+
+    // Delete everything older than the retention policy allows; returns the number of rows removed
+    pub fn prune(&self, policy: &RetentionPolicy) -> Result<usize, String> {
+        let now = unix_timestamp() as i64;
+        let raw_cutoff = cutoff(now, policy.raw_retention_hours, 3600);
+        let rollup_cutoff = cutoff(now, policy.rollup_retention_days, 86400);
+
+        let conn = self.conn()?;
+        let mut removed = conn
+            .execute(
+                "DELETE FROM samples WHERE timestamp < ?1",
+                params![raw_cutoff],
+            )
+            .map_err(|e| e.to_string())?;
+        removed += conn
+            .execute(
+                "DELETE FROM rollups WHERE bucket < ?1",
+                params![rollup_cutoff],
+            )
+            .map_err(|e| e.to_string())?;
+        // Kept at its highest value: a longer retention doesn't bring deleted samples back
+        conn.execute(
+            "INSERT INTO history_meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT (key) DO UPDATE SET value = MAX(value, excluded.value)",
+            params![RAW_PRUNED_BEFORE, raw_cutoff],
+        )
+        .map_err(|e| e.to_string())?;
+
+        if removed > 0 {
+            conn.execute_batch("PRAGMA incremental_vacuum;")
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(removed)
+    }
+
+    // Aggregate a metric over a time range, reading raw samples while every one in the range is
+    // still retained (nothing was pruned after `from`), otherwise the minute or hour rollups
+    pub fn query(
+        &self,
+        metric: &str,
+        range: TimeRange,
+        aggregation: Aggregation,
+        group_by: GroupBy,
+    ) -> Result<HistoryQueryResult, String> {
+        if range.from > range.to {
+            return Err("Invalid range: `from` is after `to`".to_string());
+        }
+
+        let conn = self.conn()?;
+        let pruned_before: Option<i64> = conn
+            .query_row(
+                "SELECT value FROM history_meta WHERE key = ?1",
+                params![RAW_PRUNED_BEFORE],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let raw_covers_range = pruned_before.is_none_or(|cutoff| cutoff <= range.from);
+
+        let percentile = aggregation.percentile();
+        if percentile.is_some() && !raw_covers_range {
+            return Err(
+                "Percentiles are only available while raw samples for the range are retained"
+                    .to_string(),
+            );
+        }
+
+        let source = match group_by {
+            _ if percentile.is_some() || raw_covers_range => HistorySource::Raw,
+            GroupBy::Hour | GroupBy::Day => HistorySource::HourRollup,
+            _ => HistorySource::MinuteRollup,
+        };
+
+        let (table, time, filter) = match source {
+            HistorySource::Raw => ("samples", "timestamp", String::new()),
+            HistorySource::MinuteRollup => {
+                ("rollups", "bucket", format!("AND resolution = {}", MINUTE))
+            }
+            HistorySource::HourRollup => {
+                ("rollups", "bucket", format!("AND resolution = {}", HOUR))
+            }
+        };
+        let value = match (source, aggregation) {
+            (HistorySource::Raw, Aggregation::Avg) => "AVG(value)",
+            (HistorySource::Raw, Aggregation::Min) => "MIN(value)",
+            (HistorySource::Raw, Aggregation::Max) => "MAX(value)",
+            (HistorySource::Raw, Aggregation::Sum) => "SUM(value)",
+            (HistorySource::Raw, Aggregation::Count) => "COUNT(*)",
+            (_, Aggregation::P50 | Aggregation::P95 | Aggregation::P99) => "value",
+            (_, Aggregation::Avg) => "SUM(avg * count) / SUM(count)",
+            (_, Aggregation::Min) => "MIN(min)",
+            (_, Aggregation::Max) => "MAX(max)",
+            (_, Aggregation::Sum) => "SUM(avg * count)",
+            (_, Aggregation::Count) => "SUM(count)",
+        };
+        let bucket = match group_by.seconds() {
+            Some(seconds) => format!("({time} / {seconds}) * {seconds}"),
+            None => "?2".to_string(),
+        };
+
+        let points = match percentile {
+            Some(percent) => {
+                let sql = format!(
+                    "SELECT {bucket} AS t, value FROM samples
+                     WHERE metric = ?1 AND timestamp >= ?2 AND timestamp < ?3
+                     ORDER BY t"
+                );
+                let mut statement = conn.prepare(&sql).map_err(|e| e.to_string())?;
+                let mut rows = statement
+                    .query(params![metric, range.from, range.to])
+                    .map_err(|e| e.to_string())?;
+
+                let mut points = Vec::new();
+                let mut bucket_values: Vec<f64> = Vec::new();
+                let mut current = None;
+                while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+                    let timestamp: i64 = row.get(0).map_err(|e| e.to_string())?;
+                    if current.is_some_and(|t| t != timestamp) {
+                        points.extend(percentile_point(current, &mut bucket_values, percent));
+                    }
+                    current = Some(timestamp);
+                    bucket_values.push(row.get(1).map_err(|e| e.to_string())?);
+                }
+                points.extend(percentile_point(current, &mut bucket_values, percent));
+                points
+            }
+            None => {
+                let sql = format!(
+                    "SELECT {bucket} AS t, CAST({value} AS REAL) FROM {table}
+                     WHERE metric = ?1 AND {time} >= ?2 AND {time} < ?3 {filter}
+                     GROUP BY t ORDER BY t"
+                );
+                let mut statement = conn.prepare(&sql).map_err(|e| e.to_string())?;
+                statement
+                    .query_map(params![metric, range.from, range.to], |row| {
+                        Ok(HistoryPoint {
+                            timestamp: row.get(0)?,
+                            value: row.get(1)?,
+                        })
+                    })
+                    .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+                    .map_err(|e| e.to_string())?
+            }
+        };
+
+        let annotations = annotations_in(&conn, range).map_err(|e| e.to_string())?;
+
+        Ok(HistoryQueryResult {
+            metric: metric.to_string(),
+            aggregation,
+            group_by,
+            source,
+            points,
+            annotations,
+        })
+    }
+
+    // Raw samples of a metric inside `range`, oldest first; empty once they are pruned
+    pub fn points(&self, metric: &str, range: TimeRange) -> Result<Vec<HistoryPoint>, String> {
+        let conn = self.conn()?;
+        let mut statement = conn
+            .prepare_cached(
+                "SELECT timestamp, value FROM samples
+                 WHERE metric = ?1 AND timestamp >= ?2 AND timestamp < ?3
+                 ORDER BY timestamp",
+            )
+            .map_err(|e| e.to_string())?;
+        statement
+            .query_map(params![metric, range.from, range.to], |row| {
+                Ok(HistoryPoint {
+                    timestamp: row.get(0)?,
+                    value: row.get(1)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| e.to_string())
+    }
+
+    fn database_bytes(&self) -> u64 {
+        let Some(path) = &self.path else {
+            return 0;
+        };
+
+        // SQLite keeps recent writes in side files until checkpointed
+        ["", "-wal", "-shm", "-journal"]
+            .iter()
+            .filter_map(|suffix| {
+                let mut file = path.clone().into_os_string();
+                file.push(suffix);
+                std::fs::metadata(file).ok()
+            })
+            .map(|meta| meta.len())
+            .sum()
+    }
+}
+
+// Background job rolling up samples every minute and applying the retention policy
+// from the config at its configured interval
+pub fn start_maintenance(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_prune: Option<Instant> = None;
+
+        loop {
+            let history = app.state::<History>();
+            if let Err(e) = history.rollup() {
+                eprintln!("History rollup failed: {}", e);
+            }
+
+            let policy = app
+                .state::<ConfigState>()
+                .get()
+                .map(|config| config.retention.clone())
+                .unwrap_or_default();
+            let prune_interval =
+                Duration::from_secs(policy.prune_interval_minutes.max(1).saturating_mul(60));
+
+            if last_prune.is_none_or(|at| at.elapsed() >= prune_interval) {
+                match history.prune(&policy) {
+                    Ok(0) => {}
+                    Ok(removed) => println!("✓ Pruned {} history rows", removed),
+                    Err(e) => eprintln!("History pruning failed: {}", e),
+                }
+                last_prune = Some(Instant::now());
+            }
+
+            std::thread::sleep(ROLLUP_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_retention_policy(config: State<ConfigState>) -> Result<RetentionPolicy, CommandError> {
+    Ok(config.get()?.retention.clone())
+}
+
+pub fn check_retention(policy: &RetentionPolicy) -> Result<(), CommandError> {
+    if !(1..=MAX_RAW_RETENTION_HOURS).contains(&policy.raw_retention_hours)
+        || !(1..=MAX_ROLLUP_RETENTION_DAYS).contains(&policy.rollup_retention_days)
+        || policy.prune_interval_minutes > MAX_PRUNE_INTERVAL_MINUTES
+    {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "history.invalidRetention")
+                .with("maxHours", MAX_RAW_RETENTION_HOURS)
+                .with("maxDays", MAX_ROLLUP_RETENTION_DAYS)
+                .with("maxMinutes", MAX_PRUNE_INTERVAL_MINUTES),
+        );
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_retention_policy(
+    policy: RetentionPolicy,
+    config: State<ConfigState>,
+    history: State<History>,
+) -> Result<RetentionPolicy, CommandError> {
+    check_retention(&policy)?;
+
+    let updated = config.update(|config| config.retention = policy.clone())?;
+    history.prune(&updated.retention)?;
+    Ok(updated.retention)
+}
+
+#[tauri::command]
+pub fn get_storage_usage(history: State<History>) -> Result<StorageUsage, CommandError> {
+    let conn = history.conn()?;
+    let (raw_samples, oldest_sample) = conn
+        .query_row("SELECT COUNT(*), MIN(timestamp) FROM samples", [], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Option<i64>>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let rollup_rows: i64 = conn
+        .query_row("SELECT COUNT(*) FROM rollups", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    drop(conn);
+
+    Ok(StorageUsage {
+        database_path: history.path.as_ref().map(|p| p.display().to_string()),
+        database_bytes: history.database_bytes(),
+        raw_samples: raw_samples as u64,
+        rollup_rows: rollup_rows as u64,
+        oldest_sample,
+    })
+}
+
+#[tauri::command]
+pub fn query_history(
+    metric: String,
+    range: TimeRange,
+    aggregation: Option<Aggregation>,
+    group_by: Option<GroupBy>,
+    history: State<History>,
+) -> Result<HistoryQueryResult, CommandError> {
+    if range.from > range.to {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "history.invalidRange",
+        ));
+    }
+    Ok(history.query(
+        &metric,
+        range,
+        aggregation.unwrap_or_default(),
+        group_by.unwrap_or_default(),
+    )?)
+}
+
+// The last `seconds` (an hour by default) of a metric in `points` values, from raw samples
+#[tauri::command]
+pub fn get_sparkline(
+    metric: String,
+    points: usize,
+    seconds: Option<i64>,
+    history: State<History>,
+) -> Result<Sparkline, CommandError> {
+    if !(1..=SPARKLINE_MAX_POINTS).contains(&points) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "history.invalidPoints")
+                .with("max", SPARKLINE_MAX_POINTS),
+        );
+    }
+    let seconds = seconds.unwrap_or(SPARKLINE_DEFAULT_SECONDS);
+    if seconds <= 0 {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "history.invalidRange",
+        ));
+    }
+
+    // `to` is exclusive, so include the current second
+    let to = unix_timestamp() as i64 + 1;
+    let range = TimeRange {
+        from: to - seconds,
+        to,
+    };
+    let raw = history.points(&metric, range)?;
+    let values = downsample(&raw, range, points);
+    let drawn: Vec<f64> = values.iter().flatten().copied().collect();
+    let summary = stats::summarize(&drawn);
+
+    Ok(Sparkline {
+        metric,
+        from: range.from,
+        to: range.to,
+        values,
+        min: summary.map(|summary| summary.min),
+        max: summary.map(|summary| summary.max),
+        latest: raw.last().map(|point| point.value),
+    })
+}
+
+#[tauri::command]
+pub fn add_annotation(
+    timestamp: i64,
+    text: String,
+    history: State<History>,
+) -> Result<Annotation, CommandError> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "history.emptyAnnotation",
+        ));
+    }
+
+    let conn = history.conn()?;
+    conn.execute(
+        "INSERT INTO annotations (timestamp, text) VALUES (?1, ?2)",
+        params![timestamp, text],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Annotation {
+        id: conn.last_insert_rowid(),
+        timestamp,
+        text,
+    })
+}
+
+#[tauri::command]
+pub fn delete_annotation(id: i64, history: State<History>) -> Result<(), CommandError> {
+    history
+        .conn()?
+        .execute("DELETE FROM annotations WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> i64 {
+        unix_timestamp() as i64
+    }
+
+    fn last_day() -> TimeRange {
+        TimeRange {
+            from: now() - 86400,
+            to: now() + 1,
+        }
+    }
+
+    fn record(history: &History, samples: &[(i64, f64)]) {
+        for &(timestamp, value) in samples {
+            history
+                .record(timestamp, &[("cpu".to_string(), value)])
+                .unwrap();
+        }
+    }
+
+    fn values(result: &HistoryQueryResult) -> Vec<f64> {
+        result.points.iter().map(|point| point.value).collect()
+    }
+
+    #[test]
+    fn range_before_first_sample_reads_raw_samples() {
+        let history = History::in_memory();
+        let now = now();
+        record(&history, &[(now - 20, 10.0), (now - 10, 20.0), (now, 60.0)]);
+
+        let result = history
+            .query("cpu", last_day(), Aggregation::Avg, GroupBy::None)
+            .unwrap();
+        assert!(matches!(result.source, HistorySource::Raw));
+        assert_eq!(values(&result), vec![30.0]);
+
+        let result = history
+            .query("cpu", last_day(), Aggregation::P50, GroupBy::None)
+            .unwrap();
+        assert_eq!(values(&result), vec![20.0]);
+    }
+
+    #[test]
+    fn hour_groups_include_the_current_hour() {
+        let history = History::in_memory();
+        let now = now();
+        record(&history, &[(now, 40.0), (now, 60.0)]);
+        history.rollup().unwrap();
+
+        for group_by in [GroupBy::Hour, GroupBy::Day] {
+            let result = history
+                .query("cpu", last_day(), Aggregation::Max, group_by)
+                .unwrap();
+            assert!(matches!(result.source, HistorySource::Raw));
+            assert_eq!(values(&result), vec![60.0]);
+        }
+    }
+
+    #[test]
+    fn pruned_range_falls_back_to_rollups() {
+        let history = History::in_memory();
+        let now = now();
+        let old = now - 2 * HOUR;
+        record(&history, &[(old, 50.0), (now - 10, 70.0)]);
+        history.rollup().unwrap();
+        let policy = RetentionPolicy {
+            raw_retention_hours: 1,
+            ..RetentionPolicy::default()
+        };
+        history.prune(&policy).unwrap();
+
+        let result = history
+            .query("cpu", last_day(), Aggregation::Avg, GroupBy::None)
+            .unwrap();
+        assert!(matches!(result.source, HistorySource::MinuteRollup));
+        assert!(history
+            .query("cpu", last_day(), Aggregation::P95, GroupBy::None)
+            .is_err());
+
+        // The retained part of the range is still read from raw samples
+        let recent = TimeRange {
+            from: now - HOUR / 2,
+            to: now + 1,
+        };
+        let result = history
+            .query("cpu", recent, Aggregation::P95, GroupBy::None)
+            .unwrap();
+        assert!(matches!(result.source, HistorySource::Raw));
+        assert_eq!(values(&result), vec![70.0]);
+    }
+
+    #[test]
+    fn huge_retention_keeps_everything() {
+        let history = History::in_memory();
+        let now = now();
+        record(&history, &[(now - 10, 1.0)]);
+        let policy = RetentionPolicy {
+            raw_retention_hours: u64::MAX,
+            rollup_retention_days: u64::MAX / 2,
+            prune_interval_minutes: u64::MAX,
+        };
+        assert_eq!(history.prune(&policy).unwrap(), 0);
+        assert!(check_retention(&policy).is_err());
+        assert!(check_retention(&RetentionPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn longer_retention_keeps_the_earlier_cutoff() {
+        let history = History::in_memory();
+        let short = RetentionPolicy {
+            raw_retention_hours: 1,
+            ..RetentionPolicy::default()
+        };
+        history.prune(&short).unwrap();
+        history.prune(&RetentionPolicy::default()).unwrap();
+
+        let range = TimeRange {
+            from: now() - 2 * HOUR,
+            to: now(),
+        };
+        let result = history
+            .query("cpu", range, Aggregation::Avg, GroupBy::None)
+            .unwrap();
+        assert!(matches!(result.source, HistorySource::MinuteRollup));
+    }
+}
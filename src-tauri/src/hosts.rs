@@ -0,0 +1,164 @@
+// Read-only view of local name resolution overrides.
+// Parses the hosts file into address/hostname entries and, on Linux, the `[Resolve]` settings
+// from systemd-resolved's config and drop-ins, which decide which DNS servers and search
+// domains are used before the network's own.
+
+use serde::Serialize;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use crate::error::CommandError;
+use crate::integrity::HOSTS_FILE;
+
+const RESOLVED_CONF: &str = "/etc/systemd/resolved.conf";
+// Drop-in directories, later ones override earlier ones for the same file name
+const RESOLVED_DROP_INS: [&str; 3] = [
+    "/usr/lib/systemd/resolved.conf.d",
+    "/run/systemd/resolved.conf.d",
+    "/etc/systemd/resolved.conf.d",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostsEntry {
+    pub line: usize,
+    pub address: String,
+    pub hostnames: Vec<String>,
+    // Trailing `# ...` comment on the same line
+    pub comment: Option<String>,
+    pub is_loopback: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedSetting {
+    pub source: String,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostsOverview {
+    pub path: String,
+    pub entries: Vec<HostsEntry>,
+    // Settings in effect after drop-ins, empty when systemd-resolved isn't configured
+    pub resolved: Vec<ResolvedSetting>,
+    // Lines that aren't `address hostname...`, and files that couldn't be read
+    pub warnings: Vec<String>,
+}
+
+fn parse_hosts(text: &str, warnings: &mut Vec<String>) -> Vec<HostsEntry> {
+    let mut entries = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let (content, comment) = match raw.split_once('#') {
+            Some((content, comment)) => (content, Some(comment.trim().to_string())),
+            None => (raw, None),
+        };
+        let mut fields = content.split_whitespace();
+        let Some(address) = fields.next() else {
+            continue;
+        };
+        let hostnames: Vec<String> = fields.map(str::to_string).collect();
+        let Ok(ip) = address.parse::<IpAddr>() else {
+            warnings.push(format!("Line {}: invalid address {}", index + 1, address));
+            continue;
+        };
+        if hostnames.is_empty() {
+            warnings.push(format!("Line {}: no hostnames for {}", index + 1, address));
+            continue;
+        }
+        entries.push(HostsEntry {
+            line: index + 1,
+            address: address.to_string(),
+            hostnames,
+            comment: comment.filter(|comment| !comment.is_empty()),
+            is_loopback: ip.is_loopback() || ip.is_unspecified(),
+        });
+    }
+    entries
+}
+
+// Key/value pairs from the `[Resolve]` section of a systemd-style config file
+fn parse_resolve_section(text: &str) -> Vec<(String, String)> {
+    let mut in_resolve = false;
+    let mut pairs = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_resolve = line == "[Resolve]";
+            continue;
+        }
+        if let (true, Some((key, value))) = (in_resolve, line.split_once('=')) {
+            pairs.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    pairs
+}
+
+fn resolved_files() -> Vec<PathBuf> {
+    let mut drop_ins: Vec<PathBuf> = Vec::new();
+    for dir in RESOLVED_DROP_INS {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.extension().is_some_and(|ext| ext == "conf") {
+                drop_ins.retain(|existing| existing.file_name() != path.file_name());
+                drop_ins.push(path);
+            }
+        }
+    }
+    // Drop-ins apply in file name order regardless of directory
+    drop_ins.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+    let mut files = vec![PathBuf::from(RESOLVED_CONF)];
+    files.extend(drop_ins);
+    files
+}
+
+fn resolved_settings(warnings: &mut Vec<String>) -> Vec<ResolvedSetting> {
+    let mut settings: Vec<ResolvedSetting> = Vec::new();
+    for file in resolved_files() {
+        let text = match std::fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                warnings.push(format!("{}: {}", file.display(), e));
+                continue;
+            }
+        };
+        for (key, value) in parse_resolve_section(&text) {
+            // A later assignment replaces the earlier one
+            settings.retain(|setting| setting.key != key);
+            settings.push(ResolvedSetting {
+                source: file.display().to_string(),
+                key,
+                value,
+            });
+        }
+    }
+    settings
+}
+
+#[tauri::command]
+pub fn get_hosts_entries() -> Result<HostsOverview, CommandError> {
+    let text = std::fs::read_to_string(HOSTS_FILE)
+        .map_err(|e| CommandError::open_failed(HOSTS_FILE, e))?;
+    let mut warnings = Vec::new();
+    let entries = parse_hosts(&text, &mut warnings);
+    let resolved = if cfg!(target_os = "linux") && Path::new("/run/systemd/resolve").exists() {
+        resolved_settings(&mut warnings)
+    } else {
+        Vec::new()
+    };
+
+    Ok(HostsOverview {
+        path: HOSTS_FILE.to_string(),
+        entries,
+        resolved,
+        warnings,
+    })
+}
@@ -0,0 +1,881 @@
+// Message catalog for errors and command descriptions shown in the UI.
+// Keys are stable identifiers the frontend can translate itself; the backend also renders
+// them in the active locale so `CommandError::message` is always ready to display.
+// Parameters are written as `{name}` and substituted at render time.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use tauri::State;
+
+use crate::config::ConfigState;
+use crate::error::CommandError;
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+const EN: &[(&str, &str)] = &[
+    ("error.internal", "{detail}"),
+    ("library.notLoaded", "The system library is not loaded"),
+    ("library.noneSelected", "No library was selected"),
+    (
+        "library.missingExports",
+        "{path} is not a systemapi library: missing {exports}",
+    ),
+    (
+        "library.stillInUse",
+        "{path} is still in use; it will be unloaded when the running calls finish",
+    ),
+    ("native.callFailed", "The system library call failed: {detail}"),
+    (
+        "native.timeout",
+        "{command} gave up after waiting {seconds} s for the system library",
+    ),
+    (
+        "factorial.outOfRange",
+        "Factorial input must be between 0 and {max}",
+    ),
+    ("file.openFailed", "Failed to open {path}: {detail}"),
+    ("file.createFailed", "Failed to create {path}: {detail}"),
+    (
+        "bundle.passwordRequired",
+        "This bundle is encrypted; a password is required",
+    ),
+    ("wol.invalidMac", "Invalid MAC address: {mac}"),
+    (
+        "wol.invalidBroadcast",
+        "Invalid broadcast address: {address}",
+    ),
+    ("agent.noHosts", "No hosts given"),
+    (
+        "history.invalidRange",
+        "Invalid range: `from` is after `to`",
+    ),
+    (
+        "history.invalidRetention",
+        "Raw retention must be 1 to {maxHours} hours, rollup retention 1 to {maxDays} days and the prune interval at most {maxMinutes} minutes",
+    ),
+    (
+        "display.invalidPrecision",
+        "Precision must be between 0 and {max} decimal places",
+    ),
+    ("events.unknown", "Unknown event stream: {event}"),
+    (
+        "history.emptyAnnotation",
+        "Annotation text must not be empty",
+    ),
+    ("latency.invalidTarget", "Invalid ping target: {target}"),
+    ("service.invalidName", "Invalid service name: {name}"),
+    ("service.notFound", "No service named {name}"),
+    (
+        "service.permissionDenied",
+        "Not permitted to control {name}; try running the app as an administrator",
+    ),
+    ("service.cancelled", "The service action was cancelled"),
+    (
+        "share.agentNotRunning",
+        "Start the remote agent before sharing a report",
+    ),
+    ("share.unknownReport", "No export with id {id}"),
+    (
+        "share.noContent",
+        "Export {id} has no stored content to share",
+    ),
+    (
+        "monitor.invalidInterval",
+        "Monitoring interval must be between {min} and {max} ms",
+    ),
+    ("kiosk.active", "Not available while kiosk mode is on"),
+    (
+        "kiosk.noViews",
+        "Kiosk mode needs at least one view to show",
+    ),
+    (
+        "memory.permissionDenied",
+        "Reading memory module details needs administrator rights",
+    ),
+    (
+        "ssd.permissionDenied",
+        "Reading SSD health needs administrator rights",
+    ),
+    ("process.notFound", "No process with PID {pid}"),
+    ("process.self", "The app can't end its own process"),
+    (
+        "process.killFailed",
+        "Could not end process {pid}; try running the app as administrator",
+    ),
+    ("process.cancelled", "Ending the process was cancelled"),
+    (
+        "benchmark.batterySaver",
+        "Benchmarks are paused while battery saver is on",
+    ),
+    (
+        "perfCounter.notAllowed",
+        "{path} is not in the performance counter allowlist",
+    ),
+    (
+        "ebpf.invalidDuration",
+        "Tracing duration must be between 1 and {max} seconds",
+    ),
+    ("ebpf.unavailable", "eBPF metrics are unavailable: {reason}"),
+    (
+        "sandbox.unknownCommand",
+        "{command} can't be run in the library helper",
+    ),
+    (
+        "template.invalidName",
+        "{name} is not a valid template name",
+    ),
+    ("template.invalid", "Template {name} is invalid: {detail}"),
+    (
+        "template.renderFailed",
+        "Rendering template {name} failed: {detail}",
+    ),
+    (
+        "policy.commandDisabled",
+        "{command} is disabled by your organization's policy",
+    ),
+    ("benchmark.notFound", "No benchmark run with ID {id}"),
+    ("session.recordingActive", "A session is already being recorded"),
+    ("session.replayActive", "A session is already being replayed"),
+    ("session.notRecording", "No session is being recorded"),
+    ("history.invalidPoints", "Points must be between 1 and {max}"),
+    ("alert.invalidRule", "Invalid alert rule: {detail}"),
+    ("alert.unknownRule", "No alert rule with ID {id}"),
+    (
+        "session.invalidFile",
+        "{path} is not a recorded session: {detail}",
+    ),
+    (
+        "session.invalidSpeed",
+        "Replay speed must be between {min} and {max}",
+    ),
+    ("export.noneSelected", "No file was selected"),
+    ("export.unknownSnapshot", "No exported system report with ID {id}"),
+    // Command descriptions for `list_commands`
+    ("command.list_commands", "List every command with its arguments and availability"),
+    ("command.get_computer_name", "Computer name"),
+    ("command.get_total_memory", "Total physical memory in bytes"),
+    ("command.get_process_id", "Process ID of the app"),
+    ("command.calculate_factorial", "Factorial of n, computed by the active backend"),
+    ("command.get_library_load_trace", "Every path tried while loading the system library"),
+    ("command.get_library_load_report", "Why the system library did or didn't load: every path tried, where it came from and the error"),
+    ("command.get_library_capabilities", "Which native functions the loaded system library exports"),
+    ("command.get_library_version", "Version and API version of the loaded system library"),
+    ("command.pick_and_load_library", "Choose a system library file and load it"),
+    ("command.reload_library", "Unload the system library and search for it again"),
+    ("command.unload_library", "Unload the system library and use the Rust providers"),
+    ("command.get_sandboxed_commands", "Which commands call the system library from a helper process"),
+    ("command.set_sandboxed_commands", "Choose the commands that call the system library from a helper process"),
+    ("command.build_library", "Build the C++ library with CMake and load it"),
+    ("command.get_platform", "Operating system the app runs on"),
+    ("command.get_os_info", "OS name, version, build, kernel, architecture and domain"),
+    ("command.get_active_backend", "Backend answering the system info commands"),
+    ("command.get_system_snapshot", "Everything the dashboard shows in one call"),
+    ("command.export_report", "Save the system snapshot, or one saved with an earlier export, as JSON, CSV or HTML"),
+    ("command.show_dashboard", "Show and focus the main window"),
+    ("command.get_startup_mode", "How the app starts"),
+    ("command.set_startup_mode", "Change how the app starts"),
+    ("command.subscribe_events", "Receive the named events in this window"),
+    ("command.unsubscribe_events", "Stop receiving the named events in this window"),
+    ("command.get_event_subscriptions", "Events this window is subscribed to"),
+    ("command.get_display_settings", "Units and formatting preferences"),
+    ("command.set_display_settings", "Change units and formatting preferences"),
+    ("command.format_values", "Format numbers, sizes and dates the way exports do"),
+    ("command.get_backend_status", "Result of the startup checks on the backend"),
+    ("command.get_startup_args", "Command-line options the app was started with"),
+    ("command.get_policy", "Commands and samplers disabled by policy, and whether an administrator set it"),
+    ("command.get_config", "All persisted settings"),
+    ("command.set_config", "Replace and save all settings, applying them without a restart where possible"),
+    ("command.get_locale", "Language of error messages"),
+    ("command.set_locale", "Change the language of error messages"),
+    ("command.get_message_catalog", "Error message translations for a language"),
+    ("command.send_wol", "Send a Wake-on-LAN packet"),
+    ("command.get_discovered_peers", "Other instances found on the local network"),
+    ("command.start_agent", "Serve metrics to other machines"),
+    ("command.stop_agent", "Stop serving metrics"),
+    ("command.start_exporter", "Serve /metrics in the Prometheus text format, fed by the monitoring loop"),
+    ("command.stop_exporter", "Stop serving Prometheus metrics"),
+    ("command.compare_machines", "Fetch metrics from other agents side by side"),
+    ("command.create_share_link", "Share a saved report through a temporary link"),
+    ("command.export_bundle", "Write a report bundle, optionally encrypted"),
+    ("command.create_support_bundle", "Write diagnostics, the report, recent history and events to one zip for support"),
+    ("command.start_recording", "Record the metric event streams to a session file"),
+    ("command.stop_recording", "Finish the session being recorded"),
+    ("command.start_replay", "Emit the events of a recorded session again, optionally sped up"),
+    ("command.stop_replay", "Stop replaying a recorded session"),
+    ("command.import_report", "Open a report bundle from another machine"),
+    ("command.get_imported_report", "Report bundle currently open"),
+    ("command.close_imported_report", "Close the open report bundle"),
+    ("command.render_report", "Render the system report through a template from the templates folder"),
+    ("command.get_export_history", "Recent exports and copied snippets"),
+    ("command.record_copied_snippet", "Add a copied snippet to the export history"),
+    ("command.get_retention_policy", "How long history is kept"),
+    ("command.set_retention_policy", "Change how long history is kept"),
+    ("command.get_storage_usage", "Size of the history database"),
+    ("command.query_history", "Recorded values of a metric over a time range"),
+    ("command.get_sparkline", "Recent values of a metric downsampled to a fixed number of points"),
+    ("command.add_annotation", "Mark a point in time on the history charts"),
+    ("command.delete_annotation", "Remove an annotation"),
+    ("command.get_thermal_history", "Recorded temperatures over a time range"),
+    ("command.get_sensors", "Current temperatures and fan speeds"),
+    ("command.get_cpu_frequency", "Current and maximum CPU clock speeds"),
+    ("command.get_cpu_info", "CPU model, cores and caches"),
+    ("command.get_cpu_usage", "Load of each CPU core"),
+    ("command.get_disks", "Mounted disks and their free space"),
+    ("command.get_gpu_info", "Graphics adapters, video memory and drivers"),
+    ("command.get_user_disk_usage", "Disk space used by each user's home directory"),
+    ("command.get_memory_details", "Used, available and cached memory, swap and page size"),
+    ("command.get_memory_modules", "Installed RAM modules and free slots"),
+    ("command.get_battery_health", "Battery capacity, wear and cycle count"),
+    ("command.get_battery_history", "Recorded battery capacity over recent days"),
+    ("command.get_power_status", "Battery charge, charging state and AC power"),
+    ("command.get_battery_saver_status", "Whether battery saver is on and background sampling is held back"),
+    ("command.get_uptime_info", "Time since boot, boot time and load averages"),
+    ("command.get_ssd_health", "SSD wear, spare capacity and TRIM status"),
+    ("command.get_ssd_wear_history", "Recorded SSD wear over recent days"),
+    ("command.get_network_interfaces", "Network interfaces, addresses and adapter details"),
+    ("command.get_network_throughput", "Bytes per second on each interface since the last call"),
+    ("command.list_processes", "Running processes, sorted and filtered"),
+    ("command.get_process_details", "Command line, environment and handles of a process"),
+    ("command.kill_process", "End a process after confirmation"),
+    ("command.get_scheduler_stats", "Context switches, interrupts and run queue length"),
+    ("command.get_handle_stats", "Open file handles, system-wide and per process"),
+    ("command.get_watchlist_status", "Whether the watched processes are running"),
+    ("command.set_watchlist", "Change the watched process names"),
+    ("command.get_process_events", "Starts and exits of watched processes"),
+    ("command.get_integrity_status", "Hashes of the watched files"),
+    ("command.set_integrity_paths", "Change the files watched for modification"),
+    ("command.get_integrity_events", "Changes to the watched files"),
+    ("command.get_inventory", "Installed disks, memory, GPUs and USB devices"),
+    ("command.get_inventory_changes", "Hardware added or removed over time"),
+    ("command.get_certificates", "Certificates and how soon they expire"),
+    ("command.get_hosts_entries", "Entries in the hosts file"),
+    ("command.get_proxy_status", "Proxy settings and captive portal detection"),
+    ("command.get_latency_status", "Latency to the configured targets"),
+    ("command.set_latency_config", "Change the latency targets"),
+    ("command.start_monitoring", "Emit system metrics at an interval"),
+    ("command.stop_monitoring", "Stop emitting system metrics"),
+    ("command.get_alert_rules", "Saved alert rules"),
+    ("command.add_alert_rule", "Save a rule that alerts when a metric stays above or below a threshold"),
+    ("command.remove_alert_rule", "Delete an alert rule"),
+    ("command.start_kiosk", "Start rotating dashboard panels full screen"),
+    ("command.stop_kiosk", "Leave kiosk mode"),
+    ("command.get_kiosk_status", "Whether kiosk mode is on and the current panel"),
+    ("command.set_kiosk_config", "Change the kiosk panels and rotation"),
+    ("command.scan_wifi_networks", "Nearby Wi-Fi networks and channel usage"),
+    ("command.get_capture_device_usage", "Apps using the camera or microphone"),
+    ("command.get_security_products", "Antivirus and firewall products registered with Windows"),
+    ("command.get_perf_counters", "Current values of the allowlisted Windows performance counters"),
+    ("command.get_ebpf_status", "Whether eBPF metrics can run here, and what is missing"),
+    ("command.collect_ebpf_metrics", "Trace per-process I/O latency and TCP retransmits with eBPF"),
+    ("command.get_activation_status", "OS edition and activation status"),
+    ("command.get_pending_reboot", "Whether a restart is pending and why"),
+    ("command.get_boot_history", "Recent boots and shutdowns"),
+    ("command.get_service_status", "State of a system service"),
+    ("command.start_service", "Start a system service after confirmation"),
+    ("command.stop_service", "Stop a system service after confirmation"),
+    ("command.restart_service", "Restart a system service after confirmation"),
+    ("command.export_history_parquet", "Write recorded history to a Parquet file"),
+    ("command.backup_app_data", "Save settings and optionally history to a file"),
+    ("command.restore_app_data", "Restore settings and history from a backup"),
+    ("command.run_benchmark", "Run a benchmark and record the score"),
+    ("command.run_scaling_benchmark", "Measure how the CPU score scales with threads"),
+    ("command.run_gpu_benchmark", "Run the GPU compute benchmark"),
+    ("command.get_benchmark_history", "Past benchmark results"),
+    ("command.get_benchmark_timeline", "A benchmark run overlaid with the load, clocks and temperatures recorded during it"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("error.internal", "{detail}"),
+    (
+        "library.notLoaded",
+        "La biblioteca del sistema no está cargada",
+    ),
+    (
+        "library.noneSelected",
+        "No se seleccionó ninguna biblioteca",
+    ),
+    (
+        "library.missingExports",
+        "{path} no es una biblioteca systemapi: falta {exports}",
+    ),
+    (
+        "library.stillInUse",
+        "{path} sigue en uso; se descargará cuando terminen las llamadas en curso",
+    ),
+    (
+        "native.callFailed",
+        "Falló la llamada a la biblioteca del sistema: {detail}",
+    ),
+    (
+        "native.timeout",
+        "{command} dejó de esperar a la biblioteca del sistema tras {seconds} s",
+    ),
+    (
+        "factorial.outOfRange",
+        "El valor del factorial debe estar entre 0 y {max}",
+    ),
+    ("file.openFailed", "No se pudo abrir {path}: {detail}"),
+    ("file.createFailed", "No se pudo crear {path}: {detail}"),
+    (
+        "bundle.passwordRequired",
+        "Este paquete está cifrado; se requiere una contraseña",
+    ),
+    ("wol.invalidMac", "Dirección MAC no válida: {mac}"),
+    (
+        "wol.invalidBroadcast",
+        "Dirección de difusión no válida: {address}",
+    ),
+    ("agent.noHosts", "No se indicó ningún equipo"),
+    (
+        "history.invalidRange",
+        "Rango no válido: `from` es posterior a `to`",
+    ),
+    (
+        "history.invalidRetention",
+        "La retención de muestras debe ser de 1 a {maxHours} horas, la de agregados de 1 a {maxDays} días y el intervalo de limpieza de como máximo {maxMinutes} minutos",
+    ),
+    (
+        "display.invalidPrecision",
+        "La precisión debe estar entre 0 y {max} decimales",
+    ),
+    ("events.unknown", "Flujo de eventos desconocido: {event}"),
+    (
+        "history.emptyAnnotation",
+        "El texto de la anotación no puede estar vacío",
+    ),
+    (
+        "latency.invalidTarget",
+        "Destino de ping no válido: {target}",
+    ),
+    ("service.invalidName", "Nombre de servicio no válido: {name}"),
+    ("service.notFound", "No existe ningún servicio llamado {name}"),
+    ("service.permissionDenied", "No tiene permiso para controlar {name}; pruebe a ejecutar la aplicación como administrador"),
+    ("service.cancelled", "Se canceló la acción sobre el servicio"),
+    ("share.agentNotRunning", "Inicie el agente remoto antes de compartir un informe"),
+    ("share.unknownReport", "No hay ninguna exportación con id {id}"),
+    ("share.noContent", "La exportación {id} no tiene contenido guardado para compartir"),
+    ("monitor.invalidInterval", "El intervalo de monitorización debe estar entre {min} y {max} ms"),
+    ("kiosk.active", "No disponible mientras el modo quiosco está activo"),
+    ("kiosk.noViews", "El modo quiosco necesita al menos una vista que mostrar"),
+    ("memory.permissionDenied", "Leer los detalles de los módulos de memoria requiere permisos de administrador"),
+    ("ssd.permissionDenied", "Leer el estado de los SSD requiere permisos de administrador"),
+    ("process.notFound", "No existe ningún proceso con PID {pid}"),
+    ("process.self", "La aplicación no puede finalizar su propio proceso"),
+    ("process.killFailed", "No se pudo finalizar el proceso {pid}; pruebe a ejecutar la aplicación como administrador"),
+    ("process.cancelled", "Se canceló la finalización del proceso"),
+    ("benchmark.batterySaver", "Las pruebas de rendimiento están en pausa mientras el ahorro de batería está activo"),
+    ("perfCounter.notAllowed", "{path} no está en la lista de contadores de rendimiento permitidos"),
+    ("ebpf.invalidDuration", "La duración del trazado debe estar entre 1 y {max} segundos"),
+    ("ebpf.unavailable", "Las métricas eBPF no están disponibles: {reason}"),
+    ("sandbox.unknownCommand", "{command} no se puede ejecutar en el proceso auxiliar de la biblioteca"),
+    ("template.invalidName", "{name} no es un nombre de plantilla válido"),
+    ("template.invalid", "La plantilla {name} no es válida: {detail}"),
+    ("template.renderFailed", "No se pudo generar la plantilla {name}: {detail}"),
+    ("policy.commandDisabled", "{command} está deshabilitado por la directiva de su organización"),
+    ("benchmark.notFound", "No existe ninguna ejecución de benchmark con ID {id}"),
+    ("session.recordingActive", "Ya se está grabando una sesión"),
+    ("session.replayActive", "Ya se está reproduciendo una sesión"),
+    ("session.notRecording", "No se está grabando ninguna sesión"),
+    ("history.invalidPoints", "El número de puntos debe estar entre 1 y {max}"),
+    ("alert.invalidRule", "Regla de alerta no válida: {detail}"),
+    ("alert.unknownRule", "No existe ninguna regla de alerta con ID {id}"),
+    ("export.noneSelected", "No se seleccionó ningún archivo"),
+    ("export.unknownSnapshot", "No existe ningún informe del sistema exportado con ID {id}"),
+    ("session.invalidFile", "{path} no es una sesión grabada: {detail}"),
+    ("session.invalidSpeed", "La velocidad de reproducción debe estar entre {min} y {max}"),
+    // Command descriptions for `list_commands`
+    ("command.list_commands", "Lista todos los comandos con sus argumentos y disponibilidad"),
+    ("command.get_computer_name", "Nombre del equipo"),
+    ("command.get_total_memory", "Memoria física total en bytes"),
+    ("command.get_process_id", "ID de proceso de la aplicación"),
+    ("command.calculate_factorial", "Factorial de n, calculado por el backend activo"),
+    ("command.get_library_load_trace", "Todas las rutas probadas al cargar la biblioteca del sistema"),
+    ("command.get_library_load_report", "Por qué la biblioteca del sistema se cargó o no: cada ruta probada, su origen y el error"),
+    ("command.get_library_capabilities", "Funciones nativas que exporta la biblioteca del sistema cargada"),
+    ("command.get_library_version", "Versión y versión de API de la biblioteca del sistema cargada"),
+    ("command.pick_and_load_library", "Elegir un archivo de biblioteca del sistema y cargarlo"),
+    ("command.reload_library", "Descargar la biblioteca del sistema y buscarla de nuevo"),
+    ("command.unload_library", "Descargar la biblioteca del sistema y usar los proveedores de Rust"),
+    ("command.get_sandboxed_commands", "Comandos que llaman a la biblioteca del sistema desde un proceso auxiliar"),
+    ("command.set_sandboxed_commands", "Elegir los comandos que llaman a la biblioteca del sistema desde un proceso auxiliar"),
+    ("command.build_library", "Compilar la biblioteca de C++ con CMake y cargarla"),
+    ("command.get_platform", "Sistema operativo en el que se ejecuta la aplicación"),
+    ("command.get_os_info", "Nombre, versión, compilación, kernel, arquitectura y dominio del sistema operativo"),
+    ("command.get_active_backend", "Backend que responde a los comandos de información del sistema"),
+    ("command.get_system_snapshot", "Todo lo que muestra el panel en una sola llamada"),
+    ("command.export_report", "Guardar la instantánea del sistema, o una guardada en una exportación anterior, como JSON, CSV o HTML"),
+    ("command.show_dashboard", "Mostrar y enfocar la ventana principal"),
+    ("command.get_startup_mode", "Cómo se inicia la aplicación"),
+    ("command.set_startup_mode", "Cambiar cómo se inicia la aplicación"),
+    ("command.subscribe_events", "Recibir los eventos indicados en esta ventana"),
+    ("command.unsubscribe_events", "Dejar de recibir los eventos indicados en esta ventana"),
+    ("command.get_event_subscriptions", "Eventos a los que está suscrita esta ventana"),
+    ("command.get_display_settings", "Preferencias de unidades y formato"),
+    ("command.set_display_settings", "Cambiar las preferencias de unidades y formato"),
+    ("command.format_values", "Formatear números, tamaños y fechas como en las exportaciones"),
+    ("command.get_backend_status", "Resultado de las comprobaciones del backend al iniciar"),
+    ("command.get_startup_args", "Opciones de línea de comandos con las que se inició la aplicación"),
+    ("command.get_policy", "Comandos y muestreadores desactivados por directiva, y si la estableció un administrador"),
+    ("command.get_config", "Toda la configuración guardada"),
+    ("command.set_config", "Reemplazar y guardar toda la configuración, aplicándola sin reiniciar cuando sea posible"),
+    ("command.get_locale", "Idioma de los mensajes de error"),
+    ("command.set_locale", "Cambiar el idioma de los mensajes de error"),
+    ("command.get_message_catalog", "Traducciones de los mensajes de error para un idioma"),
+    ("command.send_wol", "Enviar un paquete Wake-on-LAN"),
+    ("command.get_discovered_peers", "Otras instancias encontradas en la red local"),
+    ("command.start_agent", "Servir métricas a otros equipos"),
+    ("command.stop_agent", "Dejar de servir métricas"),
+    ("command.start_exporter", "Servir /metrics en el formato de texto de Prometheus, alimentado por el bucle de monitorización"),
+    ("command.stop_exporter", "Dejar de servir métricas de Prometheus"),
+    ("command.compare_machines", "Obtener métricas de otros agentes y compararlas"),
+    ("command.create_share_link", "Compartir un informe guardado mediante un enlace temporal"),
+    ("command.export_bundle", "Escribir un paquete de informe, opcionalmente cifrado"),
+    ("command.create_support_bundle", "Escribir diagnósticos, el informe, el historial reciente y los eventos en un zip para soporte"),
+    ("command.start_recording", "Grabar los flujos de eventos de métricas en un archivo de sesión"),
+    ("command.stop_recording", "Terminar la sesión que se está grabando"),
+    ("command.start_replay", "Emitir de nuevo los eventos de una sesión grabada, opcionalmente acelerados"),
+    ("command.stop_replay", "Detener la reproducción de una sesión grabada"),
+    ("command.import_report", "Abrir un paquete de informe de otro equipo"),
+    ("command.get_imported_report", "Paquete de informe abierto actualmente"),
+    ("command.close_imported_report", "Cerrar el paquete de informe abierto"),
+    ("command.render_report", "Generar el informe del sistema con una plantilla de la carpeta de plantillas"),
+    ("command.get_export_history", "Exportaciones y fragmentos copiados recientes"),
+    ("command.record_copied_snippet", "Añadir un fragmento copiado al historial de exportaciones"),
+    ("command.get_retention_policy", "Cuánto tiempo se conserva el historial"),
+    ("command.set_retention_policy", "Cambiar cuánto tiempo se conserva el historial"),
+    ("command.get_storage_usage", "Tamaño de la base de datos del historial"),
+    ("command.query_history", "Valores registrados de una métrica en un intervalo de tiempo"),
+    ("command.get_sparkline", "Valores recientes de una métrica reducidos a un número fijo de puntos"),
+    ("command.add_annotation", "Marcar un momento en los gráficos del historial"),
+    ("command.delete_annotation", "Eliminar una anotación"),
+    ("command.get_thermal_history", "Temperaturas registradas en un intervalo de tiempo"),
+    ("command.get_sensors", "Temperaturas y velocidades de ventilador actuales"),
+    ("command.get_cpu_frequency", "Velocidades de reloj actual y máxima de la CPU"),
+    ("command.get_cpu_info", "Modelo, núcleos y cachés de la CPU"),
+    ("command.get_cpu_usage", "Carga de cada núcleo de la CPU"),
+    ("command.get_disks", "Discos montados y su espacio libre"),
+    ("command.get_gpu_info", "Adaptadores gráficos, memoria de vídeo y controladores"),
+    ("command.get_user_disk_usage", "Espacio en disco usado por la carpeta personal de cada usuario"),
+    ("command.get_memory_details", "Memoria usada, disponible y en caché, intercambio y tamaño de página"),
+    ("command.get_memory_modules", "Módulos de RAM instalados y ranuras libres"),
+    ("command.get_battery_health", "Capacidad, desgaste y número de ciclos de la batería"),
+    ("command.get_battery_history", "Capacidad de la batería registrada en los últimos días"),
+    ("command.get_power_status", "Carga de la batería, estado de carga y alimentación de CA"),
+    ("command.get_battery_saver_status", "Si el ahorro de batería está activo y el muestreo en segundo plano está limitado"),
+    ("command.get_uptime_info", "Tiempo desde el arranque, hora de arranque y cargas medias"),
+    ("command.get_ssd_health", "Desgaste del SSD, capacidad de reserva y estado de TRIM"),
+    ("command.get_ssd_wear_history", "Desgaste del SSD registrado en los últimos días"),
+    ("command.get_network_interfaces", "Interfaces de red, direcciones y detalles del adaptador"),
+    ("command.get_network_throughput", "Bytes por segundo en cada interfaz desde la última llamada"),
+    ("command.list_processes", "Procesos en ejecución, ordenados y filtrados"),
+    ("command.get_process_details", "Línea de comandos, entorno e identificadores de un proceso"),
+    ("command.kill_process", "Finalizar un proceso tras confirmarlo"),
+    ("command.get_scheduler_stats", "Cambios de contexto, interrupciones y longitud de la cola de ejecución"),
+    ("command.get_handle_stats", "Identificadores de archivo abiertos, en todo el sistema y por proceso"),
+    ("command.get_watchlist_status", "Si los procesos vigilados se están ejecutando"),
+    ("command.set_watchlist", "Cambiar los nombres de los procesos vigilados"),
+    ("command.get_process_events", "Inicios y salidas de los procesos vigilados"),
+    ("command.get_integrity_status", "Hashes de los archivos vigilados"),
+    ("command.set_integrity_paths", "Cambiar los archivos vigilados en busca de modificaciones"),
+    ("command.get_integrity_events", "Cambios en los archivos vigilados"),
+    ("command.get_inventory", "Discos, memoria, GPU y dispositivos USB instalados"),
+    ("command.get_inventory_changes", "Hardware añadido o retirado a lo largo del tiempo"),
+    ("command.get_certificates", "Certificados y cuánto falta para que caduquen"),
+    ("command.get_hosts_entries", "Entradas del archivo hosts"),
+    ("command.get_proxy_status", "Configuración del proxy y detección de portal cautivo"),
+    ("command.get_latency_status", "Latencia a los destinos configurados"),
+    ("command.set_latency_config", "Cambiar los destinos de latencia"),
+    ("command.start_monitoring", "Emitir métricas del sistema a intervalos"),
+    ("command.stop_monitoring", "Dejar de emitir métricas del sistema"),
+    ("command.get_alert_rules", "Reglas de alerta guardadas"),
+    ("command.add_alert_rule", "Guardar una regla que avisa cuando una métrica se mantiene por encima o por debajo de un umbral"),
+    ("command.remove_alert_rule", "Eliminar una regla de alerta"),
+    ("command.start_kiosk", "Empezar a rotar los paneles del panel a pantalla completa"),
+    ("command.stop_kiosk", "Salir del modo quiosco"),
+    ("command.get_kiosk_status", "Si el modo quiosco está activo y el panel actual"),
+    ("command.set_kiosk_config", "Cambiar los paneles del quiosco y su rotación"),
+    ("command.scan_wifi_networks", "Redes Wi-Fi cercanas y uso de canales"),
+    ("command.get_capture_device_usage", "Aplicaciones que usan la cámara o el micrófono"),
+    ("command.get_security_products", "Productos antivirus y cortafuegos registrados en Windows"),
+    ("command.get_perf_counters", "Valores actuales de los contadores de rendimiento de Windows permitidos"),
+    ("command.get_ebpf_status", "Si las métricas de eBPF pueden ejecutarse aquí y qué falta"),
+    ("command.collect_ebpf_metrics", "Rastrear la latencia de E/S por proceso y las retransmisiones TCP con eBPF"),
+    ("command.get_activation_status", "Edición y estado de activación del sistema operativo"),
+    ("command.get_pending_reboot", "Si hay un reinicio pendiente y por qué"),
+    ("command.get_boot_history", "Arranques y apagados recientes"),
+    ("command.get_service_status", "Estado de un servicio del sistema"),
+    ("command.start_service", "Iniciar un servicio del sistema tras confirmarlo"),
+    ("command.stop_service", "Detener un servicio del sistema tras confirmarlo"),
+    ("command.restart_service", "Reiniciar un servicio del sistema tras confirmarlo"),
+    ("command.export_history_parquet", "Escribir el historial registrado en un archivo Parquet"),
+    ("command.backup_app_data", "Guardar la configuración y, opcionalmente, el historial en un archivo"),
+    ("command.restore_app_data", "Restaurar la configuración y el historial desde una copia de seguridad"),
+    ("command.run_benchmark", "Ejecutar una prueba de rendimiento y registrar la puntuación"),
+    ("command.run_scaling_benchmark", "Medir cómo escala la puntuación de la CPU con los hilos"),
+    ("command.run_gpu_benchmark", "Ejecutar la prueba de rendimiento de cálculo de la GPU"),
+    ("command.get_benchmark_history", "Resultados de pruebas de rendimiento anteriores"),
+    ("command.get_benchmark_timeline", "Una ejecución de prueba de rendimiento superpuesta con la carga, las frecuencias y las temperaturas registradas durante ella"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("error.internal", "{detail}"),
+    (
+        "library.notLoaded",
+        "Die Systembibliothek ist nicht geladen",
+    ),
+    (
+        "library.noneSelected",
+        "Es wurde keine Bibliothek ausgewählt",
+    ),
+    (
+        "library.missingExports",
+        "{path} ist keine systemapi-Bibliothek: es fehlt {exports}",
+    ),
+    (
+        "library.stillInUse",
+        "{path} wird noch verwendet und wird entladen, sobald die laufenden Aufrufe beendet sind",
+    ),
+    (
+        "native.callFailed",
+        "Der Aufruf der Systembibliothek ist fehlgeschlagen: {detail}",
+    ),
+    (
+        "native.timeout",
+        "{command} hat nach {seconds} s aufgehört, auf die Systembibliothek zu warten",
+    ),
+    (
+        "factorial.outOfRange",
+        "Die Eingabe für die Fakultät muss zwischen 0 und {max} liegen",
+    ),
+    (
+        "file.openFailed",
+        "{path} konnte nicht geöffnet werden: {detail}",
+    ),
+    (
+        "file.createFailed",
+        "{path} konnte nicht erstellt werden: {detail}",
+    ),
+    (
+        "bundle.passwordRequired",
+        "Dieses Paket ist verschlüsselt; ein Passwort ist erforderlich",
+    ),
+    ("wol.invalidMac", "Ungültige MAC-Adresse: {mac}"),
+    (
+        "wol.invalidBroadcast",
+        "Ungültige Broadcast-Adresse: {address}",
+    ),
+    ("agent.noHosts", "Keine Hosts angegeben"),
+    (
+        "history.invalidRange",
+        "Ungültiger Zeitraum: `from` liegt nach `to`",
+    ),
+    (
+        "history.invalidRetention",
+        "Rohdaten müssen 1 bis {maxHours} Stunden, Aggregate 1 bis {maxDays} Tage aufbewahrt werden und das Bereinigungsintervall darf höchstens {maxMinutes} Minuten betragen",
+    ),
+    (
+        "display.invalidPrecision",
+        "Die Genauigkeit muss zwischen 0 und {max} Nachkommastellen liegen",
+    ),
+    ("events.unknown", "Unbekannter Ereignisstrom: {event}"),
+    (
+        "history.emptyAnnotation",
+        "Der Text der Anmerkung darf nicht leer sein",
+    ),
+    ("latency.invalidTarget", "Ungültiges Ping-Ziel: {target}"),
+    ("service.invalidName", "Ungültiger Dienstname: {name}"),
+    ("service.notFound", "Es gibt keinen Dienst namens {name}"),
+    (
+        "service.permissionDenied",
+        "Keine Berechtigung, {name} zu steuern; starten Sie die App als Administrator",
+    ),
+    ("service.cancelled", "Die Dienstaktion wurde abgebrochen"),
+    (
+        "share.agentNotRunning",
+        "Starten Sie den Remote-Agenten, bevor Sie einen Bericht teilen",
+    ),
+    ("share.unknownReport", "Kein Export mit der ID {id}"),
+    (
+        "share.noContent",
+        "Export {id} hat keinen gespeicherten Inhalt zum Teilen",
+    ),
+    (
+        "monitor.invalidInterval",
+        "Das Überwachungsintervall muss zwischen {min} und {max} ms liegen",
+    ),
+    (
+        "kiosk.active",
+        "Im Kioskmodus nicht verfügbar",
+    ),
+    (
+        "kiosk.noViews",
+        "Der Kioskmodus benötigt mindestens eine Ansicht",
+    ),
+    (
+        "memory.permissionDenied",
+        "Zum Lesen der Speichermodule sind Administratorrechte nötig",
+    ),
+    (
+        "ssd.permissionDenied",
+        "Zum Lesen des SSD-Zustands sind Administratorrechte nötig",
+    ),
+    ("process.notFound", "Es gibt keinen Prozess mit PID {pid}"),
+    ("process.self", "Die App kann ihren eigenen Prozess nicht beenden"),
+    (
+        "process.killFailed",
+        "Prozess {pid} konnte nicht beendet werden; versuchen Sie, die App als Administrator auszuführen",
+    ),
+    ("process.cancelled", "Das Beenden des Prozesses wurde abgebrochen"),
+    (
+        "benchmark.batterySaver",
+        "Benchmarks sind pausiert, solange der Energiesparmodus aktiv ist",
+    ),
+    (
+        "perfCounter.notAllowed",
+        "{path} steht nicht auf der Liste der erlaubten Leistungsindikatoren",
+    ),
+    (
+        "ebpf.invalidDuration",
+        "Die Aufzeichnungsdauer muss zwischen 1 und {max} Sekunden liegen",
+    ),
+    ("ebpf.unavailable", "eBPF-Metriken sind nicht verfügbar: {reason}"),
+    (
+        "sandbox.unknownCommand",
+        "{command} kann nicht im Hilfsprozess der Bibliothek ausgeführt werden",
+    ),
+    (
+        "template.invalidName",
+        "{name} ist kein gültiger Vorlagenname",
+    ),
+    ("template.invalid", "Die Vorlage {name} ist ungültig: {detail}"),
+    (
+        "template.renderFailed",
+        "Die Vorlage {name} konnte nicht gerendert werden: {detail}",
+    ),
+    (
+        "policy.commandDisabled",
+        "{command} ist durch die Richtlinie Ihrer Organisation deaktiviert",
+    ),
+    ("benchmark.notFound", "Es gibt keinen Benchmark-Lauf mit der ID {id}"),
+    ("session.recordingActive", "Es wird bereits eine Sitzung aufgezeichnet"),
+    ("session.replayActive", "Es wird bereits eine Sitzung wiedergegeben"),
+    ("session.notRecording", "Es wird keine Sitzung aufgezeichnet"),
+    ("history.invalidPoints", "Die Anzahl der Punkte muss zwischen 1 und {max} liegen"),
+    ("alert.invalidRule", "Ungültige Warnregel: {detail}"),
+    ("alert.unknownRule", "Es gibt keine Warnregel mit der ID {id}"),
+    (
+        "session.invalidFile",
+        "{path} ist keine aufgezeichnete Sitzung: {detail}",
+    ),
+    (
+        "session.invalidSpeed",
+        "Die Wiedergabegeschwindigkeit muss zwischen {min} und {max} liegen",
+    ),
+    ("export.noneSelected", "Es wurde keine Datei ausgewählt"),
+    (
+        "export.unknownSnapshot",
+        "Es gibt keinen exportierten Systembericht mit der ID {id}",
+    ),
+    // Command descriptions for `list_commands`
+    ("command.list_commands", "Alle Befehle mit ihren Argumenten und ihrer Verfügbarkeit auflisten"),
+    ("command.get_computer_name", "Computername"),
+    ("command.get_total_memory", "Gesamter physischer Arbeitsspeicher in Bytes"),
+    ("command.get_process_id", "Prozess-ID der App"),
+    ("command.calculate_factorial", "Fakultät von n, berechnet vom aktiven Backend"),
+    ("command.get_library_load_trace", "Alle Pfade, die beim Laden der Systembibliothek versucht wurden"),
+    ("command.get_library_load_report", "Warum die Systembibliothek geladen wurde oder nicht: jeder versuchte Pfad, seine Herkunft und der Fehler"),
+    ("command.get_library_capabilities", "Welche nativen Funktionen die geladene Systembibliothek exportiert"),
+    ("command.get_library_version", "Version und API-Version der geladenen Systembibliothek"),
+    ("command.pick_and_load_library", "Eine Systembibliotheksdatei auswählen und laden"),
+    ("command.reload_library", "Die Systembibliothek entladen und erneut suchen"),
+    ("command.unload_library", "Die Systembibliothek entladen und die Rust-Anbieter verwenden"),
+    ("command.get_sandboxed_commands", "Welche Befehle die Systembibliothek aus einem Hilfsprozess aufrufen"),
+    ("command.set_sandboxed_commands", "Die Befehle auswählen, die die Systembibliothek aus einem Hilfsprozess aufrufen"),
+    ("command.build_library", "Die C++-Bibliothek mit CMake bauen und laden"),
+    ("command.get_platform", "Betriebssystem, auf dem die App läuft"),
+    ("command.get_os_info", "Name, Version, Build, Kernel, Architektur und Domäne des Betriebssystems"),
+    ("command.get_active_backend", "Backend, das die Systeminformationsbefehle beantwortet"),
+    ("command.get_system_snapshot", "Alles, was das Dashboard zeigt, in einem Aufruf"),
+    ("command.export_report", "Den Systemschnappschuss oder einen mit einem früheren Export gespeicherten als JSON, CSV oder HTML speichern"),
+    ("command.show_dashboard", "Das Hauptfenster anzeigen und fokussieren"),
+    ("command.get_startup_mode", "Wie die App startet"),
+    ("command.set_startup_mode", "Ändern, wie die App startet"),
+    ("command.subscribe_events", "Die genannten Ereignisse in diesem Fenster empfangen"),
+    ("command.unsubscribe_events", "Die genannten Ereignisse in diesem Fenster nicht mehr empfangen"),
+    ("command.get_event_subscriptions", "Ereignisse, die dieses Fenster abonniert hat"),
+    ("command.get_display_settings", "Einheiten- und Formatierungseinstellungen"),
+    ("command.set_display_settings", "Einheiten- und Formatierungseinstellungen ändern"),
+    ("command.format_values", "Zahlen, Größen und Datumsangaben wie in Exporten formatieren"),
+    ("command.get_backend_status", "Ergebnis der Startprüfungen des Backends"),
+    ("command.get_startup_args", "Befehlszeilenoptionen, mit denen die App gestartet wurde"),
+    ("command.get_policy", "Per Richtlinie deaktivierte Befehle und Sampler und ob ein Administrator sie festgelegt hat"),
+    ("command.get_config", "Alle gespeicherten Einstellungen"),
+    ("command.set_config", "Alle Einstellungen ersetzen und speichern und sie nach Möglichkeit ohne Neustart anwenden"),
+    ("command.get_locale", "Sprache der Fehlermeldungen"),
+    ("command.set_locale", "Die Sprache der Fehlermeldungen ändern"),
+    ("command.get_message_catalog", "Übersetzungen der Fehlermeldungen für eine Sprache"),
+    ("command.send_wol", "Ein Wake-on-LAN-Paket senden"),
+    ("command.get_discovered_peers", "Andere Instanzen im lokalen Netzwerk"),
+    ("command.start_agent", "Metriken für andere Rechner bereitstellen"),
+    ("command.stop_agent", "Keine Metriken mehr bereitstellen"),
+    ("command.start_exporter", "/metrics im Prometheus-Textformat bereitstellen, gespeist von der Überwachungsschleife"),
+    ("command.stop_exporter", "Keine Prometheus-Metriken mehr bereitstellen"),
+    ("command.compare_machines", "Metriken anderer Agenten abrufen und nebeneinander zeigen"),
+    ("command.create_share_link", "Einen gespeicherten Bericht über einen temporären Link teilen"),
+    ("command.export_bundle", "Ein Berichtspaket schreiben, optional verschlüsselt"),
+    ("command.create_support_bundle", "Diagnosedaten, Bericht, aktuellen Verlauf und Ereignisse für den Support in eine ZIP-Datei schreiben"),
+    ("command.start_recording", "Die Metrik-Ereignisströme in eine Sitzungsdatei aufzeichnen"),
+    ("command.stop_recording", "Die laufende Aufzeichnung beenden"),
+    ("command.start_replay", "Die Ereignisse einer aufgezeichneten Sitzung erneut senden, optional beschleunigt"),
+    ("command.stop_replay", "Die Wiedergabe einer aufgezeichneten Sitzung beenden"),
+    ("command.import_report", "Ein Berichtspaket von einem anderen Rechner öffnen"),
+    ("command.get_imported_report", "Aktuell geöffnetes Berichtspaket"),
+    ("command.close_imported_report", "Das geöffnete Berichtspaket schließen"),
+    ("command.render_report", "Den Systembericht mit einer Vorlage aus dem Vorlagenordner erzeugen"),
+    ("command.get_export_history", "Letzte Exporte und kopierte Ausschnitte"),
+    ("command.record_copied_snippet", "Einen kopierten Ausschnitt zum Exportverlauf hinzufügen"),
+    ("command.get_retention_policy", "Wie lange der Verlauf aufbewahrt wird"),
+    ("command.set_retention_policy", "Ändern, wie lange der Verlauf aufbewahrt wird"),
+    ("command.get_storage_usage", "Größe der Verlaufsdatenbank"),
+    ("command.query_history", "Aufgezeichnete Werte einer Metrik in einem Zeitraum"),
+    ("command.get_sparkline", "Aktuelle Werte einer Metrik, auf eine feste Anzahl von Punkten reduziert"),
+    ("command.add_annotation", "Einen Zeitpunkt in den Verlaufsdiagrammen markieren"),
+    ("command.delete_annotation", "Eine Anmerkung entfernen"),
+    ("command.get_thermal_history", "Aufgezeichnete Temperaturen in einem Zeitraum"),
+    ("command.get_sensors", "Aktuelle Temperaturen und Lüfterdrehzahlen"),
+    ("command.get_cpu_frequency", "Aktuelle und maximale CPU-Taktfrequenz"),
+    ("command.get_cpu_info", "CPU-Modell, Kerne und Caches"),
+    ("command.get_cpu_usage", "Auslastung jedes CPU-Kerns"),
+    ("command.get_disks", "Eingebundene Laufwerke und ihr freier Speicher"),
+    ("command.get_gpu_info", "Grafikkarten, Videospeicher und Treiber"),
+    ("command.get_user_disk_usage", "Vom Home-Verzeichnis jedes Benutzers belegter Speicherplatz"),
+    ("command.get_memory_details", "Belegter, verfügbarer und zwischengespeicherter Arbeitsspeicher, Auslagerung und Seitengröße"),
+    ("command.get_memory_modules", "Installierte RAM-Module und freie Steckplätze"),
+    ("command.get_battery_health", "Akkukapazität, Verschleiß und Ladezyklen"),
+    ("command.get_battery_history", "Aufgezeichnete Akkukapazität der letzten Tage"),
+    ("command.get_power_status", "Akkuladung, Ladezustand und Netzbetrieb"),
+    ("command.get_battery_saver_status", "Ob der Energiesparmodus aktiv ist und die Hintergrunderfassung zurückgehalten wird"),
+    ("command.get_uptime_info", "Zeit seit dem Start, Startzeitpunkt und Lastdurchschnitte"),
+    ("command.get_ssd_health", "SSD-Verschleiß, Reservekapazität und TRIM-Status"),
+    ("command.get_ssd_wear_history", "Aufgezeichneter SSD-Verschleiß der letzten Tage"),
+    ("command.get_network_interfaces", "Netzwerkschnittstellen, Adressen und Adapterdetails"),
+    ("command.get_network_throughput", "Bytes pro Sekunde auf jeder Schnittstelle seit dem letzten Aufruf"),
+    ("command.list_processes", "Laufende Prozesse, sortiert und gefiltert"),
+    ("command.get_process_details", "Befehlszeile, Umgebung und Handles eines Prozesses"),
+    ("command.kill_process", "Einen Prozess nach Bestätigung beenden"),
+    ("command.get_scheduler_stats", "Kontextwechsel, Interrupts und Länge der Ausführungswarteschlange"),
+    ("command.get_handle_stats", "Offene Datei-Handles, systemweit und pro Prozess"),
+    ("command.get_watchlist_status", "Ob die überwachten Prozesse laufen"),
+    ("command.set_watchlist", "Die Namen der überwachten Prozesse ändern"),
+    ("command.get_process_events", "Starts und Beendigungen überwachter Prozesse"),
+    ("command.get_integrity_status", "Hashes der überwachten Dateien"),
+    ("command.set_integrity_paths", "Die auf Änderungen überwachten Dateien ändern"),
+    ("command.get_integrity_events", "Änderungen an den überwachten Dateien"),
+    ("command.get_inventory", "Installierte Laufwerke, Arbeitsspeicher, GPUs und USB-Geräte"),
+    ("command.get_inventory_changes", "Im Lauf der Zeit hinzugefügte oder entfernte Hardware"),
+    ("command.get_certificates", "Zertifikate und wann sie ablaufen"),
+    ("command.get_hosts_entries", "Einträge der hosts-Datei"),
+    ("command.get_proxy_status", "Proxy-Einstellungen und Captive-Portal-Erkennung"),
+    ("command.get_latency_status", "Latenz zu den konfigurierten Zielen"),
+    ("command.set_latency_config", "Die Latenzziele ändern"),
+    ("command.start_monitoring", "Systemmetriken in einem Intervall senden"),
+    ("command.stop_monitoring", "Keine Systemmetriken mehr senden"),
+    ("command.get_alert_rules", "Gespeicherte Warnregeln"),
+    ("command.add_alert_rule", "Eine Regel speichern, die warnt, wenn eine Metrik über oder unter einem Schwellenwert bleibt"),
+    ("command.remove_alert_rule", "Eine Warnregel löschen"),
+    ("command.start_kiosk", "Dashboard-Bereiche im Vollbild rotieren lassen"),
+    ("command.stop_kiosk", "Den Kioskmodus verlassen"),
+    ("command.get_kiosk_status", "Ob der Kioskmodus aktiv ist und der aktuelle Bereich"),
+    ("command.set_kiosk_config", "Die Kioskbereiche und die Rotation ändern"),
+    ("command.scan_wifi_networks", "WLAN-Netzwerke in der Nähe und Kanalbelegung"),
+    ("command.get_capture_device_usage", "Apps, die Kamera oder Mikrofon verwenden"),
+    ("command.get_security_products", "Bei Windows registrierte Virenschutz- und Firewall-Produkte"),
+    ("command.get_perf_counters", "Aktuelle Werte der zugelassenen Windows-Leistungsindikatoren"),
+    ("command.get_ebpf_status", "Ob eBPF-Metriken hier laufen können und was fehlt"),
+    ("command.collect_ebpf_metrics", "E/A-Latenz pro Prozess und TCP-Neuübertragungen mit eBPF verfolgen"),
+    ("command.get_activation_status", "Edition und Aktivierungsstatus des Betriebssystems"),
+    ("command.get_pending_reboot", "Ob ein Neustart aussteht und warum"),
+    ("command.get_boot_history", "Letzte Starts und Herunterfahrvorgänge"),
+    ("command.get_service_status", "Status eines Systemdienstes"),
+    ("command.start_service", "Einen Systemdienst nach Bestätigung starten"),
+    ("command.stop_service", "Einen Systemdienst nach Bestätigung beenden"),
+    ("command.restart_service", "Einen Systemdienst nach Bestätigung neu starten"),
+    ("command.export_history_parquet", "Den aufgezeichneten Verlauf in eine Parquet-Datei schreiben"),
+    ("command.backup_app_data", "Einstellungen und optional den Verlauf in einer Datei sichern"),
+    ("command.restore_app_data", "Einstellungen und Verlauf aus einer Sicherung wiederherstellen"),
+    ("command.run_benchmark", "Einen Benchmark ausführen und das Ergebnis aufzeichnen"),
+    ("command.run_scaling_benchmark", "Messen, wie das CPU-Ergebnis mit der Anzahl der Threads skaliert"),
+    ("command.run_gpu_benchmark", "Den GPU-Rechen-Benchmark ausführen"),
+    ("command.get_benchmark_history", "Frühere Benchmark-Ergebnisse"),
+    ("command.get_benchmark_timeline", "Ein Benchmark-Lauf, überlagert mit der während des Laufs aufgezeichneten Last, den Taktfrequenzen und Temperaturen"),
+];
+
+fn catalog(locale: Locale) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        Locale::En => EN,
+        Locale::Es => ES,
+        Locale::De => DE,
+    }
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    catalog(locale)
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, template)| *template)
+}
+
+pub fn current() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::Es,
+        2 => Locale::De,
+        _ => Locale::En,
+    }
+}
+
+pub fn set_current(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+// Render `key` in the active locale, falling back to English and then to the key itself
+pub fn render(key: &str, params: &BTreeMap<&'static str, String>) -> String {
+    let template = lookup(current(), key)
+        .or_else(|| lookup(Locale::En, key))
+        .unwrap_or(key);
+    params
+        .iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+#[tauri::command]
+pub fn get_locale() -> Locale {
+    current()
+}
+
+#[tauri::command]
+pub fn set_locale(locale: Locale, config: State<ConfigState>) -> Result<(), CommandError> {
+    config.update(|config| config.locale = locale)?;
+    set_current(locale);
+    Ok(())
+}
+
+// Message templates for a locale (the active one by default), keyed like `CommandError::key`
+#[tauri::command]
+pub fn get_message_catalog(locale: Option<Locale>) -> BTreeMap<&'static str, &'static str> {
+    catalog(locale.unwrap_or_else(current))
+        .iter()
+        .copied()
+        .collect()
+}
@@ -0,0 +1,67 @@
+// Background refresh of values the UI otherwise fetches once: the computer name and total
+// memory. Both can change while the app runs (hostname changes, memory hot-added to a VM), so
+// they are re-read periodically and `system-identity-changed` is emitted when either differs.
+// The refresh pauses while no window is subscribed to the event.
+
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::subscriptions::EventSubscriptions;
+use crate::{read_computer_name, read_total_memory, CppLibrary};
+
+pub const IDENTITY_CHANGED_EVENT: &str = "system-identity-changed";
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+// None where the value couldn't be read
+#[derive(Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemIdentity {
+    pub computer_name: Option<String>,
+    pub total_memory: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityChange {
+    pub previous: SystemIdentity,
+    pub current: SystemIdentity,
+}
+
+fn read_identity(lib_state: &CppLibrary) -> SystemIdentity {
+    SystemIdentity {
+        computer_name: read_computer_name(lib_state).ok(),
+        total_memory: read_total_memory(lib_state).ok(),
+    }
+}
+
+pub fn start_refresh(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut previous = read_identity(&app.state::<CppLibrary>());
+
+        loop {
+            app.state::<EventSubscriptions>()
+                .wait_for_listeners(IDENTITY_CHANGED_EVENT);
+            std::thread::sleep(REFRESH_INTERVAL);
+
+            let current = read_identity(&app.state::<CppLibrary>());
+            if current == previous {
+                continue;
+            }
+
+            println!("✓ System identity changed");
+            let change = IdentityChange {
+                previous,
+                current: current.clone(),
+            };
+            if let Err(e) = app.emit(IDENTITY_CHANGED_EVENT, change) {
+                eprintln!(
+                    "⚠ Warning: Failed to emit {}: {}",
+                    IDENTITY_CHANGED_EVENT, e
+                );
+            }
+            previous = current;
+        }
+    });
+}
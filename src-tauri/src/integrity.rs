@@ -0,0 +1,230 @@
+// Integrity watch for critical files such as the hosts file.
+// Each configured path is hashed (SHA-256) periodically and compared with the last hash stored
+// in the history database, so edits made while the app wasn't running are caught as well.
+// Changes are recorded and emitted as `file-integrity-changed`.
+
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::history::History;
+use crate::report::unix_timestamp;
+
+pub const INTEGRITY_CHANGED_EVENT: &str = "file-integrity-changed";
+
+pub const HOSTS_FILE: &str = if cfg!(target_os = "windows") {
+    r"C:\Windows\System32\drivers\etc\hosts"
+} else {
+    "/etc/hosts"
+};
+
+const DEFAULT_EVENT_LIMIT: u32 = 100;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct IntegrityConfig {
+    pub paths: Vec<PathBuf>,
+    pub interval_minutes: u64,
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        IntegrityConfig {
+            paths: vec![PathBuf::from(HOSTS_FILE)],
+            interval_minutes: 15,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityChange {
+    pub path: String,
+    pub timestamp: i64,
+    // None when the file didn't exist before, or no longer exists
+    pub previous_hash: Option<String>,
+    pub current_hash: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileIntegrity {
+    pub path: String,
+    pub hash: Option<String>,
+    pub checked_at: Option<i64>,
+    pub changed_at: Option<i64>,
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Some(
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect(),
+    )
+}
+
+// Hash every path and compare with the stored baseline; the first check of a path only
+// records its baseline
+fn check(history: &History, paths: &[PathBuf]) -> Result<Vec<IntegrityChange>, String> {
+    let now = unix_timestamp() as i64;
+    let conn = history.conn()?;
+    let mut changes = Vec::new();
+
+    for path in paths {
+        let key = path.display().to_string();
+        let current = hash_file(path);
+        let stored: Option<Option<String>> = conn
+            .query_row(
+                "SELECT hash FROM file_hashes WHERE path = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+
+        let changed = stored.as_ref().is_some_and(|previous| *previous != current);
+        conn.execute(
+            "INSERT INTO file_hashes (path, hash, checked_at, changed_at) VALUES (?1, ?2, ?3, NULL)
+             ON CONFLICT (path) DO UPDATE SET hash = ?2, checked_at = ?3,
+                 changed_at = CASE WHEN ?4 THEN ?3 ELSE changed_at END",
+            params![key, current, now, changed],
+        )
+        .map_err(|e| e.to_string())?;
+
+        if changed {
+            let change = IntegrityChange {
+                path: key,
+                timestamp: now,
+                previous_hash: stored.flatten(),
+                current_hash: current,
+            };
+            conn.execute(
+                "INSERT INTO integrity_events (path, timestamp, previous_hash, current_hash)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    change.path,
+                    change.timestamp,
+                    change.previous_hash,
+                    change.current_hash
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+fn integrity_config(app: &AppHandle) -> IntegrityConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.integrity.clone())
+        .unwrap_or_default()
+}
+
+// Background job re-hashing the configured files at the configured interval
+pub fn start_watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let config = integrity_config(&app);
+        match check(&app.state::<History>(), &config.paths) {
+            Ok(changes) => {
+                for change in changes {
+                    eprintln!("⚠ Warning: {} changed", change.path);
+                    if let Err(e) = app.emit(INTEGRITY_CHANGED_EVENT, &change) {
+                        eprintln!(
+                            "⚠ Warning: Failed to emit {}: {}",
+                            INTEGRITY_CHANGED_EVENT, e
+                        );
+                    }
+                }
+            }
+            Err(e) => eprintln!("Integrity check failed: {}", e),
+        }
+        battery_saver::wait(&app, Duration::from_secs(config.interval_minutes.max(1) * 60));
+    });
+}
+
+#[tauri::command]
+pub fn get_integrity_status(
+    history: State<History>,
+    config: State<ConfigState>,
+) -> Result<Vec<FileIntegrity>, CommandError> {
+    let paths = config.get()?.integrity.paths.clone();
+    let conn = history.conn()?;
+    let mut status = Vec::new();
+    for path in paths {
+        let path = path.display().to_string();
+        let stored = conn
+            .query_row(
+                "SELECT hash, checked_at, changed_at FROM file_hashes WHERE path = ?1",
+                params![path],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let (hash, checked_at, changed_at) = stored.unwrap_or((None, None, None));
+        status.push(FileIntegrity {
+            path,
+            hash,
+            checked_at,
+            changed_at,
+        });
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn set_integrity_paths(
+    paths: Vec<PathBuf>,
+    config: State<ConfigState>,
+) -> Result<(), CommandError> {
+    config.update(|config| config.integrity.paths = paths)?;
+    Ok(())
+}
+
+// Recorded changes, newest first
+#[tauri::command]
+pub fn get_integrity_events(
+    limit: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<IntegrityChange>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT path, timestamp, previous_hash, current_hash FROM integrity_events
+             ORDER BY timestamp DESC, id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let events = statement
+        .query_map(params![limit.unwrap_or(DEFAULT_EVENT_LIMIT)], |row| {
+            Ok(IntegrityChange {
+                path: row.get(0)?,
+                timestamp: row.get(1)?,
+                previous_hash: row.get(2)?,
+                current_hash: row.get(3)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(events)
+}
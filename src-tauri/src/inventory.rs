@@ -0,0 +1,564 @@
+// Hardware inventory with a change log.
+// Disks, installed RAM, GPUs and serial-numbered USB devices are listed periodically and compared
+// with the inventory stored in the history database, so hardware swapped while the app wasn't
+// running is caught as well. Every difference is recorded as added, removed or replaced and
+// emitted as `hardware-inventory-changed`.
+// Items are keyed by where they sit (disk device, PCI slot) so a different model in the same
+// place reads as a replacement; USB devices are keyed by vendor, product and serial number.
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::history::History;
+use crate::os_command::run_text;
+use crate::report::unix_timestamp;
+use crate::{read_total_memory, CppLibrary};
+
+pub const INVENTORY_CHANGED_EVENT: &str = "hardware-inventory-changed";
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_CHANGE_LIMIT: u32 = 100;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InventoryCategory {
+    Disk,
+    Memory,
+    Gpu,
+    Usb,
+}
+
+impl InventoryCategory {
+    const ALL: [InventoryCategory; 4] = [
+        InventoryCategory::Disk,
+        InventoryCategory::Memory,
+        InventoryCategory::Gpu,
+        InventoryCategory::Usb,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            InventoryCategory::Disk => "disk",
+            InventoryCategory::Memory => "memory",
+            InventoryCategory::Gpu => "gpu",
+            InventoryCategory::Usb => "usb",
+        }
+    }
+
+    fn parse(category: &str) -> Option<Self> {
+        InventoryCategory::ALL
+            .into_iter()
+            .find(|known| known.as_str() == category)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InventoryChangeKind {
+    Added,
+    Removed,
+    Replaced,
+}
+
+impl InventoryChangeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            InventoryChangeKind::Added => "added",
+            InventoryChangeKind::Removed => "removed",
+            InventoryChangeKind::Replaced => "replaced",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "added" => Some(InventoryChangeKind::Added),
+            "removed" => Some(InventoryChangeKind::Removed),
+            "replaced" => Some(InventoryChangeKind::Replaced),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    pub category: InventoryCategory,
+    // Where the item sits, e.g. `sda` or a PCI slot; stable across replacements
+    pub key: String,
+    // Model, size and serial number as far as the OS reports them
+    pub description: String,
+    pub first_seen: i64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryChange {
+    pub id: i64,
+    pub timestamp: i64,
+    pub category: InventoryCategory,
+    pub key: String,
+    pub kind: InventoryChangeKind,
+    // None for additions and removals respectively
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+// Key -> description for one category
+type Listing = BTreeMap<String, String>;
+
+fn describe(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+// Windows inventory comes from CIM through PowerShell as a JSON array of objects
+fn cim_objects(
+    class: &str,
+    properties: &str,
+    filter: &str,
+) -> Result<Vec<serde_json::Value>, String> {
+    let script = format!(
+        "ConvertTo-Json -Compress -InputObject @(Get-CimInstance {} {} | Select-Object {})",
+        class, filter, properties
+    );
+    let output = run_text("powershell", &["-NoProfile", "-Command", &script])?;
+    serde_json::from_str(output.trim()).map_err(|e| e.to_string())
+}
+
+// macOS inventory comes from `system_profiler -json <data type>`
+fn profiler_items(data_type: &str) -> Result<Vec<serde_json::Value>, String> {
+    let output = run_text("system_profiler", &["-json", data_type])?;
+    let report: serde_json::Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+    Ok(report[data_type].as_array().cloned().unwrap_or_default())
+}
+
+fn text<'a>(value: &'a serde_json::Value, key: &str) -> &'a str {
+    value[key].as_str().unwrap_or_default()
+}
+
+fn list_disks() -> Result<Listing, String> {
+    let mut disks = Listing::new();
+    if cfg!(target_os = "windows") {
+        for disk in cim_objects("Win32_DiskDrive", "DeviceID, Model, SerialNumber, Size", "")? {
+            let size = gib(disk["Size"].as_u64().unwrap_or_default());
+            disks.insert(
+                text(&disk, "DeviceID").to_string(),
+                describe(&[text(&disk, "Model"), &size, text(&disk, "SerialNumber")]),
+            );
+        }
+    } else if cfg!(target_os = "macos") {
+        // NVMe and SATA drives are listed separately, each under its controller
+        for data_type in ["SPNVMeDataType", "SPSerialATADataType"] {
+            for controller in profiler_items(data_type)? {
+                for disk in controller["_items"].as_array().into_iter().flatten() {
+                    disks.insert(
+                        text(disk, "bsd_name").to_string(),
+                        describe(&[
+                            text(disk, "device_model"),
+                            text(disk, "size"),
+                            text(disk, "device_serial"),
+                        ]),
+                    );
+                }
+            }
+        }
+    } else {
+        let output = run_text(
+            "lsblk",
+            &["-d", "-b", "-J", "-o", "NAME,TYPE,MODEL,SERIAL,SIZE"],
+        )?;
+        let listing: serde_json::Value =
+            serde_json::from_str(&output).map_err(|e| e.to_string())?;
+        for disk in listing["blockdevices"].as_array().into_iter().flatten() {
+            if text(disk, "type") != "disk" {
+                continue;
+            }
+            // Older lsblk versions print sizes as strings
+            let size = disk["size"]
+                .as_u64()
+                .or_else(|| text(disk, "size").parse().ok())
+                .unwrap_or_default();
+            disks.insert(
+                text(disk, "name").to_string(),
+                describe(&[text(disk, "model"), &gib(size), text(disk, "serial")]),
+            );
+        }
+    }
+    Ok(disks)
+}
+
+fn list_memory(lib_state: &CppLibrary) -> Result<Listing, String> {
+    let total = read_total_memory(lib_state).map_err(|e| e.to_string())?;
+    Ok(Listing::from([("total".to_string(), gib(total))]))
+}
+
+fn list_gpus() -> Result<Listing, String> {
+    let mut gpus = Listing::new();
+    if cfg!(target_os = "windows") {
+        for gpu in cim_objects("Win32_VideoController", "PNPDeviceID, Name", "")? {
+            gpus.insert(
+                text(&gpu, "PNPDeviceID").to_string(),
+                text(&gpu, "Name").trim().to_string(),
+            );
+        }
+    } else if cfg!(target_os = "macos") {
+        for (index, gpu) in profiler_items("SPDisplaysDataType")?.iter().enumerate() {
+            let slot = match text(gpu, "sppci_bus") {
+                "" => index.to_string(),
+                bus => format!("{}:{}", bus, index),
+            };
+            gpus.insert(
+                slot,
+                describe(&[text(gpu, "sppci_model"), text(gpu, "spdisplays_vram")]),
+            );
+        }
+    } else {
+        // `lspci -mm` quotes each field: slot "class" "vendor" "device" ...
+        let output = run_text("lspci", &["-mm"])?;
+        for line in output.lines() {
+            let Some((slot, rest)) = line.split_once(' ') else {
+                continue;
+            };
+            let fields: Vec<&str> = rest
+                .split('"')
+                .filter(|field| !field.trim().is_empty())
+                .collect();
+            let [class, vendor, device, ..] = fields[..] else {
+                continue;
+            };
+            if [
+                "VGA compatible controller",
+                "3D controller",
+                "Display controller",
+            ]
+            .contains(&class)
+            {
+                gpus.insert(slot.to_string(), describe(&[vendor, device]));
+            }
+        }
+    }
+    Ok(gpus)
+}
+
+fn list_usb_devices() -> Result<Listing, String> {
+    let mut devices = Listing::new();
+    if cfg!(target_os = "windows") {
+        // Device IDs look like `USB\VID_046D&PID_C52B\<serial>`; generated instance IDs contain `&`
+        for device in cim_objects(
+            "Win32_PnPEntity",
+            "DeviceID, Name",
+            "-Filter \"DeviceID LIKE 'USB\\\\VID%'\"",
+        )? {
+            let id = text(&device, "DeviceID");
+            let Some((hardware, serial)) = id.rsplit_once('\\') else {
+                continue;
+            };
+            if serial.contains('&') {
+                continue;
+            }
+            devices.insert(
+                format!("{}\\{}", hardware.trim_start_matches("USB\\"), serial),
+                describe(&[text(&device, "Name"), serial]),
+            );
+        }
+    } else if cfg!(target_os = "macos") {
+        fn walk(items: &[serde_json::Value], devices: &mut Listing) {
+            for item in items {
+                let serial = text(item, "serial_num");
+                if !serial.is_empty() {
+                    devices.insert(
+                        format!(
+                            "{}:{}:{}",
+                            text(item, "vendor_id"),
+                            text(item, "product_id"),
+                            serial
+                        ),
+                        describe(&[text(item, "_name"), serial]),
+                    );
+                }
+                if let Some(children) = item["_items"].as_array() {
+                    walk(children, devices);
+                }
+            }
+        }
+        walk(&profiler_items("SPUSBDataType")?, &mut devices);
+    } else {
+        let entries = std::fs::read_dir("/sys/bus/usb/devices").map_err(|e| e.to_string())?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(path.join(file))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let serial = read("serial");
+            // Root hubs report the controller's PCI address as their serial
+            if serial.is_empty() || read("idVendor") == "1d6b" {
+                continue;
+            }
+            devices.insert(
+                format!("{}:{}:{}", read("idVendor"), read("idProduct"), serial),
+                describe(&[&read("manufacturer"), &read("product"), &serial]),
+            );
+        }
+    }
+    Ok(devices)
+}
+
+// Every category that could be listed; the rest are left out rather than read as empty, so a
+// missing tool doesn't look like all of its hardware was removed
+fn collect(lib_state: &CppLibrary) -> BTreeMap<InventoryCategory, Listing> {
+    let listings = [
+        (InventoryCategory::Disk, list_disks()),
+        (InventoryCategory::Memory, list_memory(lib_state)),
+        (InventoryCategory::Gpu, list_gpus()),
+        (InventoryCategory::Usb, list_usb_devices()),
+    ];
+    let mut inventory = BTreeMap::new();
+    for (category, listing) in listings {
+        match listing {
+            Ok(listing) => {
+                inventory.insert(category, listing);
+            }
+            Err(e) => eprintln!("Listing {} hardware failed: {}", category.as_str(), e),
+        }
+    }
+    inventory
+}
+
+fn stored_listing(history: &History, category: InventoryCategory) -> Result<Listing, String> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare("SELECT key, description FROM inventory WHERE category = ?1")
+        .map_err(|e| e.to_string())?;
+    let listing = statement
+        .query_map(params![category.as_str()], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Listing>>())
+        .map_err(|e| e.to_string())?;
+    Ok(listing)
+}
+
+// Differences between the stored and current listing of one category
+fn diff(
+    category: InventoryCategory,
+    stored: &Listing,
+    current: &Listing,
+    timestamp: i64,
+) -> Vec<InventoryChange> {
+    let change =
+        |key: &str, kind, previous: Option<&String>, current: Option<&String>| InventoryChange {
+            id: 0,
+            timestamp,
+            category,
+            key: key.to_string(),
+            kind,
+            previous: previous.cloned(),
+            current: current.cloned(),
+        };
+
+    let mut changes = Vec::new();
+    for (key, description) in current {
+        match stored.get(key) {
+            None => changes.push(change(
+                key,
+                InventoryChangeKind::Added,
+                None,
+                Some(description),
+            )),
+            Some(previous) if previous != description => changes.push(change(
+                key,
+                InventoryChangeKind::Replaced,
+                Some(previous),
+                Some(description),
+            )),
+            Some(_) => {}
+        }
+    }
+    for (key, previous) in stored {
+        if !current.contains_key(key) {
+            changes.push(change(
+                key,
+                InventoryChangeKind::Removed,
+                Some(previous),
+                None,
+            ));
+        }
+    }
+    changes
+}
+
+// Compare the current hardware with the stored inventory and record the differences; the first
+// scan of a category only records its baseline
+fn scan(history: &History, lib_state: &CppLibrary) -> Result<Vec<InventoryChange>, String> {
+    let now = unix_timestamp() as i64;
+    let mut changes = Vec::new();
+
+    for (category, current) in collect(lib_state) {
+        let stored = stored_listing(history, category)?;
+        let first_scan = stored.is_empty();
+
+        let conn = history.conn()?;
+        for mut change in diff(category, &stored, &current, now) {
+            match change.kind {
+                InventoryChangeKind::Removed => conn.execute(
+                    "DELETE FROM inventory WHERE category = ?1 AND key = ?2",
+                    params![category.as_str(), change.key],
+                ),
+                _ => conn.execute(
+                    "INSERT INTO inventory (category, key, description, first_seen)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT (category, key) DO UPDATE SET description = ?3, first_seen = ?4",
+                    params![category.as_str(), change.key, change.current, now],
+                ),
+            }
+            .map_err(|e| e.to_string())?;
+
+            if first_scan {
+                continue;
+            }
+            conn.execute(
+                "INSERT INTO inventory_changes (timestamp, category, key, kind, previous, current)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    change.timestamp,
+                    category.as_str(),
+                    change.key,
+                    change.kind.as_str(),
+                    change.previous,
+                    change.current
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            change.id = conn.last_insert_rowid();
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+// Background job rescanning the hardware; the first scan runs at startup
+pub fn start_tracking(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        match scan(&app.state::<History>(), &app.state::<CppLibrary>()) {
+            Ok(changes) => {
+                for change in changes {
+                    eprintln!(
+                        "⚠ Warning: Hardware {} {}: {}",
+                        change.kind.as_str(),
+                        change.category.as_str(),
+                        change.key
+                    );
+                    if let Err(e) = app.emit(INVENTORY_CHANGED_EVENT, &change) {
+                        eprintln!(
+                            "⚠ Warning: Failed to emit {}: {}",
+                            INVENTORY_CHANGED_EVENT, e
+                        );
+                    }
+                }
+            }
+            Err(e) => eprintln!("Hardware inventory scan failed: {}", e),
+        }
+        battery_saver::wait(&app, SCAN_INTERVAL);
+    });
+}
+
+// The inventory as of the last scan
+#[tauri::command]
+pub fn get_inventory(history: State<History>) -> Result<Vec<InventoryItem>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT category, key, description, first_seen FROM inventory
+             ORDER BY category, key",
+        )
+        .map_err(|e| e.to_string())?;
+    let items = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(items
+        .into_iter()
+        .filter_map(|(category, key, description, first_seen)| {
+            Some(InventoryItem {
+                category: InventoryCategory::parse(&category)?,
+                key,
+                description,
+                first_seen,
+            })
+        })
+        .collect())
+}
+
+// Recorded changes, newest first, optionally only for one category
+#[tauri::command]
+pub fn get_inventory_changes(
+    category: Option<InventoryCategory>,
+    limit: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<InventoryChange>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT id, timestamp, category, key, kind, previous, current FROM inventory_changes
+             WHERE ?1 IS NULL OR category = ?1
+             ORDER BY timestamp DESC, id DESC LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(
+            params![
+                category.map(InventoryCategory::as_str),
+                limit.unwrap_or(DEFAULT_CHANGE_LIMIT)
+            ],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            },
+        )
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, timestamp, category, key, kind, previous, current)| {
+            Some(InventoryChange {
+                id,
+                timestamp,
+                category: InventoryCategory::parse(&category)?,
+                key,
+                kind: InventoryChangeKind::parse(&kind)?,
+                previous,
+                current,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,207 @@
+// Kiosk mode for running an old laptop as a wall-mounted monitoring display.
+// Entering it makes the dashboard fullscreen and rotates through the configured views, emitting
+// `kiosk-view-changed` with the view to show (and null when kiosk mode ends). Features that need
+// someone at the keyboard, such as confirmation and file dialogs, refuse to run meanwhile.
+
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::startup::MAIN_WINDOW;
+
+pub const KIOSK_VIEW_CHANGED_EVENT: &str = "kiosk-view-changed";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct KioskConfig {
+    // Dashboard view names, shown in this order
+    pub views: Vec<String>,
+    pub rotation_seconds: u64,
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        KioskConfig {
+            views: ["overview", "cpu", "memory", "latency"]
+                .map(str::to_string)
+                .to_vec(),
+            rotation_seconds: 30,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KioskView {
+    pub view: String,
+    pub index: usize,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KioskStatus {
+    pub active: bool,
+    pub view: Option<KioskView>,
+    pub config: KioskConfig,
+}
+
+#[derive(Default)]
+pub struct Kiosk {
+    // Dropping the sender ends the rotation
+    rotation: Mutex<Option<Sender<()>>>,
+    view: Mutex<Option<KioskView>>,
+}
+
+impl Kiosk {
+    fn is_active(&self) -> bool {
+        self.rotation
+            .lock()
+            .map(|rotation| rotation.is_some())
+            .unwrap_or(false)
+    }
+}
+
+// Fails while kiosk mode is on; for commands that need someone to answer a dialog
+pub fn check_interactive(app: &AppHandle) -> Result<(), CommandError> {
+    if app.state::<Kiosk>().is_active() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "kiosk.active"));
+    }
+    Ok(())
+}
+
+fn kiosk_config(app: &AppHandle) -> KioskConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.kiosk.clone())
+        .unwrap_or_default()
+}
+
+fn show_view(app: &AppHandle, view: Option<KioskView>) {
+    if let Ok(mut current) = app.state::<Kiosk>().view.lock() {
+        *current = view.clone();
+    }
+    if let Err(e) = app.emit(KIOSK_VIEW_CHANGED_EVENT, view) {
+        eprintln!(
+            "⚠ Warning: Failed to emit {}: {}",
+            KIOSK_VIEW_CHANGED_EVENT, e
+        );
+    }
+}
+
+// Show each view for the configured time; config changes apply from the next view
+fn rotate(app: AppHandle, stop: mpsc::Receiver<()>) {
+    let mut index = 0;
+    loop {
+        let config = kiosk_config(&app);
+        let count = config.views.len();
+        if count > 0 {
+            index %= count;
+            show_view(
+                &app,
+                Some(KioskView {
+                    view: config.views[index].clone(),
+                    index,
+                    count,
+                }),
+            );
+            index += 1;
+        }
+
+        let wait = Duration::from_secs(config.rotation_seconds.max(1));
+        if let Err(RecvTimeoutError::Disconnected) = stop.recv_timeout(wait) {
+            return;
+        }
+    }
+}
+
+fn set_fullscreen(app: &AppHandle, fullscreen: bool) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        window.show().map_err(|e| e.to_string())?;
+        window
+            .set_fullscreen(fullscreen)
+            .map_err(|e| e.to_string())?;
+        if fullscreen {
+            window.set_focus().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+// Fullscreen the dashboard and start rotating views; also used by the `kiosk` startup mode
+pub fn enter(app: &AppHandle) -> Result<(), CommandError> {
+    if kiosk_config(app).views.is_empty() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "kiosk.noViews"));
+    }
+    let kiosk = app.state::<Kiosk>();
+    let mut rotation = kiosk.rotation.lock().map_err(|_| "Kiosk lock poisoned")?;
+    if rotation.is_some() {
+        return Ok(());
+    }
+
+    set_fullscreen(app, true)?;
+    let (stop, stopped) = mpsc::channel();
+    let handle = app.clone();
+    std::thread::spawn(move || rotate(handle, stopped));
+    *rotation = Some(stop);
+    println!("✓ Kiosk mode started");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn start_kiosk(app: AppHandle) -> Result<(), CommandError> {
+    enter(&app)
+}
+
+#[tauri::command]
+pub fn stop_kiosk(app: AppHandle, kiosk: State<Kiosk>) -> Result<(), CommandError> {
+    let stopped = kiosk
+        .rotation
+        .lock()
+        .map_err(|_| "Kiosk lock poisoned")?
+        .take();
+    if stopped.is_some() {
+        set_fullscreen(&app, false)?;
+        show_view(&app, None);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_kiosk_status(
+    kiosk: State<Kiosk>,
+    config: State<ConfigState>,
+) -> Result<KioskStatus, CommandError> {
+    Ok(KioskStatus {
+        active: kiosk.is_active(),
+        view: kiosk.view.lock().map_err(|e| e.to_string())?.clone(),
+        config: config.get()?.kiosk.clone(),
+    })
+}
+
+#[tauri::command]
+pub fn set_kiosk_config(
+    kiosk: KioskConfig,
+    config: State<ConfigState>,
+) -> Result<(), CommandError> {
+    let views: Vec<String> = kiosk
+        .views
+        .into_iter()
+        .map(|view| view.trim().to_string())
+        .filter(|view| !view.is_empty())
+        .collect();
+    if views.is_empty() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "kiosk.noViews"));
+    }
+    config.update(|config| {
+        config.kiosk = KioskConfig {
+            views,
+            rotation_seconds: kiosk.rotation_seconds.max(1),
+        }
+    })?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Continuous ping monitor for spotting flaky connections.
+// Every interval the system `ping` sends a short burst to the configured target (no raw
+// sockets needed), and the round's average latency, jitter and packet loss are recorded as
+// `latency.avg_ms`, `latency.jitter_ms` and `latency.loss_percent`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::exporter;
+use crate::os_command;
+use crate::report::unix_timestamp;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LatencyConfig {
+    pub enabled: bool,
+    // Host name or address to ping
+    pub target: String,
+    pub interval_seconds: u64,
+    // Echo requests per round
+    pub count: u32,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        LatencyConfig {
+            enabled: true,
+            target: "1.1.1.1".to_string(),
+            interval_seconds: 30,
+            count: 5,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyRound {
+    pub target: String,
+    pub timestamp: i64,
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f64,
+    // None when no reply came back
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    // Mean difference between consecutive round trips
+    pub jitter_ms: Option<f64>,
+}
+
+// Last completed round, for the status command
+#[derive(Default)]
+pub struct LatencyMonitor {
+    last: Mutex<Option<LatencyRound>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStatus {
+    pub config: LatencyConfig,
+    pub last: Option<LatencyRound>,
+}
+
+fn ping_args(target: &str, count: u32) -> Vec<String> {
+    let count = count.to_string();
+    let args: Vec<&str> = if cfg!(target_os = "windows") {
+        vec!["-n", &count, "-w", "1000", target]
+    } else if cfg!(target_os = "macos") {
+        vec!["-c", &count, "-W", "1000", target]
+    } else {
+        vec!["-c", &count, "-W", "1", target]
+    };
+    args.into_iter().map(str::to_string).collect()
+}
+
+// Round trips in ms from reply lines such as `time=12.3 ms`, `time=12ms` or `time<1ms`
+fn parse_round_trips(output: &str) -> Vec<f64> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let start = line.find("time=").or_else(|| line.find("time<"))? + 5;
+            let value: String = line[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            value.parse().ok()
+        })
+        .collect()
+}
+
+fn summarize(target: &str, sent: u32, round_trips: &[f64]) -> LatencyRound {
+    let received = (round_trips.len() as u32).min(sent);
+    let mean = |values: &[f64]| {
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+    let deltas: Vec<f64> = round_trips
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).abs())
+        .collect();
+
+    LatencyRound {
+        target: target.to_string(),
+        timestamp: unix_timestamp() as i64,
+        sent,
+        received,
+        loss_percent: (sent - received) as f64 / sent.max(1) as f64 * 100.0,
+        min_ms: round_trips.iter().copied().reduce(f64::min),
+        avg_ms: mean(round_trips),
+        max_ms: round_trips.iter().copied().reduce(f64::max),
+        jitter_ms: mean(&deltas),
+    }
+}
+
+// Host names and addresses only, so the target can't be read as a `ping` option
+fn valid_target(target: &str) -> bool {
+    !target.is_empty() && !target.starts_with('-') && !target.contains(char::is_whitespace)
+}
+
+fn ping(target: &str, count: u32) -> Result<LatencyRound, String> {
+    if !valid_target(target) {
+        return Err(format!("Invalid target: {}", target));
+    }
+    // `ping` exits with an error when nothing answers, which is just 100% loss here
+    let output = os_command::command("ping")
+        .args(ping_args(target, count))
+        .output()
+        .map_err(|e| format!("Failed to run ping: {}", e))?;
+    let round_trips = parse_round_trips(&String::from_utf8_lossy(&output.stdout));
+    Ok(summarize(target, count, &round_trips))
+}
+
+fn samples(round: &LatencyRound) -> Vec<(String, f64)> {
+    let mut samples = vec![("latency.loss_percent".to_string(), round.loss_percent)];
+    if let Some(avg) = round.avg_ms {
+        samples.push(("latency.avg_ms".to_string(), avg));
+    }
+    if let Some(jitter) = round.jitter_ms {
+        samples.push(("latency.jitter_ms".to_string(), jitter));
+    }
+    samples
+}
+
+fn latency_config(app: &AppHandle) -> LatencyConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.latency.clone())
+        .unwrap_or_default()
+}
+
+// Background job pinging the configured target; config changes apply from the next round
+pub fn start_monitor(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let config = latency_config(&app);
+        if config.enabled {
+            match ping(&config.target, config.count.max(1)) {
+                Ok(round) => {
+                    exporter::publish(&app, &samples(&round));
+                    if let Ok(mut last) = app.state::<LatencyMonitor>().last.lock() {
+                        *last = Some(round);
+                    }
+                }
+                Err(e) => eprintln!("Latency check failed: {}", e),
+            }
+        }
+        battery_saver::wait(&app, Duration::from_secs(config.interval_seconds.max(1)));
+    });
+}
+
+#[tauri::command]
+pub fn get_latency_status(
+    monitor: State<LatencyMonitor>,
+    config: State<ConfigState>,
+) -> Result<LatencyStatus, CommandError> {
+    let config = config.get()?.latency.clone();
+    let last = monitor
+        .last
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        // A round for the previous target no longer describes this one
+        .filter(|round| round.target == config.target);
+    Ok(LatencyStatus { config, last })
+}
+
+#[tauri::command]
+pub fn set_latency_config(
+    latency: LatencyConfig,
+    config: State<ConfigState>,
+) -> Result<(), CommandError> {
+    if !valid_target(&latency.target) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "latency.invalidTarget")
+                .with("target", &latency.target),
+        );
+    }
+    config.update(|config| config.latency = latency)?;
+    Ok(())
+}
@@ -1,82 +1,512 @@
-use libloading::{Library, Symbol};
-use std::ffi::CStr;
-use std::os::raw::c_char;
-use std::sync::Mutex;
-use tauri::State;
-
-// Define the function signatures matching the C++ library exports
-type GetComputerNameStringFn = unsafe extern "C" fn(*mut c_char, i32) -> bool;
-type GetTotalPhysicalMemoryFn = unsafe extern "C" fn() -> u64;
-type GetCurrentProcessIDFn = unsafe extern "C" fn() -> u32;
-type CalculateFactorialFn = unsafe extern "C" fn(i32) -> u64;
-
-// Global library state
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "ffi")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "ffi")]
+use std::sync::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use error::{CommandError, ErrorCode};
+use provider::SystemInfoProvider;
+
+#[cfg(all(feature = "ffi", feature = "no-ffi"))]
+compile_error!("`no-ffi` replaces the default `ffi` feature; build with --no-default-features --features no-ffi");
+#[cfg(not(any(feature = "ffi", feature = "no-ffi")))]
+compile_error!("enable either the `ffi` or the `no-ffi` feature");
+
+mod activation;
+mod alerts;
+mod agent;
+mod backup;
+mod battery;
+mod battery_saver;
+mod benchmark;
+mod boots;
+mod bundle;
+mod capture;
+mod certificates;
+mod cli;
+mod commands;
+mod config;
+mod cpu;
+#[cfg(all(feature = "ffi", debug_assertions))]
+mod dev_build;
+mod discovery;
+mod disks;
+mod display;
+#[cfg(feature = "ebpf")]
+mod ebpf;
+mod error;
+mod export_history;
+mod exporter;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod gpu;
+mod gpu_info;
+mod handles;
+mod history;
+mod hosts;
+mod i18n;
+mod identity;
+mod integrity;
+mod inventory;
+mod kiosk;
+mod latency;
+#[cfg(feature = "ffi")]
+mod loader;
+mod memory;
+mod memory_modules;
+mod monitor;
+mod network;
+mod network_adapters;
+mod os_command;
+mod os_info;
+mod parquet_export;
+mod perf_counters;
+mod policy;
+mod power;
+mod preflight;
+mod processes;
+mod prometheus;
+mod provider;
+mod proxy;
+mod reboot;
+#[cfg(feature = "ffi")]
+mod reload;
+mod report;
+mod rust_provider;
+#[cfg(feature = "ffi")]
+mod sandbox;
+mod scheduler;
+mod security;
+mod sensors;
+mod services;
+mod session;
+mod share;
+mod snapshot;
+mod snapshot_export;
+mod ssd;
+mod startup;
+pub mod stats;
+mod subscriptions;
+mod support_bundle;
+mod templates;
+mod thermal;
+mod uptime;
+mod user_disk_usage;
+mod watchlist;
+mod wifi;
+mod wol;
+
+// 20! is the largest factorial that fits in a u64
+const MAX_FACTORIAL_INPUT: i32 = 20;
+
+// Which native library file was loaded, for diagnostics
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryInfo {
+    pub path: String,
+    pub version: Option<String>,
+    // ABI version from the library's handshake; None before 1.4
+    #[serde(default)]
+    pub api_version: Option<String>,
+    #[serde(default)]
+    pub missing_features: Vec<MissingFeature>,
+}
+
+// An optional feature the loaded library is too old for; the Rust implementation stands in
+// where there is one
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingFeature {
+    pub feature: String,
+    // Library version that added it
+    pub since: String,
+    pub exports: Vec<String>,
+}
+
+impl std::fmt::Display for MissingFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} needs systemapi {} or later (missing {})",
+            self.feature,
+            self.since,
+            self.exports.join(", ")
+        )
+    }
+}
+
+// Build information for the library panel
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryVersion {
+    // None while no library is loaded
+    pub library: Option<LibraryInfo>,
+    // ABI version this build of the app was written against
+    pub supported_api_version: String,
+    // The loaded library predates these bindings, so some features fall back to Rust
+    pub update_available: bool,
+}
+
+// A function from systemapi.h and whether the loaded library exports it
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryCapability {
+    pub name: String,
+    pub signature: String,
+    // Library version that added it
+    pub since: String,
+    pub feature: String,
+    // False for every function while no library is loaded
+    pub available: bool,
+}
+
+// What happened to one candidate path during library loading
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase", tag = "outcome")]
+pub enum LoadOutcome {
+    NotFound,
+    ArchMismatch { found: Vec<String> },
+    LoadError { message: String },
+    Loaded { version: Option<String> },
+}
+
+// Why a path was searched
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PathOrigin {
+    // --lib-path, or the library picked earlier
+    Preferred,
+    // SYSTEMAPI_LIB_PATH
+    Environment,
+    // library.searchPaths in the config
+    Settings,
+    // The app's bundled resources
+    Bundled,
+    // Build output locations, in development builds
+    Development,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadAttempt {
+    pub path: String,
+    pub origin: PathOrigin,
+    #[serde(flatten)]
+    pub outcome: LoadOutcome,
+}
+
+// Everything needed to work out why the library isn't loaded
+#[cfg(feature = "ffi")]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryLoadReport {
+    pub library: Option<LibraryInfo>,
+    // Why the last load failed; None after a successful load
+    pub error: Option<String>,
+    // Current value of SYSTEMAPI_LIB_PATH
+    pub env_lib_path: Option<String>,
+    pub search_paths: Vec<std::path::PathBuf>,
+    pub attempts: Vec<LoadAttempt>,
+}
+
+// Global library state.
+// Callers clone the `Arc` and drop the lock before calling into the library, so concurrent
+// queries never wait on each other and a reload only waits for the pointer swap. A library
+// being replaced stays loaded until the last in-flight call through it returns.
 struct CppLibrary {
-    lib: Mutex<Option<Library>>,
+    #[cfg(feature = "ffi")]
+    loaded: RwLock<Option<Arc<loader::LoadedLibrary>>>,
+    #[cfg(feature = "ffi")]
+    trace: RwLock<Vec<LoadAttempt>>,
+    #[cfg(feature = "ffi")]
+    load_error: RwLock<Option<String>>,
+    // Commands whose calls go through the library helper
+    #[cfg(feature = "ffi")]
+    sandboxed: RwLock<Vec<String>>,
+    // Cleared by `library.nativeEnabled = false`, which sends every query to the Rust providers
+    #[cfg(feature = "ffi")]
+    native_enabled: AtomicBool,
 }
 
-// Tauri commands
-#[tauri::command]
-fn get_computer_name(lib_state: State<CppLibrary>) -> Result<String, String> {
-    let lib_guard = lib_state.lib.lock().unwrap();
-    let lib = lib_guard.as_ref().ok_or("Library not loaded")?;
-
-    unsafe {
-        let get_name: Symbol<GetComputerNameStringFn> = lib
-            .get(b"GetComputerNameString")
-            .map_err(|e| e.to_string())?;
-
-        let mut buffer = vec![0u8; 256];
-        if get_name(buffer.as_mut_ptr() as *mut c_char, buffer.len() as i32) {
-            let name = CStr::from_ptr(buffer.as_ptr() as *const c_char)
-                .to_string_lossy()
-                .into_owned();
-            Ok(name)
-        } else {
-            Err("Failed to get computer name".to_string())
+impl CppLibrary {
+    #[cfg(feature = "ffi")]
+    fn loaded(&self) -> Result<Arc<loader::LoadedLibrary>, CommandError> {
+        self.loaded
+            .read()
+            .map_err(|_| "Library lock poisoned")?
+            .clone()
+            .ok_or_else(CommandError::library_not_loaded)
+    }
+
+    // The loaded library, unless the config switched the native backend off
+    #[cfg(feature = "ffi")]
+    fn native(&self) -> Result<Arc<loader::LoadedLibrary>, CommandError> {
+        if !self.native_enabled.load(Ordering::Relaxed) {
+            return Err(CommandError::library_not_loaded());
+        }
+        self.loaded()
+    }
+
+    #[cfg(feature = "ffi")]
+    fn replace(&self, loaded: loader::LoadedLibrary) -> Result<(), CommandError> {
+        *self.loaded.write().map_err(|_| "Library lock poisoned")? = Some(Arc::new(loaded));
+        Ok(())
+    }
+
+    // Remove the library so new calls fall back to Rust; in-flight calls keep their `Arc`
+    #[cfg(feature = "ffi")]
+    fn take(&self) -> Result<Option<Arc<loader::LoadedLibrary>>, CommandError> {
+        Ok(self
+            .loaded
+            .write()
+            .map_err(|_| "Library lock poisoned")?
+            .take())
+    }
+
+    #[cfg(feature = "ffi")]
+    fn sandboxed(&self) -> Vec<String> {
+        self.sandboxed
+            .read()
+            .map(|sandboxed| sandboxed.clone())
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "ffi")]
+    fn set_sandboxed(&self, commands: Vec<String>) {
+        if let Ok(mut sandboxed) = self.sandboxed.write() {
+            *sandboxed = commands;
+        }
+    }
+
+    // Apply the `[library]` settings that can change while running
+    fn configure(&self, library: &config::LibraryConfig) {
+        #[cfg(feature = "ffi")]
+        {
+            self.native_enabled
+                .store(library.native_enabled, Ordering::Relaxed);
+            self.set_sandboxed(library.sandboxed_commands.clone());
+        }
+        #[cfg(feature = "no-ffi")]
+        let _ = library;
+    }
+
+    // Record the outcome of a load attempt
+    #[cfg(feature = "ffi")]
+    fn set_trace(&self, trace: Vec<LoadAttempt>, error: Option<String>) {
+        if let Ok(mut current) = self.trace.write() {
+            *current = trace;
         }
+        if let Ok(mut current) = self.load_error.write() {
+            *current = error;
+        }
+    }
+
+    fn is_loaded(&self) -> bool {
+        #[cfg(feature = "ffi")]
+        return self.loaded().is_ok();
+        #[cfg(feature = "no-ffi")]
+        return false;
+    }
+
+    fn info(&self) -> Option<LibraryInfo> {
+        #[cfg(feature = "ffi")]
+        return self.loaded().ok().map(|loaded| loaded.info.clone());
+        #[cfg(feature = "no-ffi")]
+        return None;
+    }
+
+    // The native library when one is loaded and enabled, otherwise the Rust fallback
+    fn provider(&self) -> Arc<dyn SystemInfoProvider> {
+        #[cfg(feature = "ffi")]
+        if let Ok(loaded) = self.native() {
+            return loaded;
+        }
+        Arc::new(rust_provider::RustProvider)
+    }
+
+    // The provider for `command`: the library helper when the command is sandboxed
+    #[cfg(feature = "ffi")]
+    fn provider_for(&self, command: &str) -> Arc<dyn SystemInfoProvider> {
+        match self.native() {
+            Ok(loaded) if self.sandboxed().iter().any(|name| name == command) => {
+                Arc::new(sandbox::Sandboxed(loaded))
+            }
+            _ => self.provider(),
+        }
+    }
+
+    #[cfg(feature = "no-ffi")]
+    fn provider_for(&self, _command: &str) -> Arc<dyn SystemInfoProvider> {
+        self.provider()
+    }
+
+    // Run `read` against the provider for `command`; failures of the native library get their
+    // own error code so the frontend can tell them from the Rust providers'
+    fn call<T>(
+        &self,
+        command: &str,
+        read: impl FnOnce(&dyn SystemInfoProvider) -> Result<T, String>,
+    ) -> Result<T, CommandError> {
+        let provider = self.provider_for(command);
+        read(provider.as_ref()).map_err(|e| match provider.backend() {
+            provider::Backend::Native => {
+                CommandError::new(ErrorCode::NativeCallFailed, "native.callFailed")
+                    .with("detail", e)
+            }
+            provider::Backend::Rust => CommandError::from(e),
+        })
+    }
+
+    fn trace(&self) -> Vec<LoadAttempt> {
+        #[cfg(feature = "ffi")]
+        return self.trace.read().map(|trace| trace.clone()).unwrap_or_default();
+        #[cfg(feature = "no-ffi")]
+        return Vec::new();
     }
 }
 
+fn check_factorial_input(n: i32) -> Result<(), CommandError> {
+    if (0..=MAX_FACTORIAL_INPUT).contains(&n) {
+        Ok(())
+    } else {
+        Err(CommandError::new(ErrorCode::InvalidInput, "factorial.outOfRange")
+            .with("max", MAX_FACTORIAL_INPUT))
+    }
+}
+
+// Run `work` on a blocking thread so a slow native call never holds up the async runtime,
+// and fail with a timeout after `library.callTimeoutSeconds`. A call that never returns keeps
+// its thread, but the command answers anyway.
+async fn call_blocking<T: Send + 'static>(
+    app: AppHandle,
+    command: &'static str,
+    work: impl FnOnce(&AppHandle) -> Result<T, CommandError> + Send + 'static,
+) -> Result<T, CommandError> {
+    let timeout = app
+        .state::<config::ConfigState>()
+        .get()?
+        .library
+        .call_timeout_seconds
+        .max(1);
+    let task = tauri::async_runtime::spawn_blocking(move || work(&app));
+    match tokio::time::timeout(Duration::from_secs(timeout), task).await {
+        Ok(result) => result.map_err(|e| e.to_string())?,
+        Err(_) => Err(CommandError::new(ErrorCode::Timeout, "native.timeout")
+            .with("command", command)
+            .with("seconds", timeout)),
+    }
+}
+
+// Tauri commands
 #[tauri::command]
-fn get_total_memory(lib_state: State<CppLibrary>) -> Result<u64, String> {
-    let lib_guard = lib_state.lib.lock().unwrap();
-    let lib = lib_guard.as_ref().ok_or("Library not loaded")?;
+async fn get_computer_name(app: AppHandle) -> Result<String, CommandError> {
+    call_blocking(app, "get_computer_name", |app| {
+        read_computer_name(&app.state())
+    })
+    .await
+}
 
-    unsafe {
-        let get_memory: Symbol<GetTotalPhysicalMemoryFn> = lib
-            .get(b"GetTotalPhysicalMemory")
-            .map_err(|e| e.to_string())?;
+fn read_computer_name(lib_state: &CppLibrary) -> Result<String, CommandError> {
+    lib_state.call("get_computer_name", |provider| provider.computer_name())
+}
 
-        Ok(get_memory())
-    }
+#[tauri::command]
+async fn get_total_memory(app: AppHandle) -> Result<u64, CommandError> {
+    call_blocking(app, "get_total_memory", |app| {
+        read_total_memory(&app.state())
+    })
+    .await
+}
+
+fn read_total_memory(lib_state: &CppLibrary) -> Result<u64, CommandError> {
+    lib_state.call("get_total_memory", |provider| provider.total_physical_memory())
 }
 
 #[tauri::command]
-fn get_process_id(lib_state: State<CppLibrary>) -> Result<u32, String> {
-    let lib_guard = lib_state.lib.lock().unwrap();
-    let lib = lib_guard.as_ref().ok_or("Library not loaded")?;
+async fn get_process_id(app: AppHandle) -> Result<u32, CommandError> {
+    call_blocking(app, "get_process_id", |app| read_process_id(&app.state())).await
+}
 
-    unsafe {
-        let get_pid: Symbol<GetCurrentProcessIDFn> = lib
-            .get(b"GetCurrentProcessID")
-            .map_err(|e| e.to_string())?;
+fn read_process_id(lib_state: &CppLibrary) -> Result<u32, CommandError> {
+    lib_state.call("get_process_id", |provider| provider.current_process_id())
+}
 
-        Ok(get_pid())
-    }
+#[tauri::command]
+async fn calculate_factorial(n: i32, app: AppHandle) -> Result<u64, CommandError> {
+    call_blocking(app, "calculate_factorial", move |app| {
+        read_factorial(&app.state(), n)
+    })
+    .await
+}
+
+fn read_factorial(lib_state: &CppLibrary, n: i32) -> Result<u64, CommandError> {
+    check_factorial_input(n)?;
+    lib_state.call("calculate_factorial", |provider| provider.calculate_factorial(n))
 }
 
+// Which backend currently answers the system info commands
 #[tauri::command]
-fn calculate_factorial(n: i32, lib_state: State<CppLibrary>) -> Result<u64, String> {
-    let lib_guard = lib_state.lib.lock().unwrap();
-    let lib = lib_guard.as_ref().ok_or("Library not loaded")?;
+fn get_active_backend(lib_state: State<CppLibrary>) -> provider::Backend {
+    lib_state.provider().backend()
+}
 
-    unsafe {
-        let calc_factorial: Symbol<CalculateFactorialFn> = lib
-            .get(b"CalculateFactorial")
-            .map_err(|e| e.to_string())?;
+// Every path tried while loading the library and why it was skipped
+#[tauri::command]
+fn get_library_load_trace(lib_state: State<CppLibrary>) -> Vec<LoadAttempt> {
+    lib_state.trace()
+}
 
-        Ok(calc_factorial(n))
+// The load trace together with the search settings and the resulting error
+#[cfg(feature = "ffi")]
+#[tauri::command]
+fn get_library_load_report(
+    lib_state: State<CppLibrary>,
+    config: State<config::ConfigState>,
+) -> Result<LibraryLoadReport, CommandError> {
+    Ok(LibraryLoadReport {
+        library: lib_state.info(),
+        error: lib_state
+            .load_error
+            .read()
+            .map_err(|_| "Library lock poisoned")?
+            .clone(),
+        env_lib_path: std::env::var_os(loader::LIB_PATH_VAR)
+            .map(|value| value.to_string_lossy().into_owned()),
+        search_paths: config.get()?.library.search_paths.clone(),
+        attempts: lib_state.trace(),
+    })
+}
+
+// Which native functions the loaded library provides; the Rust providers cover the rest
+#[cfg(feature = "ffi")]
+#[tauri::command]
+fn get_library_capabilities(lib_state: State<CppLibrary>) -> Vec<LibraryCapability> {
+    let loaded = lib_state.loaded().ok();
+    ffi::EXPORTS
+        .iter()
+        .map(|spec| LibraryCapability {
+            name: spec.name.to_string(),
+            signature: spec.signature.to_string(),
+            since: spec.since_version(),
+            feature: spec.feature.to_string(),
+            available: loaded.as_ref().is_some_and(|loaded| loaded.api.provides(spec)),
+        })
+        .collect()
+}
+
+#[cfg(feature = "ffi")]
+#[tauri::command]
+fn get_library_version(lib_state: State<CppLibrary>) -> LibraryVersion {
+    let loaded = lib_state.loaded().ok();
+    LibraryVersion {
+        library: loaded.as_ref().map(|loaded| loaded.info.clone()),
+        supported_api_version: format!("{}.{}", ffi::API_VERSION.0, ffi::API_VERSION.1),
+        // Builds without the handshake are older than 1.4
+        update_available: loaded
+            .is_some_and(|loaded| loaded.api.api_version().is_none_or(|api| api < ffi::API_VERSION)),
     }
 }
 
@@ -86,96 +516,316 @@ fn get_platform() -> String {
 }
 
 // Load the C++ library
-fn load_cpp_library() -> Result<Library, String> {
-    // Get the path to the executable directory
-    let exe_dir = std::env::current_exe()
-        .ok()
-        .and_then(|path| path.parent().map(|p| p.to_path_buf()));
-
-    let lib_name = if cfg!(target_os = "windows") {
-        "systemapi.dll"
-    } else if cfg!(target_os = "macos") {
-        "libsystemapi.dylib"
-    } else {
-        "libsystemapi.so"
+#[cfg(feature = "ffi")]
+fn load_library(app: &tauri::AppHandle, lib_path: Option<&std::path::Path>) -> CppLibrary {
+    let mut trace = Vec::new();
+    let (loaded, load_error) = match loader::load_cpp_library(app, lib_path, &mut trace) {
+        Ok(loaded) => {
+            println!("✓ C++ library loaded successfully!");
+            (Some(loaded), None)
+        }
+        Err(e) => {
+            eprintln!("⚠ Warning: {}", e);
+            eprintln!("Falling back to the Rust providers for system info.");
+            (None, Some(e.to_string()))
+        }
     };
 
-    // Try multiple paths in order of preference
-    let paths_to_try = vec![
-        // 1. Same directory as executable (for dev runs with cargo run)
-        exe_dir.as_ref().map(|dir| dir.join(lib_name)),
-        // 2. Tauri resources directory structure
-        // Windows NSIS/MSI: resources are in lib/ subdirectory relative to exe
-        exe_dir.as_ref().map(|dir| dir.join("lib").join(lib_name)),
-        // 3. macOS app bundle Resources directory
-        exe_dir.as_ref().map(|dir| dir.join("../Resources/lib").join(lib_name)),
-        // 4. Development path (src-tauri/lib)
-        Some(std::path::PathBuf::from(format!("lib/{}", lib_name))),
-        // 5. Development path (cpp build output)
-        Some(std::path::PathBuf::from(if cfg!(target_os = "windows") {
-            "../cpp_cross_platform/build/bin/Release/systemapi.dll"
-        } else if cfg!(target_os = "macos") {
-            "../cpp_cross_platform/build/lib/libsystemapi.dylib"
-        } else {
-            "../cpp_cross_platform/build/lib/libsystemapi.so"
-        })),
-    ];
-
-    for path_option in paths_to_try {
-        if let Some(path) = path_option {
-            if path.exists() {
-                unsafe {
-                    match Library::new(&path) {
-                        Ok(lib) => {
-                            println!("✓ Loaded C++ library from: {}", path.display());
-                            return Ok(lib);
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to load from {}: {}", path.display(), e);
-                        }
-                    }
-                }
-            }
-        }
+    CppLibrary {
+        loaded: RwLock::new(loaded.map(Arc::new)),
+        trace: RwLock::new(trace),
+        load_error: RwLock::new(load_error),
+        sandboxed: RwLock::default(),
+        native_enabled: AtomicBool::new(true),
     }
+}
 
-    Err(format!(
-        "Failed to load library '{}' from any location.\n\n\
-        For development, make sure to build the C++ library first:\n\
-        cd cpp_cross_platform && mkdir build && cd build && cmake .. && cmake --build .",
-        lib_name
-    ))
+#[cfg(feature = "no-ffi")]
+fn load_library(_: &tauri::AppHandle, lib_path: Option<&std::path::Path>) -> CppLibrary {
+    if lib_path.is_some() {
+        eprintln!("⚠ Warning: --lib-path has no effect in a build without the C++ library");
+    }
+    println!("✓ Built without the C++ library, using Rust providers");
+    CppLibrary {}
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Load the C++ library
-    let library = match load_cpp_library() {
-        Ok(lib) => {
-            println!("✓ C++ library loaded successfully!");
-            Some(lib)
-        }
-        Err(e) => {
-            eprintln!("⚠ Warning: {}", e);
-            eprintln!("The app will run but system info features will be unavailable.");
-            None
-        }
-    };
-
-    let cpp_lib_state = CppLibrary {
-        lib: Mutex::new(library),
-    };
+    let args = cli::CliArgs::from_env();
+    if args.list {
+        commands::print_list();
+        return;
+    }
+    if let Some(library) = &args.ffi_helper {
+        #[cfg(feature = "ffi")]
+        sandbox::serve(library);
+        #[cfg(feature = "no-ffi")]
+        eprintln!(
+            "⚠ Warning: --ffi-helper {} has no effect in a build without the C++ library",
+            library.display()
+        );
+        return;
+    }
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(cpp_lib_state)
-        .invoke_handler(tauri::generate_handler![
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(args)
+        .manage(agent::AgentServer::default())
+        .manage(prometheus::PrometheusExporter::default())
+        .manage(share::ShareLinks::default())
+        .manage(bundle::ImportedSession::default())
+        .manage(preflight::Preflight::default())
+        .manage(cpu::CpuMonitor::default())
+        .manage(latency::LatencyMonitor::default())
+        .manage(monitor::Monitoring::default())
+        .manage(kiosk::Kiosk::default())
+        .manage(network::NetworkMonitor::default())
+        .manage(processes::ProcessList::default())
+        .manage(user_disk_usage::UserDiskUsage::default())
+        .manage(scheduler::SchedulerMonitor::default())
+        .manage(session::Sessions::default())
+        .manage(subscriptions::EventSubscriptions::default())
+        .manage(watchlist::Watchlist::default())
+        .setup(|app| {
+            let args = app.state::<cli::CliArgs>().inner().clone();
+            let config = config::ConfigState::load(app.handle(), args.config);
+            let library = config.get()?.library.clone();
+            // --lib-path wins over a library picked earlier and saved in the config
+            let lib_path = args.lib_path.or(library.path.clone());
+            i18n::set_current(config.get()?.locale);
+            let startup_mode = config.get()?.startup_mode;
+            let policy = policy::Policy::load(&config.get()?.policy);
+            app.manage(config);
+            // Loaded here rather than before the builder so bundled resources can be resolved
+            app.manage(load_library(app.handle(), lib_path.as_deref()));
+            app.state::<CppLibrary>().configure(&library);
+            if args.build_lib {
+                #[cfg(all(feature = "ffi", debug_assertions))]
+                dev_build::build_if_missing(app.handle().clone());
+                #[cfg(not(all(feature = "ffi", debug_assertions)))]
+                eprintln!("⚠ Warning: --build-lib only works in development builds with the C++ library");
+            }
+            if args.watch_lib {
+                #[cfg(feature = "ffi")]
+                reload::start_watching(app.handle().clone());
+                #[cfg(feature = "no-ffi")]
+                eprintln!("⚠ Warning: --watch-lib has no effect in a build without the C++ library");
+            }
+            preflight::start(app.handle().clone());
+            identity::start_refresh(app.handle().clone());
+            app.manage(history::History::open(app.handle()));
+            history::start_maintenance(app.handle().clone());
+            app.manage(exporter::start_exporters(app.handle()));
+            // Named as in policy::SAMPLERS
+            let samplers = [
+                ("thermal", thermal::start_sampling as fn(_)),
+                ("cpu", cpu::start_sampling),
+                ("memory", memory::start_sampling),
+                ("handles", handles::start_sampling),
+                ("watchlist", watchlist::start_monitor),
+                ("integrity", integrity::start_watch),
+                ("latency", latency::start_monitor),
+                ("capture", capture::start_monitor),
+                ("inventory", inventory::start_tracking),
+                ("battery", battery::start_recording),
+                ("ssd", ssd::start_recording),
+                ("power", power::start_watching),
+                ("perfCounters", perf_counters::start_sampling),
+            ];
+            for (name, start) in samplers {
+                if policy.sampler_enabled(name) {
+                    start(app.handle().clone());
+                } else {
+                    println!("Sampler {} is disabled by policy", name);
+                }
+            }
+            battery_saver::start_watching(app.handle().clone());
+            let discovery = app.state::<config::ConfigState>().get()?.agent.discovery
+                && policy.sampler_enabled("discovery");
+            app.manage(discovery::Discovery::start(discovery));
+            app.manage(policy);
+            if app.state::<config::ConfigState>().get()?.prometheus.enabled {
+                if let Err(e) = prometheus::start_exporter(
+                    None,
+                    app.handle().clone(),
+                    app.state(),
+                    app.state(),
+                ) {
+                    eprintln!("⚠ Warning: Prometheus endpoint not started: {}", e.message);
+                }
+            }
+
+            // Headless mode keeps the window hidden and serves metrics through the agent
+            if args.headless {
+                for window in app.webview_windows().values() {
+                    window.hide()?;
+                }
+                agent::start_agent(None, app.handle().clone(), app.state(), app.state())?;
+            } else {
+                startup::apply(app.handle(), startup_mode)?;
+            }
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // A closed window no longer keeps its event streams running
+            if let tauri::WindowEvent::Destroyed = event {
+                window
+                    .state::<subscriptions::EventSubscriptions>()
+                    .remove_window(window.label());
+            }
+        })
+        // Keep commands::COMMANDS in step with this list
+        .invoke_handler(policy::guard(tauri::generate_handler![
+            commands::list_commands,
             get_computer_name,
             get_total_memory,
             get_process_id,
             calculate_factorial,
-            get_platform
-        ])
-        .run(tauri::generate_context!("tauri.conf.json"))
-        .expect("error while running tauri application");
+            get_library_load_trace,
+            #[cfg(feature = "ffi")]
+            get_library_load_report,
+            #[cfg(feature = "ffi")]
+            get_library_capabilities,
+            #[cfg(feature = "ffi")]
+            get_library_version,
+            #[cfg(feature = "ffi")]
+            loader::pick_and_load_library,
+            #[cfg(feature = "ffi")]
+            reload::reload_library,
+            #[cfg(feature = "ffi")]
+            reload::unload_library,
+            #[cfg(feature = "ffi")]
+            sandbox::get_sandboxed_commands,
+            #[cfg(feature = "ffi")]
+            sandbox::set_sandboxed_commands,
+            #[cfg(all(feature = "ffi", debug_assertions))]
+            dev_build::build_library,
+            get_platform,
+            os_info::get_os_info,
+            get_active_backend,
+            snapshot::get_system_snapshot,
+            snapshot_export::export_report,
+            startup::show_dashboard,
+            startup::get_startup_mode,
+            startup::set_startup_mode,
+            subscriptions::subscribe_events,
+            subscriptions::unsubscribe_events,
+            subscriptions::get_event_subscriptions,
+            display::get_display_settings,
+            display::set_display_settings,
+            display::format_values,
+            preflight::get_backend_status,
+            cli::get_startup_args,
+            policy::get_policy,
+            i18n::get_locale,
+            i18n::set_locale,
+            config::get_config,
+            config::set_config,
+            i18n::get_message_catalog,
+            wol::send_wol,
+            discovery::get_discovered_peers,
+            agent::start_agent,
+            agent::stop_agent,
+            prometheus::start_exporter,
+            prometheus::stop_exporter,
+            agent::compare_machines,
+            share::create_share_link,
+            bundle::export_bundle,
+            bundle::import_report,
+            bundle::get_imported_report,
+            bundle::close_imported_report,
+            support_bundle::create_support_bundle,
+            session::start_recording,
+            session::stop_recording,
+            session::start_replay,
+            session::stop_replay,
+            templates::render_report,
+            export_history::get_export_history,
+            export_history::record_copied_snippet,
+            history::get_retention_policy,
+            history::set_retention_policy,
+            history::get_storage_usage,
+            history::query_history,
+            history::get_sparkline,
+            history::add_annotation,
+            history::delete_annotation,
+            thermal::get_thermal_history,
+            sensors::get_sensors,
+            cpu::get_cpu_frequency,
+            cpu::get_cpu_info,
+            cpu::get_cpu_usage,
+            disks::get_disks,
+            gpu_info::get_gpu_info,
+            user_disk_usage::get_user_disk_usage,
+            memory::get_memory_details,
+            memory_modules::get_memory_modules,
+            battery::get_battery_health,
+            battery::get_battery_history,
+            power::get_power_status,
+            battery_saver::get_battery_saver_status,
+            uptime::get_uptime_info,
+            ssd::get_ssd_health,
+            ssd::get_ssd_wear_history,
+            network::get_network_interfaces,
+            network::get_network_throughput,
+            processes::list_processes,
+            processes::get_process_details,
+            processes::kill_process,
+            scheduler::get_scheduler_stats,
+            handles::get_handle_stats,
+            watchlist::get_watchlist_status,
+            watchlist::set_watchlist,
+            watchlist::get_process_events,
+            integrity::get_integrity_status,
+            integrity::set_integrity_paths,
+            integrity::get_integrity_events,
+            inventory::get_inventory,
+            inventory::get_inventory_changes,
+            certificates::get_certificates,
+            hosts::get_hosts_entries,
+            proxy::get_proxy_status,
+            latency::get_latency_status,
+            latency::set_latency_config,
+            monitor::start_monitoring,
+            monitor::stop_monitoring,
+            alerts::get_alert_rules,
+            alerts::add_alert_rule,
+            alerts::remove_alert_rule,
+            kiosk::start_kiosk,
+            kiosk::stop_kiosk,
+            kiosk::get_kiosk_status,
+            kiosk::set_kiosk_config,
+            wifi::scan_wifi_networks,
+            capture::get_capture_device_usage,
+            security::get_security_products,
+            perf_counters::get_perf_counters,
+            #[cfg(feature = "ebpf")]
+            ebpf::get_ebpf_status,
+            #[cfg(feature = "ebpf")]
+            ebpf::collect_ebpf_metrics,
+            activation::get_activation_status,
+            reboot::get_pending_reboot,
+            boots::get_boot_history,
+            services::get_service_status,
+            services::start_service,
+            services::stop_service,
+            services::restart_service,
+            parquet_export::export_history_parquet,
+            backup::backup_app_data,
+            backup::restore_app_data,
+            benchmark::run_benchmark,
+            benchmark::run_scaling_benchmark,
+            benchmark::run_gpu_benchmark,
+            benchmark::get_benchmark_history,
+            benchmark::get_benchmark_timeline
+        ]))
+        .build(tauri::generate_context!("tauri.conf.json"))
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                app.state::<exporter::ExporterRegistry>().shutdown();
+            }
+        });
 }
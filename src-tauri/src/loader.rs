@@ -0,0 +1,513 @@
+// Locating and loading the systemapi C++ library.
+// Each search directory is tried with the plain file name, an arch-suffixed name and an
+// arch subdirectory, and binaries built for a different CPU are skipped before loading.
+// On Linux, versioned names (libsystemapi.so.1.2.3) are preferred, highest compatible first.
+// Before the built-in locations come the preferred path (--lib-path or the library picked
+// earlier), the entries of `SYSTEMAPI_LIB_PATH` and `library.searchPaths` from the config;
+// each of those may name a file or a directory.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::kiosk;
+use crate::sandbox;
+use crate::{ffi, CppLibrary, LibraryInfo, LoadAttempt, LoadOutcome, PathOrigin};
+
+// Architecture of this process, using Rust's names (x86_64, aarch64, ...)
+const PROCESS_ARCH: &str = std::env::consts::ARCH;
+
+// Extra search paths, separated like PATH
+pub const LIB_PATH_VAR: &str = "SYSTEMAPI_LIB_PATH";
+
+pub const LIB_NAME: &str = if cfg!(target_os = "windows") {
+    "systemapi.dll"
+} else if cfg!(target_os = "macos") {
+    "libsystemapi.dylib"
+} else {
+    "libsystemapi.so"
+};
+
+// Major version of libsystemapi this build is compatible with
+const SUPPORTED_MAJOR: u32 = ffi::API_VERSION.0;
+
+pub struct LoadedLibrary {
+    pub api: ffi::SystemApi,
+    pub info: LibraryInfo,
+    // Runs sandboxed commands against the same file
+    pub helper: sandbox::Helper,
+}
+
+#[derive(Debug)]
+pub enum LibraryError {
+    // Every library that was found targets another architecture
+    ArchMismatch {
+        path: PathBuf,
+        expected: &'static str,
+        found: Vec<&'static str>,
+    },
+    NotFound {
+        lib_name: &'static str,
+    },
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryError::ArchMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Library '{}' is built for {} but this process is {}.\n\n\
+                Rebuild the C++ library for {} or install the matching package.",
+                path.display(),
+                found.join(", "),
+                expected,
+                expected
+            ),
+            LibraryError::NotFound { lib_name } => write!(
+                f,
+                "Failed to load library '{}' from any location.\n\n\
+                For development, make sure to build the C++ library first:\n\
+                cd cpp_cross_platform && mkdir build && cd build && cmake .. && cmake --build .\n\n\
+                To use an installed copy, add its directory to {} or to library.searchPaths \
+                in the config.",
+                lib_name, LIB_PATH_VAR
+            ),
+        }
+    }
+}
+
+// Map an ELF e_machine value to a Rust arch name
+fn elf_arch(machine: u16) -> Option<&'static str> {
+    match machine {
+        3 => Some("x86"),
+        40 => Some("arm"),
+        62 => Some("x86_64"),
+        183 => Some("aarch64"),
+        243 => Some("riscv64"),
+        _ => None,
+    }
+}
+
+// Map a Mach-O cputype to a Rust arch name
+fn macho_arch(cpu_type: u32) -> Option<&'static str> {
+    match cpu_type {
+        7 => Some("x86"),
+        12 => Some("arm"),
+        0x0100_0007 => Some("x86_64"),
+        0x0100_000c => Some("aarch64"),
+        _ => None,
+    }
+}
+
+// Map a PE/COFF machine type to a Rust arch name
+fn pe_arch(machine: u16) -> Option<&'static str> {
+    match machine {
+        0x014c => Some("x86"),
+        0x01c0 | 0x01c4 => Some("arm"),
+        0x8664 => Some("x86_64"),
+        0xaa64 => Some("aarch64"),
+        _ => None,
+    }
+}
+
+// Architectures a shared library was built for, read from its ELF, Mach-O (thin or universal)
+// or PE header. None when the format isn't recognised, in which case loading is attempted anyway.
+fn binary_archs(path: &Path) -> Option<Vec<&'static str>> {
+    let mut header = [0u8; 4096];
+    let len = File::open(path).ok()?.read(&mut header).ok()?;
+    let header = &header[..len];
+
+    let u16_le = |at: usize| Some(u16::from_le_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let u16_be = |at: usize| Some(u16::from_be_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let u32_le = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    let u32_be = |at: usize| Some(u32::from_be_bytes(header.get(at..at + 4)?.try_into().ok()?));
+
+    let archs: Vec<&'static str> = match header.get(..4)? {
+        [0x7f, b'E', b'L', b'F'] => {
+            // EI_DATA: 1 = little endian, 2 = big endian
+            let machine = if header.get(5) == Some(&2) {
+                u16_be(18)?
+            } else {
+                u16_le(18)?
+            };
+            elf_arch(machine).into_iter().collect()
+        }
+        [0xcf, 0xfa, 0xed, 0xfe] | [0xce, 0xfa, 0xed, 0xfe] => {
+            macho_arch(u32_le(4)?).into_iter().collect()
+        }
+        // Universal binary: a big-endian table of slices, 20 bytes each (32 for the 64-bit form)
+        [0xca, 0xfe, 0xba, 0xbe] | [0xca, 0xfe, 0xba, 0xbf] => {
+            let entry_size = if header[3] == 0xbf { 32 } else { 20 };
+            let count = u32_be(4)? as usize;
+            (0..count)
+                .filter_map(|index| u32_be(8 + index * entry_size))
+                .filter_map(macho_arch)
+                .collect()
+        }
+        [b'M', b'Z', _, _] => {
+            let pe = u32_le(0x3c)? as usize;
+            if header.get(pe..pe + 4)? != b"PE\0\0" {
+                return None;
+            }
+            pe_arch(u16_le(pe + 4)?).into_iter().collect()
+        }
+        _ => return None,
+    };
+    (!archs.is_empty()).then_some(archs)
+}
+
+// Version numbers from a `<name>.X[.Y[.Z]]` file name
+fn so_version(file_name: &str, name: &str) -> Option<Vec<u32>> {
+    let version = file_name.strip_prefix(name)?.strip_prefix('.')?;
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+// Version of the file a path resolves to, following the usual libfoo.so -> libfoo.so.1.2.3 links
+fn library_version(path: &Path) -> Option<String> {
+    let resolved = fs::canonicalize(path).ok()?;
+    let file_name = resolved.file_name()?.to_str()?;
+    let version = so_version(file_name, LIB_NAME)?;
+    Some(
+        version
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+// Reported by the library itself from 1.3; older builds only carry it in the file name
+fn loaded_version(api: &ffi::SystemApi, path: &Path) -> Option<String> {
+    match api.library_version() {
+        Some(Ok(version)) => Some(version),
+        Some(Err(e)) => {
+            eprintln!("⚠ Warning: {}", e);
+            library_version(path)
+        }
+        None => library_version(path),
+    }
+}
+
+// The version handshake: a library built for another ABI major is refused. Builds before 1.4
+// don't report an ABI version, so their version string stands in.
+fn check_compatible(api: &ffi::SystemApi, version: Option<&str>) -> Result<(), String> {
+    if let Some((major, minor)) = api.api_version() {
+        if major != SUPPORTED_MAJOR {
+            return Err(format!(
+                "Library API version {}.{} is not compatible (need {}.x)",
+                major, minor, SUPPORTED_MAJOR
+            ));
+        }
+        return Ok(());
+    }
+    let major = version.and_then(|version| version.split('.').next()?.parse::<u32>().ok());
+    if major.is_some_and(|major| major != SUPPORTED_MAJOR) {
+        return Err(format!(
+            "Library version {} is not compatible (need {}.x)",
+            version.unwrap_or_default(),
+            SUPPORTED_MAJOR
+        ));
+    }
+    Ok(())
+}
+
+// Diagnostics for a library that just loaded, warning about the features it is too old for
+fn library_info(api: &ffi::SystemApi, path: &Path, version: Option<String>) -> LibraryInfo {
+    let missing_features = api.missing_features().to_vec();
+    for missing in &missing_features {
+        eprintln!("⚠ Warning: {}", missing);
+    }
+    LibraryInfo {
+        path: path.display().to_string(),
+        version,
+        api_version: api
+            .api_version()
+            .map(|(major, minor)| format!("{}.{}", major, minor)),
+        missing_features,
+    }
+}
+
+// Versioned copies of `name` in `dir` with a compatible major version, highest first
+fn versioned_names(dir: &Path, name: &str) -> Vec<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut versioned: Vec<(Vec<u32>, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let version = so_version(entry.file_name().to_str()?, name)?;
+            (version.first() == Some(&SUPPORTED_MAJOR)).then(|| (version, entry.path()))
+        })
+        .collect();
+    versioned.sort_by(|a, b| b.0.cmp(&a.0));
+    versioned.into_iter().map(|(_, path)| path).collect()
+}
+
+// Every file name to try inside a search directory, most specific first
+fn candidates(dir: &Path) -> Vec<PathBuf> {
+    let (stem, extension) = LIB_NAME.split_once('.').unwrap_or((LIB_NAME, ""));
+    let arch_suffixed = format!("{}-{}.{}", stem, PROCESS_ARCH, extension);
+
+    let mut paths = Vec::new();
+    for (dir, name) in [
+        (dir.join(PROCESS_ARCH), LIB_NAME),
+        (dir.to_path_buf(), arch_suffixed.as_str()),
+        (dir.to_path_buf(), LIB_NAME),
+    ] {
+        paths.extend(versioned_names(&dir, name));
+        paths.push(dir.join(name));
+    }
+    paths
+}
+
+// The candidates for a user-supplied path: the path itself if it names a file
+fn user_candidates(path: &Path) -> Vec<PathBuf> {
+    if path.is_dir() {
+        candidates(path)
+    } else {
+        vec![path.to_path_buf()]
+    }
+}
+
+// Built-in directories to search in order of preference. Bundled builds only look in the app's
+// resources: Contents/Resources/lib in a .app, lib/ next to the exe for MSI/NSIS, and
+// usr/lib/<app>/lib inside an AppImage or .deb
+fn search_dirs(app: &AppHandle) -> Vec<(PathBuf, PathOrigin)> {
+    let mut dirs: Vec<(PathBuf, PathOrigin)> = app
+        .path()
+        .resource_dir()
+        .map(|dir| {
+            vec![
+                (dir.join("lib"), PathOrigin::Bundled),
+                (dir, PathOrigin::Bundled),
+            ]
+        })
+        .unwrap_or_default();
+
+    // Development builds also look next to the executable and in the source tree
+    if cfg!(debug_assertions) {
+        if let Some(exe_dir) = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.parent().map(|p| p.to_path_buf()))
+        {
+            dirs.push((exe_dir, PathOrigin::Development));
+        }
+        dirs.push((PathBuf::from("lib"), PathOrigin::Development));
+        dirs.push((
+            PathBuf::from(if cfg!(target_os = "windows") {
+                "../cpp_cross_platform/build/bin/Release"
+            } else {
+                "../cpp_cross_platform/build/lib"
+            }),
+            PathOrigin::Development,
+        ));
+    }
+    dirs
+}
+
+// Every candidate path in the order it is tried, with where it came from
+fn search_paths(app: &AppHandle, lib_path: Option<&Path>) -> Vec<(PathBuf, PathOrigin)> {
+    let mut user: Vec<(PathBuf, PathOrigin)> = Vec::new();
+    if let Some(path) = lib_path {
+        user.push((path.to_path_buf(), PathOrigin::Preferred));
+    }
+    if let Some(value) = std::env::var_os(LIB_PATH_VAR) {
+        user.extend(
+            std::env::split_paths(&value)
+                .filter(|path| !path.as_os_str().is_empty())
+                .map(|path| (path, PathOrigin::Environment)),
+        );
+    }
+    if let Some(config) = app.try_state::<ConfigState>() {
+        if let Ok(config) = config.get() {
+            user.extend(
+                config
+                    .library
+                    .search_paths
+                    .iter()
+                    .map(|path| (path.clone(), PathOrigin::Settings)),
+            );
+        }
+    }
+
+    let mut paths: Vec<(PathBuf, PathOrigin)> = user
+        .iter()
+        .flat_map(|(path, origin)| {
+            user_candidates(path)
+                .into_iter()
+                .map(move |candidate| (candidate, *origin))
+        })
+        .collect();
+    paths.extend(search_dirs(app).iter().flat_map(|(dir, origin)| {
+        candidates(dir)
+            .into_iter()
+            .map(move |candidate| (candidate, *origin))
+    }));
+    paths
+}
+
+// Load the C++ library, trying an explicit file or directory (from --lib-path) and the
+// user's search paths first. Every candidate path is recorded in `trace` with the reason it
+// was skipped.
+pub fn load_cpp_library(
+    app: &AppHandle,
+    lib_path: Option<&Path>,
+    trace: &mut Vec<LoadAttempt>,
+) -> Result<LoadedLibrary, LibraryError> {
+    let mut mismatch = None;
+    for (path, origin) in search_paths(app, lib_path) {
+        let mut record = |outcome| {
+            trace.push(LoadAttempt {
+                path: path.display().to_string(),
+                origin,
+                outcome,
+            })
+        };
+        if !path.exists() {
+            record(LoadOutcome::NotFound);
+            continue;
+        }
+
+        if let Some(found) = binary_archs(&path) {
+            if !found.contains(&PROCESS_ARCH) {
+                eprintln!(
+                    "Skipping {}: built for {} (need {})",
+                    path.display(),
+                    found.join(", "),
+                    PROCESS_ARCH
+                );
+                record(LoadOutcome::ArchMismatch {
+                    found: found.iter().map(|arch| arch.to_string()).collect(),
+                });
+                mismatch.get_or_insert(LibraryError::ArchMismatch {
+                    path,
+                    expected: PROCESS_ARCH,
+                    found,
+                });
+                continue;
+            }
+        }
+
+        match ffi::SystemApi::open(&path) {
+            Ok(api) => {
+                let version = loaded_version(&api, &path);
+                if let Err(e) = check_compatible(&api, version.as_deref()) {
+                    eprintln!("Skipping {}: {}", path.display(), e);
+                    record(LoadOutcome::LoadError { message: e });
+                    continue;
+                }
+                match &version {
+                    Some(version) => println!(
+                        "✓ Loaded C++ library from: {} (version {})",
+                        path.display(),
+                        version
+                    ),
+                    None => println!("✓ Loaded C++ library from: {}", path.display()),
+                }
+                record(LoadOutcome::Loaded {
+                    version: version.clone(),
+                });
+                return Ok(LoadedLibrary {
+                    info: library_info(&api, &path, version),
+                    api,
+                    helper: sandbox::Helper::new(path),
+                });
+            }
+            Err(e) => {
+                eprintln!("Failed to load from {}: {}", path.display(), e);
+                record(LoadOutcome::LoadError {
+                    message: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Err(mismatch.unwrap_or(LibraryError::NotFound { lib_name: LIB_NAME }))
+}
+
+// Check a user-chosen file before it replaces the current library
+pub fn open_validated(path: &Path) -> Result<LoadedLibrary, CommandError> {
+    if let Some(found) = binary_archs(path) {
+        if !found.contains(&PROCESS_ARCH) {
+            return Err(LibraryError::ArchMismatch {
+                path: path.to_path_buf(),
+                expected: PROCESS_ARCH,
+                found,
+            }
+            .to_string()
+            .into());
+        }
+    }
+
+    let api = ffi::SystemApi::open(path).map_err(|e| match e {
+        ffi::OpenError::MissingExports(exports) => {
+            CommandError::new(ErrorCode::SymbolMissing, "library.missingExports")
+                .with("path", path.display())
+                .with("exports", exports.join(", "))
+        }
+        ffi::OpenError::Load(e) => CommandError::from(e),
+    })?;
+    let version = loaded_version(&api, path);
+    check_compatible(&api, version.as_deref())?;
+
+    Ok(LoadedLibrary {
+        info: library_info(&api, path, version),
+        api,
+        helper: sandbox::Helper::new(path.to_path_buf()),
+    })
+}
+
+// Let the user locate the library with a file dialog, then load it and remember the choice
+#[tauri::command]
+pub async fn pick_and_load_library(app: AppHandle) -> Result<LibraryInfo, CommandError> {
+    kiosk::check_interactive(&app)?;
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_title("Locate the systemapi library");
+    // Linux libraries may carry a version suffix (libsystemapi.so.1), so don't filter there
+    if !cfg!(target_os = "linux") {
+        if let Some((_, extension)) = LIB_NAME.split_once('.') {
+            dialog = dialog.add_filter("Shared library", &[extension]);
+        }
+    }
+
+    let picked = tauri::async_runtime::spawn_blocking(move || dialog.blocking_pick_file())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| CommandError::new(ErrorCode::Cancelled, "library.noneSelected"))?;
+    let path = picked.into_path().map_err(|e| e.to_string())?;
+
+    let loaded = open_validated(&path)?;
+    let info = loaded.info.clone();
+    let lib_state = app.state::<CppLibrary>();
+    lib_state.replace(loaded)?;
+    lib_state.set_trace(
+        vec![LoadAttempt {
+            path: path.display().to_string(),
+            origin: PathOrigin::Preferred,
+            outcome: LoadOutcome::Loaded {
+                version: info.version.clone(),
+            },
+        }],
+        None,
+    );
+
+    app.state::<ConfigState>()
+        .update(|config| config.library.path = Some(path.clone()))?;
+    println!("✓ Loaded C++ library from: {}", path.display());
+    Ok(info)
+}
@@ -0,0 +1,164 @@
+// Breakdown of physical memory and swap for the memory gauge, beyond the single total that
+// `get_total_memory` returns.
+// sysinfo supplies the totals. The file cache comes from `/proc/meminfo` on Linux, `vm_stat` on
+// macOS and the standby and modified page lists on Windows, which is what Task Manager shows as
+// "Cached". Only Linux separates out buffers.
+// A background job also samples usage into history as `memory.used`, `memory.available` and
+// `memory.swapUsed` (bytes) and `memory.usage` (percent), for charts over the last hours.
+
+use serde::Serialize;
+use std::time::Duration;
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+use tauri::AppHandle;
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+use crate::{battery_saver, exporter};
+
+// Same cadence as the CPU sampler so the two line up in charts
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryDetails {
+    pub total_bytes: u64,
+    // Can be handed to programs without swapping, including reclaimable cache
+    pub available_bytes: u64,
+    pub used_bytes: u64,
+    // File cache; None where the platform doesn't report it
+    pub cached_bytes: Option<u64>,
+    // Kernel block device buffers; Linux only
+    pub buffered_bytes: Option<u64>,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub page_size: Option<u64>,
+}
+
+// (cached, buffered, page size)
+type CacheFigures = (Option<u64>, Option<u64>, Option<u64>);
+
+fn linux_figures() -> CacheFigures {
+    // `Cached:       1234567 kB`
+    let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let field = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let kib = line.strip_prefix(name)?.strip_prefix(':')?;
+            kib.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    // Reclaimable slab (mostly the dentry and inode caches) counts as cache, as in `free`
+    let cached = field("Cached").map(|kib| (kib + field("SReclaimable").unwrap_or(0)) * 1024);
+    (
+        cached,
+        field("Buffers").map(|kib| kib * 1024),
+        getconf_page_size(),
+    )
+}
+
+fn getconf_page_size() -> Option<u64> {
+    run_text("getconf", &["PAGESIZE"]).ok()?.trim().parse().ok()
+}
+
+fn macos_figures() -> CacheFigures {
+    // Mach Virtual Memory Statistics: (page size of 16384 bytes)
+    // File-backed pages:                      412345.
+    let Ok(output) = run_text("vm_stat", &[]) else {
+        return (None, None, getconf_page_size());
+    };
+    let page_size = output
+        .lines()
+        .next()
+        .and_then(|line| line.split("page size of ").nth(1))
+        .and_then(|rest| rest.split_whitespace().next()?.parse::<u64>().ok())
+        .or_else(getconf_page_size);
+    let file_backed = output.lines().find_map(|line| {
+        let pages = line.strip_prefix("File-backed pages:")?;
+        pages.trim().trim_end_matches('.').parse::<u64>().ok()
+    });
+    let cached = file_backed.zip(page_size).map(|(pages, size)| pages * size);
+    (cached, None, page_size)
+}
+
+fn windows_figures() -> CacheFigures {
+    let script = "$memory = Get-CimInstance Win32_PerfRawData_PerfOS_Memory; \
+                  ConvertTo-Json -Compress -InputObject @{ \
+                  Cached = $memory.StandbyCacheCoreBytes + $memory.StandbyCacheNormalPriorityBytes + \
+                  $memory.StandbyCacheReserveBytes + $memory.ModifiedPageListBytes; \
+                  PageSize = [Environment]::SystemPageSize }";
+    let record: Option<serde_json::Value> =
+        run_text("powershell", &["-NoProfile", "-Command", script])
+            .ok()
+            .and_then(|output| serde_json::from_str(output.trim()).ok());
+    match record {
+        Some(record) => (record["Cached"].as_u64(), None, record["PageSize"].as_u64()),
+        None => (None, None, None),
+    }
+}
+
+pub fn read_details() -> Result<MemoryDetails, String> {
+    let system = System::new_with_specifics(
+        RefreshKind::nothing().with_memory(MemoryRefreshKind::everything()),
+    );
+    if system.total_memory() == 0 {
+        return Err("Failed to read memory usage".to_string());
+    }
+    let (cached_bytes, buffered_bytes, page_size) = if cfg!(target_os = "windows") {
+        windows_figures()
+    } else if cfg!(target_os = "macos") {
+        macos_figures()
+    } else {
+        linux_figures()
+    };
+
+    Ok(MemoryDetails {
+        total_bytes: system.total_memory(),
+        available_bytes: system.available_memory(),
+        used_bytes: system.used_memory(),
+        cached_bytes,
+        buffered_bytes,
+        swap_total_bytes: system.total_swap(),
+        swap_used_bytes: system.used_swap(),
+        page_size,
+    })
+}
+
+fn samples(system: &System) -> Vec<(String, f64)> {
+    let total = system.total_memory();
+    let used = system.used_memory();
+    let mut samples = vec![
+        ("memory.used".to_string(), used as f64),
+        (
+            "memory.available".to_string(),
+            system.available_memory() as f64,
+        ),
+        ("memory.swapUsed".to_string(), system.used_swap() as f64),
+    ];
+    if total > 0 {
+        samples.push((
+            "memory.usage".to_string(),
+            used as f64 / total as f64 * 100.0,
+        ));
+    }
+    samples
+}
+
+// Background job recording memory and swap usage
+pub fn start_sampling(app: AppHandle) {
+    std::thread::spawn(move || {
+        let refresh = MemoryRefreshKind::nothing().with_ram().with_swap();
+        let mut system = System::new_with_specifics(RefreshKind::nothing().with_memory(refresh));
+        loop {
+            system.refresh_memory_specifics(refresh);
+            exporter::publish(&app, &samples(&system));
+            battery_saver::wait(&app, SAMPLE_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_memory_details() -> Result<MemoryDetails, CommandError> {
+    let details = tauri::async_runtime::spawn_blocking(read_details)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(details)
+}
@@ -0,0 +1,351 @@
+// Installed RAM modules and free slots, to answer "can I add more RAM, and what kind".
+// Windows and Linux read the raw SMBIOS table (Linux needs root for it) and decode the memory
+// array (type 16) and memory device (type 17) structures; macOS asks `system_profiler`.
+
+use serde::Serialize;
+use std::io::ErrorKind;
+
+use crate::error::{CommandError, ErrorCode};
+use crate::os_command::run_text;
+
+const SMBIOS_TABLE: &str = "/sys/firmware/dmi/tables/DMI";
+
+const MEMORY_ARRAY: u8 = 16;
+const MEMORY_DEVICE: u8 = 17;
+const END_OF_TABLE: u8 = 127;
+// Memory array use: system memory, as opposed to video or cache memory
+const SYSTEM_MEMORY: u8 = 0x03;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryModule {
+    // Slot label printed on the board, e.g. `DIMM_A1` or `ChannelA-DIMM0`
+    pub slot: String,
+    pub bank: Option<String>,
+    // None for an empty slot
+    pub size_bytes: Option<u64>,
+    // DDR4, LPDDR5, ...
+    pub memory_type: Option<String>,
+    // DIMM, SODIMM, ...
+    pub form_factor: Option<String>,
+    // Rated and configured speed in MT/s
+    pub speed_mts: Option<u32>,
+    pub configured_speed_mts: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryModules {
+    // False where the platform doesn't report modules
+    pub supported: bool,
+    // Installed modules and empty slots, in board order
+    pub modules: Vec<MemoryModule>,
+    pub total_slots: usize,
+    pub empty_slots: usize,
+    // Most RAM the board supports, from the firmware; often conservative
+    pub max_capacity_bytes: Option<u64>,
+}
+
+// One SMBIOS structure: the formatted area followed by its string set
+struct Structure<'a> {
+    kind: u8,
+    data: &'a [u8],
+    strings: Vec<&'a [u8]>,
+}
+
+impl Structure<'_> {
+    fn byte(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+
+    fn word(&self, offset: usize) -> Option<u16> {
+        Some(u16::from_le_bytes(
+            self.data.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    }
+
+    fn dword(&self, offset: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(
+            self.data.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    }
+
+    fn qword(&self, offset: usize) -> Option<u64> {
+        Some(u64::from_le_bytes(
+            self.data.get(offset..offset + 8)?.try_into().ok()?,
+        ))
+    }
+
+    // Strings are numbered from 1; 0 means none. Placeholder values are dropped.
+    fn string(&self, offset: usize) -> Option<String> {
+        let index = self.byte(offset)? as usize;
+        let value = String::from_utf8_lossy(self.strings.get(index.checked_sub(1)?)?)
+            .trim()
+            .to_string();
+        let placeholder = [
+            "",
+            "Unknown",
+            "Not Specified",
+            "NO DIMM",
+            "To Be Filled By O.E.M.",
+        ]
+        .iter()
+        .any(|placeholder| value.eq_ignore_ascii_case(placeholder));
+        (!placeholder).then_some(value)
+    }
+}
+
+fn structures(table: &[u8]) -> Vec<Structure<'_>> {
+    let mut structures = Vec::new();
+    let mut rest = table;
+    while rest.len() >= 4 {
+        let (kind, length) = (rest[0], rest[1] as usize);
+        if length < 4 || length > rest.len() {
+            break;
+        }
+        let (data, tail) = rest.split_at(length);
+
+        // The string set ends with two NULs
+        let Some(end) = tail.windows(2).position(|pair| pair == [0, 0]) else {
+            break;
+        };
+        let strings = tail[..end]
+            .split(|&byte| byte == 0)
+            .filter(|string| !string.is_empty())
+            .collect();
+        structures.push(Structure {
+            kind,
+            data,
+            strings,
+        });
+        if kind == END_OF_TABLE {
+            break;
+        }
+        rest = &tail[end + 2..];
+    }
+    structures
+}
+
+fn memory_type(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x18 => "DDR3",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        _ => return None,
+    })
+}
+
+fn form_factor(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x05 => "Chip",
+        0x09 => "DIMM",
+        0x0C => "RIMM",
+        0x0D => "SODIMM",
+        0x0F => "FB-DIMM",
+        0x10 => "Die",
+        _ => return None,
+    })
+}
+
+// Size field: 0 is an empty slot, 0xFFFF unknown, 0x7FFF means the extended size (in MiB)
+// holds it, and bit 15 selects KiB instead of MiB
+fn module_size(device: &Structure) -> Option<u64> {
+    const MIB: u64 = 1024 * 1024;
+    match device.word(0x0C)? {
+        0 | 0xFFFF => None,
+        0x7FFF => Some(device.dword(0x1C)? as u64 * MIB),
+        size if size & 0x8000 != 0 => Some((size & 0x7FFF) as u64 * 1024),
+        size => Some(size as u64 * MIB),
+    }
+}
+
+// Speed field in MT/s; 0xFFFF means the extended field holds it
+fn speed(device: &Structure, offset: usize, extended: usize) -> Option<u32> {
+    match device.word(offset)? {
+        0 => None,
+        0xFFFF => device.dword(extended).filter(|&speed| speed > 0),
+        speed => Some(speed as u32),
+    }
+}
+
+fn decode(table: &[u8]) -> MemoryModules {
+    let structures = structures(table);
+
+    // Handles of the arrays holding system memory; device slots in other arrays are ignored
+    let arrays: Vec<&Structure> = structures
+        .iter()
+        .filter(|s| s.kind == MEMORY_ARRAY && s.byte(0x05) == Some(SYSTEM_MEMORY))
+        .collect();
+    let handles: Vec<u16> = arrays.iter().filter_map(|array| array.word(0x02)).collect();
+    let max_capacity = arrays
+        .iter()
+        .filter_map(|array| match array.dword(0x07)? {
+            0x8000_0000 => array.qword(0x0F),
+            kib => Some(kib as u64 * 1024),
+        })
+        .reduce(|total, capacity| total + capacity);
+
+    let modules: Vec<MemoryModule> = structures
+        .iter()
+        .filter(|s| s.kind == MEMORY_DEVICE)
+        .filter(|device| {
+            handles.is_empty()
+                || device
+                    .word(0x04)
+                    .is_some_and(|array| handles.contains(&array))
+        })
+        .map(|device| {
+            let size = module_size(device);
+            // Empty slots keep the slot's own details and drop the rest
+            let installed = |value: Option<String>| value.filter(|_| size.is_some());
+            MemoryModule {
+                slot: device.string(0x10).unwrap_or_default(),
+                bank: device.string(0x11),
+                size_bytes: size,
+                memory_type: installed(device.byte(0x12).and_then(memory_type).map(str::to_string)),
+                form_factor: device.byte(0x0E).and_then(form_factor).map(str::to_string),
+                speed_mts: size.and(speed(device, 0x15, 0x54)),
+                configured_speed_mts: size.and(speed(device, 0x20, 0x58)),
+                manufacturer: installed(device.string(0x17)),
+                part_number: installed(device.string(0x1A)),
+                serial_number: installed(device.string(0x18)),
+            }
+        })
+        .collect();
+
+    let empty_slots = modules.iter().filter(|m| m.size_bytes.is_none()).count();
+    MemoryModules {
+        supported: true,
+        total_slots: modules.len(),
+        empty_slots,
+        max_capacity_bytes: max_capacity,
+        modules,
+    }
+}
+
+// The same raw table Linux exposes, through WMI
+fn windows_table() -> Result<Vec<u8>, String> {
+    let output = run_text(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "ConvertTo-Json -Compress -InputObject @((Get-CimInstance -Namespace root/wmi \
+             -ClassName MSSmBios_RawSMBiosTables).SMBiosData)",
+        ],
+    )?;
+    let bytes: Vec<u8> = serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+fn linux_table() -> Result<Vec<u8>, CommandError> {
+    std::fs::read(SMBIOS_TABLE).map_err(|e| {
+        if e.kind() == ErrorKind::PermissionDenied {
+            CommandError::new(ErrorCode::PermissionDenied, "memory.permissionDenied")
+        } else {
+            CommandError::from(format!("Failed to read {}: {}", SMBIOS_TABLE, e))
+        }
+    })
+}
+
+// `system_profiler` sizes look like `8 GB` and speeds like `2667 MHz`
+fn leading_number(value: &str) -> Option<u64> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+fn macos_modules() -> Result<MemoryModules, String> {
+    let output = run_text("system_profiler", &["-json", "SPMemoryDataType"])?;
+    let report: serde_json::Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+    let text = |value: &serde_json::Value, key: &str| {
+        value[key]
+            .as_str()
+            .map(str::to_string)
+            .filter(|value| !value.is_empty() && value != "Empty")
+    };
+
+    let mut modules = Vec::new();
+    for memory in report["SPMemoryDataType"].as_array().into_iter().flatten() {
+        let slots = memory["_items"].as_array().cloned().unwrap_or_default();
+        if slots.is_empty() {
+            // Apple silicon: memory is part of the chip, with no slots
+            modules.push(MemoryModule {
+                slot: "Built-in".to_string(),
+                bank: None,
+                size_bytes: text(memory, "SPMemoryDataType")
+                    .and_then(|size| leading_number(&size))
+                    .map(|gb| gb * 1024 * 1024 * 1024),
+                memory_type: text(memory, "dimm_type"),
+                form_factor: None,
+                speed_mts: None,
+                configured_speed_mts: None,
+                manufacturer: text(memory, "dimm_manufacturer"),
+                part_number: None,
+                serial_number: None,
+            });
+            continue;
+        }
+        for slot in slots {
+            let size = text(&slot, "dimm_size")
+                .and_then(|size| leading_number(&size))
+                .map(|gb| gb * 1024 * 1024 * 1024);
+            modules.push(MemoryModule {
+                slot: text(&slot, "_name").unwrap_or_default(),
+                bank: None,
+                size_bytes: size,
+                memory_type: text(&slot, "dimm_type"),
+                form_factor: None,
+                speed_mts: text(&slot, "dimm_speed")
+                    .and_then(|speed| leading_number(&speed))
+                    .map(|speed| speed as u32),
+                configured_speed_mts: None,
+                manufacturer: text(&slot, "dimm_manufacturer"),
+                part_number: text(&slot, "dimm_part_number"),
+                serial_number: text(&slot, "dimm_serial_number"),
+            });
+        }
+    }
+
+    let empty_slots = modules.iter().filter(|m| m.size_bytes.is_none()).count();
+    Ok(MemoryModules {
+        supported: true,
+        total_slots: modules.len(),
+        empty_slots,
+        max_capacity_bytes: None,
+        modules,
+    })
+}
+
+fn read_modules() -> Result<MemoryModules, CommandError> {
+    if cfg!(target_os = "windows") {
+        Ok(decode(&windows_table()?))
+    } else if cfg!(target_os = "linux") {
+        Ok(decode(&linux_table()?))
+    } else if cfg!(target_os = "macos") {
+        Ok(macos_modules()?)
+    } else {
+        Ok(MemoryModules {
+            supported: false,
+            modules: Vec::new(),
+            total_slots: 0,
+            empty_slots: 0,
+            max_capacity_bytes: None,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn get_memory_modules() -> Result<MemoryModules, CommandError> {
+    tauri::async_runtime::spawn_blocking(read_modules)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,217 @@
+// Live metrics for dashboards without a polling `invoke` on a timer.
+// `start_monitoring` spawns a sampler that refreshes CPU, memory and process counts every
+// interval and emits the result as `system-info://tick`; `stop_monitoring` ends it. Starting
+// again while running only changes the interval. Every tick is also checked against the alert
+// rules (see alerts.rs) and feeds the Prometheus endpoint (see prometheus.rs); the latest one
+// is kept for the remote agent's metrics (see agent.rs).
+
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{
+    CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, System,
+};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::alerts::{self, RuleStates};
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::prometheus::{self, Readers};
+use crate::report::unix_timestamp;
+
+pub const TICK_EVENT: &str = "system-info://tick";
+
+const DEFAULT_INTERVAL_MS: u64 = 1000;
+// CPU usage needs some time between refreshes to mean anything
+const MIN_INTERVAL_MS: u64 = 250;
+const MAX_INTERVAL_MS: u64 = 60 * 60 * 1000;
+
+// Sampler settings; `start_monitoring` without an interval uses `interval_ms`
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MonitorConfig {
+    pub interval_ms: u64,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        MonitorConfig {
+            interval_ms: DEFAULT_INTERVAL_MS,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorTick {
+    pub timestamp: u64,
+    pub interval_ms: u64,
+    // Across all cores, since the previous tick
+    pub cpu_usage: f32,
+    pub total_memory: u64,
+    pub used_memory: u64,
+    pub available_memory: u64,
+    pub process_count: usize,
+    // Resident memory and CPU usage of this app's own process
+    pub app_memory: Option<u64>,
+    pub app_cpu_usage: Option<f32>,
+}
+
+struct Sampler {
+    interval_ms: u64,
+    // Carries interval changes; dropping it wakes the sampler and ends it
+    control: Sender<u64>,
+}
+
+// Running sampler, if any, and its latest tick
+#[derive(Default)]
+pub struct Monitoring {
+    sampler: Mutex<Option<Sampler>>,
+    latest: Mutex<Option<MonitorTick>>,
+}
+
+fn sample(system: &mut System, interval_ms: u64) -> MonitorTick {
+    system.refresh_cpu_specifics(CpuRefreshKind::nothing().with_cpu_usage());
+    system.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram());
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_memory().with_cpu(),
+    );
+    let app = system.process(Pid::from_u32(std::process::id()));
+
+    MonitorTick {
+        timestamp: unix_timestamp(),
+        interval_ms,
+        cpu_usage: system.global_cpu_usage(),
+        total_memory: system.total_memory(),
+        used_memory: system.used_memory(),
+        available_memory: system.available_memory(),
+        process_count: system.processes().len(),
+        app_memory: app.map(|process| process.memory()),
+        app_cpu_usage: app.map(|process| process.cpu_usage()),
+    }
+}
+
+fn run(app: AppHandle, mut interval_ms: u64, changes: mpsc::Receiver<u64>) {
+    let mut system = System::new();
+    let mut rules = RuleStates::default();
+    let mut readers = Readers::default();
+    // Prime the CPU counters so the first tick has a usage to report
+    system.refresh_cpu_specifics(CpuRefreshKind::nothing().with_cpu_usage());
+    loop {
+        match changes.recv_timeout(Duration::from_millis(interval_ms)) {
+            Ok(interval) => {
+                interval_ms = interval;
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+        // Ticks slow down or pause with the battery saver and wait for running benchmarks
+        battery_saver::hold_back(&app, Duration::from_millis(interval_ms));
+        match changes.try_recv() {
+            Ok(interval) => {
+                interval_ms = interval;
+                continue;
+            }
+            Err(TryRecvError::Disconnected) => break,
+            Err(TryRecvError::Empty) => {}
+        }
+        let tick = sample(&mut system, interval_ms);
+        alerts::evaluate(&app, &tick, &mut rules);
+        prometheus::update(&app, &tick, &mut readers);
+        if let Ok(mut latest) = app.state::<Monitoring>().latest.lock() {
+            *latest = Some(tick.clone());
+        }
+        if let Err(e) = app.emit(TICK_EVENT, tick) {
+            eprintln!("⚠ Warning: Failed to emit {}: {}", TICK_EVENT, e);
+        }
+    }
+    // Stopped: the last tick no longer describes the machine
+    if let Ok(mut latest) = app.state::<Monitoring>().latest.lock() {
+        latest.take();
+    }
+}
+
+pub fn check_interval(interval_ms: u64) -> Result<(), CommandError> {
+    if !(MIN_INTERVAL_MS..=MAX_INTERVAL_MS).contains(&interval_ms) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "monitor.invalidInterval")
+                .with("min", MIN_INTERVAL_MS)
+                .with("max", MAX_INTERVAL_MS),
+        );
+    }
+    Ok(())
+}
+
+// Change the interval of the running sampler, if there is one; returns the interval in use
+fn change_interval(sampler: &mut Option<Sampler>, interval_ms: u64) -> Option<u64> {
+    sampler.as_mut().map(|running| {
+        if running.interval_ms != interval_ms && running.control.send(interval_ms).is_ok() {
+            running.interval_ms = interval_ms;
+        }
+        running.interval_ms
+    })
+}
+
+impl Monitoring {
+    pub fn set_interval(&self, interval_ms: u64) -> Result<Option<u64>, CommandError> {
+        let mut sampler = self.sampler.lock().map_err(|_| "Monitor lock poisoned")?;
+        Ok(change_interval(&mut sampler, interval_ms))
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.sampler.lock().is_ok_and(|sampler| sampler.is_some())
+    }
+
+    // The most recent tick; None until the running sampler has ticked
+    pub fn latest(&self) -> Option<MonitorTick> {
+        self.latest.lock().ok()?.clone()
+    }
+}
+
+// Start emitting ticks every `interval_ms` (`monitor.intervalMs` of the config by default), or
+// change the interval of the running sampler; returns the interval in use
+#[tauri::command]
+pub fn start_monitoring(
+    interval_ms: Option<u64>,
+    app: AppHandle,
+    monitoring: State<Monitoring>,
+    config: State<ConfigState>,
+) -> Result<u64, CommandError> {
+    let interval_ms = match interval_ms {
+        Some(interval_ms) => interval_ms,
+        None => config.get()?.monitor.interval_ms,
+    };
+    check_interval(interval_ms)?;
+
+    let mut sampler = monitoring
+        .sampler
+        .lock()
+        .map_err(|_| "Monitor lock poisoned")?;
+    if let Some(running) = change_interval(&mut sampler, interval_ms) {
+        return Ok(running);
+    }
+
+    let (control, changes) = mpsc::channel();
+    std::thread::spawn(move || run(app, interval_ms, changes));
+    println!("✓ Monitoring started every {} ms", interval_ms);
+    *sampler = Some(Sampler {
+        interval_ms,
+        control,
+    });
+    Ok(interval_ms)
+}
+
+#[tauri::command]
+pub fn stop_monitoring(monitoring: State<Monitoring>) -> Result<(), CommandError> {
+    monitoring
+        .sampler
+        .lock()
+        .map_err(|_| "Monitor lock poisoned")?
+        .take();
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Network interfaces for the network tab: addresses, link state, adapter details and traffic
+// counters.
+// `get_network_throughput` turns the cumulative counters into bytes per second by comparing them
+// with the previous call's, which the managed `NetworkMonitor` keeps.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use sysinfo::{InterfaceOperationalState, IpNetwork, NetworkData, Networks};
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::network_adapters::{self, AdapterDetails};
+use crate::report::unix_timestamp;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkState {
+    Up,
+    Down,
+    // Dormant, testing or waiting on a lower layer
+    Pending,
+    // Loopback on Linux, and platforms that don't report it
+    Unknown,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpAddress {
+    pub address: String,
+    pub prefix: u8,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterface {
+    pub name: String,
+    // None for interfaces without one, such as loopback and tunnels
+    pub mac_address: Option<String>,
+    pub ipv4: Vec<IpAddress>,
+    pub ipv6: Vec<IpAddress>,
+    pub link_state: LinkState,
+    pub mtu: u64,
+    // Since boot or since the driver last reset its counters
+    pub received_bytes: u64,
+    pub transmitted_bytes: u64,
+    pub adapter: AdapterDetails,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfaceThroughput {
+    pub name: String,
+    pub received_bytes_per_sec: f64,
+    pub transmitted_bytes_per_sec: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkThroughput {
+    pub timestamp: u64,
+    // Time since the previous call; 0 on the first call, which has nothing to compare with
+    pub interval_ms: u64,
+    pub interfaces: Vec<InterfaceThroughput>,
+    pub received_bytes_per_sec: f64,
+    pub transmitted_bytes_per_sec: f64,
+}
+
+// Counters as of the previous throughput call
+struct Counters {
+    taken: Instant,
+    totals: HashMap<String, (u64, u64)>,
+}
+
+#[derive(Default)]
+pub struct NetworkMonitor {
+    previous: Mutex<Option<Counters>>,
+}
+
+fn link_state(data: &NetworkData) -> LinkState {
+    match data.operational_state() {
+        InterfaceOperationalState::Up => LinkState::Up,
+        InterfaceOperationalState::Down | InterfaceOperationalState::NotPresent => LinkState::Down,
+        InterfaceOperationalState::Testing
+        | InterfaceOperationalState::Dormant
+        | InterfaceOperationalState::LowerLayerDown => LinkState::Pending,
+        _ => LinkState::Unknown,
+    }
+}
+
+fn interface(name: &str, data: &NetworkData) -> NetworkInterface {
+    let mac = data.mac_address();
+    let (ipv4, ipv6): (Vec<_>, Vec<_>) = data
+        .ip_networks()
+        .iter()
+        .partition(|network| network.addr.is_ipv4());
+    let addresses = |networks: Vec<&IpNetwork>| -> Vec<IpAddress> {
+        networks
+            .into_iter()
+            .map(|network| IpAddress {
+                address: network.addr.to_string(),
+                prefix: network.prefix,
+            })
+            .collect()
+    };
+
+    NetworkInterface {
+        name: name.to_string(),
+        mac_address: (!mac.is_unspecified()).then(|| mac.to_string()),
+        ipv4: addresses(ipv4),
+        ipv6: addresses(ipv6),
+        link_state: link_state(data),
+        mtu: data.mtu(),
+        received_bytes: data.total_received(),
+        transmitted_bytes: data.total_transmitted(),
+        adapter: AdapterDetails::default(),
+    }
+}
+
+fn read_interfaces() -> Vec<NetworkInterface> {
+    let networks = Networks::new_with_refreshed_list();
+    let mut interfaces: Vec<NetworkInterface> = networks
+        .iter()
+        .map(|(name, data)| interface(name, data))
+        .collect();
+    let names: Vec<String> = interfaces.iter().map(|i| i.name.clone()).collect();
+    let mut details = network_adapters::read_details(&names);
+    for interface in &mut interfaces {
+        if let Some(adapter) = details.remove(&interface.name) {
+            interface.adapter = adapter;
+        }
+    }
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+#[tauri::command]
+pub async fn get_network_interfaces() -> Result<Vec<NetworkInterface>, CommandError> {
+    let interfaces = tauri::async_runtime::spawn_blocking(read_interfaces)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(interfaces)
+}
+
+// Bytes per second per interface since the previous call. Interfaces that appeared in between,
+// or whose counters went backwards after a reset, report 0 until the next call.
+#[tauri::command]
+pub fn get_network_throughput(
+    monitor: State<NetworkMonitor>,
+) -> Result<NetworkThroughput, CommandError> {
+    let networks = Networks::new_with_refreshed_list();
+    let now = Instant::now();
+    let totals: HashMap<String, (u64, u64)> = networks
+        .iter()
+        .map(|(name, data)| {
+            (
+                name.clone(),
+                (data.total_received(), data.total_transmitted()),
+            )
+        })
+        .collect();
+
+    let mut previous = monitor
+        .previous
+        .lock()
+        .map_err(|_| "Network monitor lock poisoned")?;
+    let elapsed = previous
+        .as_ref()
+        .map(|counters| now.duration_since(counters.taken))
+        .unwrap_or_default();
+    let seconds = elapsed.as_secs_f64();
+
+    let mut interfaces: Vec<InterfaceThroughput> = totals
+        .iter()
+        .map(|(name, &(received, transmitted))| {
+            let before = previous
+                .as_ref()
+                .and_then(|counters| counters.totals.get(name));
+            let rate = |current: u64, before: Option<u64>| match before {
+                Some(before) if seconds > 0.0 && current >= before => {
+                    (current - before) as f64 / seconds
+                }
+                _ => 0.0,
+            };
+            InterfaceThroughput {
+                name: name.clone(),
+                received_bytes_per_sec: rate(received, before.map(|b| b.0)),
+                transmitted_bytes_per_sec: rate(transmitted, before.map(|b| b.1)),
+            }
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    *previous = Some(Counters { taken: now, totals });
+
+    Ok(NetworkThroughput {
+        timestamp: unix_timestamp(),
+        interval_ms: elapsed.as_millis() as u64,
+        received_bytes_per_sec: interfaces.iter().map(|i| i.received_bytes_per_sec).sum(),
+        transmitted_bytes_per_sec: interfaces.iter().map(|i| i.transmitted_bytes_per_sec).sum(),
+        interfaces,
+    })
+}
@@ -0,0 +1,288 @@
+// Adapter details behind each network interface: driver, firmware, the link speeds the hardware
+// supports against the one negotiated, and offload features. Enough to tell a gigabit adapter
+// stuck at 100 Mb/s by a bad cable from one that never did more.
+// Linux reads sysfs and `ethtool`, Windows `Get-NetAdapter` and macOS `ifconfig`; anything a
+// platform doesn't report is left empty.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::os_command::run_text;
+
+const NET_CLASS_DIR: &str = "/sys/class/net";
+// Offloads worth showing; `ethtool -k` lists dozens, mostly internal
+const LINUX_OFFLOADS: [&str; 7] = [
+    "rx-checksumming",
+    "tx-checksumming",
+    "scatter-gather",
+    "tcp-segmentation-offload",
+    "generic-segmentation-offload",
+    "generic-receive-offload",
+    "large-receive-offload",
+];
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Duplex {
+    Full,
+    Half,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Offload {
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterDetails {
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+    pub firmware_version: Option<String>,
+    // Negotiated speed; None while the link is down
+    pub link_speed_mbps: Option<u64>,
+    pub duplex: Option<Duplex>,
+    // Fastest speed the adapter supports, from the modes below
+    pub max_speed_mbps: Option<u64>,
+    // As the platform names them, e.g. `1000baseT/Full` or `1.0 Gbps Full Duplex`
+    pub supported_link_modes: Vec<String>,
+    pub offloads: Vec<Offload>,
+}
+
+// Leading speed of a link mode: `1000baseT/Full`, `10Gbase-T`, `2.5 Gbps Full Duplex`
+fn mode_speed_mbps(mode: &str) -> Option<u64> {
+    let number: String = mode
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let value: f64 = number.parse().ok()?;
+    let unit = mode[number.len()..].trim_start();
+    let mbps = if unit.starts_with('G') {
+        value * 1000.0
+    } else {
+        value
+    };
+    Some(mbps.round() as u64)
+}
+
+fn max_speed(modes: &[String]) -> Option<u64> {
+    modes.iter().filter_map(|mode| mode_speed_mbps(mode)).max()
+}
+
+fn ethtool_field(output: &str, field: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(field)?.strip_prefix(':'))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && value != "N/A")
+}
+
+// `Supported link modes:` continues on indented lines without a label
+fn ethtool_link_modes(output: &str) -> Vec<String> {
+    let mut modes = Vec::new();
+    let mut in_list = false;
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(first) = line.strip_prefix("Supported link modes:") {
+            in_list = true;
+            modes.extend(first.split_whitespace().map(str::to_string));
+        } else if in_list && !line.contains(':') {
+            modes.extend(line.split_whitespace().map(str::to_string));
+        } else {
+            in_list = false;
+        }
+    }
+    modes.retain(|mode| mode != "Not" && mode != "reported");
+    modes
+}
+
+fn linux_details(name: &str) -> AdapterDetails {
+    let dir = Path::new(NET_CLASS_DIR).join(name);
+    let read = |file: &str| {
+        std::fs::read_to_string(dir.join(file))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+    let driver = std::fs::read_link(dir.join("device/driver"))
+        .ok()
+        .and_then(|link| Some(link.file_name()?.to_string_lossy().into_owned()));
+
+    // ethtool is optional; without it only sysfs details are filled in
+    let info = run_text("ethtool", &["-i", name]).unwrap_or_default();
+    let settings = run_text("ethtool", &[name]).unwrap_or_default();
+    let features = run_text("ethtool", &["-k", name]).unwrap_or_default();
+    let supported_link_modes = ethtool_link_modes(&settings);
+
+    AdapterDetails {
+        driver: driver.or_else(|| ethtool_field(&info, "driver")),
+        driver_version: ethtool_field(&info, "version"),
+        firmware_version: ethtool_field(&info, "firmware-version"),
+        // -1 while the link is down
+        link_speed_mbps: read("speed")
+            .and_then(|speed| speed.parse::<i64>().ok())
+            .filter(|&speed| speed > 0)
+            .map(|speed| speed as u64),
+        duplex: match read("duplex").as_deref() {
+            Some("full") => Some(Duplex::Full),
+            Some("half") => Some(Duplex::Half),
+            _ => None,
+        },
+        max_speed_mbps: max_speed(&supported_link_modes),
+        supported_link_modes,
+        offloads: LINUX_OFFLOADS
+            .iter()
+            .filter_map(|&offload| {
+                let value = ethtool_field(&features, offload)?;
+                Some(Offload {
+                    name: offload.to_string(),
+                    enabled: value.starts_with("on"),
+                })
+            })
+            .collect(),
+    }
+}
+
+fn windows_details() -> Result<HashMap<String, AdapterDetails>, String> {
+    // Supported speeds are the choices of the `*SpeedDuplex` advanced property; offloads are the
+    // advanced properties named after them
+    let script = "ConvertTo-Json -Compress -Depth 4 -InputObject @(Get-NetAdapter | ForEach-Object { \
+                  $properties = @(Get-NetAdapterAdvancedProperty -Name $_.Name -ErrorAction SilentlyContinue); \
+                  [pscustomobject]@{ Name = $_.Name; Driver = $_.DriverDescription; \
+                  DriverVersion = $_.DriverVersionString; Speed = $_.Speed; FullDuplex = $_.FullDuplex; \
+                  Up = ($_.Status -eq 'Up'); \
+                  Modes = @(($properties | Where-Object RegistryKeyword -eq '*SpeedDuplex').ValidDisplayValues); \
+                  Offloads = @($properties | Where-Object RegistryKeyword -match 'Offload|Lso|Rsc' | \
+                  ForEach-Object { [pscustomobject]@{ Name = $_.DisplayName; Value = $_.DisplayValue } }) } })";
+    let output = run_text("powershell", &["-NoProfile", "-Command", script])?;
+    let records: Vec<serde_json::Value> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+
+    Ok(records
+        .iter()
+        .filter_map(|record| {
+            let text = |key: &str| record[key].as_str().map(str::to_string);
+            let up = record["Up"].as_bool().unwrap_or(false);
+            let supported_link_modes: Vec<String> = record["Modes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|mode| mode.as_str().map(str::to_string))
+                .collect();
+            let details = AdapterDetails {
+                driver: text("Driver"),
+                driver_version: text("DriverVersion"),
+                firmware_version: None,
+                // Reported in bits per second
+                link_speed_mbps: record["Speed"]
+                    .as_u64()
+                    .filter(|_| up)
+                    .map(|bps| bps / 1_000_000),
+                duplex: record["FullDuplex"].as_bool().filter(|_| up).map(|full| {
+                    if full {
+                        Duplex::Full
+                    } else {
+                        Duplex::Half
+                    }
+                }),
+                max_speed_mbps: max_speed(&supported_link_modes),
+                supported_link_modes,
+                offloads: record["Offloads"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|offload| {
+                        Some(Offload {
+                            name: offload["Name"].as_str()?.to_string(),
+                            enabled: offload["Value"].as_str()? != "Disabled",
+                        })
+                    })
+                    .collect(),
+            };
+            Some((text("Name")?, details))
+        })
+        .collect())
+}
+
+fn macos_details(name: &str) -> AdapterDetails {
+    // `media: autoselect (1000baseT <full-duplex>)`, `options=...<RXCSUM,TXCSUM,TSO4>` and,
+    // with -m, `media 1000baseT mediaopt full-duplex` for each supported mode
+    let output = run_text("ifconfig", &["-m", name]).unwrap_or_default();
+    let active = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("media:"))
+        .and_then(|media| {
+            let start = media.find('(')?;
+            Some(media[start + 1..].trim_end_matches(')').to_string())
+        });
+    let up = output.contains("status: active");
+    let mut supported_link_modes: Vec<String> = output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("media "))
+        .map(|mode| mode.replace(" mediaopt ", " "))
+        .filter(|mode| mode_speed_mbps(mode).is_some())
+        .collect();
+    supported_link_modes.dedup();
+    let offloads = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("options="))
+        .and_then(|options| {
+            let start = options.find('<')?;
+            Some(options[start + 1..].trim_end_matches('>').to_string())
+        })
+        .map(|options| {
+            options
+                .split(',')
+                .filter(|option| option.contains("CSUM") || option.starts_with("TSO"))
+                .map(|option| Offload {
+                    name: option.to_string(),
+                    enabled: true,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    AdapterDetails {
+        driver: None,
+        driver_version: None,
+        firmware_version: None,
+        link_speed_mbps: active.as_deref().filter(|_| up).and_then(mode_speed_mbps),
+        duplex: active.as_deref().filter(|_| up).and_then(|media| {
+            if media.contains("full-duplex") {
+                Some(Duplex::Full)
+            } else if media.contains("half-duplex") {
+                Some(Duplex::Half)
+            } else {
+                None
+            }
+        }),
+        max_speed_mbps: max_speed(&supported_link_modes),
+        supported_link_modes,
+        offloads,
+    }
+}
+
+// Details for each named interface; interfaces a platform can't describe get empty details
+pub fn read_details(names: &[String]) -> HashMap<String, AdapterDetails> {
+    if cfg!(target_os = "windows") {
+        return windows_details().unwrap_or_else(|e| {
+            eprintln!("⚠ Warning: Failed to read network adapter details: {}", e);
+            HashMap::new()
+        });
+    }
+    names
+        .iter()
+        .map(|name| {
+            let details = if cfg!(target_os = "linux") {
+                linux_details(name)
+            } else if cfg!(target_os = "macos") {
+                macos_details(name)
+            } else {
+                AdapterDetails::default()
+            };
+            (name.clone(), details)
+        })
+        .collect()
+}
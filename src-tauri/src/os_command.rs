@@ -0,0 +1,47 @@
+// Helpers for reading state through the platform's own command-line tools, for things
+// the OS only exposes that way (e.g. `security`, `powershell`, `gsettings`).
+
+use std::process::{Command, Output};
+
+// Windows `CREATE_NO_WINDOW` process creation flag
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+// A command for `program` that runs without a console window. Release builds on Windows are GUI
+// programs, so each console tool they start would otherwise flash a window, every few seconds
+// for the background samplers.
+pub fn command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
+
+// Output of a run whatever its exit status
+pub fn output(program: &str, args: &[&str]) -> Result<Output, String> {
+    command(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))
+}
+
+// Stdout of a successful run, or an error with the program's stderr
+pub fn run(program: &str, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = output(program, args)?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+pub fn run_text(program: &str, args: &[&str]) -> Result<String, String> {
+    run(program, args).map(|stdout| String::from_utf8_lossy(&stdout).into_owned())
+}
@@ -0,0 +1,111 @@
+// Operating system details beyond `get_platform`'s family name: product name and version, build
+// number, kernel version, CPU architecture and the machine's DNS or Active Directory domain.
+// sysinfo answers the common fields; the build number comes from the registry on Windows
+// (with the update revision, as `winver` shows it), `sw_vers` on macOS and `BUILD_ID` in
+// os-release on the Linux distributions that set it.
+
+use serde::Serialize;
+use sysinfo::System;
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OsInfo {
+    // e.g. `Windows`, `Ubuntu`, `Darwin`
+    pub name: String,
+    // e.g. `11`, `24.04`, `14.5`
+    pub version: Option<String>,
+    // e.g. `Windows 11 Pro`, `Linux (Ubuntu 24.04 LTS)`, `macOS 14.5 Sonoma`
+    pub long_name: Option<String>,
+    // e.g. `22631.3880` or `23F79`
+    pub build: Option<String>,
+    // Windows feature update, e.g. `23H2`
+    pub release: Option<String>,
+    pub kernel_version: Option<String>,
+    // Of the OS, which differs from the app's own when it runs under emulation
+    pub architecture: String,
+    pub hostname: Option<String>,
+    // DNS domain, or the Active Directory domain on Windows; None when not joined to one
+    pub domain: Option<String>,
+}
+
+// (build, release, domain)
+type PlatformFields = (Option<String>, Option<String>, Option<String>);
+
+fn windows_fields() -> PlatformFields {
+    let script =
+        "$version = Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion'; \
+         $computer = Get-CimInstance Win32_ComputerSystem; \
+         ConvertTo-Json -Compress -InputObject @{ CurrentBuild = $version.CurrentBuild; \
+         UBR = $version.UBR; DisplayVersion = $version.DisplayVersion; \
+         Domain = $computer.Domain; PartOfDomain = $computer.PartOfDomain }";
+    let Some(record) = run_text("powershell", &["-NoProfile", "-Command", script])
+        .ok()
+        .and_then(|output| serde_json::from_str::<serde_json::Value>(output.trim()).ok())
+    else {
+        return (None, None, None);
+    };
+    let text = |key: &str| record[key].as_str().map(str::to_string);
+
+    let build = text("CurrentBuild").map(|build| match record["UBR"].as_u64() {
+        Some(revision) => format!("{}.{}", build, revision),
+        None => build,
+    });
+    // Outside a domain this holds the workgroup name
+    let domain = text("Domain").filter(|_| record["PartOfDomain"].as_bool() == Some(true));
+    (build, text("DisplayVersion"), domain)
+}
+
+fn os_release_field(field: &str) -> Option<String> {
+    let release = std::fs::read_to_string("/etc/os-release").ok()?;
+    release.lines().find_map(|line| {
+        let value = line.strip_prefix(field)?.strip_prefix('=')?;
+        Some(value.trim_matches('"').to_string())
+    })
+}
+
+// The part of the fully qualified host name after the first label; `.local` is mDNS, not a domain
+fn unix_domain() -> Option<String> {
+    let fqdn = run_text("hostname", &["-f"]).ok()?;
+    let (_, domain) = fqdn.trim().split_once('.')?;
+    (!domain.is_empty() && domain != "local").then(|| domain.to_string())
+}
+
+fn platform_fields() -> PlatformFields {
+    if cfg!(target_os = "windows") {
+        windows_fields()
+    } else if cfg!(target_os = "macos") {
+        let build = run_text("sw_vers", &["-buildVersion"])
+            .ok()
+            .map(|build| build.trim().to_string());
+        (build, None, unix_domain())
+    } else {
+        (os_release_field("BUILD_ID"), None, unix_domain())
+    }
+}
+
+pub fn read_os_info() -> Result<OsInfo, String> {
+    let name = System::name().ok_or("Failed to read the OS name")?;
+    let (build, release, domain) = platform_fields();
+    Ok(OsInfo {
+        name,
+        version: System::os_version(),
+        long_name: System::long_os_version(),
+        build,
+        release,
+        kernel_version: System::kernel_version(),
+        architecture: System::cpu_arch(),
+        hostname: System::host_name(),
+        domain,
+    })
+}
+
+#[tauri::command]
+pub async fn get_os_info() -> Result<OsInfo, CommandError> {
+    let info = tauri::async_runtime::spawn_blocking(read_os_info)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(info)
+}
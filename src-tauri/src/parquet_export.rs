@@ -0,0 +1,258 @@
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use rusqlite::params;
+use serde::Serialize;
+use std::fs::File;
+use std::sync::Arc;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::export_history::{self, ExportKind};
+use crate::history::{History, HistorySource, TimeRange, HOUR, MINUTE};
+
+// Rows buffered per Parquet row group
+const ROW_GROUP_SIZE: usize = 64 * 1024;
+
+const SAMPLES_SCHEMA: &str = "
+    message sample {
+        REQUIRED BYTE_ARRAY metric (UTF8);
+        REQUIRED INT64 timestamp (TIMESTAMP(MILLIS,true));
+        REQUIRED DOUBLE value;
+    }";
+
+const ROLLUPS_SCHEMA: &str = "
+    message rollup {
+        REQUIRED BYTE_ARRAY metric (UTF8);
+        REQUIRED INT64 bucket (TIMESTAMP(MILLIS,true));
+        REQUIRED INT64 resolution_seconds;
+        REQUIRED DOUBLE min;
+        REQUIRED DOUBLE max;
+        REQUIRED DOUBLE avg;
+        REQUIRED INT64 count;
+    }";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParquetExportSummary {
+    pub path: String,
+    pub rows: u64,
+    pub row_groups: u64,
+}
+
+// One buffered column of a row group, in schema order
+enum Column {
+    Text(Vec<ByteArray>),
+    Int64(Vec<i64>),
+    Double(Vec<f64>),
+}
+
+impl Column {
+    fn clear(&mut self) {
+        match self {
+            Column::Text(values) => values.clear(),
+            Column::Int64(values) => values.clear(),
+            Column::Double(values) => values.clear(),
+        }
+    }
+}
+
+fn write_row_group(
+    writer: &mut SerializedFileWriter<File>,
+    columns: &mut [Column],
+) -> parquet::errors::Result<()> {
+    let mut row_group = writer.next_row_group()?;
+    for column in columns.iter_mut() {
+        let Some(mut column_writer) = row_group.next_column()? else {
+            break;
+        };
+        match column {
+            Column::Text(values) => column_writer
+                .typed::<ByteArrayType>()
+                .write_batch(values, None, None)?,
+            Column::Int64(values) => column_writer
+                .typed::<Int64Type>()
+                .write_batch(values, None, None)?,
+            Column::Double(values) => column_writer
+                .typed::<DoubleType>()
+                .write_batch(values, None, None)?,
+        };
+        column_writer.close()?;
+        column.clear();
+    }
+    row_group.close()?;
+    Ok(())
+}
+
+// One page of the export query: rows of the range after the `after` (timestamp, rowid) key
+struct Chunk<'a> {
+    sql: &'a str,
+    metric: &'a Option<String>,
+    range: &'a TimeRange,
+    resolution: Option<i64>,
+    after: (i64, i64),
+}
+
+// Reads up to a row group of rows into `columns`, returning how many were read and the
+// (timestamp, rowid) of the last one
+fn read_chunk(
+    history: &History,
+    chunk: &Chunk,
+    columns: &mut [Column],
+) -> Result<(usize, (i64, i64)), String> {
+    let conn = history.conn()?;
+    let mut statement = conn.prepare_cached(chunk.sql).map_err(|e| e.to_string())?;
+    let (metric, range, (after_time, after_rowid)) = (chunk.metric, chunk.range, chunk.after);
+    let limit = ROW_GROUP_SIZE as i64;
+    let mut rows = match chunk.resolution {
+        Some(resolution) => statement.query(params![
+            metric,
+            range.from,
+            range.to,
+            after_time,
+            after_rowid,
+            limit,
+            resolution
+        ]),
+        None => statement.query(params![
+            metric,
+            range.from,
+            range.to,
+            after_time,
+            after_rowid,
+            limit
+        ]),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let (mut read, mut last) = (0, chunk.after);
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        for (index, column) in columns.iter_mut().enumerate() {
+            match column {
+                Column::Text(values) => {
+                    let text: String = row.get(index).map_err(|e| e.to_string())?;
+                    values.push(ByteArray::from(text.as_str()));
+                }
+                // The first integer column is always a timestamp in seconds
+                Column::Int64(values) => {
+                    let value: i64 = row.get(index).map_err(|e| e.to_string())?;
+                    values.push(if index == 1 { value * 1000 } else { value });
+                }
+                Column::Double(values) => values.push(row.get(index).map_err(|e| e.to_string())?),
+            }
+        }
+        last = (
+            row.get(1).map_err(|e| e.to_string())?,
+            row.get(columns.len()).map_err(|e| e.to_string())?,
+        );
+        read += 1;
+    }
+    Ok((read, last))
+}
+
+#[tauri::command]
+pub fn export_history_parquet(
+    path: String,
+    metric: Option<String>,
+    range: Option<TimeRange>,
+    source: Option<HistorySource>,
+    history: State<History>,
+) -> Result<ParquetExportSummary, CommandError> {
+    let source = source.unwrap_or(HistorySource::Raw);
+    let range = range.unwrap_or(TimeRange {
+        from: i64::MIN,
+        to: i64::MAX,
+    });
+
+    let (schema, sql, resolution) = match source {
+        HistorySource::Raw => (
+            SAMPLES_SCHEMA,
+            "SELECT metric, timestamp, value, rowid FROM samples
+             WHERE (?1 IS NULL OR metric = ?1) AND timestamp >= ?2 AND timestamp < ?3
+               AND (timestamp, rowid) > (?4, ?5)
+             ORDER BY timestamp, rowid LIMIT ?6",
+            None,
+        ),
+        HistorySource::MinuteRollup | HistorySource::HourRollup => (
+            ROLLUPS_SCHEMA,
+            "SELECT metric, bucket, resolution, min, max, avg, count, rowid FROM rollups
+             WHERE (?1 IS NULL OR metric = ?1) AND bucket >= ?2 AND bucket < ?3
+               AND (bucket, rowid) > (?4, ?5) AND resolution = ?7
+             ORDER BY bucket, rowid LIMIT ?6",
+            Some(if matches!(source, HistorySource::HourRollup) {
+                HOUR
+            } else {
+                MINUTE
+            }),
+        ),
+    };
+
+    let schema = Arc::new(parse_message_type(schema).map_err(|e| e.to_string())?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let file = File::create(&path).map_err(|e| CommandError::create_failed(&path, e))?;
+    let mut writer =
+        SerializedFileWriter::new(file, schema, properties).map_err(|e| e.to_string())?;
+
+    let mut columns = match source {
+        HistorySource::Raw => vec![
+            Column::Text(Vec::new()),
+            Column::Int64(Vec::new()),
+            Column::Double(Vec::new()),
+        ],
+        _ => vec![
+            Column::Text(Vec::new()),
+            Column::Int64(Vec::new()),
+            Column::Int64(Vec::new()),
+            Column::Double(Vec::new()),
+            Column::Double(Vec::new()),
+            Column::Double(Vec::new()),
+            Column::Int64(Vec::new()),
+        ],
+    };
+
+    // Each row group is read under its own lock, so sampling and queries aren't held up for
+    // the whole export; rows resume after the (timestamp, rowid) of the last one written
+    let (mut total_rows, mut row_groups) = (0u64, 0u64);
+    let mut after = (range.from, i64::MIN);
+    loop {
+        let chunk = Chunk {
+            sql,
+            metric: &metric,
+            range: &range,
+            resolution,
+            after,
+        };
+        let (read, last) = read_chunk(&history, &chunk, &mut columns)?;
+        if read == 0 {
+            break;
+        }
+        write_row_group(&mut writer, &mut columns).map_err(|e| e.to_string())?;
+        total_rows += read as u64;
+        row_groups += 1;
+        after = last;
+        if read < ROW_GROUP_SIZE {
+            break;
+        }
+    }
+    writer.close().map_err(|e| e.to_string())?;
+
+    let summary = format!(
+        "{} history rows ({})",
+        total_rows,
+        metric.as_deref().unwrap_or("all metrics")
+    );
+    export_history::record_or_warn(&history, ExportKind::Parquet, Some(&path), &summary, None);
+
+    println!("✓ Exported {} history rows to {}", total_rows, path);
+    Ok(ParquetExportSummary {
+        path,
+        rows: total_rows,
+        row_groups,
+    })
+}
@@ -0,0 +1,362 @@
+// Windows performance counters the built-in metrics don't cover, such as disk queue length or
+// context switches. Only the counter paths listed under `[perfCounters]` in config.toml are
+// read, one value each, so wildcard instances are not accepted. They are read through PDH with
+// English counter names, so the paths work whatever the display language, and sampled into
+// history as `perf.<path>` at the configured interval, where `query_history` can chart them.
+// Other platforms have no counters to read.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::exporter;
+use crate::report::unix_timestamp;
+
+const METRIC_PREFIX: &str = "perf.";
+// Rate counters such as `Context Switches/sec` need two collections; a one-off read waits this
+// long between them, like `Get-Counter` does
+const RATE_SAMPLE: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PerfCounterConfig {
+    // Counter paths that may be read, e.g. `\Processor(_Total)\% Processor Time`
+    pub counters: Vec<String>,
+    pub interval_seconds: u64,
+}
+
+impl Default for PerfCounterConfig {
+    fn default() -> Self {
+        PerfCounterConfig {
+            counters: [
+                r"\Processor(_Total)\% Processor Time",
+                r"\PhysicalDisk(_Total)\Current Disk Queue Length",
+                r"\Memory\Pages/sec",
+                r"\System\Processor Queue Length",
+                r"\System\Context Switches/sec",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            interval_seconds: 60,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfCounterReading {
+    pub path: String,
+    // History metric the counter is sampled into
+    pub metric: String,
+    pub value: Option<f64>,
+    // Why there is no value, e.g. the counter doesn't exist on this machine
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfCounterValues {
+    // False on platforms without performance counters
+    pub supported: bool,
+    pub timestamp: i64,
+    pub counters: Vec<PerfCounterReading>,
+}
+
+fn metric(path: &str) -> String {
+    format!("{}{}", METRIC_PREFIX, path)
+}
+
+fn reading(path: &str, value: Result<f64, String>) -> PerfCounterReading {
+    let (value, error) = match value {
+        Ok(value) => (Some(value), None),
+        Err(e) => (None, Some(e)),
+    };
+    PerfCounterReading {
+        path: path.to_string(),
+        metric: metric(path),
+        value,
+        error,
+    }
+}
+
+#[cfg(windows)]
+mod pdh {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    const PDH_FMT_DOUBLE: u32 = 0x0000_0200;
+    // Percentages such as `% Processor Time` may exceed 100 on multi-core counters
+    const PDH_FMT_NOCAP100: u32 = 0x0000_8000;
+    const PDH_CSTATUS_NO_OBJECT: u32 = 0xC000_0BB8;
+    const PDH_CSTATUS_NO_COUNTER: u32 = 0xC000_0BB9;
+    const PDH_CSTATUS_NO_INSTANCE: u32 = 0x8000_0BB9;
+
+    // PDH_FMT_COUNTERVALUE with only the double member of its value union
+    #[repr(C)]
+    struct FormattedValue {
+        status: u32,
+        value: f64,
+    }
+
+    #[link(name = "pdh")]
+    extern "system" {
+        fn PdhOpenQueryW(data_source: *const u16, user_data: usize, query: *mut isize) -> u32;
+        fn PdhAddEnglishCounterW(
+            query: isize,
+            path: *const u16,
+            user_data: usize,
+            counter: *mut isize,
+        ) -> u32;
+        fn PdhCollectQueryData(query: isize) -> u32;
+        fn PdhGetFormattedCounterValue(
+            counter: isize,
+            format: u32,
+            kind: *mut u32,
+            value: *mut FormattedValue,
+        ) -> u32;
+        fn PdhCloseQuery(query: isize) -> u32;
+    }
+
+    fn check(status: u32) -> Result<(), String> {
+        match status {
+            0 => Ok(()),
+            PDH_CSTATUS_NO_OBJECT | PDH_CSTATUS_NO_COUNTER | PDH_CSTATUS_NO_INSTANCE => {
+                Err("Counter not found".to_string())
+            }
+            status => Err(format!("Counter status 0x{:08x}", status)),
+        }
+    }
+
+    // A PDH query over a fixed list of counters; a counter that can't be added keeps its error
+    pub struct Query {
+        handle: isize,
+        counters: Vec<Result<isize, String>>,
+    }
+
+    impl Query {
+        pub fn open(paths: &[&str]) -> Result<Self, String> {
+            let mut handle = 0;
+            check(unsafe { PdhOpenQueryW(std::ptr::null(), 0, &mut handle) })?;
+            let counters = paths
+                .iter()
+                .map(|path| {
+                    let wide: Vec<u16> = OsStr::new(path).encode_wide().chain(Some(0)).collect();
+                    let mut counter = 0;
+                    check(unsafe { PdhAddEnglishCounterW(handle, wide.as_ptr(), 0, &mut counter) })
+                        .map(|()| counter)
+                })
+                .collect();
+            Ok(Query { handle, counters })
+        }
+
+        pub fn collect(&self) -> Result<(), String> {
+            check(unsafe { PdhCollectQueryData(self.handle) })
+        }
+
+        // Values as of the last collection, in the order the paths were opened
+        pub fn values(&self) -> Vec<Result<f64, String>> {
+            self.counters
+                .iter()
+                .map(|counter| {
+                    let counter = counter.as_ref().map_err(String::clone)?;
+                    let mut value = FormattedValue {
+                        status: 0,
+                        value: 0.0,
+                    };
+                    check(unsafe {
+                        PdhGetFormattedCounterValue(
+                            *counter,
+                            PDH_FMT_DOUBLE | PDH_FMT_NOCAP100,
+                            std::ptr::null_mut(),
+                            &mut value,
+                        )
+                    })?;
+                    check(value.status)?;
+                    Ok(value.value)
+                })
+                .collect()
+        }
+    }
+
+    impl Drop for Query {
+        fn drop(&mut self) {
+            // Closing the query also removes its counters
+            unsafe { PdhCloseQuery(self.handle) };
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod pdh {
+    pub struct Query;
+
+    impl Query {
+        pub fn open(_paths: &[&str]) -> Result<Self, String> {
+            Err("Performance counters are only available on Windows".to_string())
+        }
+
+        pub fn collect(&self) -> Result<(), String> {
+            Ok(())
+        }
+
+        pub fn values(&self) -> Vec<Result<f64, String>> {
+            Vec::new()
+        }
+    }
+}
+
+// The counters of `paths` behind one open query, collected once on open so the next read
+// has a previous value to compute rates from
+struct Counters {
+    paths: Vec<String>,
+    wildcards: Vec<String>,
+    single: Vec<String>,
+    query: Result<pdh::Query, String>,
+}
+
+impl Counters {
+    fn open(paths: &[String]) -> Self {
+        let (wildcards, single): (Vec<String>, Vec<String>) =
+            paths.iter().cloned().partition(|path| path.contains('*'));
+        let query = if single.is_empty() {
+            Err("No counters to read".to_string())
+        } else {
+            let paths: Vec<&str> = single.iter().map(String::as_str).collect();
+            pdh::Query::open(&paths).and_then(|query| {
+                query.collect()?;
+                Ok(query)
+            })
+        };
+        Counters {
+            paths: paths.to_vec(),
+            wildcards,
+            single,
+            query,
+        }
+    }
+
+    fn read(&self) -> Vec<PerfCounterReading> {
+        let mut readings: Vec<PerfCounterReading> = self
+            .wildcards
+            .iter()
+            .map(|path| {
+                reading(
+                    path,
+                    Err("Wildcards aren't supported; list each instance".into()),
+                )
+            })
+            .collect();
+        if self.single.is_empty() {
+            return readings;
+        }
+
+        let values = self
+            .query
+            .as_ref()
+            .map_err(String::clone)
+            .and_then(|query| {
+                query.collect()?;
+                Ok(query.values())
+            });
+        match values {
+            Ok(values) => readings.extend(
+                self.single
+                    .iter()
+                    .zip(values)
+                    .map(|(path, value)| reading(path, value)),
+            ),
+            Err(e) => readings.extend(self.single.iter().map(|path| reading(path, Err(e.clone())))),
+        }
+        readings
+    }
+}
+
+fn read_counters(paths: &[String]) -> Vec<PerfCounterReading> {
+    let counters = Counters::open(paths);
+    if counters.query.is_ok() {
+        std::thread::sleep(RATE_SAMPLE);
+    }
+    counters.read()
+}
+
+fn perf_counter_config(app: &AppHandle) -> PerfCounterConfig {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.perf_counters.clone())
+        .unwrap_or_default()
+}
+
+// Background job sampling the allowlisted counters; config changes apply from the next round
+pub fn start_sampling(app: AppHandle) {
+    if !cfg!(target_os = "windows") {
+        return;
+    }
+    std::thread::spawn(move || {
+        // Kept open between rounds so rates are averaged over the whole interval
+        let mut counters: Option<Counters> = None;
+        loop {
+            let config = perf_counter_config(&app);
+            let counters = match counters.take() {
+                Some(open) if open.paths == config.counters => counters.insert(open),
+                _ => {
+                    let open = Counters::open(&config.counters);
+                    if open.query.is_ok() {
+                        std::thread::sleep(RATE_SAMPLE);
+                    }
+                    counters.insert(open)
+                }
+            };
+            let samples: Vec<(String, f64)> = counters
+                .read()
+                .into_iter()
+                .filter_map(|reading| Some((reading.metric, reading.value?)))
+                .collect();
+            if !samples.is_empty() {
+                exporter::publish(&app, &samples);
+            }
+            battery_saver::wait(&app, Duration::from_secs(config.interval_seconds.max(1)));
+        }
+    });
+}
+
+// Current values of `paths`, which must all be allowlisted, or of every allowlisted counter
+#[tauri::command]
+pub async fn get_perf_counters(
+    paths: Option<Vec<String>>,
+    config: State<'_, ConfigState>,
+) -> Result<PerfCounterValues, CommandError> {
+    let allowed = config.get()?.perf_counters.counters.clone();
+    let paths = match paths {
+        Some(paths) => {
+            if let Some(path) = paths.iter().find(|path| {
+                !allowed
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(path))
+            }) {
+                return Err(
+                    CommandError::new(ErrorCode::InvalidInput, "perfCounter.notAllowed")
+                        .with("path", path),
+                );
+            }
+            paths
+        }
+        None => allowed,
+    };
+
+    let supported = cfg!(target_os = "windows");
+    let counters = if supported {
+        tauri::async_runtime::spawn_blocking(move || read_counters(&paths))
+            .await
+            .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+    Ok(PerfCounterValues {
+        supported,
+        timestamp: unix_timestamp() as i64,
+        counters,
+    })
+}
@@ -0,0 +1,148 @@
+// Admin policy for managed deployments: commands and background samplers switched off entirely,
+// e.g. no process list or installed apps on privacy-sensitive machines. It is read from the
+// `[policy]` table of config.toml, unless a system-wide `policy.toml` exists; that file then
+// replaces the user's policy, so users can't turn blocked data back on. Disabled commands are
+// rejected before they run, and disabled samplers are never started. Changes apply on restart.
+//
+// System-wide locations:
+// - Linux: /etc/system-info-app/policy.toml
+// - macOS: /Library/Application Support/system-info-app/policy.toml
+// - Windows: %ProgramData%\system-info-app\policy.toml
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::ipc::Invoke;
+use tauri::{Manager, Runtime, State};
+
+use crate::error::{CommandError, ErrorCode};
+
+const POLICY_FILE: &str = "policy.toml";
+const APP_DIR: &str = "system-info-app";
+
+// Background samplers a policy can switch off
+pub const SAMPLERS: &[&str] = &[
+    "thermal",
+    "cpu",
+    "memory",
+    "handles",
+    "watchlist",
+    "integrity",
+    "latency",
+    "capture",
+    "inventory",
+    "battery",
+    "ssd",
+    "power",
+    "perfCounters",
+    // mDNS browsing and announcing the agent
+    "discovery",
+];
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PolicyConfig {
+    // Command names, as in `list_commands`
+    pub disabled_commands: Vec<String>,
+    // Names from `SAMPLERS`
+    pub disabled_samplers: Vec<String>,
+}
+
+// The policy in effect and where it came from
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Policy {
+    #[serde(flatten)]
+    pub config: PolicyConfig,
+    // The system-wide file, or None when the user config applies
+    pub managed_by: Option<String>,
+}
+
+fn system_path() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("ProgramData")
+            .map(|dir| PathBuf::from(dir).join(APP_DIR).join(POLICY_FILE))
+    } else if cfg!(target_os = "macos") {
+        Some(
+            PathBuf::from("/Library/Application Support")
+                .join(APP_DIR)
+                .join(POLICY_FILE),
+        )
+    } else {
+        Some(PathBuf::from("/etc").join(APP_DIR).join(POLICY_FILE))
+    }
+}
+
+impl Policy {
+    // The system-wide policy if there is one, otherwise `user`. An unreadable system-wide file
+    // blocks nothing but is reported, rather than silently falling back to the user's policy.
+    pub fn load(user: &PolicyConfig) -> Self {
+        let Some(path) = system_path().filter(|path| path.exists()) else {
+            return Policy {
+                config: user.clone(),
+                managed_by: None,
+            };
+        };
+
+        let config = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| toml::from_str(&text).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("⚠ Warning: Ignoring invalid {}: {}", path.display(), e);
+                PolicyConfig::default()
+            });
+        for name in config
+            .disabled_samplers
+            .iter()
+            .filter(|name| !SAMPLERS.contains(&name.as_str()))
+        {
+            eprintln!("⚠ Warning: Unknown sampler {} in {}", name, path.display());
+        }
+        println!("✓ Using the managed policy from {}", path.display());
+        Policy {
+            config,
+            managed_by: Some(path.display().to_string()),
+        }
+    }
+
+    pub fn command_enabled(&self, command: &str) -> bool {
+        !self
+            .config
+            .disabled_commands
+            .iter()
+            .any(|name| name == command)
+    }
+
+    pub fn sampler_enabled(&self, sampler: &str) -> bool {
+        !self
+            .config
+            .disabled_samplers
+            .iter()
+            .any(|name| name == sampler)
+    }
+}
+
+// Wrap the invoke handler so commands the policy disables are rejected before they run
+pub fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let webview = invoke.message.webview();
+        let command = invoke.message.command().to_string();
+        let enabled = webview
+            .try_state::<Policy>()
+            .is_none_or(|policy| policy.command_enabled(&command));
+        if enabled {
+            return handler(invoke);
+        }
+        invoke.resolver.reject(
+            CommandError::new(ErrorCode::PermissionDenied, "policy.commandDisabled")
+                .with("command", command),
+        );
+        true
+    }
+}
+
+#[tauri::command]
+pub fn get_policy(policy: State<Policy>) -> Policy {
+    policy.inner().clone()
+}
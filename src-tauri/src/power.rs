@@ -0,0 +1,262 @@
+// Battery charge, charging state, time remaining and AC adapter presence.
+// Answered by the systemapi library's `GetPowerStatus` (1.2 and later) or, without it, by the
+// Rust implementation below, which reads the same sources as the battery crates: sysfs on Linux,
+// `Win32_Battery` on Windows and `pmset` on macOS. A background job polls the status and emits
+// `power-status-changed` when the adapter is plugged in or out or the charge state changes.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::battery_saver;
+use crate::error::CommandError;
+use crate::os_command::run_text;
+use crate::CppLibrary;
+
+pub const POWER_STATUS_CHANGED_EVENT: &str = "power-status-changed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChargeState {
+    Unknown,
+    Charging,
+    Discharging,
+    Full,
+    // On AC power but held below full, e.g. by a charge limit
+    NotCharging,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub has_battery: bool,
+    // Combined across batteries; None without a battery
+    pub battery_percent: Option<u8>,
+    pub charge_state: ChargeState,
+    // None where the platform doesn't say
+    pub ac_online: Option<bool>,
+    // Until empty when discharging, until full when charging
+    pub seconds_remaining: Option<u64>,
+}
+
+impl PowerStatus {
+    fn no_battery(ac_online: Option<bool>) -> Self {
+        PowerStatus {
+            has_battery: false,
+            battery_percent: None,
+            charge_state: ChargeState::Unknown,
+            ac_online,
+            seconds_remaining: None,
+        }
+    }
+
+    // The parts whose change is worth an event; the charge level moves all the time
+    fn state(&self) -> (Option<bool>, ChargeState) {
+        (self.ac_online, self.charge_state)
+    }
+}
+
+fn linux_status() -> Result<PowerStatus, String> {
+    let entries = std::fs::read_dir(POWER_SUPPLY_DIR).map_err(|e| e.to_string())?;
+    let mut ac_online = None;
+    let mut batteries = 0;
+    // Energy (µWh) or charge (µAh) across batteries, with the matching rate (µW or µA)
+    let (mut now, mut full, mut rate) = (0u64, 0u64, 0u64);
+    let mut states = Vec::new();
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |file: &str| {
+            std::fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let number = |file: &str| read(file).and_then(|value| value.parse::<i64>().ok());
+        match read("type").as_deref() {
+            Some("Mains") | Some("USB") => {
+                ac_online = Some(ac_online.unwrap_or(false) || number("online") == Some(1));
+            }
+            Some("Battery") if number("present") != Some(0) => {
+                batteries += 1;
+                let readings = match (number("energy_now"), number("energy_full")) {
+                    (Some(now), Some(full)) => Some((now, full, number("power_now"))),
+                    _ => match (number("charge_now"), number("charge_full")) {
+                        (Some(now), Some(full)) => Some((now, full, number("current_now"))),
+                        _ => None,
+                    },
+                };
+                if let Some((battery_now, battery_full, battery_rate)) = readings {
+                    now += battery_now.max(0) as u64;
+                    full += battery_full.max(0) as u64;
+                    // Some drivers report the rate as negative while discharging
+                    rate += battery_rate.unwrap_or(0).unsigned_abs();
+                }
+                states.push(read("status").unwrap_or_default());
+            }
+            _ => {}
+        }
+    }
+
+    if batteries == 0 {
+        return Ok(PowerStatus::no_battery(ac_online));
+    }
+    let charge_state = if states.iter().any(|state| state == "Charging") {
+        ChargeState::Charging
+    } else if states.iter().any(|state| state == "Discharging") {
+        ChargeState::Discharging
+    } else if states.iter().all(|state| state == "Full") {
+        ChargeState::Full
+    } else if ac_online == Some(true) {
+        ChargeState::NotCharging
+    } else {
+        ChargeState::Unknown
+    };
+    let seconds_remaining = match charge_state {
+        _ if rate == 0 => None,
+        ChargeState::Discharging => Some(now * 3600 / rate),
+        ChargeState::Charging => Some(full.saturating_sub(now) * 3600 / rate),
+        _ => None,
+    };
+    Ok(PowerStatus {
+        has_battery: true,
+        battery_percent: (full > 0).then(|| (now * 100 / full).min(100) as u8),
+        charge_state,
+        ac_online,
+        seconds_remaining,
+    })
+}
+
+fn windows_status() -> Result<PowerStatus, String> {
+    let output = run_text(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "ConvertTo-Json -Compress -InputObject @(Get-CimInstance Win32_Battery | \
+             Select-Object EstimatedChargeRemaining, EstimatedRunTime, BatteryStatus)",
+        ],
+    )?;
+    let batteries: Vec<serde_json::Value> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+    let Some(battery) = batteries.first() else {
+        return Ok(PowerStatus::no_battery(None));
+    };
+
+    // BatteryStatus 1 is discharging, 2 on AC, 3 fully charged and 6 to 9 charging
+    let status = battery["BatteryStatus"].as_u64().unwrap_or_default();
+    let charge_state = match status {
+        1 => ChargeState::Discharging,
+        2 => ChargeState::NotCharging,
+        3 => ChargeState::Full,
+        6..=9 => ChargeState::Charging,
+        _ => ChargeState::Unknown,
+    };
+    Ok(PowerStatus {
+        has_battery: true,
+        battery_percent: battery["EstimatedChargeRemaining"]
+            .as_u64()
+            .map(|percent| percent.min(100) as u8),
+        charge_state,
+        ac_online: (status != 0).then_some(status != 1),
+        // Minutes; on AC power the field holds a placeholder of 71582788
+        seconds_remaining: battery["EstimatedRunTime"]
+            .as_u64()
+            .filter(|_| charge_state == ChargeState::Discharging)
+            .map(|minutes| minutes * 60),
+    })
+}
+
+fn macos_status() -> Result<PowerStatus, String> {
+    // Now drawing from 'AC Power'
+    //  -InternalBattery-0 (id=4653155)<tab>87%; charging; 1:02 remaining present: true
+    let output = run_text("pmset", &["-g", "batt"])?;
+    let ac_online = output
+        .lines()
+        .next()
+        .map(|line| line.contains("'AC Power'"));
+    let Some(battery) = output.lines().find(|line| line.contains("InternalBattery")) else {
+        return Ok(PowerStatus::no_battery(ac_online));
+    };
+    let fields: Vec<&str> = battery
+        .split_once('\t')
+        .map_or("", |(_, fields)| fields)
+        .split(';')
+        .map(str::trim)
+        .collect();
+
+    let charge_state = match fields.get(1).copied() {
+        Some("charging") | Some("finishing charge") => ChargeState::Charging,
+        Some("discharging") => ChargeState::Discharging,
+        Some("charged") => ChargeState::Full,
+        Some(state) if state.contains("not charging") => ChargeState::NotCharging,
+        _ => ChargeState::Unknown,
+    };
+    // `1:02 remaining`, or `(no estimate)` while it is still being calculated
+    let seconds_remaining = fields.get(2).and_then(|time| {
+        let (hours, rest) = time.split_once(':')?;
+        let minutes = rest.split_whitespace().next()?;
+        Some(hours.parse::<u64>().ok()? * 3600 + minutes.parse::<u64>().ok()? * 60)
+    });
+    Ok(PowerStatus {
+        has_battery: true,
+        battery_percent: fields
+            .first()
+            .and_then(|percent| percent.trim_end_matches('%').parse::<u8>().ok()),
+        charge_state,
+        ac_online,
+        seconds_remaining: seconds_remaining.filter(|_| {
+            matches!(
+                charge_state,
+                ChargeState::Charging | ChargeState::Discharging
+            )
+        }),
+    })
+}
+
+// The Rust implementation behind `RustProvider::power_status`
+pub fn read_os_status() -> Result<PowerStatus, String> {
+    if cfg!(target_os = "windows") {
+        windows_status()
+    } else if cfg!(target_os = "macos") {
+        macos_status()
+    } else {
+        linux_status()
+    }
+}
+
+// Background job emitting `power-status-changed` when the adapter or charge state flips
+pub fn start_watching(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut previous = None;
+        loop {
+            match app.state::<CppLibrary>().provider().power_status() {
+                Ok(status) => {
+                    let state = status.state();
+                    if previous.is_some_and(|previous| previous != state) {
+                        if let Err(e) = app.emit(POWER_STATUS_CHANGED_EVENT, &status) {
+                            eprintln!(
+                                "⚠ Warning: Failed to emit {}: {}",
+                                POWER_STATUS_CHANGED_EVENT, e
+                            );
+                        }
+                    }
+                    previous = Some(state);
+                }
+                Err(e) => eprintln!("Reading power status failed: {}", e),
+            }
+            battery_saver::wait(&app, POLL_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_power_status(app: AppHandle) -> Result<PowerStatus, CommandError> {
+    crate::call_blocking(app, "get_power_status", |app| {
+        app.state::<CppLibrary>()
+            .call("get_power_status", |provider| provider.power_status())
+    })
+    .await
+}
@@ -0,0 +1,156 @@
+// Startup self-test of the system backend.
+// Each cheap library call is made once, timed and sanity-checked, and the results are emitted as
+// `backend-ready` so a broken library build shows up before the user clicks anything. The
+// result is also kept for `get_backend_status`, since the event can fire before the UI listens.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::provider::Backend;
+use crate::{read_computer_name, read_factorial, read_process_id, read_total_memory, CppLibrary};
+
+pub const BACKEND_READY_EVENT: &str = "backend-ready";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub name: &'static str,
+    pub passed: bool,
+    pub elapsed_micros: u64,
+    pub value: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendStatus {
+    pub library_loaded: bool,
+    // The Rust fallback answers when the native library isn't loaded
+    pub backend: Backend,
+    // False when preflight is disabled in the config, in which case `checks` is empty
+    pub preflight_ran: bool,
+    pub passed: bool,
+    pub checks: Vec<PreflightCheck>,
+}
+
+#[derive(Default)]
+pub struct Preflight {
+    status: Mutex<Option<BackendStatus>>,
+}
+
+// Time one call and validate its output; `validate` returns why a value is wrong
+fn check<T: ToString>(
+    name: &'static str,
+    call: impl FnOnce() -> Result<T, CommandError>,
+    validate: impl FnOnce(&T) -> Option<String>,
+) -> PreflightCheck {
+    let started = Instant::now();
+    let result = call();
+    let elapsed_micros = started.elapsed().as_micros() as u64;
+
+    let (value, error) = match result {
+        Ok(value) => {
+            let error = validate(&value);
+            (Some(value.to_string()), error)
+        }
+        Err(e) => (None, Some(e.message)),
+    };
+    PreflightCheck {
+        name,
+        passed: error.is_none(),
+        elapsed_micros,
+        value,
+        error,
+    }
+}
+
+pub fn run_checks(lib_state: &CppLibrary) -> Vec<PreflightCheck> {
+    vec![
+        check(
+            "computerName",
+            || read_computer_name(lib_state),
+            |name| {
+                name.trim()
+                    .is_empty()
+                    .then(|| "empty computer name".to_string())
+            },
+        ),
+        check(
+            "totalMemory",
+            || read_total_memory(lib_state),
+            |&bytes| (bytes == 0).then(|| "reported 0 bytes of memory".to_string()),
+        ),
+        check(
+            "processId",
+            || read_process_id(lib_state),
+            |&pid| {
+                (pid != std::process::id())
+                    .then(|| format!("expected {}, got {}", std::process::id(), pid))
+            },
+        ),
+        check(
+            "factorial",
+            || read_factorial(lib_state, 5),
+            |&result| (result != 120).then(|| format!("5! returned {}", result)),
+        ),
+    ]
+}
+
+// Run the checks on a background thread so startup isn't held up by a slow library
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || {
+        let enabled = app
+            .state::<ConfigState>()
+            .get()
+            .map(|config| config.preflight.enabled)
+            .unwrap_or(true);
+        let lib_state = app.state::<CppLibrary>();
+
+        let checks = if enabled {
+            run_checks(&lib_state)
+        } else {
+            Vec::new()
+        };
+        let status = BackendStatus {
+            library_loaded: lib_state.is_loaded(),
+            backend: lib_state.provider().backend(),
+            preflight_ran: enabled,
+            passed: checks.iter().all(|check| check.passed),
+            checks,
+        };
+
+        for failed in status.checks.iter().filter(|check| !check.passed) {
+            eprintln!(
+                "⚠ Warning: Preflight check {} failed: {}",
+                failed.name,
+                failed.error.as_deref().unwrap_or_default()
+            );
+        }
+        if enabled && status.passed {
+            println!("✓ Preflight checks passed");
+        }
+
+        if let Ok(mut stored) = app.state::<Preflight>().status.lock() {
+            *stored = Some(status.clone());
+        }
+        if let Err(e) = app.emit(BACKEND_READY_EVENT, status) {
+            eprintln!("⚠ Warning: Failed to emit {}: {}", BACKEND_READY_EVENT, e);
+        }
+    });
+}
+
+// Preflight results, or None while the checks are still running
+#[tauri::command]
+pub fn get_backend_status(
+    preflight: State<Preflight>,
+) -> Result<Option<BackendStatus>, CommandError> {
+    Ok(preflight
+        .status
+        .lock()
+        .map_err(|_| "Preflight lock poisoned")?
+        .clone())
+}
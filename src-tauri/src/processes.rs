@@ -0,0 +1,295 @@
+// Running processes for the process table. Sorting, filtering and the row limit are applied here
+// so a machine with thousands of processes doesn't serialize all of them to the webview.
+// CPU usage is measured since the previous call, using the process list kept in `ProcessList`.
+// Ending a process asks for confirmation in a native dialog first, like the service controls.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::Mutex;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::error::{CommandError, ErrorCode};
+use crate::kiosk;
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSortKey {
+    Pid,
+    Name,
+    #[default]
+    Cpu,
+    Memory,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    // Percentage of one core, so it can exceed 100 for multithreaded processes
+    pub cpu_usage: f32,
+    // Resident set size in bytes
+    pub memory: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessListing {
+    pub processes: Vec<ProcessInfo>,
+    // Processes matching the filter, before the limit was applied
+    pub matched: usize,
+    pub total: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessDetails {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command_line: Vec<String>,
+    pub exe_path: Option<String>,
+    // Unix timestamp in seconds
+    pub start_time: u64,
+    // File descriptors on Unix, handles on Windows; None when we may not inspect the process
+    pub open_handles: Option<u64>,
+    // `KEY=value` entries; empty when we may not read them
+    pub environment: Vec<String>,
+}
+
+// Kept between calls so CPU usage covers the time since the previous refresh
+#[derive(Default)]
+pub struct ProcessList {
+    system: Mutex<Option<System>>,
+}
+
+fn refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::nothing().with_cpu().with_memory()
+}
+
+fn compare(a: &ProcessInfo, b: &ProcessInfo, key: ProcessSortKey) -> Ordering {
+    match key {
+        ProcessSortKey::Pid => a.pid.cmp(&b.pid),
+        ProcessSortKey::Name => a
+            .name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then(a.pid.cmp(&b.pid)),
+        ProcessSortKey::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+        ProcessSortKey::Memory => a.memory.cmp(&b.memory),
+    }
+}
+
+fn read_processes(
+    list: &ProcessList,
+    key: ProcessSortKey,
+    order: SortOrder,
+    filter: Option<&str>,
+    limit: Option<usize>,
+) -> Result<ProcessListing, String> {
+    let mut system = list
+        .system
+        .lock()
+        .map_err(|_| "Process list lock poisoned")?;
+    let system = system.get_or_insert_with(|| {
+        // The first refresh only sets a baseline for CPU usage
+        let mut system = System::new();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind());
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        system
+    });
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind());
+
+    // Case-insensitive substring match on the process name
+    let filter = filter
+        .map(|filter| filter.trim().to_lowercase())
+        .filter(|filter| !filter.is_empty());
+    let mut processes: Vec<ProcessInfo> = system
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let name = process.name().to_string_lossy().into_owned();
+            if let Some(filter) = &filter {
+                if !name.to_lowercase().contains(filter) {
+                    return None;
+                }
+            }
+            Some(ProcessInfo {
+                pid: pid.as_u32(),
+                parent_pid: process.parent().map(|parent| parent.as_u32()),
+                name,
+                cpu_usage: process.cpu_usage(),
+                memory: process.memory(),
+            })
+        })
+        .collect();
+
+    processes.sort_by(|a, b| match order {
+        SortOrder::Asc => compare(a, b, key),
+        SortOrder::Desc => compare(b, a, key),
+    });
+    let matched = processes.len();
+    if let Some(limit) = limit {
+        processes.truncate(limit);
+    }
+    Ok(ProcessListing {
+        processes,
+        matched,
+        total: system.processes().len(),
+    })
+}
+
+// All processes sorted by CPU usage, highest first, unless told otherwise. The first call
+// after startup waits briefly so there is an interval to measure CPU usage over.
+#[tauri::command]
+pub async fn list_processes(
+    sort_by: Option<ProcessSortKey>,
+    order: Option<SortOrder>,
+    filter: Option<String>,
+    limit: Option<usize>,
+    app: AppHandle,
+) -> Result<ProcessListing, CommandError> {
+    let listing = tauri::async_runtime::spawn_blocking(move || {
+        read_processes(
+            &app.state::<ProcessList>(),
+            sort_by.unwrap_or_default(),
+            order.unwrap_or_default(),
+            filter.as_deref(),
+            limit,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    Ok(listing)
+}
+
+fn not_found(pid: u32) -> CommandError {
+    CommandError::new(ErrorCode::InvalidInput, "process.notFound").with("pid", pid)
+}
+
+// A fresh refresh of one process, with the details the list doesn't load
+fn refresh_process(pid: u32) -> Result<System, CommandError> {
+    let mut system = System::new();
+    let refresh = ProcessRefreshKind::nothing()
+        .with_cmd(UpdateKind::Always)
+        .with_exe(UpdateKind::Always)
+        .with_environ(UpdateKind::Always);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+        true,
+        refresh,
+    );
+    if system.process(Pid::from_u32(pid)).is_none() {
+        return Err(not_found(pid));
+    }
+    Ok(system)
+}
+
+fn read_details(pid: u32) -> Result<ProcessDetails, CommandError> {
+    let system = refresh_process(pid)?;
+    let process = system
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| not_found(pid))?;
+    let strings = |values: &[std::ffi::OsString]| {
+        values
+            .iter()
+            .map(|value| value.to_string_lossy().into_owned())
+            .collect()
+    };
+    Ok(ProcessDetails {
+        pid,
+        parent_pid: process.parent().map(|parent| parent.as_u32()),
+        name: process.name().to_string_lossy().into_owned(),
+        command_line: strings(process.cmd()),
+        exe_path: process.exe().map(|path| path.display().to_string()),
+        start_time: process.start_time(),
+        open_handles: process.open_files().map(|open| open as u64),
+        environment: strings(process.environ()),
+    })
+}
+
+// Without `force` the process is asked to exit (SIGTERM); Windows has no such request, so
+// there it is always terminated. `confirmed` is the process the user agreed to end: if the PID
+// now has another start time or executable, it was reused while the dialog was open.
+fn end_process(pid: u32, confirmed: &ProcessDetails, force: bool) -> Result<(), CommandError> {
+    let system = refresh_process(pid)?;
+    let process = system
+        .process(Pid::from_u32(pid))
+        .filter(|process| {
+            process.start_time() == confirmed.start_time
+                && process.exe().map(|path| path.display().to_string()) == confirmed.exe_path
+        })
+        .ok_or_else(|| not_found(pid))?;
+    let sent = if force {
+        process.kill()
+    } else {
+        process
+            .kill_with(Signal::Term)
+            .unwrap_or_else(|| process.kill())
+    };
+    if !sent {
+        return Err(
+            CommandError::new(ErrorCode::PermissionDenied, "process.killFailed").with("pid", pid),
+        );
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_process_details(pid: u32) -> Result<ProcessDetails, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || read_details(pid))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Asks the user to confirm before ending the process; the app won't end itself
+#[tauri::command]
+pub async fn kill_process(
+    app: AppHandle,
+    pid: u32,
+    force: Option<bool>,
+) -> Result<(), CommandError> {
+    let force = force.unwrap_or(false);
+    if pid == std::process::id() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "process.self"));
+    }
+    kiosk::check_interactive(&app)?;
+    let details = read_details(pid)?;
+    let (verb, message) = if force {
+        ("Force quit", "Unsaved work in it will be lost.")
+    } else {
+        ("End", "Unsaved work in it may be lost.")
+    };
+    let confirm = app
+        .dialog()
+        .message(format!(
+            "{} \"{}\" (PID {})? {}",
+            verb, details.name, pid, message
+        ))
+        .title(format!("{} process", verb))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            verb.to_string(),
+            "Cancel".to_string(),
+        ));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if !confirm.blocking_show() {
+            return Err(CommandError::new(ErrorCode::Cancelled, "process.cancelled"));
+        }
+        end_process(pid, &details, force)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,315 @@
+// `/metrics` in the Prometheus text format, for home-lab setups that already scrape everything
+// else. Off unless `start_exporter` is called or `enabled = true` is set under `[prometheus]`,
+// which starts it at launch. It listens on `address` (localhost by default, so only this machine
+// can scrape it) and serves the values of the latest monitoring tick: CPU and memory from the
+// tick itself, disk space and network byte counters read alongside it while the endpoint runs.
+// Starting the endpoint also starts monitoring if it isn't running.
+//
+//     [prometheus]
+//     enabled = true
+//     port = 47821
+//     address = "0.0.0.0"   # to let another machine scrape it
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use sysinfo::{Disks, Networks};
+use tauri::{AppHandle, Manager, State};
+use tiny_http::{Header, Response, Server};
+
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::monitor::{self, MonitorTick, Monitoring};
+
+const METRICS_ROUTE: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PrometheusConfig {
+    // Start the endpoint at launch
+    pub enabled: bool,
+    pub port: u16,
+    // Interface to listen on
+    pub address: String,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        PrometheusConfig {
+            enabled: false,
+            port: 47821,
+            address: "127.0.0.1".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+// One metric family and its samples as (labels, value)
+struct Metric {
+    name: &'static str,
+    help: &'static str,
+    kind: MetricKind,
+    samples: Vec<(Option<(&'static str, String)>, f64)>,
+}
+
+impl Metric {
+    fn new(name: &'static str, help: &'static str, kind: MetricKind) -> Self {
+        Metric {
+            name,
+            help,
+            kind,
+            samples: Vec::new(),
+        }
+    }
+
+    fn single(name: &'static str, help: &'static str, value: f64) -> Self {
+        let mut metric = Metric::new(name, help, MetricKind::Gauge);
+        metric.samples.push((None, value));
+        metric
+    }
+}
+
+// Running endpoint, if any, and the metrics it serves
+#[derive(Default)]
+pub struct PrometheusExporter {
+    server: Mutex<Option<(u16, Arc<Server>)>>,
+    // Exposition text of the latest tick
+    latest: Mutex<String>,
+}
+
+impl PrometheusExporter {
+    fn is_running(&self) -> bool {
+        self.server.lock().is_ok_and(|server| server.is_some())
+    }
+}
+
+// Disk and network readers kept by the monitoring loop between ticks; created on the first tick
+// the endpoint is running
+#[derive(Default)]
+pub struct Readers {
+    readers: Option<(Disks, Networks)>,
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn render(metrics: &[Metric]) -> String {
+    let mut text = String::new();
+    for metric in metrics.iter().filter(|metric| !metric.samples.is_empty()) {
+        let kind = match metric.kind {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+        };
+        let _ = writeln!(text, "# HELP {} {}", metric.name, metric.help);
+        let _ = writeln!(text, "# TYPE {} {}", metric.name, kind);
+        for (label, value) in &metric.samples {
+            match label {
+                Some((name, label)) => {
+                    let _ = writeln!(
+                        text,
+                        "{}{{{}=\"{}\"}} {}",
+                        metric.name,
+                        name,
+                        escape_label(label),
+                        value
+                    );
+                }
+                None => {
+                    let _ = writeln!(text, "{} {}", metric.name, value);
+                }
+            }
+        }
+    }
+    text
+}
+
+fn collect(tick: &MonitorTick, disks: &Disks, networks: &Networks) -> Vec<Metric> {
+    let mut disk_total = Metric::new(
+        "system_info_disk_total_bytes",
+        "Size of the file system",
+        MetricKind::Gauge,
+    );
+    let mut disk_available = Metric::new(
+        "system_info_disk_available_bytes",
+        "Space available on the file system",
+        MetricKind::Gauge,
+    );
+    for disk in disks.list() {
+        let mount = disk.mount_point().display().to_string();
+        disk_total.samples.push((
+            Some(("mountpoint", mount.clone())),
+            disk.total_space() as f64,
+        ));
+        disk_available
+            .samples
+            .push((Some(("mountpoint", mount)), disk.available_space() as f64));
+    }
+
+    let mut received = Metric::new(
+        "system_info_network_received_bytes_total",
+        "Bytes received on the interface",
+        MetricKind::Counter,
+    );
+    let mut transmitted = Metric::new(
+        "system_info_network_transmitted_bytes_total",
+        "Bytes sent on the interface",
+        MetricKind::Counter,
+    );
+    for (name, data) in networks.list() {
+        received.samples.push((
+            Some(("interface", name.clone())),
+            data.total_received() as f64,
+        ));
+        transmitted.samples.push((
+            Some(("interface", name.clone())),
+            data.total_transmitted() as f64,
+        ));
+    }
+
+    vec![
+        Metric::single(
+            "system_info_cpu_usage_percent",
+            "CPU usage across all cores",
+            tick.cpu_usage as f64,
+        ),
+        Metric::single(
+            "system_info_memory_total_bytes",
+            "Physical memory",
+            tick.total_memory as f64,
+        ),
+        Metric::single(
+            "system_info_memory_used_bytes",
+            "Physical memory in use",
+            tick.used_memory as f64,
+        ),
+        Metric::single(
+            "system_info_memory_available_bytes",
+            "Physical memory available to new processes",
+            tick.available_memory as f64,
+        ),
+        Metric::single(
+            "system_info_processes",
+            "Running processes",
+            tick.process_count as f64,
+        ),
+        disk_total,
+        disk_available,
+        received,
+        transmitted,
+        Metric::single(
+            "system_info_last_tick_timestamp_seconds",
+            "When these values were sampled",
+            tick.timestamp as f64,
+        ),
+    ]
+}
+
+// Called by the monitoring loop on every tick; does nothing while the endpoint is stopped
+pub fn update(app: &AppHandle, tick: &MonitorTick, readers: &mut Readers) {
+    let exporter = app.state::<PrometheusExporter>();
+    if !exporter.is_running() {
+        readers.readers = None;
+        return;
+    }
+    let (disks, networks) = readers.readers.get_or_insert_with(|| {
+        (
+            Disks::new_with_refreshed_list(),
+            Networks::new_with_refreshed_list(),
+        )
+    });
+    disks.refresh(true);
+    networks.refresh(true);
+
+    let text = render(&collect(tick, disks, networks));
+    let Ok(mut latest) = exporter.latest.lock() else {
+        return;
+    };
+    *latest = text;
+}
+
+fn serve(app: AppHandle, server: Arc<Server>) {
+    let content_type = Header::from_bytes("Content-Type", CONTENT_TYPE).unwrap();
+    for request in server.incoming_requests() {
+        let route = request.url().split('?').next().unwrap_or_default();
+        let response = if route == METRICS_ROUTE {
+            let latest = app
+                .state::<PrometheusExporter>()
+                .latest
+                .lock()
+                .map(|latest| latest.clone())
+                .unwrap_or_default();
+            Response::from_string(latest).with_header(content_type.clone())
+        } else {
+            Response::from_string("Not found").with_status_code(404)
+        };
+        if let Err(e) = request.respond(response) {
+            eprintln!("Prometheus endpoint failed to respond: {}", e);
+        }
+    }
+}
+
+// Serve `/metrics` on `port` (`prometheus.port` of the config by default); returns the port in
+// use, which is the running one if the endpoint was already started
+#[tauri::command]
+pub fn start_exporter(
+    port: Option<u16>,
+    app: AppHandle,
+    exporter: State<PrometheusExporter>,
+    config: State<ConfigState>,
+) -> Result<u16, CommandError> {
+    let mut running = exporter
+        .server
+        .lock()
+        .map_err(|_| "Prometheus lock poisoned")?;
+    if let Some((port, _)) = running.as_ref() {
+        return Ok(*port);
+    }
+
+    let settings = config.get()?.prometheus.clone();
+    let port = port.unwrap_or(settings.port);
+    let server = Arc::new(
+        Server::http((settings.address.as_str(), port))
+            .map_err(|e| format!("Failed to listen on {}:{}: {}", settings.address, port, e))?,
+    );
+    let worker = server.clone();
+    let handle = app.clone();
+    std::thread::spawn(move || serve(handle, worker));
+    *running = Some((port, server));
+    drop(running);
+
+    // The endpoint only has values while monitoring ticks
+    if !app.state::<Monitoring>().is_running() {
+        monitor::start_monitoring(None, app.clone(), app.state(), config)?;
+    }
+    println!(
+        "✓ Prometheus metrics at http://{}:{}{}",
+        settings.address, port, METRICS_ROUTE
+    );
+    Ok(port)
+}
+
+// Stop serving `/metrics`; monitoring keeps running
+#[tauri::command]
+pub fn stop_exporter(exporter: State<PrometheusExporter>) -> Result<(), CommandError> {
+    let mut running = exporter
+        .server
+        .lock()
+        .map_err(|_| "Prometheus lock poisoned")?;
+    if let Some((_, server)) = running.take() {
+        server.unblock();
+    }
+    if let Ok(mut latest) = exporter.latest.lock() {
+        latest.clear();
+    }
+    Ok(())
+}
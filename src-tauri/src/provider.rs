@@ -0,0 +1,129 @@
+// The backend answering the basic system info commands.
+// The native systemapi library is used when one is loaded; otherwise, or in `no-ffi` builds,
+// the pure-Rust implementation answers instead, so the app degrades to "served by Rust" rather
+// than "unavailable". `CppLibrary::provider` picks the backend for each call.
+
+use serde::{Deserialize, Serialize};
+
+use crate::cpu::CpuInfo;
+use crate::power::PowerStatus;
+use crate::report::unix_timestamp;
+use crate::uptime::UptimeInfo;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Backend {
+    // The C++ systemapi library
+    Native,
+    // sysinfo and std
+    Rust,
+}
+
+// Where one value in a report came from, finer-grained than the backend
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricSource {
+    // An export of the C++ systemapi library
+    CppLib,
+    // Read from /proc by the Rust providers
+    Procfs,
+    // Rust providers going through std or sysinfo's platform APIs
+    Fallback,
+}
+
+// Source of a report field and when it was read, so values from different backends can be told
+// apart and a cached value shows its age
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attribution {
+    pub source: MetricSource,
+    // Unix seconds
+    pub read_at: u64,
+}
+
+pub trait SystemInfoProvider: Send + Sync {
+    fn backend(&self) -> Backend;
+    // Where `field` of a snapshot or report (`computerName`, `totalMemory`, ...) is read from
+    fn source(&self, field: &str) -> MetricSource;
+    fn attribution(&self, field: &str) -> Attribution {
+        Attribution {
+            source: self.source(field),
+            read_at: unix_timestamp(),
+        }
+    }
+    fn computer_name(&self) -> Result<String, String>;
+    fn total_physical_memory(&self) -> Result<u64, String>;
+    fn current_process_id(&self) -> Result<u32, String>;
+    fn calculate_factorial(&self, n: i32) -> Result<u64, String>;
+    fn cpu_info(&self) -> Result<CpuInfo, String>;
+    // Percent per logical core since the previous call
+    fn core_usage(&self) -> Result<Vec<f64>, String>;
+    fn power_status(&self) -> Result<PowerStatus, String>;
+    fn uptime_info(&self) -> Result<UptimeInfo, String>;
+}
+
+#[cfg(feature = "ffi")]
+impl SystemInfoProvider for crate::loader::LoadedLibrary {
+    fn backend(&self) -> Backend {
+        Backend::Native
+    }
+
+    // The basic report fields come from exports every library has; the others from the library
+    // when it has their exports and from the Rust implementation otherwise
+    fn source(&self, field: &str) -> MetricSource {
+        let exported = match field {
+            "computerName" | "totalMemory" | "processId" => true,
+            "cpuInfo" | "cpuUsage" => self.api.has_cpu_exports(),
+            "uptime" => self.api.has_uptime_info(),
+            _ => false,
+        };
+        if exported {
+            MetricSource::CppLib
+        } else {
+            crate::rust_provider::RustProvider.source(field)
+        }
+    }
+
+    fn computer_name(&self) -> Result<String, String> {
+        self.api.computer_name()
+    }
+
+    fn total_physical_memory(&self) -> Result<u64, String> {
+        Ok(self.api.total_physical_memory())
+    }
+
+    fn current_process_id(&self) -> Result<u32, String> {
+        Ok(self.api.current_process_id())
+    }
+
+    fn calculate_factorial(&self, n: i32) -> Result<u64, String> {
+        self.api.calculate_factorial(n)
+    }
+
+    // Libraries older than 1.1 lack the CPU exports; the Rust implementation fills in for them
+    fn cpu_info(&self) -> Result<CpuInfo, String> {
+        self.api
+            .cpu_info()
+            .unwrap_or_else(|| crate::rust_provider::RustProvider.cpu_info())
+    }
+
+    fn core_usage(&self) -> Result<Vec<f64>, String> {
+        self.api
+            .core_usage()
+            .unwrap_or_else(|| crate::rust_provider::RustProvider.core_usage())
+    }
+
+    // Libraries older than 1.2 lack `GetPowerStatus`
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        self.api
+            .power_status()
+            .unwrap_or_else(|| crate::rust_provider::RustProvider.power_status())
+    }
+
+    // Libraries older than 1.3 lack `GetUptimeInfo`
+    fn uptime_info(&self) -> Result<UptimeInfo, String> {
+        self.api
+            .uptime_info()
+            .unwrap_or_else(|| crate::rust_provider::RustProvider.uptime_info())
+    }
+}
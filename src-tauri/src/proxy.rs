@@ -0,0 +1,307 @@
+// Proxy configuration and captive-portal detection.
+// Proxies come from the environment plus the desktop settings (GNOME `gsettings`, macOS
+// `scutil --proxy`, the WinINet registry key on Windows). The captive-portal check fetches a
+// URL that normally answers `204 No Content`; a redirect or any other answer means something
+// between us and the internet (usually a hotel or airport login page) intercepted it.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+const PORTAL_CHECK_URL: &str = "http://connectivitycheck.gstatic.com/generate_204";
+const PORTAL_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+const ENV_PROXIES: [(&str, &str); 4] = [
+    ("http", "http_proxy"),
+    ("https", "https_proxy"),
+    ("ftp", "ftp_proxy"),
+    ("all", "all_proxy"),
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySetting {
+    // Where the setting was found, e.g. `environment` or `gsettings`
+    pub source: String,
+    pub scheme: String,
+    pub server: String,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PortalState {
+    // The check URL answered as expected
+    Open,
+    // The request was redirected or answered by something else
+    CaptivePortal,
+    // The request failed entirely
+    Unreachable,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptivePortalCheck {
+    pub url: String,
+    pub state: PortalState,
+    pub status_code: Option<u16>,
+    // Where a portal redirected the request
+    pub redirect_to: Option<String>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyStatus {
+    pub proxies: Vec<ProxySetting>,
+    pub pac_urls: Vec<String>,
+    pub auto_detect: bool,
+    // Hosts that bypass the proxy
+    pub bypass: Vec<String>,
+    // Skipped when `check_portal` is false
+    pub captive_portal: Option<CaptivePortalCheck>,
+    // Settings sources that couldn't be read
+    pub errors: Vec<String>,
+}
+
+impl ProxyStatus {
+    fn add_proxy(&mut self, source: &str, scheme: &str, server: String) {
+        self.proxies.push(ProxySetting {
+            source: source.to_string(),
+            scheme: scheme.to_string(),
+            server,
+        });
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .or_else(|_| std::env::var(name.to_uppercase()))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+fn read_environment(status: &mut ProxyStatus) {
+    for (scheme, name) in ENV_PROXIES {
+        if let Some(server) = env_var(name) {
+            status.add_proxy("environment", scheme, server);
+        }
+    }
+    if let Some(no_proxy) = env_var("no_proxy") {
+        status
+            .bypass
+            .extend(no_proxy.split(',').map(|host| host.trim().to_string()));
+    }
+}
+
+fn which_gsettings() -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join("gsettings").is_file()))
+}
+
+// `gsettings get` prints GVariant text such as `'manual'`, `8080` or `['localhost', '::1']`
+fn gsettings(schema: &str, key: &str) -> Result<String, String> {
+    run_text("gsettings", &["get", schema, key]).map(|value| value.trim().to_string())
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('\'').to_string()
+}
+
+fn read_gsettings(status: &mut ProxyStatus) -> Result<(), String> {
+    let mode = unquote(&gsettings("org.gnome.system.proxy", "mode")?);
+    match mode.as_str() {
+        "auto" => {
+            let url = unquote(&gsettings("org.gnome.system.proxy", "autoconfig-url")?);
+            if url.is_empty() {
+                status.auto_detect = true;
+            } else {
+                status.pac_urls.push(url);
+            }
+        }
+        "manual" => {
+            for scheme in ["http", "https", "ftp", "socks"] {
+                let schema = format!("org.gnome.system.proxy.{}", scheme);
+                let host = unquote(&gsettings(&schema, "host")?);
+                let port = gsettings(&schema, "port")?;
+                if !host.is_empty() && port != "0" {
+                    status.add_proxy("gsettings", scheme, format!("{}:{}", host, port));
+                }
+            }
+        }
+        _ => return Ok(()),
+    }
+    let ignore = gsettings("org.gnome.system.proxy", "ignore-hosts")?;
+    status.bypass.extend(
+        ignore
+            .trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .map(unquote)
+            .filter(|host| !host.is_empty()),
+    );
+    Ok(())
+}
+
+// `scutil --proxy` prints `Key : value` lines, with `ExceptionsList` as a nested `N : host` array
+fn read_scutil(status: &mut ProxyStatus) -> Result<(), String> {
+    let output = run_text("scutil", &["--proxy"])?;
+    let mut values = std::collections::BTreeMap::new();
+    let mut in_exceptions = false;
+    for line in output.lines().map(str::trim) {
+        if line == "}" {
+            in_exceptions = false;
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        if in_exceptions {
+            status.bypass.push(value.to_string());
+        } else if key == "ExceptionsList" {
+            in_exceptions = true;
+        } else {
+            values.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    let enabled = |key: &str| values.get(key).is_some_and(|value| value == "1");
+    for (prefix, scheme) in [
+        ("HTTP", "http"),
+        ("HTTPS", "https"),
+        ("FTP", "ftp"),
+        ("SOCKS", "socks"),
+    ] {
+        if !enabled(&format!("{}Enable", prefix)) {
+            continue;
+        }
+        if let Some(host) = values.get(&format!("{}Proxy", prefix)) {
+            let server = match values.get(&format!("{}Port", prefix)) {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.clone(),
+            };
+            status.add_proxy("scutil", scheme, server);
+        }
+    }
+    if enabled("ProxyAutoConfigEnable") {
+        if let Some(url) = values.get("ProxyAutoConfigURLString") {
+            status.pac_urls.push(url.clone());
+        }
+    }
+    status.auto_detect = enabled("ProxyAutoDiscoveryEnable");
+    Ok(())
+}
+
+// `reg query` prints `    Name    REG_TYPE    value` lines
+fn read_registry(status: &mut ProxyStatus) -> Result<(), String> {
+    let output = run_text(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+        ],
+    )?;
+    let value = |name: &str| {
+        output.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next() == Some(name))
+                .then(|| fields.skip(1).collect::<Vec<_>>().join(" "))
+                .filter(|value| !value.is_empty())
+        })
+    };
+
+    if value("ProxyEnable").as_deref() == Some("0x1") {
+        if let Some(servers) = value("ProxyServer") {
+            // Either one server for everything or `scheme=host:port` pairs separated by `;`
+            for server in servers.split(';').filter(|server| !server.is_empty()) {
+                match server.split_once('=') {
+                    Some((scheme, server)) => status.add_proxy("registry", scheme, server.into()),
+                    None => status.add_proxy("registry", "all", server.to_string()),
+                }
+            }
+        }
+        if let Some(bypass) = value("ProxyOverride") {
+            status.bypass.extend(bypass.split(';').map(str::to_string));
+        }
+    }
+    if let Some(url) = value("AutoConfigURL") {
+        status.pac_urls.push(url);
+    }
+    Ok(())
+}
+
+fn check_captive_portal() -> CaptivePortalCheck {
+    // Don't follow redirects: the redirect itself is what gives a portal away
+    let client: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(PORTAL_CHECK_TIMEOUT))
+        .max_redirects(0)
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let started = Instant::now();
+    let result = client.get(PORTAL_CHECK_URL).call();
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let mut check = CaptivePortalCheck {
+        url: PORTAL_CHECK_URL.to_string(),
+        state: PortalState::Unreachable,
+        status_code: None,
+        redirect_to: None,
+        latency_ms,
+        error: None,
+    };
+    match result {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            check.status_code = Some(status);
+            check.redirect_to = response
+                .headers()
+                .get("location")
+                .and_then(|location| location.to_str().ok())
+                .map(str::to_string);
+            check.state = if status == 204 {
+                PortalState::Open
+            } else {
+                PortalState::CaptivePortal
+            };
+        }
+        Err(e) => check.error = Some(e.to_string()),
+    }
+    check
+}
+
+fn collect(check_portal: bool) -> ProxyStatus {
+    let mut status = ProxyStatus::default();
+    read_environment(&mut status);
+
+    let desktop = if cfg!(target_os = "linux") {
+        // Only GNOME-based desktops have these keys; without gsettings there is nothing to read
+        which_gsettings().then(|| ("gsettings", read_gsettings(&mut status)))
+    } else if cfg!(target_os = "macos") {
+        Some(("scutil", read_scutil(&mut status)))
+    } else if cfg!(target_os = "windows") {
+        Some(("registry", read_registry(&mut status)))
+    } else {
+        None
+    };
+    if let Some((source, Err(e))) = desktop {
+        status.errors.push(format!("{}: {}", source, e));
+    }
+
+    if check_portal {
+        status.captive_portal = Some(check_captive_portal());
+    }
+    status
+}
+
+// Configured proxies and PAC URLs; the captive-portal check runs unless `check_portal` is false
+#[tauri::command]
+pub async fn get_proxy_status(check_portal: Option<bool>) -> Result<ProxyStatus, CommandError> {
+    let status =
+        tauri::async_runtime::spawn_blocking(move || collect(check_portal.unwrap_or(true)))
+            .await
+            .map_err(|e| e.to_string())?;
+    Ok(status)
+}
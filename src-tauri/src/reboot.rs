@@ -0,0 +1,183 @@
+// Pending reboot detection from the breadcrumbs each platform leaves behind.
+// Windows: servicing and Windows Update registry flags, pending file renames and a pending
+// computer rename. Linux: Debian's `/var/run/reboot-required`, `needrestart` and RHEL's
+// `needs-restarting`. macOS doesn't record a pending restart anywhere readable.
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+const REBOOT_REQUIRED_FILE: &str = "/var/run/reboot-required";
+const REBOOT_REQUIRED_PACKAGES: &str = "/var/run/reboot-required.pkgs";
+
+// Registry keys whose mere existence means a reboot is pending
+const WINDOWS_REBOOT_KEYS: [(&str, &str); 2] = [
+    (
+        r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Component Based Servicing\RebootPending",
+        "Component servicing has changes waiting for a restart",
+    ),
+    (
+        r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\WindowsUpdate\Auto Update\RebootRequired",
+        "Windows Update installed updates that need a restart",
+    ),
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebootReason {
+    // Breadcrumb that was found, e.g. a file or registry path
+    pub source: String,
+    pub description: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingReboot {
+    // False where no breadcrumbs are checked
+    pub supported: bool,
+    pub required: bool,
+    pub reasons: Vec<RebootReason>,
+}
+
+fn reason(source: &str, description: impl Into<String>) -> RebootReason {
+    RebootReason {
+        source: source.to_string(),
+        description: description.into(),
+    }
+}
+
+// A value from `reg query <key> /v <name>`, which prints `    Name    REG_TYPE    data`
+fn registry_value(key: &str, name: &str) -> Option<String> {
+    let output = run_text("reg", &["query", key, "/v", name]).ok()?;
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        (fields.next() == Some(name)).then(|| fields.skip(1).collect::<Vec<_>>().join(" "))
+    })
+}
+
+fn windows_reasons() -> Vec<RebootReason> {
+    let mut reasons: Vec<RebootReason> = WINDOWS_REBOOT_KEYS
+        .iter()
+        .filter(|(key, _)| run_text("reg", &["query", key]).is_ok())
+        .map(|(key, description)| reason(key, *description))
+        .collect();
+
+    let session_manager = r"HKLM\SYSTEM\CurrentControlSet\Control\Session Manager";
+    if registry_value(session_manager, "PendingFileRenameOperations")
+        .is_some_and(|value| !value.is_empty())
+    {
+        reasons.push(reason(
+            &format!(r"{}\PendingFileRenameOperations", session_manager),
+            "Files are waiting to be replaced at the next boot",
+        ));
+    }
+
+    let updates = r"HKLM\SOFTWARE\Microsoft\Updates";
+    if registry_value(updates, "UpdateExeVolatile").is_some_and(|value| value != "0x0") {
+        reasons.push(reason(
+            &format!(r"{}\UpdateExeVolatile", updates),
+            "An installer is waiting for a restart to finish",
+        ));
+    }
+
+    let computer_name = r"HKLM\SYSTEM\CurrentControlSet\Control\ComputerName";
+    let active = registry_value(
+        &format!(r"{}\ActiveComputerName", computer_name),
+        "ComputerName",
+    );
+    let pending = registry_value(&format!(r"{}\ComputerName", computer_name), "ComputerName");
+    if let (Some(active), Some(pending)) = (active, pending) {
+        if !active.eq_ignore_ascii_case(&pending) {
+            reasons.push(reason(
+                computer_name,
+                format!("Computer is being renamed from {} to {}", active, pending),
+            ));
+        }
+    }
+    reasons
+}
+
+// `needrestart -b` prints `NEEDRESTART-KSTA: n`: 1 running kernel is current, 2 an ABI-compatible
+// upgrade is pending, 3 a newer kernel version is installed
+fn needrestart_reason() -> Option<RebootReason> {
+    let output = run_text("needrestart", &["-b", "-k"]).ok()?;
+    let value = |key: &str| {
+        output.lines().find_map(|line| {
+            line.strip_prefix(key)
+                .map(|value| value.trim_start_matches(':').trim().to_string())
+        })
+    };
+    match value("NEEDRESTART-KSTA")?.as_str() {
+        "2" | "3" => Some(reason(
+            "needrestart",
+            format!(
+                "Kernel {} is running but {} is installed",
+                value("NEEDRESTART-KCUR").unwrap_or_default(),
+                value("NEEDRESTART-KEXP").unwrap_or_default()
+            ),
+        )),
+        _ => None,
+    }
+}
+
+// `needs-restarting -r` exits with 1 when a reboot is needed and lists the updated core packages
+fn needs_restarting_reason() -> Option<RebootReason> {
+    let output = std::process::Command::new("needs-restarting")
+        .arg("-r")
+        .output()
+        .ok()?;
+    (output.status.code() == Some(1)).then(|| {
+        reason(
+            "needs-restarting",
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        )
+    })
+}
+
+fn linux_reasons() -> Vec<RebootReason> {
+    let mut reasons = Vec::new();
+    if Path::new(REBOOT_REQUIRED_FILE).exists() {
+        let packages = std::fs::read_to_string(REBOOT_REQUIRED_PACKAGES)
+            .map(|packages| {
+                let mut packages: Vec<&str> = packages.lines().collect();
+                packages.sort_unstable();
+                packages.dedup();
+                packages.join(", ")
+            })
+            .unwrap_or_default();
+        let description = if packages.is_empty() {
+            "Installed updates need a restart".to_string()
+        } else {
+            format!("Updated packages need a restart: {}", packages)
+        };
+        reasons.push(reason(REBOOT_REQUIRED_FILE, description));
+    }
+    reasons.extend(needrestart_reason());
+    reasons.extend(needs_restarting_reason());
+    reasons
+}
+
+fn check() -> PendingReboot {
+    let (supported, reasons) = if cfg!(target_os = "windows") {
+        (true, windows_reasons())
+    } else if cfg!(target_os = "linux") {
+        (true, linux_reasons())
+    } else {
+        (false, Vec::new())
+    };
+    PendingReboot {
+        supported,
+        required: !reasons.is_empty(),
+        reasons,
+    }
+}
+
+#[tauri::command]
+pub async fn get_pending_reboot() -> Result<PendingReboot, CommandError> {
+    let pending = tauri::async_runtime::spawn_blocking(check)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(pending)
+}
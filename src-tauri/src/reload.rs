@@ -0,0 +1,178 @@
+// Unloading and reloading the C++ library without restarting the app, for rebuilding
+// `libsystemapi` during development. The library is taken out of `CppLibrary` first, so new
+// calls go to the Rust providers, and only closed once every in-flight call through it has
+// returned; reopening before that would hand back the old, still-mapped image. Each change is
+// emitted as `library-status-changed`. With `--watch-lib` the loaded file is polled and
+// reloaded whenever it is rebuilt. Windows won't let a loaded DLL be overwritten, so there
+// unload, rebuild, then reload.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::cli::CliArgs;
+use crate::config::ConfigState;
+use crate::error::{CommandError, ErrorCode};
+use crate::loader::{self, LoadedLibrary};
+use crate::provider::Backend;
+use crate::{CppLibrary, LibraryInfo};
+
+pub const LIBRARY_STATUS_CHANGED_EVENT: &str = "library-status-changed";
+
+// How long an unload waits for calls still running in the old library
+const RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+const RELEASE_POLL: Duration = Duration::from_millis(10);
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+// Held for a whole unload or reload so two of them can't interleave
+static RELOADING: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryStatus {
+    pub backend: Backend,
+    // None while the Rust providers answer
+    pub library: Option<LibraryInfo>,
+    // Why the last reload found no library
+    pub error: Option<String>,
+}
+
+fn emit_status(app: &AppHandle, error: Option<String>) -> LibraryStatus {
+    let lib_state = app.state::<CppLibrary>();
+    let status = LibraryStatus {
+        backend: lib_state.provider().backend(),
+        library: lib_state.info(),
+        error,
+    };
+    if let Err(e) = app.emit(LIBRARY_STATUS_CHANGED_EVENT, &status) {
+        eprintln!(
+            "⚠ Warning: Failed to emit {}: {}",
+            LIBRARY_STATUS_CHANGED_EVENT, e
+        );
+    }
+    status
+}
+
+// Wait for the last other reference to go, then drop the library, which closes it
+fn release(mut loaded: Arc<LoadedLibrary>) -> Result<(), CommandError> {
+    let started = Instant::now();
+    loop {
+        match Arc::try_unwrap(loaded) {
+            Ok(library) => {
+                println!("✓ Unloaded C++ library from: {}", library.info.path);
+                return Ok(());
+            }
+            Err(shared) if started.elapsed() < RELEASE_TIMEOUT => {
+                loaded = shared;
+                std::thread::sleep(RELEASE_POLL);
+            }
+            // Closed by whichever call finishes last
+            Err(shared) => {
+                return Err(CommandError::new(ErrorCode::Timeout, "library.stillInUse")
+                    .with("path", &shared.info.path));
+            }
+        }
+    }
+}
+
+fn unload(app: &AppHandle) -> Result<(), CommandError> {
+    match app.state::<CppLibrary>().take()? {
+        Some(loaded) => release(loaded),
+        None => Ok(()),
+    }
+}
+
+// Same precedence as at startup: --lib-path, then the library picked earlier
+fn preferred_path(app: &AppHandle) -> Option<PathBuf> {
+    app.state::<CliArgs>().lib_path.clone().or_else(|| {
+        app.state::<ConfigState>()
+            .get()
+            .ok()
+            .and_then(|config| config.library.path.clone())
+    })
+}
+
+fn reload(app: &AppHandle) -> Result<LibraryInfo, CommandError> {
+    unload(app)?;
+    let lib_state = app.state::<CppLibrary>();
+    let mut trace = Vec::new();
+    let result = loader::load_cpp_library(app, preferred_path(app).as_deref(), &mut trace);
+    lib_state.set_trace(trace, result.as_ref().err().map(ToString::to_string));
+
+    let loaded = result.map_err(|e| e.to_string())?;
+    let info = loaded.info.clone();
+    lib_state.replace(loaded)?;
+    Ok(info)
+}
+
+fn unload_and_notify(app: &AppHandle) -> Result<LibraryStatus, CommandError> {
+    let _guard = RELOADING.lock().map_err(|_| "Reload lock poisoned")?;
+    let result = unload(app);
+    let status = emit_status(app, result.as_ref().err().map(ToString::to_string));
+    result.map(|_| status)
+}
+
+fn reload_and_notify(app: &AppHandle) -> Result<LibraryInfo, CommandError> {
+    let _guard = RELOADING.lock().map_err(|_| "Reload lock poisoned")?;
+    let result = reload(app);
+    if let Err(e) = &result {
+        eprintln!("⚠ Warning: Reloading the C++ library failed: {}", e);
+        eprintln!("Falling back to the Rust providers for system info.");
+    }
+    emit_status(app, result.as_ref().err().map(ToString::to_string));
+    result
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+// `--watch-lib`: reload when the loaded file's modification time changes. A file caught
+// mid-write fails to load and is retried once the linker touches it again.
+pub fn start_watching(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut watched: Option<(String, Option<SystemTime>)> = None;
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            // Follow whatever is loaded; with nothing loaded keep watching the last file, so a
+            // rebuild after a failed reload is still picked up
+            if let Some(info) = app.state::<CppLibrary>().info() {
+                if watched.as_ref().is_none_or(|(path, _)| *path != info.path) {
+                    watched = Some((info.path.clone(), modified(&info.path)));
+                    continue;
+                }
+            }
+            let Some((path, seen)) = &mut watched else {
+                continue;
+            };
+            let current = modified(path);
+            if current.is_some() && current != *seen {
+                *seen = current;
+                println!("Library changed on disk, reloading: {}", path);
+                let _ = reload_and_notify(&app);
+            }
+        }
+    });
+}
+
+// Drop the library and answer from the Rust providers until it is reloaded
+#[tauri::command]
+pub async fn unload_library(app: AppHandle) -> Result<LibraryStatus, CommandError> {
+    let status = tauri::async_runtime::spawn_blocking(move || unload_and_notify(&app))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(status)
+}
+
+// Unload the current library and run the library search again
+#[tauri::command]
+pub async fn reload_library(app: AppHandle) -> Result<LibraryInfo, CommandError> {
+    let info = tauri::async_runtime::spawn_blocking(move || reload_and_notify(&app))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(info)
+}
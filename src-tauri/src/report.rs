@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::display::DisplaySettings;
+use crate::provider::{Attribution, Backend};
+use crate::snapshot::{read_identity, Collector};
+use crate::{CppLibrary, LibraryInfo};
+
+// Point-in-time system report, as shown in the dashboard and written into bundles
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemReport {
+    pub generated_at: u64,
+    pub app_version: String,
+    pub platform: String,
+    pub arch: String,
+    pub computer_name: Option<String>,
+    pub total_memory: Option<u64>,
+    pub process_id: Option<u32>,
+    // Fields rendered with the exporter's display settings and locale, keyed like the fields above
+    #[serde(default)]
+    pub formatted: BTreeMap<String, String>,
+    // Where each field above was read from and when; older bundles have none
+    #[serde(default)]
+    pub sources: BTreeMap<String, Attribution>,
+}
+
+// Information about the app itself, useful when someone else reads the report
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    pub library_loaded: bool,
+    // Older bundles predate this field
+    #[serde(default)]
+    pub library: Option<LibraryInfo>,
+    // Which backend produced the values; missing in older bundles
+    #[serde(default)]
+    pub backend: Option<Backend>,
+    pub errors: Vec<String>,
+}
+
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Gather every available field; failures are recorded in the diagnostics instead of aborting
+pub fn collect_report(
+    lib_state: &CppLibrary,
+    display: &DisplaySettings,
+) -> (SystemReport, Diagnostics) {
+    let mut collector = Collector::new(lib_state);
+    let (computer_name, total_memory, process_id) = read_identity(&mut collector, lib_state);
+    let library_loaded = lib_state.is_loaded();
+    let backend = collector.backend();
+
+    let generated_at = unix_timestamp();
+    let mut formatted = BTreeMap::new();
+    formatted.insert(
+        "generatedAt".to_string(),
+        display.date_time(generated_at as i64),
+    );
+    if let Some(total_memory) = total_memory {
+        formatted.insert("totalMemory".to_string(), display.bytes(total_memory));
+    }
+
+    let report = SystemReport {
+        generated_at,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        computer_name,
+        total_memory,
+        process_id,
+        formatted,
+        sources: collector
+            .sources
+            .into_iter()
+            .map(|(field, source)| (field.to_string(), source))
+            .collect(),
+    };
+
+    (
+        report,
+        Diagnostics {
+            library_loaded,
+            library: lib_state.info(),
+            backend: Some(backend),
+            errors: collector
+                .errors
+                .into_iter()
+                .map(|(field, error)| format!("{}: {}", field, error))
+                .collect(),
+        },
+    )
+}
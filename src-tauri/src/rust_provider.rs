@@ -0,0 +1,102 @@
+// Pure-Rust implementations of the systemapi exports, used when the C++ library isn't loaded
+// and in `no-ffi` builds. Results match the C++ library so the frontend can't tell which one
+// answered, except through the reported backend.
+
+use std::sync::Mutex;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+use crate::cpu::{read_cpufreq, CpuInfo};
+use crate::power::{self, PowerStatus};
+use crate::provider::{Backend, MetricSource, SystemInfoProvider};
+use crate::uptime::{self, UptimeInfo};
+use crate::MAX_FACTORIAL_INPUT;
+
+// Kept between `core_usage` calls so usage covers the time since the previous one
+static CPU_USAGE: Mutex<Option<System>> = Mutex::new(None);
+
+pub struct RustProvider;
+
+impl SystemInfoProvider for RustProvider {
+    fn backend(&self) -> Backend {
+        Backend::Rust
+    }
+
+    // sysinfo reads memory from /proc/meminfo on Linux
+    fn source(&self, field: &str) -> MetricSource {
+        match field {
+            "totalMemory" | "memory" if cfg!(target_os = "linux") => MetricSource::Procfs,
+            _ => MetricSource::Fallback,
+        }
+    }
+
+    fn computer_name(&self) -> Result<String, String> {
+        Ok(gethostname::gethostname().to_string_lossy().into_owned())
+    }
+
+    fn total_physical_memory(&self) -> Result<u64, String> {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_memory(MemoryRefreshKind::everything()),
+        );
+        match system.total_memory() {
+            0 => Err("Failed to read total memory".to_string()),
+            bytes => Ok(bytes),
+        }
+    }
+
+    fn current_process_id(&self) -> Result<u32, String> {
+        Ok(std::process::id())
+    }
+
+    fn calculate_factorial(&self, n: i32) -> Result<u64, String> {
+        if !(0..=MAX_FACTORIAL_INPUT).contains(&n) {
+            return Err(format!(
+                "Factorial input must be between 0 and {}",
+                MAX_FACTORIAL_INPUT
+            ));
+        }
+        Ok((1..=n as u64).product())
+    }
+
+    fn cpu_info(&self) -> Result<CpuInfo, String> {
+        let system =
+            System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing()));
+        let cpu = system
+            .cpus()
+            .first()
+            .ok_or("Failed to read CPU information")?;
+        Ok(CpuInfo {
+            model: cpu.brand().trim().to_string(),
+            vendor: cpu.vendor_id().to_string(),
+            physical_cores: System::physical_core_count().map(|cores| cores as u32),
+            logical_cores: system.cpus().len() as u32,
+            // Written by intel_pstate and amd-pstate, in kHz
+            base_frequency_mhz: read_cpufreq("base_frequency")
+                .and_then(|khz| khz.parse::<u64>().ok())
+                .map(|khz| khz / 1000),
+        })
+    }
+
+    fn core_usage(&self) -> Result<Vec<f64>, String> {
+        let mut system = CPU_USAGE.lock().map_err(|_| "CPU usage lock poisoned")?;
+        let system = system.get_or_insert_with(|| {
+            let mut system = System::new();
+            system.refresh_cpu_usage();
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            system
+        });
+        system.refresh_cpu_usage();
+        Ok(system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage() as f64)
+            .collect())
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        power::read_os_status()
+    }
+
+    fn uptime_info(&self) -> Result<UptimeInfo, String> {
+        uptime::read_os_uptime()
+    }
+}
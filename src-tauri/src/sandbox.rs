@@ -0,0 +1,303 @@
+// Calling the C++ library from a helper process, so a crash in native code takes down the
+// helper rather than the app. The helper is this executable started as
+// `--ffi-helper <library>`: it loads the library and answers one JSON call per line on stdin
+// with one JSON response per line on stdout. If it dies mid-call, the caller gets an error and
+// a new helper is started for the next call. Only the commands listed in
+// `library.sandboxedCommands` go through the helper; the rest call the library in-process.
+// Calls through one helper run one at a time.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::config::ConfigState;
+use crate::cpu::CpuInfo;
+use crate::error::{CommandError, ErrorCode};
+use crate::loader::{self, LoadedLibrary};
+use crate::power::PowerStatus;
+use crate::provider::{Backend, MetricSource, SystemInfoProvider};
+use crate::uptime::UptimeInfo;
+use crate::CppLibrary;
+
+// Commands that can be routed through the helper
+pub const SANDBOXABLE_COMMANDS: &[&str] = &[
+    "get_computer_name",
+    "get_total_memory",
+    "get_process_id",
+    "calculate_factorial",
+    "get_cpu_info",
+    "get_cpu_usage",
+    "get_power_status",
+    "get_uptime_info",
+];
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "camelCase")]
+enum Call {
+    ComputerName,
+    TotalPhysicalMemory,
+    CurrentProcessId,
+    CalculateFactorial { n: i32 },
+    CpuInfo,
+    CoreUsage,
+    PowerStatus,
+    UptimeInfo,
+}
+
+// `{"Ok": value}` or `{"Err": message}`
+type Response = Result<Value, String>;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxedCommand {
+    pub command: String,
+    pub sandboxed: bool,
+}
+
+fn write_line(out: &mut impl Write, value: &impl Serialize) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    out.write_all(line.as_bytes())?;
+    out.flush()
+}
+
+fn answer(loaded: &LoadedLibrary, call: Call) -> Response {
+    fn value<T: Serialize>(result: Result<T, String>) -> Response {
+        result.and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()))
+    }
+    match call {
+        Call::ComputerName => value(loaded.computer_name()),
+        Call::TotalPhysicalMemory => value(loaded.total_physical_memory()),
+        Call::CurrentProcessId => value(loaded.current_process_id()),
+        Call::CalculateFactorial { n } => value(loaded.calculate_factorial(n)),
+        Call::CpuInfo => value(loaded.cpu_info()),
+        Call::CoreUsage => value(loaded.core_usage()),
+        Call::PowerStatus => value(loaded.power_status()),
+        Call::UptimeInfo => value(loaded.uptime_info()),
+    }
+}
+
+// Main loop of the helper process
+pub fn serve(library: &Path) {
+    let loaded = loader::open_validated(library);
+    let mut stdout = std::io::stdout().lock();
+    // The first line tells the app whether the library loaded
+    let ready: Result<(), String> = loaded.as_ref().map(|_| ()).map_err(ToString::to_string);
+    if write_line(&mut stdout, &ready).is_err() {
+        return;
+    }
+    let Ok(loaded) = loaded else {
+        return;
+    };
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let response = serde_json::from_str(&line)
+            .map_err(|e| e.to_string())
+            .and_then(|call| answer(&loaded, call));
+        if write_line(&mut stdout, &response).is_err() {
+            break;
+        }
+    }
+}
+
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Process {
+    fn spawn(library: &Path) -> Result<Self, String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let mut child = Command::new(exe)
+            .arg("--ffi-helper")
+            .arg(library)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start the library helper: {}", e))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err("Library helper has no pipes".to_string());
+        };
+
+        let mut process = Process {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        };
+        process
+            .read()?
+            .map_err(|e| format!("Library helper failed to load the library: {}", e))?;
+        Ok(process)
+    }
+
+    // The next response, skipping anything else the library printed to stdout
+    fn read(&mut self) -> Result<Response, String> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Err(self.stop());
+            }
+            if let Ok(response) = serde_json::from_str(&line) {
+                return Ok(response);
+            }
+        }
+    }
+
+    fn request(&mut self, call: &Call) -> Result<Response, String> {
+        if write_line(&mut self.stdin, call).is_err() {
+            return Err(self.stop());
+        }
+        self.read()
+    }
+
+    // Make sure the helper is gone and say how it ended
+    fn stop(&mut self) -> String {
+        let _ = self.child.kill();
+        match self.child.wait() {
+            Ok(status) => format!("Library helper exited ({})", status),
+            Err(e) => format!("Library helper exited: {}", e),
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// The helper for one loaded library, started on first use
+pub struct Helper {
+    library: PathBuf,
+    process: Mutex<Option<Process>>,
+}
+
+impl Helper {
+    pub fn new(library: PathBuf) -> Self {
+        Helper {
+            library,
+            process: Mutex::new(None),
+        }
+    }
+
+    fn call<T: DeserializeOwned>(&self, call: Call) -> Result<T, String> {
+        let mut process = self
+            .process
+            .lock()
+            .map_err(|_| "Library helper lock poisoned")?;
+        let running = match process.as_mut() {
+            Some(running) => running,
+            None => process.insert(Process::spawn(&self.library)?),
+        };
+
+        match running.request(&call) {
+            Ok(response) => serde_json::from_value(response?).map_err(|e| e.to_string()),
+            Err(e) => {
+                // Replace the dead helper now so the next call doesn't pay for the restart
+                *process = Process::spawn(&self.library)
+                    .map_err(|e| eprintln!("⚠ Warning: {}", e))
+                    .ok();
+                eprintln!("⚠ Warning: {}; restarted it", e);
+                Err(format!("The native library failed during the call: {}", e))
+            }
+        }
+    }
+}
+
+// Provider answering through the helper of a loaded library
+pub struct Sandboxed(pub Arc<LoadedLibrary>);
+
+impl SystemInfoProvider for Sandboxed {
+    fn backend(&self) -> Backend {
+        Backend::Native
+    }
+
+    fn source(&self, field: &str) -> MetricSource {
+        self.0.source(field)
+    }
+
+    fn computer_name(&self) -> Result<String, String> {
+        self.0.helper.call(Call::ComputerName)
+    }
+
+    fn total_physical_memory(&self) -> Result<u64, String> {
+        self.0.helper.call(Call::TotalPhysicalMemory)
+    }
+
+    fn current_process_id(&self) -> Result<u32, String> {
+        self.0.helper.call(Call::CurrentProcessId)
+    }
+
+    fn calculate_factorial(&self, n: i32) -> Result<u64, String> {
+        self.0.helper.call(Call::CalculateFactorial { n })
+    }
+
+    fn cpu_info(&self) -> Result<CpuInfo, String> {
+        self.0.helper.call(Call::CpuInfo)
+    }
+
+    fn core_usage(&self) -> Result<Vec<f64>, String> {
+        self.0.helper.call(Call::CoreUsage)
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        self.0.helper.call(Call::PowerStatus)
+    }
+
+    fn uptime_info(&self) -> Result<UptimeInfo, String> {
+        self.0.helper.call(Call::UptimeInfo)
+    }
+}
+
+#[tauri::command]
+pub fn get_sandboxed_commands(lib_state: State<CppLibrary>) -> Vec<SandboxedCommand> {
+    let sandboxed = lib_state.sandboxed();
+    SANDBOXABLE_COMMANDS
+        .iter()
+        .map(|command| SandboxedCommand {
+            command: command.to_string(),
+            sandboxed: sandboxed.iter().any(|name| name == command),
+        })
+        .collect()
+}
+
+pub fn check_commands(commands: &[String]) -> Result<(), CommandError> {
+    if let Some(command) = commands
+        .iter()
+        .find(|command| !SANDBOXABLE_COMMANDS.contains(&command.as_str()))
+    {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "sandbox.unknownCommand")
+                .with("command", command),
+        );
+    }
+    Ok(())
+}
+
+// Route `commands` through the helper and every other command in-process
+#[tauri::command]
+pub fn set_sandboxed_commands(
+    commands: Vec<String>,
+    lib_state: State<CppLibrary>,
+    config: State<ConfigState>,
+) -> Result<(), CommandError> {
+    check_commands(&commands)?;
+    config.update(|config| config.library.sandboxed_commands = commands.clone())?;
+    lib_state.set_sandboxed(commands);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// Interrupt and context-switch rates plus run-queue length.
+// High context-switch rates often explain a machine that looks idle but feels slow. The
+// counters come from /proc/stat on Linux; other platforms report None for every field.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::error::CommandError;
+
+// Gap between the two readings needed for a rate on the first call
+const FIRST_READING_GAP: Duration = Duration::from_millis(250);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    taken_at: Instant,
+    interrupts: u64,
+    context_switches: u64,
+}
+
+// Readings are kept between calls so rates cover the time since the last query
+#[derive(Default)]
+pub struct SchedulerMonitor {
+    last: Mutex<Option<Counters>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerStats {
+    pub interrupts_per_sec: Option<f64>,
+    pub context_switches_per_sec: Option<f64>,
+    // Runnable tasks right now, and tasks blocked on I/O
+    pub run_queue: Option<u64>,
+    pub blocked: Option<u64>,
+    // Seconds the rates were measured over
+    pub interval_secs: Option<f64>,
+}
+
+struct ProcStat {
+    interrupts: u64,
+    context_switches: u64,
+    running: u64,
+    blocked: u64,
+}
+
+fn read_proc_stat() -> Option<ProcStat> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let text = std::fs::read_to_string("/proc/stat").ok()?;
+    let field = |name: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+            .and_then(|rest| rest.split_whitespace().next()?.parse::<u64>().ok())
+    };
+    Some(ProcStat {
+        // The first number after `intr` is the total across all interrupt lines
+        interrupts: field("intr")?,
+        context_switches: field("ctxt")?,
+        running: field("procs_running")?,
+        blocked: field("procs_blocked")?,
+    })
+}
+
+fn counters(stat: &ProcStat) -> Counters {
+    Counters {
+        taken_at: Instant::now(),
+        interrupts: stat.interrupts,
+        context_switches: stat.context_switches,
+    }
+}
+
+#[tauri::command]
+pub async fn get_scheduler_stats(
+    monitor: State<'_, SchedulerMonitor>,
+) -> Result<SchedulerStats, CommandError> {
+    let Some(mut stat) = read_proc_stat() else {
+        return Ok(SchedulerStats {
+            interrupts_per_sec: None,
+            context_switches_per_sec: None,
+            run_queue: None,
+            blocked: None,
+            interval_secs: None,
+        });
+    };
+
+    let previous = *monitor.last.lock().map_err(|_| "Scheduler lock poisoned")?;
+    let previous = match previous {
+        Some(previous) => previous,
+        None => {
+            let first = counters(&stat);
+            tauri::async_runtime::spawn_blocking(|| std::thread::sleep(FIRST_READING_GAP))
+                .await
+                .map_err(|e| e.to_string())?;
+            stat = read_proc_stat().ok_or("Failed to read /proc/stat")?;
+            first
+        }
+    };
+    let current = counters(&stat);
+    *monitor.last.lock().map_err(|_| "Scheduler lock poisoned")? = Some(current);
+
+    let elapsed = current
+        .taken_at
+        .duration_since(previous.taken_at)
+        .as_secs_f64();
+    let rate = |now: u64, before: u64| {
+        (elapsed > 0.0).then(|| now.saturating_sub(before) as f64 / elapsed)
+    };
+
+    Ok(SchedulerStats {
+        interrupts_per_sec: rate(current.interrupts, previous.interrupts),
+        context_switches_per_sec: rate(current.context_switches, previous.context_switches),
+        run_queue: Some(stat.running),
+        blocked: Some(stat.blocked),
+        interval_secs: Some(elapsed),
+    })
+}
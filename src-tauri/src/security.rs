@@ -0,0 +1,120 @@
+// Security software registered with Windows Security Center.
+// Products are read from the `root/SecurityCenter2` WMI namespace through PowerShell; their
+// `productState` packs the enabled and signature status into nibbles. The namespace only exists
+// on client editions of Windows, and other platforms have no equivalent registry of products.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SecurityProductKind {
+    Antivirus,
+    Antispyware,
+    Firewall,
+}
+
+impl SecurityProductKind {
+    fn wmi_class(self) -> &'static str {
+        match self {
+            SecurityProductKind::Antivirus => "AntiVirusProduct",
+            SecurityProductKind::Antispyware => "AntiSpywareProduct",
+            SecurityProductKind::Firewall => "FirewallProduct",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityProduct {
+    pub kind: SecurityProductKind,
+    pub name: String,
+    pub enabled: bool,
+    // None for firewalls, which have no signatures
+    pub up_to_date: Option<bool>,
+    pub product_state: u32,
+    pub executable: Option<String>,
+    // Last time the product reported its state, as given by Security Center
+    pub timestamp: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityProducts {
+    // False where there is no Security Center to ask
+    pub supported: bool,
+    pub products: Vec<SecurityProduct>,
+    // Product classes that couldn't be queried
+    pub errors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WmiProduct {
+    display_name: String,
+    product_state: u32,
+    path_to_signed_product_exe: Option<String>,
+    timestamp: Option<String>,
+}
+
+fn query(kind: SecurityProductKind) -> Result<Vec<SecurityProduct>, String> {
+    // `@(...)` keeps a single product serialized as an array
+    let script = format!(
+        "ConvertTo-Json -Compress -InputObject @(Get-CimInstance -Namespace root/SecurityCenter2 \
+         -ClassName {} | Select-Object displayName, productState, pathToSignedProductExe, timestamp)",
+        kind.wmi_class()
+    );
+    let output = run_text("powershell", &["-NoProfile", "-Command", &script])?;
+    let products: Vec<WmiProduct> =
+        serde_json::from_str(output.trim()).map_err(|e| e.to_string())?;
+
+    Ok(products
+        .into_iter()
+        .map(|product| {
+            // Bits 12-15: 1 = on (0 off, 2 snoozed, 3 expired); bits 4-7: 0 = signatures current
+            let state = product.product_state;
+            SecurityProduct {
+                kind,
+                name: product.display_name,
+                enabled: (state >> 12) & 0xF == 1,
+                up_to_date: (!matches!(kind, SecurityProductKind::Firewall))
+                    .then_some((state >> 4) & 0xF == 0),
+                product_state: state,
+                executable: product.path_to_signed_product_exe,
+                timestamp: product.timestamp,
+            }
+        })
+        .collect())
+}
+
+fn collect() -> SecurityProducts {
+    let mut result = SecurityProducts {
+        supported: cfg!(target_os = "windows"),
+        products: Vec::new(),
+        errors: Vec::new(),
+    };
+    if !result.supported {
+        return result;
+    }
+    for kind in [
+        SecurityProductKind::Antivirus,
+        SecurityProductKind::Antispyware,
+        SecurityProductKind::Firewall,
+    ] {
+        match query(kind) {
+            Ok(products) => result.products.extend(products),
+            Err(e) => result.errors.push(format!("{}: {}", kind.wmi_class(), e)),
+        }
+    }
+    result
+}
+
+#[tauri::command]
+pub async fn get_security_products() -> Result<SecurityProducts, CommandError> {
+    let products = tauri::async_runtime::spawn_blocking(collect)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(products)
+}
@@ -0,0 +1,131 @@
+// Current temperature and fan readings for a sensors panel.
+// Temperatures come from sysinfo (hwmon on Linux, SMC on macOS, ACPI thermal zones on Windows)
+// in the preferred display unit, plus `nvidia-smi` for NVIDIA GPUs, whose proprietary driver
+// doesn't register with hwmon. Fan speeds are read from hwmon on Linux only; macOS and Windows
+// have no fan API short of vendor tools. A platform without sensor access returns an empty
+// list rather than an error.
+
+use serde::Serialize;
+use std::path::Path;
+use sysinfo::Components;
+use tauri::State;
+
+use crate::config::ConfigState;
+use crate::display::{self, DisplaySettings};
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+const HWMON_CLASS_DIR: &str = "/sys/class/hwmon";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorReading {
+    pub label: String,
+    pub value: f64,
+    // `°C`/`°F` for temperatures, `RPM` for fans
+    pub unit: String,
+}
+
+fn reading(label: String, value: f64, unit: &str) -> SensorReading {
+    SensorReading {
+        label,
+        value,
+        unit: unit.to_string(),
+    }
+}
+
+fn component_temperatures(display: &DisplaySettings) -> Vec<SensorReading> {
+    Components::new_with_refreshed_list()
+        .iter()
+        .filter_map(|component| {
+            let celsius = component.temperature()?;
+            // Some drivers report NaN or 0 for sensors that aren't wired up
+            (celsius.is_finite() && celsius != 0.0).then(|| {
+                let (value, unit) = display.convert(celsius as f64, "°C");
+                reading(component.label().to_string(), value, unit)
+            })
+        })
+        .collect()
+}
+
+fn nvidia_temperatures(display: &DisplaySettings) -> Vec<SensorReading> {
+    let Ok(output) = run_text(
+        "nvidia-smi",
+        &[
+            "--query-gpu=index,name,temperature.gpu",
+            "--format=csv,noheader,nounits",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [index, name, celsius] = fields[..] else {
+                return None;
+            };
+            let (value, unit) = display.convert(celsius.parse().ok()?, "°C");
+            Some(reading(format!("GPU {} {}", index, name), value, unit))
+        })
+        .collect()
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+// `fanN_input` in each hwmon device, labelled by `fanN_label` or the device name
+fn hwmon_fans() -> Vec<SensorReading> {
+    let Ok(devices) = std::fs::read_dir(HWMON_CLASS_DIR) else {
+        return Vec::new();
+    };
+    let mut fans = Vec::new();
+    for device in devices.flatten() {
+        let dir = device.path();
+        let chip = read_trimmed(&dir.join("name")).unwrap_or_else(|| "hwmon".to_string());
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut inputs: Vec<String> = files
+            .flatten()
+            .filter_map(|file| file.file_name().into_string().ok())
+            .filter(|name| name.starts_with("fan") && name.ends_with("_input"))
+            .collect();
+        inputs.sort();
+
+        for input in inputs {
+            let Some(rpm) = read_trimmed(&dir.join(&input)).and_then(|v| v.parse::<f64>().ok())
+            else {
+                continue;
+            };
+            let fan = input.trim_end_matches("_input");
+            let label = read_trimmed(&dir.join(format!("{}_label", fan)))
+                .unwrap_or_else(|| format!("{} {}", chip, fan));
+            fans.push(reading(label, rpm, "RPM"));
+        }
+    }
+    fans
+}
+
+fn read_sensors(display: &DisplaySettings) -> Vec<SensorReading> {
+    let mut readings = component_temperatures(display);
+    readings.extend(nvidia_temperatures(display));
+    if cfg!(target_os = "linux") {
+        readings.extend(hwmon_fans());
+    }
+    readings
+}
+
+#[tauri::command]
+pub async fn get_sensors(
+    config: State<'_, ConfigState>,
+) -> Result<Vec<SensorReading>, CommandError> {
+    let display = display::current(&config);
+    let readings = tauri::async_runtime::spawn_blocking(move || read_sensors(&display))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(readings)
+}
@@ -0,0 +1,270 @@
+// Start, stop and restart system services.
+// Every action asks for confirmation in a native dialog before it runs, and failures are sorted
+// into "not permitted", "no such service" and everything else so the UI can suggest running
+// elevated instead of showing raw tool output. Uses `systemctl` on Linux, `sc` on Windows and
+// `launchctl` (system domain) on macOS.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::error::{CommandError, ErrorCode};
+use crate::kiosk;
+use crate::os_command;
+
+// How long a Windows restart waits for the service to stop before starting it again
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Output fragments that mean the caller lacks the rights to control the service
+const PERMISSION_MARKERS: [&str; 5] = [
+    "access denied",
+    "access is denied",
+    "interactive authentication required",
+    "operation not permitted",
+    "failed 5:",
+];
+const NOT_FOUND_MARKERS: [&str; 4] = [
+    "not found",
+    "not loaded",
+    "could not find service",
+    "failed 1060:",
+];
+
+#[derive(Clone, Copy)]
+enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    fn verb(self) -> &'static str {
+        match self {
+            ServiceAction::Start => "Start",
+            ServiceAction::Stop => "Stop",
+            ServiceAction::Restart => "Restart",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    pub name: String,
+    // As the service manager reports it, e.g. `active (running)`, `STOPPED`, `running`
+    pub state: String,
+    pub description: Option<String>,
+}
+
+// Unit names, service names and launchd labels; nothing that could be read as an option
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_.@:-".contains(c))
+}
+
+fn check_name(name: &str) -> Result<(), CommandError> {
+    if valid_name(name) {
+        Ok(())
+    } else {
+        Err(CommandError::new(ErrorCode::InvalidInput, "service.invalidName").with("name", name))
+    }
+}
+
+// Run a service manager command, mapping a failure to a permission, not-found or internal error.
+// `sc` writes its errors to stdout, so both streams are checked.
+fn run(name: &str, program: &str, args: &[&str]) -> Result<String, CommandError> {
+    let output = os_command::output(program, args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if output.status.success() {
+        return Ok(stdout);
+    }
+
+    let detail = format!("{}{}", stdout, String::from_utf8_lossy(&output.stderr))
+        .trim()
+        .to_string();
+    let lower = detail.to_lowercase();
+    let matches = |markers: &[&str]| markers.iter().any(|marker| lower.contains(marker));
+    Err(if matches(&PERMISSION_MARKERS) {
+        CommandError::new(ErrorCode::PermissionDenied, "service.permissionDenied")
+            .with("name", name)
+    } else if matches(&NOT_FOUND_MARKERS) {
+        CommandError::new(ErrorCode::InvalidInput, "service.notFound").with("name", name)
+    } else {
+        CommandError::from(format!("{} failed: {}", program, detail))
+    })
+}
+
+fn launchd_target(name: &str) -> String {
+    format!("system/{}", name)
+}
+
+// `sc query` prints `STATE              : 4  RUNNING`
+fn sc_state(name: &str) -> Result<String, CommandError> {
+    let output = run(name, "sc", &["query", name])?;
+    Ok(output
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim() != "STATE" {
+                return None;
+            }
+            value.split_whitespace().nth(1).map(str::to_string)
+        })
+        .unwrap_or_default())
+}
+
+fn sc_restart(name: &str) -> Result<(), CommandError> {
+    if sc_state(name)? != "STOPPED" {
+        run(name, "sc", &["stop", name])?;
+        let started = Instant::now();
+        while sc_state(name)? != "STOPPED" {
+            if started.elapsed() > STOP_TIMEOUT {
+                return Err(format!("{} did not stop within {:?}", name, STOP_TIMEOUT).into());
+            }
+            std::thread::sleep(Duration::from_millis(500));
+        }
+    }
+    run(name, "sc", &["start", name])?;
+    Ok(())
+}
+
+fn perform(name: &str, action: ServiceAction) -> Result<(), CommandError> {
+    if cfg!(target_os = "windows") {
+        match action {
+            ServiceAction::Start => run(name, "sc", &["start", name]).map(drop),
+            ServiceAction::Stop => run(name, "sc", &["stop", name]).map(drop),
+            ServiceAction::Restart => sc_restart(name),
+        }
+    } else if cfg!(target_os = "macos") {
+        let target = launchd_target(name);
+        let args: &[&str] = match action {
+            ServiceAction::Start => &["kickstart", &target],
+            ServiceAction::Stop => &["kill", "SIGTERM", &target],
+            ServiceAction::Restart => &["kickstart", "-k", &target],
+        };
+        run(name, "launchctl", args).map(drop)
+    } else {
+        let verb = match action {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        };
+        // Fail instead of waiting on a polkit prompt the user may never see
+        run(name, "systemctl", &[verb, "--no-ask-password", name]).map(drop)
+    }
+}
+
+fn read_status(name: &str) -> Result<ServiceStatus, CommandError> {
+    if cfg!(target_os = "windows") {
+        return Ok(ServiceStatus {
+            name: name.to_string(),
+            state: sc_state(name)?,
+            description: None,
+        });
+    }
+    if cfg!(target_os = "macos") {
+        // `launchctl print` lists `state = running` among many other properties
+        let output = run(name, "launchctl", &["print", &launchd_target(name)])?;
+        let state = output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("state = "))
+            .unwrap_or_default();
+        return Ok(ServiceStatus {
+            name: name.to_string(),
+            state: state.to_string(),
+            description: None,
+        });
+    }
+
+    let output = run(
+        name,
+        "systemctl",
+        &[
+            "show",
+            "--property=LoadState,ActiveState,SubState,Description",
+            name,
+        ],
+    )?;
+    let property = |key: &str| {
+        output.lines().find_map(|line| {
+            let (k, value) = line.split_once('=')?;
+            (k == key).then(|| value.to_string())
+        })
+    };
+    // `systemctl show` succeeds for units that don't exist
+    if property("LoadState").as_deref() == Some("not-found") {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "service.notFound").with("name", name),
+        );
+    }
+    Ok(ServiceStatus {
+        name: name.to_string(),
+        state: format!(
+            "{} ({})",
+            property("ActiveState").unwrap_or_default(),
+            property("SubState").unwrap_or_default()
+        ),
+        description: property("Description").filter(|description| !description.is_empty()),
+    })
+}
+
+async fn control(
+    app: AppHandle,
+    name: String,
+    action: ServiceAction,
+) -> Result<ServiceStatus, CommandError> {
+    check_name(&name)?;
+    kiosk::check_interactive(&app)?;
+    let confirm = app
+        .dialog()
+        .message(format!(
+            "{} the service \"{}\"? Other programs may depend on it.",
+            action.verb(),
+            name
+        ))
+        .title(format!("{} service", action.verb()))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            action.verb().to_string(),
+            "Cancel".to_string(),
+        ));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if !confirm.blocking_show() {
+            return Err(CommandError::new(ErrorCode::Cancelled, "service.cancelled"));
+        }
+        perform(&name, action)?;
+        println!("✓ {} service {}", action.verb(), name);
+        read_status(&name)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_service_status(name: String) -> Result<ServiceStatus, CommandError> {
+    check_name(&name)?;
+    tauri::async_runtime::spawn_blocking(move || read_status(&name))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// The control commands return the service's status after the action
+#[tauri::command]
+pub async fn start_service(app: AppHandle, name: String) -> Result<ServiceStatus, CommandError> {
+    control(app, name, ServiceAction::Start).await
+}
+
+#[tauri::command]
+pub async fn stop_service(app: AppHandle, name: String) -> Result<ServiceStatus, CommandError> {
+    control(app, name, ServiceAction::Stop).await
+}
+
+#[tauri::command]
+pub async fn restart_service(app: AppHandle, name: String) -> Result<ServiceStatus, CommandError> {
+    control(app, name, ServiceAction::Restart).await
+}
@@ -0,0 +1,352 @@
+// Recording the app's metric event streams to a file and replaying them, for demos and for
+// reproducing what a user saw. `start_recording` listens to every stream in `RECORDED_EVENTS`
+// and appends one JSON line per event with its offset from the start. `start_replay` emits the
+// recorded events again under their original names, at the recorded pace divided by `speed`,
+// so the frontend shows them like live data; `session-replay-finished` follows the last one.
+// Recording and replay exclude each other, so a replay never ends up in a recording.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, EventId, Listener, State};
+
+use crate::alerts::ALERT_TRIGGERED_EVENT;
+use crate::battery_saver::BATTERY_SAVER_CHANGED_EVENT;
+use crate::capture::CAPTURE_USAGE_CHANGED_EVENT;
+use crate::error::{CommandError, ErrorCode};
+use crate::identity::IDENTITY_CHANGED_EVENT;
+use crate::integrity::INTEGRITY_CHANGED_EVENT;
+use crate::inventory::INVENTORY_CHANGED_EVENT;
+use crate::monitor::TICK_EVENT;
+use crate::power::POWER_STATUS_CHANGED_EVENT;
+use crate::report::unix_timestamp;
+use crate::watchlist::PROCESS_STOPPED_EVENT;
+
+pub const REPLAY_FINISHED_EVENT: &str = "session-replay-finished";
+
+const SESSION_FORMAT_VERSION: u32 = 1;
+
+const RECORDED_EVENTS: [&str; 9] = [
+    TICK_EVENT,
+    ALERT_TRIGGERED_EVENT,
+    POWER_STATUS_CHANGED_EVENT,
+    BATTERY_SAVER_CHANGED_EVENT,
+    IDENTITY_CHANGED_EVENT,
+    CAPTURE_USAGE_CHANGED_EVENT,
+    INTEGRITY_CHANGED_EVENT,
+    INVENTORY_CHANGED_EVENT,
+    PROCESS_STOPPED_EVENT,
+];
+
+const MIN_SPEED: f64 = 0.1;
+const MAX_SPEED: f64 = 100.0;
+
+// First line of a session file
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionHeader {
+    format_version: u32,
+    started_at: u64,
+    app_version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionEvent {
+    offset_ms: u64,
+    event: String,
+    payload: Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub path: String,
+    pub events: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFinished {
+    pub path: String,
+    // False when `stop_replay` ended it early
+    pub completed: bool,
+}
+
+struct SessionFile {
+    out: BufWriter<File>,
+    events: u64,
+    // First write error; recording stops writing after it
+    error: Option<String>,
+}
+
+struct Recording {
+    path: String,
+    started: Instant,
+    file: Arc<Mutex<SessionFile>>,
+    listeners: Vec<EventId>,
+}
+
+struct Replay {
+    // Dropping it wakes the replay thread and ends it
+    _stop: Sender<()>,
+}
+
+#[derive(Default)]
+pub struct Sessions {
+    recording: Mutex<Option<Recording>>,
+    replay: Arc<Mutex<Option<Replay>>>,
+}
+
+fn recording_active() -> CommandError {
+    CommandError::new(ErrorCode::InvalidInput, "session.recordingActive")
+}
+
+fn replay_active() -> CommandError {
+    CommandError::new(ErrorCode::InvalidInput, "session.replayActive")
+}
+
+fn write_line(out: &mut impl Write, value: &impl Serialize) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *out, value)?;
+    out.write_all(b"\n")
+}
+
+fn record(file: &Mutex<SessionFile>, started: Instant, event: &str, payload: &str) {
+    let Ok(mut file) = file.lock() else {
+        return;
+    };
+    if file.error.is_some() {
+        return;
+    }
+    let entry = SessionEvent {
+        offset_ms: started.elapsed().as_millis() as u64,
+        event: event.to_string(),
+        payload: serde_json::from_str(payload).unwrap_or(Value::Null),
+    };
+    match write_line(&mut file.out, &entry) {
+        Ok(()) => file.events += 1,
+        Err(e) => {
+            eprintln!("⚠ Warning: Session recording stopped writing: {}", e);
+            file.error = Some(e.to_string());
+        }
+    }
+}
+
+fn read_session(path: &str) -> Result<Vec<SessionEvent>, CommandError> {
+    let invalid = |detail: String| {
+        CommandError::new(ErrorCode::InvalidInput, "session.invalidFile")
+            .with("path", path)
+            .with("detail", detail)
+    };
+    let file = File::open(path).map_err(|e| CommandError::open_failed(path, e))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| invalid("the file is empty".to_string()))?
+        .map_err(|e| CommandError::open_failed(path, e))?;
+    let header: SessionHeader =
+        serde_json::from_str(&header).map_err(|e| invalid(e.to_string()))?;
+    if header.format_version > SESSION_FORMAT_VERSION {
+        return Err(invalid(format!(
+            "format version {} is newer than this app supports",
+            header.format_version
+        )));
+    }
+
+    lines
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|line| {
+            let line = line.map_err(|e| CommandError::open_failed(path, e))?;
+            serde_json::from_str(&line).map_err(|e| invalid(e.to_string()))
+        })
+        .collect()
+}
+
+fn replay(
+    app: AppHandle,
+    path: String,
+    events: Vec<SessionEvent>,
+    speed: f64,
+    stop: mpsc::Receiver<()>,
+    replay: Arc<Mutex<Option<Replay>>>,
+) {
+    let started = Instant::now();
+    let mut completed = true;
+    for entry in events {
+        let due = Duration::from_secs_f64(entry.offset_ms as f64 / 1000.0 / speed);
+        match stop.recv_timeout(due.saturating_sub(started.elapsed())) {
+            Err(RecvTimeoutError::Timeout) => {}
+            _ => {
+                completed = false;
+                break;
+            }
+        }
+        if let Err(e) = app.emit(&entry.event, entry.payload) {
+            eprintln!("⚠ Warning: Failed to emit {}: {}", entry.event, e);
+        }
+    }
+
+    // A replay stopped early was already taken out by `stop_replay`
+    if completed {
+        if let Ok(mut replay) = replay.lock() {
+            replay.take();
+        }
+    }
+    println!("✓ Replay of {} finished", path);
+    if let Err(e) = app.emit(REPLAY_FINISHED_EVENT, ReplayFinished { path, completed }) {
+        eprintln!("⚠ Warning: Failed to emit {}: {}", REPLAY_FINISHED_EVENT, e);
+    }
+}
+
+// Record every metric event stream to `path` until `stop_recording`
+#[tauri::command]
+pub fn start_recording(
+    path: String,
+    app: AppHandle,
+    sessions: State<Sessions>,
+) -> Result<(), CommandError> {
+    let mut recording = sessions
+        .recording
+        .lock()
+        .map_err(|_| "Session lock poisoned")?;
+    if recording.is_some() {
+        return Err(recording_active());
+    }
+    if sessions
+        .replay
+        .lock()
+        .map_err(|_| "Session lock poisoned")?
+        .is_some()
+    {
+        return Err(replay_active());
+    }
+
+    let started_at = unix_timestamp();
+    let mut out = File::create(&path)
+        .map(BufWriter::new)
+        .map_err(|e| CommandError::create_failed(&path, e))?;
+    let header = SessionHeader {
+        format_version: SESSION_FORMAT_VERSION,
+        started_at,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    write_line(&mut out, &header).map_err(|e| CommandError::create_failed(&path, e))?;
+
+    let started = Instant::now();
+    let file = Arc::new(Mutex::new(SessionFile {
+        out,
+        events: 0,
+        error: None,
+    }));
+    let listeners = RECORDED_EVENTS
+        .iter()
+        .map(|&name| {
+            let file = file.clone();
+            app.listen_any(name, move |event| {
+                record(&file, started, name, event.payload())
+            })
+        })
+        .collect();
+
+    println!("✓ Recording session to {}", path);
+    *recording = Some(Recording {
+        path,
+        started,
+        file,
+        listeners,
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_recording(
+    app: AppHandle,
+    sessions: State<Sessions>,
+) -> Result<SessionSummary, CommandError> {
+    let recording = sessions
+        .recording
+        .lock()
+        .map_err(|_| "Session lock poisoned")?
+        .take()
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidInput, "session.notRecording"))?;
+    for listener in recording.listeners {
+        app.unlisten(listener);
+    }
+
+    let mut file = recording.file.lock().map_err(|_| "Session lock poisoned")?;
+    let flushed = file.out.flush().map_err(|e| e.to_string());
+    if let Some(e) = file.error.clone().or(flushed.err()) {
+        return Err(CommandError::create_failed(&recording.path, e));
+    }
+    println!("✓ Recorded {} events to {}", file.events, recording.path);
+    Ok(SessionSummary {
+        path: recording.path.clone(),
+        events: file.events,
+        duration_ms: recording.started.elapsed().as_millis() as u64,
+    })
+}
+
+// Emit the events recorded in `path` again, `speed` times as fast as recorded (1 by default)
+#[tauri::command]
+pub fn start_replay(
+    path: String,
+    speed: Option<f64>,
+    app: AppHandle,
+    sessions: State<Sessions>,
+) -> Result<SessionSummary, CommandError> {
+    let speed = speed.unwrap_or(1.0);
+    if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "session.invalidSpeed")
+                .with("min", MIN_SPEED)
+                .with("max", MAX_SPEED),
+        );
+    }
+    if sessions
+        .recording
+        .lock()
+        .map_err(|_| "Session lock poisoned")?
+        .is_some()
+    {
+        return Err(recording_active());
+    }
+    let mut replay_slot = sessions
+        .replay
+        .lock()
+        .map_err(|_| "Session lock poisoned")?;
+    if replay_slot.is_some() {
+        return Err(replay_active());
+    }
+
+    let events = read_session(&path)?;
+    let summary = SessionSummary {
+        path: path.clone(),
+        events: events.len() as u64,
+        duration_ms: events.last().map_or(0, |entry| entry.offset_ms),
+    };
+    let (stop, stopped) = mpsc::channel();
+    let replay_state = sessions.replay.clone();
+    std::thread::spawn(move || replay(app, path, events, speed, stopped, replay_state));
+    println!(
+        "✓ Replaying {} events from {} at {}x",
+        summary.events, summary.path, speed
+    );
+    *replay_slot = Some(Replay { _stop: stop });
+    Ok(summary)
+}
+
+#[tauri::command]
+pub fn stop_replay(sessions: State<Sessions>) -> Result<(), CommandError> {
+    sessions
+        .replay
+        .lock()
+        .map_err(|_| "Session lock poisoned")?
+        .take();
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// One-time share links for exported reports.
+// A link serves a copy of an export history entry from the remote agent's HTTP server at
+// `/api/share/<token>`, so another machine on the LAN can pull it without files changing hands.
+// Tokens are random, expire after their TTL, and are consumed by the first successful fetch.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::UdpSocket;
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::agent::AgentServer;
+use crate::error::{CommandError, ErrorCode};
+use crate::export_history::{self, ExportKind};
+use crate::history::History;
+use crate::report::unix_timestamp;
+
+pub const SHARE_ROUTE: &str = "/api/share/";
+
+const DEFAULT_TTL_SECONDS: u64 = 10 * 60;
+const MAX_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+struct SharedReport {
+    content: String,
+    content_type: &'static str,
+    expires_at: u64,
+}
+
+// Links handed out and not yet fetched
+#[derive(Default)]
+pub struct ShareLinks {
+    links: Mutex<HashMap<String, SharedReport>>,
+}
+
+impl ShareLinks {
+    // Content and content type for `token`, removing it so the link works only once
+    pub fn take(&self, token: &str) -> Option<(String, &'static str)> {
+        let mut links = self.links.lock().ok()?;
+        let now = unix_timestamp();
+        links.retain(|_, link| link.expires_at > now);
+        links
+            .remove(token)
+            .map(|link| (link.content, link.content_type))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareLink {
+    pub url: String,
+    pub token: String,
+    pub expires_at: u64,
+}
+
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+// The address other machines on the LAN reach us at: the source address the OS would use for
+// an outside destination. Connecting a UDP socket sends nothing.
+fn lan_address() -> Option<String> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("192.0.2.1", 80)).ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+// Share export history entry `report_id` for `ttl` seconds (10 minutes by default, at most a
+// day); the remote agent must be running to serve it
+#[tauri::command]
+pub fn create_share_link(
+    report_id: i64,
+    ttl: Option<u64>,
+    history: State<History>,
+    agent: State<AgentServer>,
+    links: State<ShareLinks>,
+) -> Result<ShareLink, CommandError> {
+    let port = agent
+        .port()
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidInput, "share.agentNotRunning"))?;
+    let record = export_history::find(&history, report_id)?.ok_or_else(|| {
+        CommandError::new(ErrorCode::InvalidInput, "share.unknownReport").with("id", report_id)
+    })?;
+    let content = record.content.ok_or_else(|| {
+        CommandError::new(ErrorCode::InvalidInput, "share.noContent").with("id", report_id)
+    })?;
+    let content_type = match record.kind {
+        ExportKind::Snippet => "text/plain; charset=utf-8",
+        ExportKind::Bundle | ExportKind::Parquet | ExportKind::Report => "application/json",
+    };
+
+    let token = new_token()?;
+    let expires_at =
+        unix_timestamp() + ttl.unwrap_or(DEFAULT_TTL_SECONDS).clamp(1, MAX_TTL_SECONDS);
+    links
+        .links
+        .lock()
+        .map_err(|_| "Share links lock poisoned")?
+        .insert(
+            token.clone(),
+            SharedReport {
+                content,
+                content_type,
+                expires_at,
+            },
+        );
+
+    let host = lan_address().unwrap_or_else(|| "localhost".to_string());
+    Ok(ShareLink {
+        url: format!("http://{}:{}{}{}", host, port, SHARE_ROUTE, token),
+        token,
+        expires_at,
+    })
+}
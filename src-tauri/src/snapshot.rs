@@ -0,0 +1,144 @@
+// Every system value the dashboard shows in one command, so it makes a single `invoke` on load
+// instead of one per value. A value that can't be read is None and its error is kept in
+// `errors` under the same field name, so one failure doesn't hide the rest, and each value read
+// has its source and read time in `sources`.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::cpu::{CpuInfo, CpuUsage};
+use crate::disks::{self, DiskInfo};
+use crate::error::CommandError;
+use crate::memory::{self, MemoryDetails};
+use crate::os_info::{self, OsInfo};
+use crate::provider::{Attribution, Backend, SystemInfoProvider};
+use crate::report::unix_timestamp;
+use crate::uptime::UptimeInfo;
+use crate::{read_computer_name, read_process_id, read_total_memory, CppLibrary};
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemSnapshot {
+    pub timestamp: u64,
+    pub app_version: String,
+    pub platform: String,
+    pub arch: String,
+    pub library_loaded: bool,
+    // Which backend served the values below
+    pub backend: Backend,
+    pub computer_name: Option<String>,
+    pub total_memory: Option<u64>,
+    pub process_id: Option<u32>,
+    pub os_info: Option<OsInfo>,
+    pub cpu_info: Option<CpuInfo>,
+    pub cpu_usage: Option<CpuUsage>,
+    pub memory: Option<MemoryDetails>,
+    pub uptime: Option<UptimeInfo>,
+    pub disks: Option<Vec<DiskInfo>>,
+    // Field name -> why it is None
+    pub errors: BTreeMap<&'static str, String>,
+    // Field name -> where its value came from
+    pub sources: BTreeMap<&'static str, Attribution>,
+}
+
+// Reads the fields of a snapshot or report, keeping the error of each failed read and the
+// source of each successful one under the field name
+pub struct Collector {
+    provider: Arc<dyn SystemInfoProvider>,
+    pub errors: BTreeMap<&'static str, String>,
+    pub sources: BTreeMap<&'static str, Attribution>,
+}
+
+impl Collector {
+    pub fn new(lib_state: &CppLibrary) -> Self {
+        Collector {
+            provider: lib_state.provider(),
+            errors: BTreeMap::new(),
+            sources: BTreeMap::new(),
+        }
+    }
+
+    pub fn read<T, E: Display>(
+        &mut self,
+        field: &'static str,
+        read: impl FnOnce() -> Result<T, E>,
+    ) -> Option<T> {
+        match read() {
+            Ok(value) => {
+                self.sources.insert(field, self.provider.attribution(field));
+                Some(value)
+            }
+            Err(e) => {
+                self.errors.insert(field, e.to_string());
+                None
+            }
+        }
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.provider.backend()
+    }
+}
+
+// The fields every snapshot and report has: computer name, total memory and process ID
+pub fn read_identity(
+    collector: &mut Collector,
+    lib_state: &CppLibrary,
+) -> (Option<String>, Option<u64>, Option<u32>) {
+    (
+        collector.read("computerName", || read_computer_name(lib_state)),
+        collector.read("totalMemory", || read_total_memory(lib_state)),
+        collector.read("processId", || read_process_id(lib_state)),
+    )
+}
+
+pub fn collect_snapshot(lib_state: &CppLibrary) -> SystemSnapshot {
+    let mut collector = Collector::new(lib_state);
+    let (computer_name, total_memory, process_id) = read_identity(&mut collector, lib_state);
+    let os_info = collector.read("osInfo", os_info::read_os_info);
+    let cpu_info = collector.read("cpuInfo", || {
+        lib_state.call("get_cpu_info", |provider| provider.cpu_info())
+    });
+    let cpu_usage = collector
+        .read("cpuUsage", || {
+            lib_state.call("get_cpu_usage", |provider| provider.core_usage())
+        })
+        .map(CpuUsage::from_cores);
+    let memory = collector.read("memory", memory::read_details);
+    let uptime = collector.read("uptime", || {
+        lib_state.call("get_uptime_info", |provider| provider.uptime_info())
+    });
+    let disks = collector.read("disks", || Ok::<_, String>(disks::list_disks()));
+    let backend = collector.backend();
+
+    SystemSnapshot {
+        timestamp: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        library_loaded: lib_state.is_loaded(),
+        backend,
+        computer_name,
+        total_memory,
+        process_id,
+        os_info,
+        cpu_info,
+        cpu_usage,
+        memory,
+        uptime,
+        disks,
+        errors: collector.errors,
+        sources: collector.sources,
+    }
+}
+
+#[tauri::command]
+pub async fn get_system_snapshot(app: AppHandle) -> Result<SystemSnapshot, CommandError> {
+    crate::call_blocking(app, "get_system_snapshot", |app| {
+        Ok(collect_snapshot(&app.state()))
+    })
+    .await
+}
@@ -0,0 +1,334 @@
+// Saving the system snapshot to a file for a support ticket: JSON as `get_system_snapshot`
+// returns it, CSV with one `field,value` row per value, or a self-contained HTML page (inline
+// styles, no scripts or external resources). Without a `path` the user picks the file in a save
+// dialog. Every export is kept in the export history with the snapshot it held, and passing its
+// ID as `exportId` writes that snapshot again instead of taking a new one, e.g. in another format.
+
+use handlebars::Handlebars;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::config::ConfigState;
+use crate::display::{self, DisplaySettings};
+use crate::error::{CommandError, ErrorCode};
+use crate::export_history::{self, ExportKind};
+use crate::history::History;
+use crate::kiosk;
+use crate::snapshot::collect_snapshot;
+
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>System report: {{title}}</title>
+<style>
+  body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2933; }
+  h1 { font-size: 1.4rem; }
+  h2 { font-size: 1.1rem; margin-top: 2rem; }
+  table { border-collapse: collapse; min-width: 28rem; }
+  th, td { text-align: left; padding: 0.3rem 0.8rem; border-bottom: 1px solid #d9e2ec; }
+  th { color: #52606d; font-weight: 600; }
+  .muted { color: #7b8794; }
+</style>
+</head>
+<body>
+<h1>System report: {{title}}</h1>
+<p class="muted">Snapshot taken {{takenAt}}, exported by System Info App {{appVersion}}</p>
+{{#each sections}}
+<h2>{{name}}</h2>
+{{#if rows}}
+<table>
+{{#each rows}}<tr><th>{{this.[0]}}</th><td>{{this.[1]}}</td></tr>
+{{/each}}</table>
+{{else}}
+<p class="muted">None</p>
+{{/if}}
+{{/each}}
+</body>
+</html>
+"#;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Html,
+}
+
+impl ExportFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    fn filter_name(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Html => "HTML report",
+        }
+    }
+}
+
+// One table of the HTML report
+#[derive(Serialize)]
+struct Section {
+    name: String,
+    rows: Vec<(String, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HtmlContext {
+    title: String,
+    taken_at: String,
+    app_version: &'static str,
+    sections: Vec<Section>,
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+// (dotted field name, value) for every value under `value`, e.g. `sources.totalMemory.readAt`
+fn flatten(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                let name = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten(&name, field, rows);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{}.{}", prefix, i), item, rows);
+            }
+        }
+        value => rows.push((prefix.to_string(), text(value))),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_csv(snapshot: &Value) -> String {
+    let mut rows = Vec::new();
+    flatten("", snapshot, &mut rows);
+    let mut csv = String::from("field,value\n");
+    for (field, value) in rows {
+        csv.push_str(&format!("{},{}\n", csv_field(&field), csv_field(&value)));
+    }
+    csv
+}
+
+// Top-level values form the first table and each nested object or list (`memory`, `disks`,
+// `errors`, `sources`, ...) its own;
+// the timestamp and memory size are written the way the app shows them
+fn render_html(snapshot: &Value, display: &DisplaySettings) -> Result<String, String> {
+    let mut summary = Vec::new();
+    let mut sections = Vec::new();
+    for (name, value) in snapshot.as_object().into_iter().flatten() {
+        match (name.as_str(), value) {
+            ("timestamp", _) => {}
+            ("totalMemory", Value::Number(bytes)) => summary.push((
+                name.clone(),
+                display.bytes(bytes.as_u64().unwrap_or_default()),
+            )),
+            (_, Value::Object(_) | Value::Array(_)) => {
+                let mut rows = Vec::new();
+                flatten("", value, &mut rows);
+                let mut chars = name.chars();
+                let title = chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default();
+                sections.push(Section { name: title, rows });
+            }
+            _ => flatten(name, value, &mut summary),
+        }
+    }
+    sections.insert(
+        0,
+        Section {
+            name: "System".to_string(),
+            rows: summary,
+        },
+    );
+
+    let timestamp = snapshot["timestamp"].as_i64().unwrap_or_default();
+    let context = HtmlContext {
+        title: snapshot["computerName"]
+            .as_str()
+            .unwrap_or("unknown computer")
+            .to_string(),
+        taken_at: display.date_time(timestamp),
+        app_version: env!("CARGO_PKG_VERSION"),
+        sections,
+    };
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars
+        .render_template(HTML_TEMPLATE, &context)
+        .map_err(|e| e.to_string())
+}
+
+// The snapshot saved with an earlier report export
+fn exported_snapshot(history: &History, id: i64) -> Result<Value, CommandError> {
+    let unknown =
+        || CommandError::new(ErrorCode::InvalidInput, "export.unknownSnapshot").with("id", id);
+    let record = export_history::find(history, id)?.ok_or_else(unknown)?;
+    if !matches!(record.kind, ExportKind::Report) {
+        return Err(unknown());
+    }
+    let content = record.content.ok_or_else(unknown)?;
+    Ok(serde_json::from_str(&content).map_err(|e| e.to_string())?)
+}
+
+fn write_report(
+    app: &AppHandle,
+    format: ExportFormat,
+    path: &str,
+    export_id: Option<i64>,
+) -> Result<(), CommandError> {
+    let history = app.state::<History>();
+    let snapshot = match export_id {
+        Some(id) => exported_snapshot(&history, id)?,
+        None => serde_json::to_value(collect_snapshot(&app.state())).map_err(|e| e.to_string())?,
+    };
+    let contents = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?,
+        ExportFormat::Csv => render_csv(&snapshot),
+        ExportFormat::Html => {
+            render_html(&snapshot, &display::current(&app.state::<ConfigState>()))?
+        }
+    };
+    std::fs::write(path, contents).map_err(|e| CommandError::create_failed(path, e))?;
+
+    export_history::record_or_warn(
+        &history,
+        ExportKind::Report,
+        Some(path),
+        &format!("System report ({})", format.as_str()),
+        Some(&snapshot.to_string()),
+    );
+    Ok(())
+}
+
+// Write the current snapshot, or the one saved with export `export_id`, to `path` or a file the
+// user picks; returns the path written
+#[tauri::command]
+pub async fn export_report(
+    format: ExportFormat,
+    path: Option<String>,
+    export_id: Option<i64>,
+    app: AppHandle,
+) -> Result<String, CommandError> {
+    let path = match path {
+        Some(path) => path,
+        None => {
+            kiosk::check_interactive(&app)?;
+            let dialog = app
+                .dialog()
+                .file()
+                .set_title("Save system report")
+                .set_file_name(format!("system-report.{}", format.as_str()))
+                .add_filter(format.filter_name(), &[format.as_str()]);
+            tauri::async_runtime::spawn_blocking(move || dialog.blocking_save_file())
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| CommandError::new(ErrorCode::Cancelled, "export.noneSelected"))?
+                .into_path()
+                .map_err(|e| e.to_string())?
+                .display()
+                .to_string()
+        }
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        write_report(&app, format, &path, export_id)?;
+        Ok(path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain value"), "plain value");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn flatten_joins_nested_paths() {
+        let snapshot = json!({
+            "computerName": "desk",
+            "errors": { "computerName": "access denied" },
+            "sources": {
+                "totalMemory": { "backend": "rust", "readAt": 1700000000 },
+            },
+            "tags": ["a", null],
+        });
+        let mut rows = Vec::new();
+        flatten("", &snapshot, &mut rows);
+        let row = |name: &str, value: &str| (name.to_string(), value.to_string());
+        assert_eq!(
+            rows,
+            vec![
+                row("computerName", "desk"),
+                row("errors.computerName", "access denied"),
+                row("sources.totalMemory.backend", "rust"),
+                row("sources.totalMemory.readAt", "1700000000"),
+                row("tags.0", "a"),
+                row("tags.1", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_csv_quotes_values() {
+        let csv = render_csv(&json!({ "errors": { "cpu": "failed, retrying" } }));
+        assert_eq!(csv, "field,value\nerrors.cpu,\"failed, retrying\"\n");
+    }
+
+    #[test]
+    fn render_html_escapes_snapshot_values() {
+        let snapshot = json!({
+            "computerName": "<script>alert(1)</script>",
+            "timestamp": 0,
+            "errors": { "osInfo": "<b>denied</b>" },
+        });
+        let html = render_html(&snapshot, &DisplaySettings::default()).unwrap();
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<b>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&lt;b&gt;denied&lt;/b&gt;"));
+    }
+}
@@ -0,0 +1,352 @@
+// SSD health: TRIM, lifetime writes and wear, for the disk-health view. Drive data comes from
+// smartmontools (`smartctl -j`), which reads NVMe health logs and ATA SMART attributes the same
+// way on every platform but has to be installed and usually needs administrator rights. A
+// reading per drive is stored every few hours so wear can be trended over the drive's life.
+
+use rusqlite::params;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::ErrorKind;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::battery_saver;
+use crate::error::{CommandError, ErrorCode};
+use crate::history::History;
+use crate::os_command::{self, run_text};
+use crate::report::unix_timestamp;
+
+const RECORD_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const DEFAULT_HISTORY_DAYS: u32 = 365;
+// NVMe counts data in units of 1000 512-byte blocks
+const NVME_DATA_UNIT: u64 = 512 * 1000;
+// ATA attributes vendors use for wear leveling and remaining life
+const WEAR_ATTRIBUTES: [u64; 5] = [173, 177, 202, 231, 233];
+const TOTAL_WRITTEN_ATTRIBUTE: u64 = 241;
+
+#[derive(Clone, Copy, Serialize)]
+pub enum SsdProtocol {
+    #[serde(rename = "NVMe")]
+    Nvme,
+    #[serde(rename = "ATA")]
+    Ata,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WearAttribute {
+    pub id: u64,
+    pub name: String,
+    // Normalized value, usually counting down from 100 as the drive wears
+    pub value: u64,
+    pub worst: u64,
+    pub threshold: u64,
+    pub raw: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsdHealth {
+    pub device: String,
+    pub model: Option<String>,
+    // Identifies the drive across readings; device names can change between boots
+    pub serial_number: Option<String>,
+    pub protocol: SsdProtocol,
+    // None where the OS doesn't say
+    pub trim_enabled: Option<bool>,
+    pub bytes_written: Option<u64>,
+    // Vendor estimate of life used; NVMe only, and may exceed 100
+    pub percentage_used: Option<u8>,
+    pub available_spare_percent: Option<u8>,
+    // ATA drives only
+    pub wear_attributes: Vec<WearAttribute>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsdReport {
+    // False when smartmontools isn't installed
+    pub supported: bool,
+    pub drives: Vec<SsdHealth>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsdWearPoint {
+    pub drive: String,
+    pub timestamp: i64,
+    pub percentage_used: Option<u8>,
+    pub bytes_written: Option<u64>,
+}
+
+// None when smartctl isn't installed. Exit status bits 0 and 1 mean the command line or the
+// device couldn't be used; the higher bits only describe the drive's health.
+fn smartctl(args: &[&str]) -> Result<Option<Value>, String> {
+    let output = match os_command::command("smartctl").args(args).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to run smartctl: {}", e)),
+    };
+    let report: Value = serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    if output.status.code().is_none_or(|code| code & 0b11 != 0) {
+        let messages: Vec<&str> = report["smartctl"]["messages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|message| message["string"].as_str())
+            .collect();
+        return Err(format!("smartctl failed: {}", messages.join("; ")));
+    }
+    Ok(Some(report))
+}
+
+// Devices whose kernel queue accepts discards; Linux only
+fn linux_trim(device: &str) -> Option<bool> {
+    let name = device.strip_prefix("/dev/")?;
+    let path = format!("/sys/block/{}/queue/discard_max_bytes", name);
+    let max: u64 = std::fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(max > 0)
+}
+
+// Windows sends TRIM to every drive or to none: `NTFS DisableDeleteNotify = 0` means enabled
+fn windows_trim() -> Option<bool> {
+    let output = run_text("fsutil", &["behavior", "query", "DisableDeleteNotify"]).ok()?;
+    let line = output.lines().find(|line| line.contains("NTFS"))?;
+    let value = line.split('=').nth(1)?.split_whitespace().next()?;
+    Some(value == "0")
+}
+
+// `system_profiler` reports TRIM per drive, keyed by BSD name (`disk0`)
+fn macos_trim() -> Vec<(String, bool)> {
+    let mut drives = Vec::new();
+    for (data_type, key) in [
+        ("SPNVMeDataType", "spnvme_trim_support"),
+        ("SPSerialATADataType", "spsata_trim_support"),
+    ] {
+        let Ok(output) = run_text("system_profiler", &["-json", data_type]) else {
+            continue;
+        };
+        let Ok(report) = serde_json::from_str::<Value>(&output) else {
+            continue;
+        };
+        for controller in report[data_type].as_array().into_iter().flatten() {
+            for drive in controller["_items"].as_array().into_iter().flatten() {
+                if let (Some(name), Some(trim)) = (drive["bsd_name"].as_str(), drive[key].as_str())
+                {
+                    drives.push((name.to_string(), trim == "Yes"));
+                }
+            }
+        }
+    }
+    drives
+}
+
+fn trim_lookup() -> impl Fn(&str) -> Option<bool> {
+    let windows = cfg!(target_os = "windows").then(windows_trim).flatten();
+    let macos = if cfg!(target_os = "macos") {
+        macos_trim()
+    } else {
+        Vec::new()
+    };
+    move |device: &str| {
+        if cfg!(target_os = "linux") {
+            linux_trim(device)
+        } else if cfg!(target_os = "windows") {
+            windows
+        } else {
+            let name = device.trim_start_matches("/dev/");
+            macos
+                .iter()
+                .find(|(drive, _)| drive == name)
+                .map(|&(_, trim)| trim)
+        }
+    }
+}
+
+fn nvme_health(device: &str, report: &Value, trim: Option<bool>) -> SsdHealth {
+    let log = &report["nvme_smart_health_information_log"];
+    let percent = |key: &str| log[key].as_u64().map(|value| value.min(255) as u8);
+    SsdHealth {
+        device: device.to_string(),
+        model: report["model_name"].as_str().map(str::to_string),
+        serial_number: report["serial_number"].as_str().map(str::to_string),
+        protocol: SsdProtocol::Nvme,
+        trim_enabled: trim,
+        bytes_written: log["data_units_written"]
+            .as_u64()
+            .map(|units| units.saturating_mul(NVME_DATA_UNIT)),
+        percentage_used: percent("percentage_used"),
+        available_spare_percent: percent("available_spare"),
+        wear_attributes: Vec::new(),
+    }
+}
+
+// Attribute 241 counts LBAs on most drives; some vendors count GiB or 32 MiB units instead,
+// which shows in the attribute name
+fn ata_bytes_written(attribute: &Value, block_size: u64) -> Option<u64> {
+    let raw = attribute["raw"]["value"].as_u64()?;
+    let name = attribute["name"].as_str().unwrap_or_default();
+    let unit = if name.contains("32MiB") {
+        32 * 1024 * 1024
+    } else if name.contains("GiB") {
+        1024 * 1024 * 1024
+    } else {
+        block_size
+    };
+    Some(raw.saturating_mul(unit))
+}
+
+// None for spinning drives
+fn ata_health(device: &str, report: &Value, trim: Option<bool>) -> Option<SsdHealth> {
+    if report["rotation_rate"].as_u64() != Some(0) {
+        return None;
+    }
+    let attributes = report["ata_smart_attributes"]["table"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let block_size = report["logical_block_size"].as_u64().unwrap_or(512);
+
+    let bytes_written = attributes
+        .iter()
+        .find(|attribute| attribute["id"].as_u64() == Some(TOTAL_WRITTEN_ATTRIBUTE))
+        .and_then(|attribute| ata_bytes_written(attribute, block_size));
+    let wear_attributes = attributes
+        .iter()
+        .filter(|attribute| {
+            attribute["id"]
+                .as_u64()
+                .is_some_and(|id| WEAR_ATTRIBUTES.contains(&id))
+        })
+        .map(|attribute| WearAttribute {
+            id: attribute["id"].as_u64().unwrap_or_default(),
+            name: attribute["name"].as_str().unwrap_or_default().to_string(),
+            value: attribute["value"].as_u64().unwrap_or_default(),
+            worst: attribute["worst"].as_u64().unwrap_or_default(),
+            threshold: attribute["thresh"].as_u64().unwrap_or_default(),
+            raw: attribute["raw"]["value"].as_u64().unwrap_or_default(),
+        })
+        .collect();
+
+    Some(SsdHealth {
+        device: device.to_string(),
+        model: report["model_name"].as_str().map(str::to_string),
+        serial_number: report["serial_number"].as_str().map(str::to_string),
+        protocol: SsdProtocol::Ata,
+        trim_enabled: trim,
+        bytes_written,
+        percentage_used: None,
+        available_spare_percent: None,
+        wear_attributes,
+    })
+}
+
+fn read_drives() -> Result<SsdReport, CommandError> {
+    let Some(scan) = smartctl(&["--scan", "-j"])? else {
+        return Ok(SsdReport {
+            supported: false,
+            drives: Vec::new(),
+        });
+    };
+    let trim = trim_lookup();
+
+    let mut drives = Vec::new();
+    for entry in scan["devices"].as_array().into_iter().flatten() {
+        let (Some(device), Some(kind)) = (entry["name"].as_str(), entry["type"].as_str()) else {
+            continue;
+        };
+        let report = match smartctl(&["-j", "-i", "-A", "-d", kind, device]) {
+            Ok(Some(report)) => report,
+            Ok(None) => continue,
+            Err(e) if e.contains("Permission denied") => {
+                return Err(CommandError::new(
+                    ErrorCode::PermissionDenied,
+                    "ssd.permissionDenied",
+                ));
+            }
+            Err(e) => {
+                eprintln!("⚠ Warning: Skipping {}: {}", device, e);
+                continue;
+            }
+        };
+        if report["device"]["protocol"].as_str() == Some("NVMe") {
+            drives.push(nvme_health(device, &report, trim(device)));
+        } else {
+            drives.extend(ata_health(device, &report, trim(device)));
+        }
+    }
+    Ok(SsdReport {
+        supported: true,
+        drives,
+    })
+}
+
+fn record(history: &History, drives: &[SsdHealth]) -> Result<(), String> {
+    let now = unix_timestamp() as i64;
+    let conn = history.conn()?;
+    for drive in drives {
+        conn.execute(
+            "INSERT INTO ssd_wear (drive, timestamp, percentage_used, bytes_written)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                drive.serial_number.as_deref().unwrap_or(&drive.device),
+                now,
+                drive.percentage_used,
+                drive.bytes_written.map(|bytes| bytes as i64)
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Background job storing a wear reading for each SSD; skipped without smartmontools
+pub fn start_recording(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        match read_drives() {
+            Ok(report) => {
+                if let Err(e) = record(&app.state::<History>(), &report.drives) {
+                    eprintln!("Recording SSD wear failed: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Reading SSD health failed: {}", e),
+        }
+        battery_saver::wait(&app, RECORD_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub async fn get_ssd_health() -> Result<SsdReport, CommandError> {
+    tauri::async_runtime::spawn_blocking(read_drives)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Stored readings from the last `days` days (a year by default), oldest first. Drives are
+// identified by serial number, or by device name when they don't report one.
+#[tauri::command]
+pub fn get_ssd_wear_history(
+    days: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<SsdWearPoint>, CommandError> {
+    let since = unix_timestamp() as i64 - days.unwrap_or(DEFAULT_HISTORY_DAYS) as i64 * 86400;
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT drive, timestamp, percentage_used, bytes_written
+             FROM ssd_wear WHERE timestamp >= ?1 ORDER BY timestamp, drive",
+        )
+        .map_err(|e| e.to_string())?;
+    let points = statement
+        .query_map(params![since], |row| {
+            Ok(SsdWearPoint {
+                drive: row.get(0)?,
+                timestamp: row.get(1)?,
+                percentage_used: row.get(2)?,
+                bytes_written: row.get::<_, Option<i64>>(3)?.map(|bytes| bytes as u64),
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(points)
+}
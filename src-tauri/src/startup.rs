@@ -0,0 +1,122 @@
+// How the app starts: with the dashboard visible, minimized, hidden with only a tray icon
+// while the samplers keep recording, or fullscreen in kiosk mode. `show_dashboard` (also the tray's menu and left click)
+// brings the window back on demand.
+
+use serde::{Deserialize, Serialize};
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, State, WindowEvent};
+
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::kiosk;
+
+pub const MAIN_WINDOW: &str = "main";
+const SHOW_ITEM: &str = "show";
+const QUIT_ITEM: &str = "quit";
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StartupMode {
+    #[default]
+    Normal,
+    Minimized,
+    // No window until the user asks for it; closing the window hides it again
+    TrayOnly,
+    // Fullscreen, rotating through the kiosk views
+    Kiosk,
+}
+
+fn show(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        window.show()?;
+        window.unminimize()?;
+        window.set_focus()?;
+    }
+    Ok(())
+}
+
+fn build_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, SHOW_ITEM, "Show dashboard", true, None::<&str>)?,
+            &MenuItem::with_id(app, QUIT_ITEM, "Quit", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut tray = TrayIconBuilder::new()
+        .tooltip("System Info")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            SHOW_ITEM => {
+                let _ = show(app);
+            }
+            QUIT_ITEM => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                let _ = show(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    Ok(())
+}
+
+// Apply the configured mode to the main window; called once from setup
+pub fn apply(app: &AppHandle, mode: StartupMode) -> tauri::Result<()> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return Ok(());
+    };
+
+    match mode {
+        StartupMode::Normal => {}
+        StartupMode::Minimized => window.minimize()?,
+        StartupMode::TrayOnly => {
+            build_tray(app)?;
+            window.hide()?;
+
+            // Closing the dashboard sends it back to the tray instead of quitting
+            let hidden = window.clone();
+            window.on_window_event(move |event| {
+                if let WindowEvent::CloseRequested { api, .. } = event {
+                    api.prevent_close();
+                    let _ = hidden.hide();
+                }
+            });
+        }
+        StartupMode::Kiosk => {
+            if let Err(e) = kiosk::enter(app) {
+                eprintln!("⚠ Warning: Kiosk mode could not start: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn show_dashboard(app: AppHandle) -> Result<(), CommandError> {
+    Ok(show(&app).map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub fn get_startup_mode(config: State<ConfigState>) -> Result<StartupMode, CommandError> {
+    Ok(config.get()?.startup_mode)
+}
+
+// Takes effect the next time the app starts
+#[tauri::command]
+pub fn set_startup_mode(mode: StartupMode, config: State<ConfigState>) -> Result<(), CommandError> {
+    config.update(|config| config.startup_mode = mode)?;
+    Ok(())
+}
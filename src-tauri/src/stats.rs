@@ -0,0 +1,201 @@
+// Aggregation kernels for the history rollups.
+// Values are processed in fixed-width lanes with independent accumulators so the compiler
+// emits packed SIMD min/max/add instructions on every target without nightly `std::simd`.
+
+// 8 f64 lanes fill an AVX-512 register, or two AVX / four SSE2 / NEON registers
+const LANES: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+}
+
+impl Summary {
+    pub fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+// Branch-free select so the lane loop lowers to minpd/maxpd
+#[inline(always)]
+fn lane_min(a: f64, b: f64) -> f64 {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline(always)]
+fn lane_max(a: f64, b: f64) -> f64 {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+// Single pass min/max/sum over the values; None for an empty slice
+pub fn summarize(values: &[f64]) -> Option<Summary> {
+    let first = *values.first()?;
+    let mut min = [first; LANES];
+    let mut max = [first; LANES];
+    let mut sum = [0.0; LANES];
+
+    let chunks = values.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        for lane in 0..LANES {
+            min[lane] = lane_min(min[lane], chunk[lane]);
+            max[lane] = lane_max(max[lane], chunk[lane]);
+            sum[lane] += chunk[lane];
+        }
+    }
+
+    let mut summary = Summary {
+        count: values.len(),
+        min: min.into_iter().fold(first, lane_min),
+        max: max.into_iter().fold(first, lane_max),
+        sum: sum.into_iter().sum(),
+    };
+    for &value in remainder {
+        summary.min = lane_min(summary.min, value);
+        summary.max = lane_max(summary.max, value);
+        summary.sum += value;
+    }
+    Some(summary)
+}
+
+// Straightforward one-accumulator version, kept as the reference for the benchmark
+pub fn summarize_scalar(values: &[f64]) -> Option<Summary> {
+    let first = *values.first()?;
+    let mut summary = Summary {
+        count: values.len(),
+        min: first,
+        max: first,
+        sum: 0.0,
+    };
+    for &value in values {
+        summary.min = summary.min.min(value);
+        summary.max = summary.max.max(value);
+        summary.sum += value;
+    }
+    Some(summary)
+}
+
+// Linearly interpolated percentile (0-100) using quickselect; reorders `values` in place
+pub fn percentile(values: &mut [f64], percent: f64) -> Option<f64> {
+    if values.is_empty() || !(0.0..=100.0).contains(&percent) {
+        return None;
+    }
+
+    let rank = percent / 100.0 * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let (_, &mut low, above) = values.select_nth_unstable_by(lower, f64::total_cmp);
+    if lower as f64 == rank || above.is_empty() {
+        return Some(low);
+    }
+
+    // The next order statistic is the smallest value above the pivot
+    let high = above.iter().copied().min_by(f64::total_cmp)?;
+    Some(low + (high - low) * (rank - lower as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Percentile of an already sorted slice, interpolating between the neighbouring ranks
+    fn sorted_percentile(sorted: &[f64], percent: f64) -> f64 {
+        let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+    }
+
+    // Whole numbers, so every summation order gives the same sum
+    fn values(len: usize) -> Vec<f64> {
+        (0..len).map(|i| ((i * 7919) % 23) as f64 - 11.0).collect()
+    }
+
+    #[test]
+    fn percentile_interpolates_between_ranks() {
+        let mut values = vec![40.0, 10.0, 30.0, 20.0];
+        // Rank 1.5 of 10, 20, 30, 40
+        assert_eq!(percentile(&mut values, 50.0), Some(25.0));
+        // Rank 0.75
+        assert_eq!(percentile(&mut values, 25.0), Some(17.5));
+        // Rank 2, exactly on a value
+        assert_eq!(percentile(&mut values, 100.0 * 2.0 / 3.0), Some(30.0));
+    }
+
+    #[test]
+    fn percentile_bounds_are_min_and_max() {
+        let mut values = vec![3.0, -1.0, 7.5, 2.0, 7.5];
+        assert_eq!(percentile(&mut values, 0.0), Some(-1.0));
+        assert_eq!(percentile(&mut values, 100.0), Some(7.5));
+        assert_eq!(percentile(&mut [4.0], 0.0), Some(4.0));
+        assert_eq!(percentile(&mut [4.0], 100.0), Some(4.0));
+    }
+
+    #[test]
+    fn percentile_rejects_out_of_range_input() {
+        let mut values = vec![1.0, 2.0];
+        assert_eq!(percentile(&mut values, -0.1), None);
+        assert_eq!(percentile(&mut values, 100.1), None);
+        assert_eq!(percentile(&mut values, f64::NAN), None);
+        assert_eq!(percentile(&mut [], 50.0), None);
+    }
+
+    #[test]
+    fn summarize_empty_is_none() {
+        assert_eq!(summarize(&[]), None);
+        assert_eq!(summarize_scalar(&[]), None);
+    }
+
+    #[test]
+    fn summarize_matches_scalar_around_lane_boundaries() {
+        // Below one chunk, exactly one or two chunks, and every remainder length in between
+        for len in 1..=2 * LANES + 1 {
+            let values = values(len);
+            let summary = summarize(&values).unwrap();
+            assert_eq!(Some(summary), summarize_scalar(&values), "len {}", len);
+            assert_eq!(summary.count, len);
+        }
+    }
+
+    #[test]
+    fn summarize_finds_extremes_in_remainder() {
+        // The extremes sit past the last full chunk, where only the remainder loop sees them
+        let mut values = vec![0.0; LANES + 2];
+        values[LANES] = -5.0;
+        values[LANES + 1] = 5.0;
+        let summary = summarize(&values).unwrap();
+        assert_eq!((summary.min, summary.max, summary.sum), (-5.0, 5.0, 0.0));
+    }
+
+    proptest! {
+        #[test]
+        fn percentile_matches_sorted_reference(
+            mut values in prop::collection::vec(-1e6f64..1e6, 1..200),
+            percent in 0.0f64..=100.0,
+        ) {
+            let mut sorted = values.clone();
+            sorted.sort_by(f64::total_cmp);
+            prop_assert_eq!(
+                percentile(&mut values, percent),
+                Some(sorted_percentile(&sorted, percent))
+            );
+        }
+
+        #[test]
+        fn summarize_matches_scalar(
+            values in prop::collection::vec((-1000i32..1000).prop_map(f64::from), 1..100),
+        ) {
+            prop_assert_eq!(summarize(&values), summarize_scalar(&values));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Which windows listen to which event streams.
+// Windows call `subscribe_events` after registering their listeners, and samplers that exist
+// only to feed an event stream block in `wait_for_listeners` while nobody is subscribed, so an
+// idle app does no background work for them. A closed window drops its subscriptions.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Condvar, Mutex};
+use tauri::{State, Window};
+
+use crate::capture::CAPTURE_USAGE_CHANGED_EVENT;
+use crate::error::{CommandError, ErrorCode};
+use crate::identity::IDENTITY_CHANGED_EVENT;
+
+// Streams that pause without subscribers
+const SUBSCRIBABLE_EVENTS: [&str; 2] = [IDENTITY_CHANGED_EVENT, CAPTURE_USAGE_CHANGED_EVENT];
+
+#[derive(Default)]
+pub struct EventSubscriptions {
+    // Event name -> labels of the windows listening to it
+    listeners: Mutex<BTreeMap<String, BTreeSet<String>>>,
+    changed: Condvar,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamSubscribers {
+    pub event: &'static str,
+    pub windows: Vec<String>,
+}
+
+impl EventSubscriptions {
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, BTreeSet<String>>)) {
+        if let Ok(mut listeners) = self.listeners.lock() {
+            change(&mut listeners);
+            listeners.retain(|_, windows| !windows.is_empty());
+        }
+        self.changed.notify_all();
+    }
+
+    pub fn remove_window(&self, window: &str) {
+        self.update(|listeners| {
+            for windows in listeners.values_mut() {
+                windows.remove(window);
+            }
+        });
+    }
+
+    // Block the calling sampler until at least one window listens to `event`
+    pub fn wait_for_listeners(&self, event: &str) {
+        let Ok(listeners) = self.listeners.lock() else {
+            return;
+        };
+        let _listeners = self
+            .changed
+            .wait_while(listeners, |listeners| !listeners.contains_key(event));
+    }
+}
+
+fn check_events(events: &[String]) -> Result<(), CommandError> {
+    match events
+        .iter()
+        .find(|event| !SUBSCRIBABLE_EVENTS.contains(&event.as_str()))
+    {
+        Some(event) => {
+            Err(CommandError::new(ErrorCode::InvalidInput, "events.unknown").with("event", event))
+        }
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn subscribe_events(
+    events: Vec<String>,
+    window: Window,
+    subscriptions: State<EventSubscriptions>,
+) -> Result<(), CommandError> {
+    check_events(&events)?;
+    subscriptions.update(|listeners| {
+        for event in events {
+            listeners
+                .entry(event)
+                .or_default()
+                .insert(window.label().to_string());
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unsubscribe_events(
+    events: Vec<String>,
+    window: Window,
+    subscriptions: State<EventSubscriptions>,
+) -> Result<(), CommandError> {
+    check_events(&events)?;
+    subscriptions.update(|listeners| {
+        for event in events {
+            if let Some(windows) = listeners.get_mut(&event) {
+                windows.remove(window.label());
+            }
+        }
+    });
+    Ok(())
+}
+
+// Every stream that can be subscribed to, with the windows currently listening
+#[tauri::command]
+pub fn get_event_subscriptions(
+    subscriptions: State<EventSubscriptions>,
+) -> Result<Vec<StreamSubscribers>, CommandError> {
+    let listeners = subscriptions
+        .listeners
+        .lock()
+        .map_err(|_| "Subscriptions lock poisoned")?;
+    Ok(SUBSCRIBABLE_EVENTS
+        .iter()
+        .map(|&event| StreamSubscribers {
+            event,
+            windows: listeners
+                .get(event)
+                .map(|windows| windows.iter().cloned().collect())
+                .unwrap_or_default(),
+        })
+        .collect())
+}
@@ -0,0 +1,206 @@
+// One zip with everything support needs to look into a problem, so end users have a single
+// button to press. Besides the report and diagnostics of an exported bundle (so `import_report`
+// opens it too) it holds a fresh preflight run, the library load trace, the last hour of
+// history at minute resolution and the most recent process, integrity and inventory events.
+// The app writes no log files, so those event logs stand in for them. Before anything is
+// written, the `[privacy]` settings are applied to every string in the bundle.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use tauri::{AppHandle, Manager};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::bundle::{
+    write_entry, BundleManifest, BUNDLE_FORMAT_VERSION, DIAGNOSTICS_FILE, MANIFEST_FILE,
+    REPORT_FILE,
+};
+use crate::config::ConfigState;
+use crate::display;
+use crate::error::CommandError;
+use crate::export_history::{self, ExportKind};
+use crate::history::{Aggregation, GroupBy, History, TimeRange};
+use crate::report::{collect_report, unix_timestamp};
+use crate::{integrity, inventory, preflight, watchlist, CppLibrary};
+
+const PREFLIGHT_FILE: &str = "preflight.json";
+const LOAD_TRACE_FILE: &str = "load-trace.json";
+const HISTORY_FILE: &str = "history.json";
+const EVENTS_FILE: &str = "events.json";
+
+const HISTORY_SECONDS: i64 = 3600;
+// Newest entries taken from each event log
+const EVENT_LIMIT: u32 = 200;
+
+// What is removed from support bundles before they leave the machine
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PrivacyConfig {
+    // Replace the computer name with a hash, so bundles from one machine still match up
+    pub redact_computer_name: bool,
+    // Shorten paths in the user's home directory to `~`, hiding the user name
+    pub redact_home_directory: bool,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        PrivacyConfig {
+            redact_computer_name: true,
+            redact_home_directory: true,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Events {
+    pub processes: Value,
+    pub integrity: Value,
+    pub inventory: Value,
+}
+
+fn anonymized_name(name: &str) -> String {
+    let digest = Sha256::digest(name.as_bytes());
+    let hex: String = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("host-{}", hex)
+}
+
+fn redact(value: &mut Value, replacements: &[(String, String)]) {
+    match value {
+        Value::String(text) => {
+            for (from, to) in replacements {
+                if text.contains(from.as_str()) {
+                    *text = text.replace(from.as_str(), to);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, replacements)),
+        Value::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| redact(field, replacements)),
+        _ => {}
+    }
+}
+
+// Find and replace pairs for the enabled privacy settings, longest first
+fn replacements(
+    app: &AppHandle,
+    privacy: &PrivacyConfig,
+    computer_name: Option<&str>,
+) -> Vec<(String, String)> {
+    let mut replacements = Vec::new();
+    if privacy.redact_home_directory {
+        if let Ok(home) = app.path().home_dir() {
+            let home = home.display().to_string();
+            if !home.is_empty() {
+                replacements.push((home, "~".to_string()));
+            }
+        }
+    }
+    if privacy.redact_computer_name {
+        if let Some(name) = computer_name.filter(|name| !name.is_empty()) {
+            replacements.push((name.to_string(), anonymized_name(name)));
+        }
+    }
+    replacements.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+    replacements
+}
+
+fn to_value(value: impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|e| Value::String(e.to_string()))
+}
+
+// Minute averages of every metric over the last hour
+fn recent_history(history: &History) -> Result<Value, String> {
+    let to = unix_timestamp() as i64;
+    let range = TimeRange {
+        from: to - HISTORY_SECONDS,
+        to,
+    };
+    let results: Vec<Value> = history
+        .metrics_with_prefix("")?
+        .iter()
+        .filter_map(|metric| {
+            history
+                .query(metric, range, Aggregation::Avg, GroupBy::Minute)
+                .map_err(|e| eprintln!("⚠ Warning: Skipping {} in support bundle: {}", metric, e))
+                .ok()
+        })
+        .filter(|result| !result.points.is_empty())
+        .map(to_value)
+        .collect();
+    Ok(Value::Array(results))
+}
+
+pub fn recent_events(app: &AppHandle) -> Events {
+    let events =
+        |result: Result<Value, CommandError>| result.unwrap_or_else(|e| Value::String(e.message));
+    Events {
+        processes: events(
+            watchlist::get_process_events(None, Some(EVENT_LIMIT), app.state()).map(to_value),
+        ),
+        integrity: events(
+            integrity::get_integrity_events(Some(EVENT_LIMIT), app.state()).map(to_value),
+        ),
+        inventory: events(
+            inventory::get_inventory_changes(None, Some(EVENT_LIMIT), app.state()).map(to_value),
+        ),
+    }
+}
+
+fn write_bundle(app: &AppHandle, path: &str) -> Result<(), CommandError> {
+    let config = app.state::<ConfigState>();
+    let privacy = config.get()?.privacy.clone();
+    let lib_state = app.state::<CppLibrary>();
+    let history = app.state::<History>();
+
+    let (report, diagnostics) = collect_report(&lib_state, &display::current(&config));
+    let replacements = replacements(app, &privacy, report.computer_name.as_deref());
+    let mut entries = vec![
+        (REPORT_FILE, to_value(&report)),
+        (DIAGNOSTICS_FILE, to_value(&diagnostics)),
+        (PREFLIGHT_FILE, to_value(preflight::run_checks(&lib_state))),
+        (LOAD_TRACE_FILE, to_value(lib_state.trace())),
+        (HISTORY_FILE, recent_history(&history)?),
+        (EVENTS_FILE, to_value(recent_events(app))),
+    ];
+    for (_, value) in &mut entries {
+        redact(value, &replacements);
+    }
+
+    let file = File::create(path).map_err(|e| CommandError::create_failed(path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        created_at: unix_timestamp(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        encrypted: false,
+        files: entries.iter().map(|(name, _)| name.to_string()).collect(),
+    };
+    write_entry(&mut zip, MANIFEST_FILE, &manifest, options)?;
+    for (name, value) in &entries {
+        write_entry(&mut zip, name, value, options)?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+
+    export_history::record_or_warn(
+        &history,
+        ExportKind::Bundle,
+        Some(path),
+        "Support bundle",
+        None,
+    );
+    println!("✓ Wrote support bundle to {}", path);
+    Ok(())
+}
+
+// Collect diagnostics, the report, recent history and events into one zip at `path`
+#[tauri::command]
+pub async fn create_support_bundle(path: String, app: AppHandle) -> Result<(), CommandError> {
+    tauri::async_runtime::spawn_blocking(move || write_bundle(&app, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,88 @@
+// Custom report layouts. Each `<name>.hbs` file in the `templates` folder of the app config
+// directory is a Handlebars template rendered with `report` and `diagnostics` (the same data as
+// in an exported bundle), so an organization can lay out its own report, e.g.
+// `{{report.computerName}}: {{report.formatted.totalMemory}}`. HTML output escapes every value;
+// Markdown and plain text output leave them as they are.
+
+use handlebars::Handlebars;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::ConfigState;
+use crate::display;
+use crate::error::{CommandError, ErrorCode};
+use crate::report::{collect_report, Diagnostics, SystemReport};
+use crate::CppLibrary;
+
+const TEMPLATE_DIR: &str = "templates";
+const TEMPLATE_EXTENSION: &str = "hbs";
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+    Text,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateContext {
+    report: SystemReport,
+    diagnostics: Diagnostics,
+}
+
+fn template_path(app: &AppHandle, name: &str) -> Result<PathBuf, CommandError> {
+    // A bare name, so templates can't be read from outside the folder
+    if name.is_empty() || name.contains(['/', '\\', '.']) {
+        return Err(
+            CommandError::new(ErrorCode::InvalidInput, "template.invalidName").with("name", name),
+        );
+    }
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join(TEMPLATE_DIR);
+    Ok(dir.join(format!("{}.{}", name, TEMPLATE_EXTENSION)))
+}
+
+// Render the report through the template `template` from the templates folder
+#[tauri::command]
+pub fn render_report(
+    template: String,
+    format: ReportFormat,
+    app: AppHandle,
+    lib_state: State<CppLibrary>,
+    config: State<ConfigState>,
+) -> Result<String, CommandError> {
+    let path = template_path(&app, &template)?;
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| CommandError::open_failed(&path.display().to_string(), e))?;
+
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    if !matches!(format, ReportFormat::Html) {
+        handlebars.register_escape_fn(handlebars::no_escape);
+    }
+    handlebars
+        .register_template_string(&template, source)
+        .map_err(|e| {
+            CommandError::new(ErrorCode::InvalidInput, "template.invalid")
+                .with("name", &template)
+                .with("detail", e)
+        })?;
+
+    let (report, diagnostics) = collect_report(&lib_state, &display::current(&config));
+    let context = TemplateContext {
+        report,
+        diagnostics,
+    };
+    let rendered = handlebars.render(&template, &context).map_err(|e| {
+        CommandError::new(ErrorCode::InvalidInput, "template.renderFailed")
+            .with("name", &template)
+            .with("detail", e)
+    })?;
+    Ok(rendered)
+}
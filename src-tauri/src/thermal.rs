@@ -0,0 +1,141 @@
+// Temperature sensors sampled into history, and per-sensor series for a heatmap.
+// Each sensor is stored as its own metric (`thermal.<label>`) in °C; conversion to the
+// preferred unit happens when the history is read back.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use sysinfo::Components;
+use tauri::{AppHandle, State};
+
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::display;
+use crate::error::{CommandError, ErrorCode};
+use crate::exporter;
+use crate::history::{Aggregation, GroupBy, History, TimeRange};
+
+pub const METRIC_PREFIX: &str = "thermal.";
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+// Heatmap-ready thermal history: one row per sensor, one column per timestamp.
+// `values[row][column]` is None where the sensor has no samples in that bucket.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThermalHistory {
+    pub unit: String,
+    pub aggregation: Aggregation,
+    pub group_by: GroupBy,
+    pub timestamps: Vec<i64>,
+    pub sensors: Vec<SensorSeries>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorSeries {
+    pub sensor: String,
+    pub values: Vec<Option<f64>>,
+}
+
+pub fn read_temperatures(components: &Components) -> Vec<(String, f64)> {
+    components
+        .iter()
+        .filter_map(|component| {
+            let celsius = component.temperature()?;
+            // Some drivers report NaN or 0 for sensors that aren't wired up
+            (celsius.is_finite() && celsius != 0.0).then(|| {
+                (
+                    format!("{}{}", METRIC_PREFIX, component.label()),
+                    celsius as f64,
+                )
+            })
+        })
+        .collect()
+}
+
+// Background job sampling every temperature sensor into history
+pub fn start_sampling(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut components = Components::new_with_refreshed_list();
+        if components.is_empty() {
+            println!("No temperature sensors found, thermal history disabled");
+            return;
+        }
+
+        loop {
+            let samples = read_temperatures(&components);
+            if !samples.is_empty() {
+                exporter::publish(&app, &samples);
+            }
+
+            battery_saver::wait(&app, SAMPLE_INTERVAL);
+            components.refresh(false);
+        }
+    });
+}
+
+// Every temperature sensor over `range`, bucketed by `group_by` (minute by default) and
+// aggregated with `aggregation` (max by default, so short spikes stay visible)
+#[tauri::command]
+pub fn get_thermal_history(
+    range: TimeRange,
+    aggregation: Option<Aggregation>,
+    group_by: Option<GroupBy>,
+    history: State<History>,
+    config: State<ConfigState>,
+) -> Result<ThermalHistory, CommandError> {
+    if range.from > range.to {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "history.invalidRange",
+        ));
+    }
+    let aggregation = aggregation.unwrap_or(Aggregation::Max);
+    let group_by = group_by.unwrap_or(GroupBy::Minute);
+    let display = display::current(&config);
+
+    let mut series = Vec::new();
+    for metric in history.metrics_with_prefix(METRIC_PREFIX)? {
+        let result = history.query(&metric, range, aggregation, group_by)?;
+        if result.points.is_empty() {
+            continue;
+        }
+        let points: BTreeMap<i64, f64> = result
+            .points
+            .into_iter()
+            .map(|point| (point.timestamp, point.value))
+            .collect();
+        series.push((metric[METRIC_PREFIX.len()..].to_string(), points));
+    }
+
+    // Columns are the union of buckets seen by any sensor
+    let timestamps: Vec<i64> = series
+        .iter()
+        .flat_map(|(_, points)| points.keys().copied())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let sensors = series
+        .into_iter()
+        .map(|(sensor, points)| SensorSeries {
+            sensor,
+            values: timestamps
+                .iter()
+                .map(|timestamp| {
+                    points
+                        .get(timestamp)
+                        .map(|&celsius| display.convert(celsius, "°C").0)
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(ThermalHistory {
+        unit: display.convert(0.0, "°C").1.to_string(),
+        aggregation,
+        group_by,
+        timestamps,
+        sensors,
+    })
+}
@@ -0,0 +1,55 @@
+// Time since boot, when the machine booted and the 1/5/15-minute load averages.
+// Answered by the systemapi library's `GetUptimeInfo` (1.3 and later) or by sysinfo. Windows
+// keeps no load averages, so they are left out there.
+
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+use tauri::{AppHandle, Manager};
+
+use crate::error::CommandError;
+use crate::CppLibrary;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeInfo {
+    pub uptime_seconds: u64,
+    // Unix timestamp in seconds
+    pub boot_time: i64,
+    // None on Windows
+    pub load_average: Option<LoadAverage>,
+}
+
+// The Rust implementation behind `RustProvider::uptime_info`
+pub fn read_os_uptime() -> Result<UptimeInfo, String> {
+    let boot_time = System::boot_time();
+    if boot_time == 0 {
+        return Err("Failed to read boot time".to_string());
+    }
+    let load = System::load_average();
+    Ok(UptimeInfo {
+        uptime_seconds: System::uptime(),
+        boot_time: boot_time as i64,
+        load_average: (!cfg!(target_os = "windows")).then_some(LoadAverage {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        }),
+    })
+}
+
+#[tauri::command]
+pub async fn get_uptime_info(app: AppHandle) -> Result<UptimeInfo, CommandError> {
+    crate::call_blocking(app, "get_uptime_info", |app| {
+        app.state::<CppLibrary>()
+            .call("get_uptime_info", |provider| provider.uptime_info())
+    })
+    .await
+}
@@ -0,0 +1,222 @@
+// Disk space used by each local user's home directory, for admins of shared workstations.
+// Walking every home directory can take minutes, so it runs as a background scan: the first
+// `get_user_disk_usage` call (or one with `rescan`) starts it, progress is emitted as
+// `user-disk-usage-progress`, and later calls return the progress and the last finished result.
+// Other users' files are only readable with administrator rights; unreadable directories are
+// counted and skipped.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::error::CommandError;
+use crate::report::unix_timestamp;
+
+pub const USER_DISK_USAGE_PROGRESS_EVENT: &str = "user-disk-usage-progress";
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+// Regular accounts on Linux start at this UID
+const FIRST_USER_UID: u32 = 1000;
+const NOBODY_UID: u32 = 65534;
+// Profile folders under C:\Users and /Users that don't belong to a person
+const SHARED_PROFILES: [&str; 5] = ["Public", "Default", "Default User", "All Users", "Shared"];
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUsage {
+    pub user: String,
+    pub home: String,
+    pub bytes: u64,
+    pub files: u64,
+    // Directories skipped for lack of permission; the totals are a lower bound when non-zero
+    pub unreadable_dirs: u64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProgress {
+    // User being scanned
+    pub user: String,
+    pub users_done: usize,
+    pub users_total: usize,
+    pub bytes_scanned: u64,
+    pub files_scanned: u64,
+    pub done: bool,
+}
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserDiskScan {
+    pub scanning: bool,
+    pub progress: Option<ScanProgress>,
+    // From the last finished scan, largest first
+    pub users: Vec<UserUsage>,
+    pub finished_at: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct UserDiskUsage {
+    scan: Mutex<UserDiskScan>,
+}
+
+// (user, home directory) for each local account with a home on disk
+fn home_directories() -> Vec<(String, PathBuf)> {
+    if cfg!(target_os = "linux") {
+        let passwd = fs::read_to_string("/etc/passwd").unwrap_or_default();
+        return passwd
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(':').collect();
+                let [user, _, uid, _, _, home, ..] = fields[..] else {
+                    return None;
+                };
+                let uid: u32 = uid.parse().ok()?;
+                (uid >= FIRST_USER_UID && uid != NOBODY_UID && Path::new(home).is_dir())
+                    .then(|| (user.to_string(), PathBuf::from(home)))
+            })
+            .collect();
+    }
+
+    let root = if cfg!(target_os = "windows") {
+        let drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+        PathBuf::from(format!("{}\\Users", drive))
+    } else {
+        PathBuf::from("/Users")
+    };
+    let mut homes: Vec<(String, PathBuf)> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            )
+        })
+        .filter(|(user, _)| !user.starts_with('.') && !SHARED_PROFILES.contains(&user.as_str()))
+        .collect();
+    homes.sort();
+    homes
+}
+
+struct Walker<'a> {
+    app: &'a AppHandle,
+    progress: ScanProgress,
+    last_emit: Instant,
+}
+
+impl Walker<'_> {
+    fn emit(&mut self) {
+        self.last_emit = Instant::now();
+        if let Err(e) = self
+            .app
+            .emit(USER_DISK_USAGE_PROGRESS_EVENT, &self.progress)
+        {
+            eprintln!(
+                "⚠ Warning: Failed to emit {}: {}",
+                USER_DISK_USAGE_PROGRESS_EVENT, e
+            );
+        }
+        if let Ok(mut scan) = self.app.state::<UserDiskUsage>().scan.lock() {
+            scan.progress = Some(self.progress.clone());
+        }
+    }
+
+    // Symlinks aren't followed, so nothing is counted twice or outside the home directory
+    fn walk(&mut self, dir: &Path, usage: &mut UserUsage) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            usage.unreadable_dirs += 1;
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = fs::symlink_metadata(entry.path()) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                self.walk(&entry.path(), usage);
+            } else if metadata.is_file() {
+                usage.bytes += metadata.len();
+                usage.files += 1;
+                self.progress.bytes_scanned += metadata.len();
+                self.progress.files_scanned += 1;
+                if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+                    self.emit();
+                }
+            }
+        }
+    }
+}
+
+fn scan(app: &AppHandle) -> Vec<UserUsage> {
+    let homes = home_directories();
+    let mut walker = Walker {
+        app,
+        progress: ScanProgress {
+            user: String::new(),
+            users_done: 0,
+            users_total: homes.len(),
+            bytes_scanned: 0,
+            files_scanned: 0,
+            done: false,
+        },
+        last_emit: Instant::now(),
+    };
+
+    let mut users = Vec::new();
+    for (user, home) in homes {
+        walker.progress.user = user.clone();
+        walker.emit();
+        let mut usage = UserUsage {
+            user,
+            home: home.display().to_string(),
+            bytes: 0,
+            files: 0,
+            unreadable_dirs: 0,
+        };
+        walker.walk(&home, &mut usage);
+        walker.progress.users_done += 1;
+        users.push(usage);
+    }
+    walker.progress.user = String::new();
+    walker.progress.done = true;
+    walker.emit();
+
+    users.sort_by_key(|usage| std::cmp::Reverse(usage.bytes));
+    users
+}
+
+// Current scan state; starts a scan when none has finished yet or `rescan` is set, unless one
+// is already running
+#[tauri::command]
+pub fn get_user_disk_usage(
+    rescan: Option<bool>,
+    app: AppHandle,
+    usage: State<UserDiskUsage>,
+) -> Result<UserDiskScan, CommandError> {
+    let mut state = usage
+        .scan
+        .lock()
+        .map_err(|_| "User disk usage lock poisoned")?;
+    if !state.scanning && (state.finished_at.is_none() || rescan.unwrap_or(false)) {
+        state.scanning = true;
+        state.progress = None;
+        std::thread::spawn(move || {
+            let started = Instant::now();
+            let users = scan(&app);
+            if let Ok(mut state) = app.state::<UserDiskUsage>().scan.lock() {
+                state.scanning = false;
+                state.users = users;
+                state.finished_at = Some(unix_timestamp());
+            }
+            println!(
+                "✓ User disk usage scanned in {:.1}s",
+                started.elapsed().as_secs_f64()
+            );
+        });
+    }
+    Ok(state.clone())
+}
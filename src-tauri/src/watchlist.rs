@@ -0,0 +1,256 @@
+// A lightweight service monitor: the configured process names are polled, starts, stops and
+// restarts are recorded in the history database, and `watched-process-stopped` is emitted when
+// a watched process disappears. Names match the process name exactly (`nginx`, `sshd.exe`).
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::battery_saver;
+use crate::config::ConfigState;
+use crate::error::CommandError;
+use crate::history::History;
+use crate::report::unix_timestamp;
+
+pub const PROCESS_STOPPED_EVENT: &str = "watched-process-stopped";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_EVENT_LIMIT: u32 = 100;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessEventKind {
+    Started,
+    Stopped,
+    // Every instance was replaced between two polls
+    Restarted,
+}
+
+impl ProcessEventKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ProcessEventKind::Started => "started",
+            ProcessEventKind::Stopped => "stopped",
+            ProcessEventKind::Restarted => "restarted",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "started" => Some(ProcessEventKind::Started),
+            "stopped" => Some(ProcessEventKind::Stopped),
+            "restarted" => Some(ProcessEventKind::Restarted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEvent {
+    pub name: String,
+    pub timestamp: i64,
+    pub kind: ProcessEventKind,
+}
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedProcess {
+    pub name: String,
+    pub running: bool,
+    pub pids: Vec<u32>,
+    // Restarts and stops seen since the app started
+    pub restarts: u32,
+    pub stops: u32,
+    pub last_change: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct Watchlist {
+    processes: Mutex<BTreeMap<String, WatchedProcess>>,
+}
+
+fn running_pids(system: &System, names: &[String]) -> BTreeMap<String, BTreeSet<u32>> {
+    let mut running: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    for process in system.processes().values() {
+        let name = process.name().to_string_lossy();
+        if let Some(watched) = names.iter().find(|watched| **watched == name) {
+            running
+                .entry(watched.clone())
+                .or_default()
+                .insert(process.pid().as_u32());
+        }
+    }
+    running
+}
+
+// Compare one poll with the previous state, returning what changed
+fn update(
+    processes: &mut BTreeMap<String, WatchedProcess>,
+    names: &[String],
+    running: &BTreeMap<String, BTreeSet<u32>>,
+    now: i64,
+) -> Vec<ProcessEvent> {
+    processes.retain(|name, _| names.contains(name));
+
+    let mut events = Vec::new();
+    for name in names {
+        let pids = running.get(name).cloned().unwrap_or_default();
+        let watched = processes
+            .entry(name.clone())
+            .or_insert_with(|| WatchedProcess {
+                name: name.clone(),
+                running: !pids.is_empty(),
+                pids: pids.iter().copied().collect(),
+                ..WatchedProcess::default()
+            });
+
+        let previous: BTreeSet<u32> = watched.pids.iter().copied().collect();
+        let kind = match (watched.running, pids.is_empty()) {
+            (false, false) => Some(ProcessEventKind::Started),
+            (true, true) => Some(ProcessEventKind::Stopped),
+            (true, false) if previous.is_disjoint(&pids) => Some(ProcessEventKind::Restarted),
+            _ => None,
+        };
+
+        watched.running = !pids.is_empty();
+        watched.pids = pids.into_iter().collect();
+        if let Some(kind) = kind {
+            match kind {
+                ProcessEventKind::Stopped => watched.stops += 1,
+                ProcessEventKind::Restarted => watched.restarts += 1,
+                ProcessEventKind::Started => {}
+            }
+            watched.last_change = Some(now);
+            events.push(ProcessEvent {
+                name: name.clone(),
+                timestamp: now,
+                kind,
+            });
+        }
+    }
+    events
+}
+
+fn save_events(history: &History, events: &[ProcessEvent]) -> Result<(), String> {
+    let conn = history.conn()?;
+    for event in events {
+        conn.execute(
+            "INSERT INTO process_events (name, timestamp, kind) VALUES (?1, ?2, ?3)",
+            params![event.name, event.timestamp, event.kind.as_str()],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn watched_names(app: &AppHandle) -> Vec<String> {
+    app.state::<ConfigState>()
+        .get()
+        .map(|config| config.watchlist.clone())
+        .unwrap_or_default()
+}
+
+// Background job polling the watched processes
+pub fn start_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut system = System::new();
+        loop {
+            let names = watched_names(&app);
+            if !names.is_empty() {
+                system.refresh_processes_specifics(
+                    ProcessesToUpdate::All,
+                    true,
+                    ProcessRefreshKind::nothing(),
+                );
+                let running = running_pids(&system, &names);
+                let now = unix_timestamp() as i64;
+
+                let events = match app.state::<Watchlist>().processes.lock() {
+                    Ok(mut processes) => update(&mut processes, &names, &running, now),
+                    Err(_) => return,
+                };
+                if let Err(e) = save_events(&app.state::<History>(), &events) {
+                    eprintln!("Recording process events failed: {}", e);
+                }
+                for event in events {
+                    if event.kind == ProcessEventKind::Stopped {
+                        eprintln!("⚠ Warning: Watched process {} stopped", event.name);
+                        if let Err(e) = app.emit(PROCESS_STOPPED_EVENT, &event) {
+                            eprintln!("⚠ Warning: Failed to emit {}: {}", PROCESS_STOPPED_EVENT, e);
+                        }
+                    }
+                }
+            }
+            battery_saver::wait(&app, POLL_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_watchlist_status(
+    watchlist: State<Watchlist>,
+    config: State<ConfigState>,
+) -> Result<Vec<WatchedProcess>, CommandError> {
+    let names = config.get()?.watchlist.clone();
+    let processes = watchlist
+        .processes
+        .lock()
+        .map_err(|_| "Watchlist lock poisoned")?;
+    // Names added since the last poll show up as not running until it catches up
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            processes.get(&name).cloned().unwrap_or(WatchedProcess {
+                name,
+                ..WatchedProcess::default()
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn set_watchlist(names: Vec<String>, config: State<ConfigState>) -> Result<(), CommandError> {
+    let mut names: Vec<String> = names
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    names.dedup();
+    config.update(|config| config.watchlist = names)?;
+    Ok(())
+}
+
+// Recorded starts, stops and restarts, newest first, optionally for one process
+#[tauri::command]
+pub fn get_process_events(
+    name: Option<String>,
+    limit: Option<u32>,
+    history: State<History>,
+) -> Result<Vec<ProcessEvent>, CommandError> {
+    let conn = history.conn()?;
+    let mut statement = conn
+        .prepare(
+            "SELECT name, timestamp, kind FROM process_events
+             WHERE (?1 IS NULL OR name = ?1)
+             ORDER BY timestamp DESC, id DESC LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let events = statement
+        .query_map(params![name, limit.unwrap_or(DEFAULT_EVENT_LIMIT)], |row| {
+            let kind: String = row.get(2)?;
+            Ok(ProcessEvent {
+                name: row.get(0)?,
+                timestamp: row.get(1)?,
+                kind: ProcessEventKind::parse(&kind).unwrap_or(ProcessEventKind::Stopped),
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    Ok(events)
+}
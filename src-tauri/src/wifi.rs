@@ -0,0 +1,305 @@
+// Nearby Wi-Fi networks per wireless interface.
+// Uses the platform's own scanner (`nmcli` on Linux, `system_profiler` on macOS, `netsh wlan`
+// on Windows), with one entry per access point (BSSID) so roaming between them is visible,
+// plus a per-channel count for picking a quieter channel.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::error::CommandError;
+use crate::os_command::run_text;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum WifiBand {
+    #[serde(rename = "2.4GHz")]
+    Band2_4,
+    #[serde(rename = "5GHz")]
+    Band5,
+    #[serde(rename = "6GHz")]
+    Band6,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WifiNetwork {
+    pub interface: Option<String>,
+    // Empty for hidden networks
+    pub ssid: String,
+    pub bssid: Option<String>,
+    pub signal_percent: Option<u8>,
+    pub signal_dbm: Option<i32>,
+    pub channel: Option<u32>,
+    pub band: Option<WifiBand>,
+    pub security: Option<String>,
+    // The access point this machine is associated with
+    pub connected: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelUsage {
+    pub band: Option<WifiBand>,
+    pub channel: u32,
+    pub networks: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WifiScan {
+    // Strongest signal first
+    pub networks: Vec<WifiNetwork>,
+    pub channels: Vec<ChannelUsage>,
+}
+
+fn band_from_frequency(mhz: u32) -> Option<WifiBand> {
+    match mhz {
+        2400..=2500 => Some(WifiBand::Band2_4),
+        5150..=5925 => Some(WifiBand::Band5),
+        5926..=7125 => Some(WifiBand::Band6),
+        _ => None,
+    }
+}
+
+// Channels 1-14 are only used on 2.4 GHz; higher numbers are ambiguous between 5 and 6 GHz
+// so they are assumed to be 5 GHz when the scanner doesn't say
+fn band_from_channel(channel: u32) -> WifiBand {
+    if channel <= 14 {
+        WifiBand::Band2_4
+    } else {
+        WifiBand::Band5
+    }
+}
+
+fn band_from_label(label: &str) -> Option<WifiBand> {
+    let label = label.replace(' ', "");
+    if label.starts_with("2.4") || label.starts_with("2GHz") {
+        Some(WifiBand::Band2_4)
+    } else if label.starts_with('5') {
+        Some(WifiBand::Band5)
+    } else if label.starts_with('6') {
+        Some(WifiBand::Band6)
+    } else {
+        None
+    }
+}
+
+// `nmcli -t` separates fields with `:` and escapes literal colons (as in BSSIDs) as `\:`
+fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => fields.last_mut().unwrap().extend(chars.next()),
+            ':' => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+fn scan_nmcli() -> Result<Vec<WifiNetwork>, String> {
+    let output = run_text(
+        "nmcli",
+        &[
+            "-t",
+            "-f",
+            "DEVICE,IN-USE,SSID,BSSID,SIGNAL,CHAN,FREQ,SECURITY",
+            "device",
+            "wifi",
+            "list",
+            "--rescan",
+            "yes",
+        ],
+    )?;
+    Ok(output
+        .lines()
+        .map(split_terse)
+        .filter(|fields| fields.len() >= 8)
+        .map(|fields| {
+            let frequency = fields[6].trim_end_matches(" MHz").parse().ok();
+            let channel = fields[5].parse().ok();
+            WifiNetwork {
+                interface: Some(fields[0].clone()),
+                connected: fields[1] == "*",
+                ssid: fields[2].clone(),
+                bssid: Some(fields[3].clone()),
+                signal_percent: fields[4].parse().ok(),
+                signal_dbm: None,
+                channel,
+                band: frequency
+                    .and_then(band_from_frequency)
+                    .or(channel.map(band_from_channel)),
+                security: Some(fields[7].clone()).filter(|security| !security.is_empty()),
+            }
+        })
+        .collect())
+}
+
+// `system_profiler SPAirPortDataType -json` lists the current and other networks per interface;
+// channels look like `36 (5GHz, 80MHz)` and signal like `-60 dBm / -92 dBm`
+fn scan_system_profiler() -> Result<Vec<WifiNetwork>, String> {
+    let output = run_text("system_profiler", &["SPAirPortDataType", "-json"])?;
+    let json: serde_json::Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+    let text = |value: &serde_json::Value, key: &str| {
+        value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+    };
+
+    let mut networks = Vec::new();
+    let interfaces = json["SPAirPortDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|entry| entry["spairport_airport_interfaces"].as_array())
+        .flatten();
+    for interface in interfaces {
+        let name = text(interface, "_name");
+        let current = interface["spairport_current_network_information"].clone();
+        let others = interface["spairport_airport_other_local_wireless_networks"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let current = (!current.is_null()).then_some((current, true));
+        for (network, connected) in current
+            .into_iter()
+            .chain(others.into_iter().map(|network| (network, false)))
+        {
+            let channel_text = text(&network, "spairport_network_channel").unwrap_or_default();
+            let channel = channel_text
+                .split_whitespace()
+                .next()
+                .and_then(|channel| channel.parse().ok());
+            let band = channel_text
+                .split_once('(')
+                .and_then(|(_, rest)| band_from_label(rest))
+                .or(channel.map(band_from_channel));
+            networks.push(WifiNetwork {
+                interface: name.clone(),
+                ssid: text(&network, "_name").unwrap_or_default(),
+                bssid: text(&network, "spairport_network_bssid"),
+                signal_percent: None,
+                signal_dbm: text(&network, "spairport_signal_noise")
+                    .and_then(|signal| signal.split_whitespace().next()?.parse().ok()),
+                channel,
+                band,
+                security: text(&network, "spairport_security_mode"),
+                connected,
+            });
+        }
+    }
+    Ok(networks)
+}
+
+// `netsh wlan show networks mode=bssid` prints `Key : value` lines: an `Interface name` header,
+// then `SSID n` blocks each containing one or more `BSSID n` blocks
+fn scan_netsh() -> Result<Vec<WifiNetwork>, String> {
+    let output = run_text("netsh", &["wlan", "show", "networks", "mode=bssid"])?;
+    let connected_bssid = run_text("netsh", &["wlan", "show", "interfaces"])
+        .ok()
+        .and_then(|interfaces| {
+            interfaces.lines().find_map(|line| {
+                let (key, value) = line.split_once(" : ")?;
+                (key.trim() == "BSSID").then(|| value.trim().to_lowercase())
+            })
+        });
+
+    let mut networks: Vec<WifiNetwork> = Vec::new();
+    let mut interface = None;
+    let mut ssid = String::new();
+    let mut security = None;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let network = networks.last_mut().filter(|_| !key.starts_with("BSSID"));
+        match (key, network) {
+            ("Interface name", _) => interface = Some(value.to_string()),
+            (key, _) if key.starts_with("SSID") => {
+                ssid = value.to_string();
+                security = None;
+            }
+            ("Authentication", _) => security = Some(value.to_string()),
+            (key, _) if key.starts_with("BSSID") => networks.push(WifiNetwork {
+                interface: interface.clone(),
+                ssid: ssid.clone(),
+                // MAC addresses contain colons, so take the rest of the line
+                bssid: line
+                    .split_once(" : ")
+                    .map(|(_, bssid)| bssid.trim().to_string()),
+                signal_percent: None,
+                signal_dbm: None,
+                channel: None,
+                band: None,
+                security: security.clone(),
+                connected: false,
+            }),
+            ("Signal", Some(network)) => {
+                network.signal_percent = value.trim_end_matches('%').parse().ok()
+            }
+            ("Channel", Some(network)) => {
+                network.channel = value.parse().ok();
+                network.band = network.band.or(network.channel.map(band_from_channel));
+            }
+            ("Band", Some(network)) => network.band = band_from_label(value),
+            _ => {}
+        }
+    }
+    if let Some(connected_bssid) = connected_bssid {
+        for network in &mut networks {
+            network.connected = network
+                .bssid
+                .as_ref()
+                .is_some_and(|bssid| bssid.to_lowercase() == connected_bssid);
+        }
+    }
+    Ok(networks)
+}
+
+fn channel_usage(networks: &[WifiNetwork]) -> Vec<ChannelUsage> {
+    let mut counts: BTreeMap<(Option<WifiBand>, u32), usize> = BTreeMap::new();
+    for network in networks {
+        if let Some(channel) = network.channel {
+            *counts.entry((network.band, channel)).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((band, channel), networks)| ChannelUsage {
+            band,
+            channel,
+            networks,
+        })
+        .collect()
+}
+
+fn scan() -> Result<WifiScan, String> {
+    let mut networks = if cfg!(target_os = "linux") {
+        scan_nmcli()?
+    } else if cfg!(target_os = "macos") {
+        scan_system_profiler()?
+    } else if cfg!(target_os = "windows") {
+        scan_netsh()?
+    } else {
+        Vec::new()
+    };
+    // dBm and percent aren't comparable, but each platform only reports one of them
+    networks.sort_by_key(|network| {
+        std::cmp::Reverse(
+            network
+                .signal_dbm
+                .or(network.signal_percent.map(i32::from))
+                .unwrap_or(i32::MIN),
+        )
+    });
+    let channels = channel_usage(&networks);
+    Ok(WifiScan { networks, channels })
+}
+
+// Trigger a scan and list nearby access points; can take a few seconds
+#[tauri::command]
+pub async fn scan_wifi_networks() -> Result<WifiScan, CommandError> {
+    let scan = tauri::async_runtime::spawn_blocking(scan)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(scan)
+}
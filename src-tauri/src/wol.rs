@@ -0,0 +1,62 @@
+use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+
+use crate::error::{CommandError, ErrorCode};
+
+// Wake-on-LAN is conventionally sent to UDP port 9 (discard)
+const WOL_PORT: u16 = 9;
+
+// Parse a MAC address in `aa:bb:cc:dd:ee:ff`, `aa-bb-cc-dd-ee-ff` or `aabbccddeeff` form
+fn parse_mac(mac: &str) -> Result<[u8; 6], CommandError> {
+    let invalid = || CommandError::new(ErrorCode::InvalidInput, "wol.invalidMac").with("mac", mac);
+    let hex: String = mac
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.'))
+        .collect();
+
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let mut bytes = [0u8; 6];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+// Magic packet: 6 bytes of 0xFF followed by the MAC repeated 16 times
+fn magic_packet(mac: &[u8; 6]) -> [u8; 102] {
+    let mut packet = [0xFFu8; 102];
+    for chunk in packet[6..].chunks_exact_mut(6) {
+        chunk.copy_from_slice(mac);
+    }
+    packet
+}
+
+#[tauri::command]
+pub fn send_wol(mac: String, broadcast: Option<String>) -> Result<(), CommandError> {
+    let mac_bytes = parse_mac(&mac)?;
+
+    let broadcast_addr = match broadcast {
+        Some(addr) if !addr.trim().is_empty() => addr.trim().parse::<Ipv4Addr>().map_err(|_| {
+            CommandError::new(ErrorCode::InvalidInput, "wol.invalidBroadcast")
+                .with("address", &addr)
+        })?,
+        _ => Ipv4Addr::BROADCAST,
+    };
+
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|e| e.to_string())?;
+    socket.set_broadcast(true).map_err(|e| e.to_string())?;
+    socket
+        .send_to(
+            &magic_packet(&mac_bytes),
+            SocketAddrV4::new(broadcast_addr, WOL_PORT),
+        )
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "✓ Sent Wake-on-LAN packet to {} via {}",
+        mac, broadcast_addr
+    );
+    Ok(())
+}
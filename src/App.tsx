@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import "./App.css";
 
 interface SystemInfo {
@@ -9,6 +10,42 @@ interface SystemInfo {
   platform: string;
 }
 
+// Result of `get_system_snapshot`; a null value has its reason in `errors` under the same name
+interface SystemSnapshot {
+  platform: string;
+  computerName: string | null;
+  totalMemory: number | null;
+  processId: number | null;
+  errors: Record<string, string>;
+  // Where each non-null value came from and when it was read (unix seconds)
+  sources: Record<string, { source: "cpp-lib" | "procfs" | "fallback"; readAt: number }>;
+}
+
+// Result of `get_cpu_info`; mirrors `CpuInfo` in src-tauri/src/cpu.rs
+interface CpuInfo {
+  model: string;
+  vendor: string;
+  physicalCores: number | null;
+  logicalCores: number;
+  baseFrequencyMhz: number | null;
+}
+
+// Error returned by every backend command; `message` is already in the active locale
+interface CommandError {
+  code: string;
+  key: string;
+  params: Record<string, string>;
+  message: string;
+}
+
+// Payload of `system-identity-changed`; null where the backend couldn't read a value
+interface IdentityChange {
+  current: { computerName: string | null; totalMemory: number | null };
+}
+
+const errorMessage = (err: unknown): string =>
+  typeof err === "string" ? err : (err as CommandError).message;
+
 function App() {
   const [systemInfo, setSystemInfo] = useState<SystemInfo>({
     computerName: "Loading...",
@@ -16,6 +53,7 @@ function App() {
     processId: 0,
     platform: "unknown"
   });
+  const [cpuInfo, setCpuInfo] = useState<CpuInfo | null>(null);
   const [factorialInput, setFactorialInput] = useState(10);
   const [factorialResult, setFactorialResult] = useState<number | null>(null);
   const [loading, setLoading] = useState(true);
@@ -26,25 +64,27 @@ function App() {
     setError(null);
 
     try {
-      const [computerName, totalMemory, processId, platform] = await Promise.all([
-        invoke<string>("get_computer_name"),
-        invoke<number>("get_total_memory"),
-        invoke<number>("get_process_id"),
-        invoke<string>("get_platform")
-      ]);
+      const snapshot = await invoke<SystemSnapshot>("get_system_snapshot");
+      const { computerName, totalMemory, processId, platform } = snapshot;
+      if (computerName === null && totalMemory === null && processId === null) {
+        throw Object.values(snapshot.errors)[0];
+      }
 
       setSystemInfo({
-        computerName,
-        totalMemory,
-        processId,
+        computerName: computerName ?? "Unavailable",
+        totalMemory: totalMemory ?? 0,
+        processId: processId ?? 0,
         platform
       });
 
+      // CPU details are optional; the card is hidden when they can't be read
+      setCpuInfo(await invoke<CpuInfo>("get_cpu_info").catch(() => null));
+
       // Also calculate factorial on load
       const factorial = await invoke<number>("calculate_factorial", { n: factorialInput });
       setFactorialResult(factorial);
     } catch (err) {
-      setError(err as string);
+      setError(errorMessage(err));
       console.error("Error fetching system info:", err);
     } finally {
       setLoading(false);
@@ -64,6 +104,25 @@ function App() {
     fetchSystemInfo();
   }, []);
 
+  // The backend re-reads hostname and memory periodically and reports changes,
+  // but only while some window is subscribed to the event
+  useEffect(() => {
+    const events = ["system-identity-changed"];
+    const unlisten = listen<IdentityChange>("system-identity-changed", ({ payload }) => {
+      const { computerName, totalMemory } = payload.current;
+      setSystemInfo((info) => ({
+        ...info,
+        computerName: computerName ?? info.computerName,
+        totalMemory: totalMemory ?? info.totalMemory
+      }));
+    });
+    unlisten.then(() => invoke("subscribe_events", { events }));
+    return () => {
+      invoke("unsubscribe_events", { events });
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
   useEffect(() => {
     calculateFactorial(factorialInput);
   }, [factorialInput]);
@@ -147,6 +206,26 @@ function App() {
               </p>
             </div>
 
+            {/* CPU Card */}
+            {cpuInfo && (
+              <div className="bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6 transform transition-all hover:scale-105">
+                <div className="flex items-center mb-2">
+                  <span className="text-3xl mr-3">🔲</span>
+                  <h2 className="text-xl font-semibold text-gray-700 dark:text-gray-300">
+                    Processor
+                  </h2>
+                </div>
+                <p className="text-2xl font-bold text-teal-600 dark:text-teal-400 ml-12">
+                  {cpuInfo.model}
+                </p>
+                <p className="text-sm text-gray-500 dark:text-gray-400 ml-12 mt-1">
+                  {cpuInfo.physicalCores ?? "?"} cores / {cpuInfo.logicalCores} threads
+                  {cpuInfo.baseFrequencyMhz !== null && ` @ ${(cpuInfo.baseFrequencyMhz / 1000).toFixed(2)} GHz`}
+                  {` (${cpuInfo.vendor})`}
+                </p>
+              </div>
+            )}
+
             {/* Process ID Card */}
             <div className="bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6 transform transition-all hover:scale-105">
               <div className="flex items-center mb-2">